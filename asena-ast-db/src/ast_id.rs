@@ -0,0 +1,73 @@
+//! Stable ids of the nodes of a file, they're the positions of the nodes in a pre-order walk of
+//! the syntax tree, so they're the same every time the same source is parsed, and the external
+//! tools, like the language server clients, can refer to a node without holding the tree.
+
+use std::{collections::HashMap, fmt::Display};
+
+use asena_leaf::ast::{GreenTree, Located};
+use asena_leaf::node::TreeKind;
+use asena_span::Loc;
+
+/// The id of a node in the [AstIdMap] of its file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AstId(pub u32);
+
+/// The nodes of a file by their [AstId]s, see [crate::db::AstDatabase::ast_ids].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AstIdMap {
+    nodes: Vec<GreenTree>,
+
+    /// The nodes are found by their locations and kinds, the nested nodes with the same ones,
+    /// like a group in a group, get the id of the outermost node.
+    ids: HashMap<(Loc, TreeKind), AstId>,
+}
+
+impl AstIdMap {
+    /// Numbers the nodes of the tree in pre-order, the root is the id 0.
+    pub fn new(root: &GreenTree) -> Self {
+        let mut map = Self::default();
+
+        for node in root.preorder() {
+            let id = AstId(map.nodes.len() as u32);
+            let key = (node.location().into_owned(), node.kind());
+
+            map.ids.entry(key).or_insert(id);
+            map.nodes.push(node);
+        }
+
+        map
+    }
+
+    /// Returns the node with the id, if it exists in the file.
+    pub fn get(&self, id: AstId) -> Option<&GreenTree> {
+        self.nodes.get(id.0 as usize)
+    }
+
+    /// Returns the id of the node, if it's a node of the file.
+    pub fn id_of(&self, node: &GreenTree) -> Option<AstId> {
+        let key = (node.location().into_owned(), node.kind());
+
+        self.ids.get(&key).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (AstId, &GreenTree)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(nth, node)| (AstId(nth as u32), node))
+    }
+}
+
+impl Display for AstId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
@@ -1,17 +1,62 @@
-use std::{hash::Hash, path::PathBuf};
+use std::{hash::Hash, path::PathBuf, sync::RwLock};
 
 use dashmap::{DashMap, DashSet};
 
-use crate::{package::Package, ModuleRef};
+use asena_ast::{command::Result, AsenaFile, Command, Decl};
 
-#[derive(Default, Debug)]
+use crate::{
+    commands::CommandRegistry, db::AstDatabase, package::Package, prelude::DEFAULT_PRELUDE,
+    ModuleRef,
+};
+
+#[derive(Debug)]
 pub struct BuildSystem {
     pub files: DashMap<PathBuf, ModuleRef>,
     pub modules: DashMap<ModuleRef, Package>,
     pub packages: DashSet<Package>,
+
+    /// The name of the module imported into every file, or [None] if there's no prelude.
+    pub prelude: RwLock<Option<String>>,
+
+    /// The commands that are expanded before the resolution.
+    pub commands: RwLock<CommandRegistry>,
+}
+
+impl Default for BuildSystem {
+    fn default() -> Self {
+        Self {
+            files: Default::default(),
+            modules: Default::default(),
+            packages: Default::default(),
+            prelude: RwLock::new(Some(DEFAULT_PRELUDE.into())),
+            commands: Default::default(),
+        }
+    }
 }
 
 impl BuildSystem {
+    pub fn prelude(&self) -> Option<String> {
+        self.prelude.read().unwrap().clone()
+    }
+
+    /// Sets the module imported into every file, it should be set before resolving the files.
+    pub fn set_prelude(&self, prelude: Option<&str>) {
+        *self.prelude.write().unwrap() = prelude.map(ToString::to_string);
+    }
+
+    pub fn commands(&self) -> CommandRegistry {
+        self.commands.read().unwrap().clone()
+    }
+
+    /// Registers the expansion of a command, it should be registered before parsing the files,
+    /// as the expansion is a part of the [AstDatabase::ast] query.
+    pub fn register_command<F>(&self, name: &str, expand: F)
+    where
+        F: Fn(&dyn AstDatabase, &AsenaFile, &Command) -> Result<Vec<Decl>> + Send + Sync + 'static,
+    {
+        self.commands.write().unwrap().register(name, expand);
+    }
+
     pub fn add_package(&self, package: Package) -> Package {
         self.packages.insert(package);
         package
@@ -0,0 +1,81 @@
+//! The cancellation of the analysis, the language server cancels the analysis of the previous
+//! edits when a new one arrives, so the stale diagnostics aren't computed. The analysis is
+//! cancelled by a [CancellationToken], that is checked at the boundaries of the phases, or by
+//! salsa, when the inputs of the database are changed while the queries are running.
+
+use std::panic::UnwindSafe;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use thiserror::Error;
+
+/// The reason the analysis was cancelled.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cancelled {
+    /// The token was cancelled, the analysis is stale, and shouldn't be retried.
+    #[error("the analysis was cancelled by its token")]
+    Token,
+
+    /// The inputs of the database changed while the queries were running, the analysis can be
+    /// retried with the new revision.
+    #[error("the analysis was cancelled by a new revision of the database")]
+    Revision,
+}
+
+/// Token shared between the analysis and the ones that can cancel it, the clones share the same
+/// state, so cancelling a clone cancels all of them.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns [Cancelled::Token] if the token was cancelled, it's checked at the boundaries of
+    /// the phases, like `token.check()?`.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            return Err(Cancelled::Token);
+        }
+
+        Ok(())
+    }
+}
+
+impl Cancelled {
+    /// Runs the analysis, catching the cancellations of salsa, that unwind the queries, as
+    /// [Cancelled::Revision].
+    pub fn catch<F, T>(f: F) -> Result<T, Cancelled>
+    where
+        F: FnOnce() -> Result<T, Cancelled> + UnwindSafe,
+    {
+        salsa::Cancelled::catch(f).unwrap_or(Err(Cancelled::Revision))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancellation_works() {
+        let token = CancellationToken::new();
+        let shared = token.clone();
+        assert_eq!(Cancelled::catch(|| shared.check()), Ok(()));
+
+        token.cancel();
+        assert!(shared.is_cancelled());
+        assert_eq!(Cancelled::catch(|| shared.check()), Err(Cancelled::Token));
+    }
+}
@@ -0,0 +1,60 @@
+//! The fixes suggested by the diagnostics of the files, like adding the missing `use` of a name,
+//! the tools can list them with [code_actions], and apply them with [apply_code_actions].
+
+use asena_ast::FunctionId;
+use asena_report::{apply_edits, CodeAction, TextEdit};
+use asena_span::Loc;
+
+use crate::db::AstDatabase;
+use crate::vfs::VfsFile;
+
+/// Returns the fixes of the diagnostics reported in the file, in the order they were reported.
+pub fn code_actions(db: &dyn AstDatabase, vfs_file: VfsFile) -> Vec<CodeAction> {
+    let data = db.lookup_intern_vfs_file(vfs_file);
+    let package = db.lookup_intern_package(data.pkg);
+    let errors = package.errors.read().unwrap();
+
+    errors
+        .iter()
+        .filter(|diagnostic| diagnostic.message.span.file.as_ref() == Some(&data.id.path))
+        .flat_map(|diagnostic| diagnostic.all_fixes())
+        .collect()
+}
+
+/// Applies the fixes to the source of the file, and returns the new source. The edits located
+/// in other files are skipped.
+pub fn apply_code_actions(db: &dyn AstDatabase, vfs_file: VfsFile, fixes: &[CodeAction]) -> String {
+    let data = db.lookup_intern_vfs_file(vfs_file);
+    let edits = fixes
+        .iter()
+        .flat_map(|fix| fix.edits.iter().cloned())
+        .filter(|edit| edit.loc.file.as_ref() == Some(&data.id.path))
+        .collect::<Vec<_>>();
+
+    apply_edits(&db.source(vfs_file), &edits)
+}
+
+/// Returns the fix that adds the `use` of the module that exports the unresolved name, if there's
+/// one in the package of the file.
+pub fn import_fix(
+    db: &dyn AstDatabase,
+    vfs_file: VfsFile,
+    name: &FunctionId,
+) -> Option<CodeAction> {
+    let data = db.lookup_intern_vfs_file(vfs_file);
+
+    let definition = db
+        .package_definitions(data.pkg)
+        .iter()
+        .filter(|definition| definition.file != vfs_file && definition.name == *name)
+        .find(|definition| db.exports(definition.file).contains_key(name))
+        .cloned()?;
+
+    let module = db.lookup_intern_vfs_file(definition.file).name;
+    let start = Loc::new(data.id.path.clone(), 0, 0);
+
+    Some(CodeAction::new(
+        format!("Add `use {module}`"),
+        vec![TextEdit::insert_before(&start, format!("use {module}\n"))],
+    ))
+}
@@ -1,9 +1,36 @@
-use asena_ast::{command::Result, AsenaVisitor, Command};
+//! The commands are the declarations like `#infixl`, that are evaluated by the compiler. The
+//! passes can handle them with [CommandHandler]s, and the commands registered in the
+//! [CommandRegistry] are expanded before the resolution, into the declarations they generate.
+
+use std::{fmt::Debug, sync::Arc};
+
+use asena_ast::command::{CommandError, Result};
+use asena_ast::{AsenaFile, AsenaVisitor, Command, Decl, FunctionId};
+use asena_leaf::ast::{Located, Node};
+use asena_leaf::node::{Child, Tree};
+use asena_lexer::Lexer;
+use asena_parser::Parser;
 use asena_report::WithError;
+use asena_span::Loc;
 
 use crate::db::AstDatabase;
 use crate::package::HasDiagnostic;
 
+/// The maximum nesting of the expansions, the commands can generate other commands, and a
+/// command that generates itself would expand forever.
+pub const MAX_EXPANSION_DEPTH: usize = 16;
+
+/// Function that expands a command invocation in the file, into the declarations that replace
+/// it.
+pub type ExpandFn =
+    Arc<dyn Fn(&dyn AstDatabase, &AsenaFile, &Command) -> Result<Vec<Decl>> + Send + Sync>;
+
+/// The commands that are expanded before the resolution, by their names.
+#[derive(Default, Clone)]
+pub struct CommandRegistry {
+    expanders: im::HashMap<FunctionId, ExpandFn>,
+}
+
 pub trait CommandHandler {
     fn on_command(&mut self, value: Command) -> Result {
         let _ = value;
@@ -32,3 +59,121 @@ impl<'db, 'handler> AsenaVisitor<()> for CommandHandlerEval<'db, 'handler> {
         }
     }
 }
+
+impl CommandRegistry {
+    /// Registers the expansion of the command with the given name, replacing the previous one.
+    pub fn register<F>(&mut self, name: &str, expand: F)
+    where
+        F: Fn(&dyn AstDatabase, &AsenaFile, &Command) -> Result<Vec<Decl>> + Send + Sync + 'static,
+    {
+        self.expanders
+            .insert(FunctionId::new(name), Arc::new(expand));
+    }
+
+    pub fn get(&self, name: &FunctionId) -> Option<ExpandFn> {
+        self.expanders.get(name).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expanders.is_empty()
+    }
+}
+
+impl Debug for CommandRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.expanders.keys()).finish()
+    }
+}
+
+/// Expands the registered commands of the file, the invocations are replaced by the declarations
+/// they generate, and the other commands are kept, to be handled by the next passes.
+pub fn expand_commands(db: &dyn AstDatabase, file: AsenaFile) -> AsenaFile {
+    let registry = db.build_system().commands();
+    if registry.is_empty() {
+        return file;
+    }
+
+    let declarations = expand_declarations(db, &registry, &file, file.declarations(), 0);
+    file.set_declarations(declarations);
+    file
+}
+
+fn expand_declarations(
+    db: &dyn AstDatabase,
+    registry: &CommandRegistry,
+    file: &AsenaFile,
+    declarations: Vec<Decl>,
+    depth: usize,
+) -> Vec<Decl> {
+    let mut expanded = vec![];
+
+    for decl in declarations {
+        let Some(command) = decl.as_command() else {
+            expanded.push(decl);
+            continue;
+        };
+
+        let Some(expand) = registry.get(&command.name().to_fn_id()) else {
+            expanded.push(decl);
+            continue;
+        };
+
+        if depth >= MAX_EXPANSION_DEPTH {
+            let limit = MAX_EXPANSION_DEPTH;
+            command
+                .fail(CommandError::TooDeepExpansion { limit })
+                .push(db);
+            continue;
+        }
+
+        match expand(db, file, &command) {
+            Ok(declarations) => expanded.extend(expand_declarations(
+                db,
+                registry,
+                file,
+                declarations,
+                depth + 1,
+            )),
+            Err(err) => command.name().fail(err).push(db),
+        }
+    }
+
+    expanded
+}
+
+/// Parses the code generated by the command, all the nodes are located at the command
+/// invocation, so the errors in the generated code are reported on it, and the declarations are
+/// derived from it, see [asena_leaf::ast::GreenTreeKind::origin].
+pub fn parse_expansion(command: &Command, code: &str) -> Result<Vec<Decl>> {
+    let origin = command.location().into_owned();
+
+    let tree = Parser::from(Lexer::new(None, code))
+        .run(asena_grammar::file)
+        .build_tree();
+    let errors = tree.report.diagnostics.len();
+    if errors > 0 {
+        return Err(CommandError::InvalidExpansion { errors });
+    }
+
+    // The tokens don't have an origin, so the spans of the generated code are relocated too
+    let mut tree = tree.data;
+    relocate(&mut tree.value, &origin);
+
+    let declarations = AsenaFile::new(tree).declarations().into_iter().map(|decl| {
+        let mut tree = decl.unwrap();
+        tree.set_origin(origin.clone());
+        Decl::new(tree)
+    });
+
+    Ok(declarations.collect())
+}
+
+fn relocate(tree: &mut Tree, origin: &Loc) {
+    for child in tree.children.iter_mut() {
+        child.span = origin.clone();
+
+        if let Child::Tree(ref mut tree) = child.value {
+            relocate(tree, origin);
+        }
+    }
+}
@@ -1,36 +1,81 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 
-use asena_ast::{AsenaFile, BindingId, GlobalName, QualifiedPath, Variant};
-use asena_leaf::ast::{AstParam, GreenTree, Located, Node};
+use asena_ast::{AsenaFile, BindingId, GlobalName, QualifiedPath, Variant, Visibility};
+use asena_leaf::ast::{AstParam, GreenTree, Located, Node, NodeArena};
 use asena_lexer::Lexer;
 use asena_parser::Parser;
-use asena_report::{BoxInternalError, Diagnostic};
-use asena_span::{Loc, Spanned};
+use asena_report::profile::{self, ProfilePhase};
+use asena_report::{BoxInternalError, CodeAction, Diagnostic, TextEdit, WithError};
+use asena_span::{LineIndex, Loc, Spanned};
 use itertools::Itertools;
+use salsa::Database;
 
+use crate::ast_id::AstIdMap;
 use crate::build_system::BuildSystem;
 use crate::def::{Def, DefData, DefWithId};
+use crate::definition::Definition;
+use crate::error::BuildError;
 use crate::package::{HasDiagnostic, Package, PackageData};
-use crate::scope::{ScopeRef, VariantResolution};
+use crate::scope::{ScopeData, ScopeRef, VariantResolution};
+use crate::suggestions::closest_names;
 use crate::vfs::{VfsFile, VfsFileData};
 use crate::*;
 
 type Constructors = HashMap<FunctionId, Arc<Variant>>;
 
+/// The command that lists the names exported by the module, like `#export Maybe, FromMaybe`.
+pub const EXPORT: &str = "export";
+
 #[salsa::query_group(AstDatabaseStorage)]
 pub trait AstDatabase {
     fn build_system(&self) -> Arc<BuildSystem>;
 
+    /// Returns the arena of the database, where the metadata of the nodes of its syntax trees is
+    /// stored, the queries that build the trees enter it, see [NodeArena::enter].
+    fn node_arena(&self) -> Arc<NodeArena>;
+
     fn path_module(&self, path: PathBuf) -> ModuleRef;
     fn items(&self, module: VfsFile) -> Arc<HashMap<FunctionId, Arc<Decl>>>;
+
+    /// Returns the declarations visible to the other modules, they're the `pub` declarations,
+    /// unless the module has `#export` commands, then they're only the listed names, that can be
+    /// the declarations of the module, or re-exports of the names imported with `use`.
+    fn exports(&self, module: VfsFile) -> Arc<HashMap<FunctionId, Arc<Decl>>>;
+
+    /// Returns the modules of the `use` declarations of the module, the ones that aren't loaded
+    /// are skipped, they're reported by the resolution.
+    fn used_modules(&self, module: VfsFile) -> Arc<Vec<VfsFile>>;
+
+    /// Returns the path of the `use` declarations from `to` back to `from`, like `[A, B, A]` for
+    /// the `use B` in `A`, if the modules use each other. The imports in a cycle are rejected,
+    /// as the scopes of the modules would depend on each other.
+    fn import_cycle(&self, from: VfsFile, to: VfsFile) -> Option<Vec<VfsFile>>;
     fn constructors(&self, module: VfsFile) -> Arc<HashMap<FunctionId, Arc<Variant>>>;
     fn source(&self, module: VfsFile) -> Arc<String>;
+
+    /// Returns the index of the lines of the source of the file, to convert the offsets of the
+    /// locations into lines and columns.
+    fn line_index(&self, module: VfsFile) -> Arc<LineIndex>;
+
+    /// Returns the parsed file, with the registered commands expanded, see
+    /// [crate::commands::CommandRegistry].
     fn ast(&self, vfs_file: VfsFile) -> asena_ast::AsenaFile;
     fn cst(&self, vfs_file: VfsFile) -> GreenTree;
+
+    /// Returns the stable ids of the nodes of the syntax tree of the file.
+    fn ast_ids(&self, vfs_file: VfsFile) -> Arc<AstIdMap>;
+
     fn package_of(&self, module: Loc) -> Package;
-    fn vfs_file(&self, module: ModuleRef) -> VfsFile;
+    /// The file of the module, it's [None] if the module wasn't found, as the error is reported
+    /// where the module is resolved.
+    fn vfs_file(&self, module: ModuleRef) -> Option<VfsFile>;
     fn diagnostics(&self, vfs_file: VfsFile) -> Vec<Diagnostic<BoxInternalError>>;
 
+    /// Returns all the named top-level declarations of the package, sorted by the file path.
+    #[salsa::invoke(crate::definition::package_definitions)]
+    fn package_definitions(&self, package: Package) -> Arc<Vec<Definition>>;
+
     fn module_ref(&self, module: Spanned<FunctionId>) -> ModuleRef;
     fn function_data(&self, name: QualifiedPath, vfs_file: VfsFile) -> Def;
     fn constructor_data(&self, name: BindingId, vfs_file: VfsFile) -> VariantResolution;
@@ -56,6 +101,10 @@ fn diagnostics(db: &dyn AstDatabase, vfs_file: VfsFile) -> Vec<Diagnostic<BoxInt
     let vfs_file = db.lookup_intern_vfs_file(vfs_file);
     let package = db.lookup_intern_package(vfs_file.pkg);
 
+    // The diagnostics are pushed by the other queries, and cleared when the files are edited, so
+    // they're collected again in the next revisions.
+    db.salsa_runtime().report_untracked_read();
+
     let errors = package.errors.read().unwrap();
     let errors = errors
         .iter()
@@ -83,14 +132,20 @@ fn path_module(db: &dyn AstDatabase, path: PathBuf) -> ModuleRef {
     })
 }
 
-fn global_scope(_: &dyn AstDatabase) -> ScopeRef {
-    ScopeRef::default()
+fn global_scope(db: &dyn AstDatabase) -> ScopeRef {
+    let scope = ScopeRef::default();
+    crate::intrinsic::install_intrinsics(db, &mut scope.write().unwrap());
+    scope
 }
 
 fn build_system(_: &dyn AstDatabase) -> Arc<BuildSystem> {
     Arc::new(BuildSystem::default())
 }
 
+fn node_arena(_: &dyn AstDatabase) -> Arc<NodeArena> {
+    Arc::new(NodeArena::default())
+}
+
 fn package_of(db: &dyn AstDatabase, loc: Loc) -> Package {
     db.build_system()
         .file_package(&loc.file.clone().unwrap_or_default())
@@ -130,10 +185,10 @@ fn function_data(db: &dyn AstDatabase, name: QualifiedPath, file: VfsFile) -> De
         .or_else(|| db.global_scope().read().unwrap().find_value(&name))
 }
 
-fn vfs_file(_db: &dyn AstDatabase, path: ModuleRef) -> VfsFile {
+fn vfs_file(_db: &dyn AstDatabase, path: ModuleRef) -> Option<VfsFile> {
     match path {
-        ModuleRef::NotFound => todo!("Not handling unresolved declarations: {path:?}"),
-        ModuleRef::Found(path) => path,
+        ModuleRef::NotFound => None,
+        ModuleRef::Found(path) => Some(path),
     }
 }
 
@@ -143,14 +198,137 @@ fn items(db: &dyn AstDatabase, vfs_file: VfsFile) -> Arc<HashMap<FunctionId, Arc
     for decl in ast.declarations() {
         if let Some(named_decl) = Decl::downcast_has_name(&decl) {
             decls.insert(named_decl.name().to_fn_id(), Arc::new(decl));
+        } else if let Decl::Destructure(ref destructure) = decl {
+            // Every name of the pattern is an item, of the same declaration.
+            let decl = Arc::new(decl.clone());
+            for name in destructure.pattern().bindings() {
+                decls.insert(name.to_fn_id(), decl.clone());
+            }
         }
     }
     Arc::new(decls)
 }
 
+fn exports(db: &dyn AstDatabase, vfs_file: VfsFile) -> Arc<HashMap<FunctionId, Arc<Decl>>> {
+    let ast = db.ast(vfs_file);
+    let items = db.items(vfs_file);
+
+    let export_list = ast
+        .declarations()
+        .into_iter()
+        .filter_map(|decl| decl.as_command())
+        .filter(|command| command.is_command(EXPORT))
+        .flat_map(|command| command.arguments())
+        .collect_vec();
+
+    if export_list.is_empty() {
+        let exports = items
+            .iter()
+            .filter(|(_, decl)| decl.visibility() == Visibility::Public)
+            .map(|(name, decl)| (name.clone(), decl.clone()))
+            .collect();
+
+        return Arc::new(exports);
+    }
+
+    let imports = ast
+        .declarations()
+        .into_iter()
+        .filter_map(|decl| decl.as_use())
+        .filter_map(|decl| {
+            let fn_id = Spanned::new(decl.location().into_owned(), decl.to_fn_id());
+
+            match db.module_ref(fn_id) {
+                ModuleRef::Found(module) => Some(module),
+                ModuleRef::NotFound => None,
+            }
+        })
+        .filter(|module| db.import_cycle(vfs_file, *module).is_none())
+        .collect_vec();
+
+    let mut exports = HashMap::new();
+    for argument in export_list {
+        let Some(name) = argument.as_local_expr().map(|name| name.to_fn_id()) else {
+            argument.fail(ExpectedExportNameError).push(db);
+            continue;
+        };
+
+        // The declarations of the module take precedence over the imported ones.
+        let decl = items.get(&name).cloned().or_else(|| {
+            imports
+                .iter()
+                .find_map(|module| db.exports(*module).get(&name).cloned())
+        });
+
+        match decl {
+            Some(decl) => {
+                exports.insert(name, decl);
+            }
+            None => argument.fail(UnresolvedExportError(name)).push(db),
+        }
+    }
+
+    Arc::new(exports)
+}
+
+fn used_modules(db: &dyn AstDatabase, vfs_file: VfsFile) -> Arc<Vec<VfsFile>> {
+    let modules = db
+        .ast(vfs_file)
+        .declarations()
+        .into_iter()
+        .filter_map(|decl| decl.as_use())
+        .filter_map(|decl| find_module(db, &decl.to_fn_id().to_string()))
+        .collect();
+
+    Arc::new(modules)
+}
+
+fn import_cycle(db: &dyn AstDatabase, from: VfsFile, to: VfsFile) -> Option<Vec<VfsFile>> {
+    fn search(
+        db: &dyn AstDatabase,
+        target: VfsFile,
+        path: &mut Vec<VfsFile>,
+        visited: &mut HashSet<VfsFile>,
+    ) -> bool {
+        let current = *path.last().unwrap();
+
+        for module in db.used_modules(current).iter().copied() {
+            path.push(module);
+            if module == target || (visited.insert(module) && search(db, target, path, visited)) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+
+    let mut path = vec![from, to];
+    let mut visited = HashSet::from([to]);
+
+    (to == from || search(db, from, &mut path, &mut visited)).then_some(path)
+}
+
+/// Finds the module with the name in the global scope, without reporting it if it isn't loaded.
+pub fn find_module(db: &dyn AstDatabase, name: &str) -> Option<VfsFile> {
+    let global_scope = db.global_scope();
+    let global_scope = global_scope.read().unwrap();
+
+    match global_scope.modules.get(name) {
+        Some(ModuleRef::Found(module)) => Some(*module),
+        _ => None,
+    }
+}
+
 fn source(db: &dyn AstDatabase, vfs_file: VfsFile) -> Arc<String> {
     let vfs_file = db.lookup_intern_vfs_file(vfs_file);
 
+    // The sources in the memory are edited by their owners, like the REPL does on every input,
+    // so they're read again in the next revisions.
+    if vfs_file.vfs().memory.contains_key(&vfs_file.name) {
+        db.salsa_runtime().report_untracked_read();
+    }
+
     let file = vfs_file
         .vfs()
         .read_file(&vfs_file.name)
@@ -159,32 +337,42 @@ fn source(db: &dyn AstDatabase, vfs_file: VfsFile) -> Arc<String> {
     Arc::new(file)
 }
 
+fn line_index(db: &dyn AstDatabase, vfs_file: VfsFile) -> Arc<LineIndex> {
+    Arc::new(LineIndex::new(&db.source(vfs_file)))
+}
+
 fn cst(db: &dyn AstDatabase, vfs_file: VfsFile) -> GreenTree {
+    let _arena = db.node_arena().enter();
     let source = db.source(vfs_file);
     let data = db.lookup_intern_vfs_file(vfs_file);
 
-    let lexer = Lexer::new(data.id.path, &source);
-    let parser = Parser::from(lexer).run(asena_grammar::file);
-    let tree = parser.build_tree();
+    let edition = data.pkg.edition(db);
+    let lexer = profile::phase(ProfilePhase::Lexing, || {
+        Lexer::with_edition(data.id.path, &source, edition)
+    });
+    let tree = profile::phase(ProfilePhase::Parsing, || {
+        Parser::from(lexer).run(asena_grammar::file).build_tree()
+    });
 
     tree.data.into()
 }
 
+fn ast_ids(db: &dyn AstDatabase, vfs_file: VfsFile) -> Arc<AstIdMap> {
+    Arc::new(AstIdMap::new(&db.cst(vfs_file)))
+}
+
 fn ast(db: &dyn AstDatabase, vfs_file: VfsFile) -> asena_ast::AsenaFile {
+    let _arena = db.node_arena().enter();
     let tree = db.cst(vfs_file);
 
-    AsenaFile::new(tree)
+    crate::commands::expand_commands(db, AsenaFile::new(tree))
 }
 
 fn constructors(db: &dyn AstDatabase, f: VfsFile) -> Arc<Constructors> {
     let mut variants = HashMap::new();
 
     let ast = db.ast(f);
-    for decl in ast.declarations() {
-        let Decl::Enum(enum_decl) = decl else {
-            continue;
-        };
-
+    for enum_decl in ast.declarations().iter().filter_map(Decl::as_enum) {
         variants.extend(enum_decl.constructors());
     }
 
@@ -192,9 +380,14 @@ fn constructors(db: &dyn AstDatabase, f: VfsFile) -> Arc<Constructors> {
 }
 
 fn add_path_dep(db: &dyn AstDatabase, vfs_file: VfsFile, module: ModuleRef) {
+    // The unresolved modules are reported by the resolution of the `use`, so they're skipped.
+    let Some(module) = db.vfs_file(module) else {
+        return;
+    };
+
     let data = db.lookup_intern_vfs_file(vfs_file);
     let mut scope_data = data.scope.write().unwrap();
-    scope_data.import(db, db.vfs_file(module), None);
+    scope_data.import_exports(db, module, None);
 }
 
 fn mk_global_name(db: &dyn AstDatabase, module: FunctionId, decl: AstParam<Decl>) -> DefWithId {
@@ -224,6 +417,7 @@ fn mk_vfs_file(db: &dyn AstDatabase, vfs_file: VfsFileData) -> VfsFile {
 
     scope.modules.insert(name.to_string(), module.clone());
     scope.paths.insert(path, module);
+    // The exports can't be computed yet, as the modules used by the file might not be loaded.
     scope.import(db, id, Some(name));
 
     let pkg_data = db.lookup_intern_package(pkg);
@@ -246,8 +440,51 @@ fn module_ref(db: &dyn AstDatabase, module: Spanned<FunctionId>) -> ModuleRef {
             db.build_system().add_module(module, pkg)
         })
         .unwrap_or_else(|| {
-            Diagnostic::of(module.span, ModuleNotFoundError(module.value)).push(db);
+            module_not_found(db, &global_scope, module).push(db);
 
             ModuleRef::NotFound
         })
 }
+
+/// Reports the module that couldn't be found, with the locations searched for it in the file
+/// system of the package of the `use`, and the modules with similar names, that are loaded or
+/// that can be read.
+fn module_not_found(
+    db: &dyn AstDatabase,
+    global_scope: &ScopeData,
+    module: Spanned<FunctionId>,
+) -> Diagnostic<BuildError> {
+    let name = module.value.to_string();
+    let location = module.span.clone();
+    let mut diagnostic = Diagnostic::of(location.clone(), ModuleNotFoundError(module.value));
+
+    let file = location.file.clone().unwrap_or_default();
+    let Some(package) = db.build_system().file_package(&file) else {
+        return diagnostic;
+    };
+    let vfs = db.lookup_intern_package(package).vfs;
+
+    for path in vfs.search_paths(&name) {
+        let hint = Spanned::new(location.clone(), ModuleSearchedHint(path));
+        diagnostic = diagnostic.add_child(hint);
+    }
+
+    let mut modules = vfs.modules();
+    modules.extend(global_scope.modules.keys().cloned());
+
+    for similar in closest_names(&name, modules.iter().map(String::as_str)) {
+        let fix = CodeAction::new(
+            format!("Replace with `{similar}`"),
+            vec![TextEdit::replace(location.clone(), similar)],
+        );
+
+        diagnostic = diagnostic
+            .add_child(Spanned::new(
+                location.clone(),
+                SimilarModuleHint(similar.into()),
+            ))
+            .with_fix(fix);
+    }
+
+    diagnostic
+}
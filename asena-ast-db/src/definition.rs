@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use asena_ast::{Decl, FunctionId, GlobalName, Visibility};
+use asena_leaf::ast::Located;
+use asena_span::Loc;
+use itertools::Itertools;
+
+use crate::db::{find_module, AstDatabase};
+use crate::package::Package;
+use crate::prelude::has_no_prelude;
+use crate::vfs::VfsFile;
+
+/// The kind of a top-level [Definition].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DefinitionKind {
+    Signature,
+    Assign,
+    Enum,
+    Class,
+    Trait,
+}
+
+/// A named top-level declaration of a package, it's collected once by the
+/// [AstDatabase::package_definitions] query, so the tools that need all the declarations of a
+/// package, like the docs generator or the completion, don't need to walk every file again.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Definition {
+    pub name: FunctionId,
+    pub kind: DefinitionKind,
+    pub visibility: Visibility,
+    pub file: VfsFile,
+
+    /// The location of the name of the declaration.
+    pub name_location: Loc,
+
+    /// The location of the entire declaration.
+    pub location: Loc,
+    pub decl: Arc<Decl>,
+}
+
+impl Definition {
+    /// Creates a definition for the given declaration, returns [None] if the declaration isn't
+    /// named, like the `use` declarations, and the instances.
+    pub fn new(file: VfsFile, decl: Decl) -> Option<Self> {
+        let (kind, name) = match decl {
+            Decl::Signature(ref signature) => (DefinitionKind::Signature, signature.name()),
+            Decl::Assign(ref assign) => (DefinitionKind::Assign, assign.name()),
+            Decl::Enum(ref enum_decl) => (DefinitionKind::Enum, enum_decl.name()),
+            Decl::Class(ref class) => (DefinitionKind::Class, class.name()),
+            Decl::Trait(ref trait_decl) => (DefinitionKind::Trait, trait_decl.name()),
+            _ => return None,
+        };
+
+        Some(Self {
+            name: name.to_fn_id(),
+            kind,
+            visibility: decl.visibility(),
+            file,
+            name_location: name.location().into_owned(),
+            location: decl.location().into_owned(),
+            decl: Arc::new(decl),
+        })
+    }
+}
+
+/// Finds the definition of the name that isn't exported by its module, in the modules the file
+/// uses, or in the module of the qualified name, like `Math.Twice`. It's used to report that the
+/// name is private, instead of unresolved.
+pub fn find_private_definition(
+    db: &dyn AstDatabase,
+    file: VfsFile,
+    name: &FunctionId,
+) -> Option<Definition> {
+    let ast = db.ast(file);
+    let prelude = db
+        .build_system()
+        .prelude()
+        .filter(|_| !has_no_prelude(&ast));
+
+    let candidates = match name.as_str().rsplit_once('.') {
+        Some((module, item)) => find_module(db, module)
+            .map(|module| (module, FunctionId::new(item)))
+            .into_iter()
+            .collect_vec(),
+        None => ast
+            .declarations()
+            .into_iter()
+            .filter_map(|decl| decl.as_use())
+            .map(|decl| decl.to_fn_id().to_string())
+            .chain(prelude)
+            .filter_map(|module| find_module(db, &module))
+            .map(|module| (module, name.clone()))
+            .collect_vec(),
+    };
+
+    candidates
+        .into_iter()
+        .filter(|(module, _)| *module != file)
+        .find_map(|(module, item)| {
+            let decl = db.items(module).get(&item).cloned()?;
+            if db.exports(module).contains_key(&item) {
+                return None;
+            }
+
+            Definition::new(module, decl.as_ref().clone())
+        })
+}
+
+pub(crate) fn package_definitions(db: &dyn AstDatabase, package: Package) -> Arc<Vec<Definition>> {
+    let files = package
+        .files(db)
+        .iter()
+        .map(|file| *file)
+        .sorted_by_key(|file| db.lookup_intern_vfs_file(*file).id.path)
+        .collect_vec();
+
+    let definitions = files
+        .into_iter()
+        .flat_map(|file| {
+            db.ast(file)
+                .declarations()
+                .into_iter()
+                .filter_map(move |decl| Definition::new(file, decl))
+        })
+        .collect();
+
+    Arc::new(definitions)
+}
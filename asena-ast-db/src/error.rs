@@ -12,6 +12,18 @@ pub enum BuildError {
 
     #[error("file not found: `{0}`")]
     FileNotFoundError(PathBuf),
+
+    #[error("could not find the exported name: `{0}`")]
+    UnresolvedExportError(FunctionId),
+
+    #[error("expected the name of a declaration to export")]
+    ExpectedExportNameError,
+
+    #[error("searched at `{0}`")]
+    ModuleSearchedHint(PathBuf),
+
+    #[error("a module with a similar name exists: `{0}`")]
+    SimilarModuleHint(String),
 }
 
 impl BuildError {
@@ -0,0 +1,36 @@
+//! Fixtures to build the packages in the memory, without touching the disk, like the ones of
+//! the tests.
+
+use std::sync::Arc;
+
+use crate::db::AstDatabase;
+use crate::package::Package;
+use crate::vfs::{FileSystem, VfsFile, VfsFileData};
+
+/// Creates the package `Local` with the modules in the memory, indexed by their names, returns
+/// the package and the files of the modules, in the same order of the modules.
+pub fn memory_package(db: &dyn AstDatabase, modules: &[(&str, &str)]) -> (Package, Vec<VfsFile>) {
+    let fs = FileSystem::default();
+    for (name, source) in modules {
+        fs.memory.insert(name.to_string(), source.to_string());
+    }
+
+    let package = Package::new(db, "Local", "0.0.0", Arc::new(fs));
+    let files = modules
+        .iter()
+        .map(|(name, _)| {
+            let path = format!("./{name}.ase");
+
+            VfsFileData::new(db, name, path.as_str().into(), package)
+        })
+        .collect();
+
+    (package, files)
+}
+
+/// Creates the package `Local` with the single module `Test`, returns its file.
+pub fn memory_file(db: &dyn AstDatabase, source: &str) -> VfsFile {
+    let (_, files) = memory_package(db, &[("Test", source)]);
+
+    files[0]
+}
@@ -0,0 +1,90 @@
+//! The names of the intrinsic functions, that are implemented by the compiler instead of the
+//! source code. They're defined in the global scope when it's created, so the resolution treats
+//! them like any other name, the ids of the intrinsics are in the HIR, as `HirIntrinsic`.
+
+use asena_ast::FunctionId;
+use asena_span::{Loc, Spanned};
+
+use crate::db::AstDatabase;
+use crate::def::{DefData, DefWithId};
+use crate::scope::ScopeData;
+
+/// The table of the intrinsics, the variants of `HirIntrinsic` with their names in the global
+/// scope. It calls the macro with the entries, like `Add => "+"`, so both the names and the
+/// variants are derived from the same table.
+#[macro_export]
+macro_rules! intrinsic_table {
+    ($callback:ident) => {
+        $callback! {
+            // arithmetic
+            Add => "+",
+            Sub => "-",
+            Mul => "*",
+            Div => "/",
+            Neg => "negate",
+
+            // comparison
+            Eq => "==",
+            Neq => "/=",
+            Lt => "<",
+            Le => "<=",
+            Gt => ">",
+            Ge => ">=",
+
+            // boolean, they are only strict when they aren't applied infix, like `(&&)`, the
+            // infix applications are lowered into matches, so they short-circuit
+            And => "&&",
+            Or => "||",
+
+            // functions, the infix applications are lowered into calls and lambdas, like the
+            // boolean operators
+            Pipe => "|>",
+            Compose => ".",
+            ComposeLeft => "<<",
+            ComposeRight => ">>",
+
+            // strings
+            Concat => "concat",
+            Length => "length",
+            Show => "show",
+
+            // io
+            Print => "print",
+            Println => "println",
+            ReadLine => "readLine",
+        }
+    };
+}
+
+macro_rules! intrinsic_names {
+    ($($variant:ident => $name:literal,)*) => {
+        /// The names of the intrinsics in the global scope, in the order of the `HirIntrinsic`
+        /// variants.
+        pub const INTRINSIC_NAMES: &[&str] = &[$($name),*];
+    };
+}
+
+intrinsic_table!(intrinsic_names);
+
+/// Returns the definition of the intrinsic with the name, as the definitions are interned, it's
+/// always the same for the same intrinsic. It has a virtual location, as it isn't in the source
+/// code.
+pub fn intrinsic_def(db: &dyn AstDatabase, name: &str) -> DefWithId {
+    let name = FunctionId::new(name);
+
+    db.intern_def(DefData {
+        name: name.clone(),
+        token: Spanned::new(Loc::default(), name),
+        defined_at: Loc::default(),
+    })
+}
+
+/// Defines all the intrinsics in the scope, it's called when the global scope is created, before
+/// the files are imported, so the declarations with the same names shadow the intrinsics.
+pub(crate) fn install_intrinsics(db: &dyn AstDatabase, scope: &mut ScopeData) {
+    for name in INTRINSIC_NAMES {
+        scope
+            .functions
+            .insert(FunctionId::new(name), intrinsic_def(db, name));
+    }
+}
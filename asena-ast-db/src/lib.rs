@@ -14,14 +14,25 @@ pub enum ModuleRef {
     Found(VfsFile),
 }
 
+pub mod ast_id;
 pub mod build_system;
+pub mod cancellation;
+pub mod code_action;
 pub mod commands;
 pub mod db;
 pub mod def;
+pub mod definition;
 pub mod error;
+
+#[cfg(any(test, feature = "fixture"))]
+pub mod fixture;
+
+pub mod intrinsic;
 pub mod package;
+pub mod prelude;
 pub mod report;
 pub mod scope;
+pub mod suggestions;
 pub mod vfs;
 
 pub use error::BuildError::*;
@@ -1,7 +1,11 @@
 use std::hash::Hash;
 use std::sync::{Arc, RwLock};
 
-use asena_report::{BoxInternalError, Diagnostic, InternalError, Reports};
+use asena_lexer::Edition;
+use asena_report::{
+    BoxInternalError, Diagnostic, DiagnosticSink, ErrorBudget, FnSink, InternalError, ReportConfig,
+    Reports, Sinks,
+};
 use dashmap::DashSet;
 use im::Vector;
 
@@ -21,18 +25,77 @@ impl salsa::InternKey for Package {
     }
 }
 
+/// The default limit of the values nested in the lowering, the values nested deeper are lowered
+/// into errors instead of overflowing the stack.
+pub const DEFAULT_MAX_LOWERING_DEPTH: usize = 256;
+
+/// The options of the lowering of the files of a package, they're part of the package, so the
+/// queries that read them are recomputed for another package.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct LoweringOptions {
+    /// The limit of the values nested in the lowering.
+    pub max_depth: usize,
+
+    /// If the integer literals that overflow their types are wrapped around, and reported as
+    /// warnings, instead of being reported as errors.
+    pub wrapping_integers: bool,
+
+    /// If the expressions are interned without their locations, so the structurally identical
+    /// ones share the same id, see `asena_hir::hash_consing`.
+    pub hash_consing: bool,
+}
+
+impl Default for LoweringOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_LOWERING_DEPTH,
+            wrapping_integers: false,
+            hash_consing: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PackageData {
     pub name: String,
     pub version: String,
     pub errors: Arc<RwLock<Vec<Diagnostic<BoxInternalError>>>>,
+    pub sinks: Sinks,
+    pub budget: Arc<ErrorBudget>,
     pub vfs: Arc<FileSystem>,
     pub files: Arc<DashSet<VfsFile>>,
     pub dependencies: im::Vector<Package>,
+
+    /// The edition the files of the package are lexed with, it gates the keywords.
+    pub edition: Edition,
+
+    /// The options the files of the package are lowered with.
+    pub lowering: LoweringOptions,
 }
 
 impl Package {
     pub fn new(db: &dyn AstDatabase, name: &str, version: &str, vfs: Arc<FileSystem>) -> Self {
+        Self::with_edition(db, name, version, vfs, Edition::default())
+    }
+
+    pub fn with_edition(
+        db: &dyn AstDatabase,
+        name: &str,
+        version: &str,
+        vfs: Arc<FileSystem>,
+        edition: Edition,
+    ) -> Self {
+        Self::with_options(db, name, version, vfs, edition, LoweringOptions::default())
+    }
+
+    pub fn with_options(
+        db: &dyn AstDatabase,
+        name: &str,
+        version: &str,
+        vfs: Arc<FileSystem>,
+        edition: Edition,
+        lowering: LoweringOptions,
+    ) -> Self {
         db.build_system()
             .add_package(db.intern_package(PackageData {
                 name: name.to_string(),
@@ -40,7 +103,11 @@ impl Package {
                 vfs,
                 files: Default::default(),
                 errors: Arc::new(RwLock::new(Default::default())),
+                sinks: Sinks::default(),
+                budget: Arc::new(ErrorBudget::default()),
                 dependencies: Vector::new(),
+                edition,
+                lowering,
             }))
     }
 
@@ -54,6 +121,62 @@ impl Package {
     {
         db.lookup_intern_package(*self).diagnostic(diagnostic);
     }
+
+    /// Adds a sink that receives every diagnostic reported in the package, besides the ones
+    /// collected in [PackageData::errors].
+    pub fn add_sink<S: DiagnosticSink + 'static>(&self, db: &dyn AstDatabase, sink: S) {
+        db.lookup_intern_package(*self).sinks.add(sink);
+    }
+
+    /// Calls the function with every diagnostic reported in the package as soon as it's reported,
+    /// during the resolution and the lowering, instead of only when they're collected at the end,
+    /// like for the progress reports, or to stop at the first error.
+    pub fn on_diagnostic<F>(&self, db: &dyn AstDatabase, f: F)
+    where
+        F: Fn(Diagnostic<BoxInternalError>) + Send + Sync + 'static,
+    {
+        self.add_sink(db, FnSink(f));
+    }
+
+    /// Sets the reporting configuration of the package, like the errors limit, and the warnings
+    /// that should be treated as errors.
+    pub fn configure(&self, db: &dyn AstDatabase, config: ReportConfig) {
+        db.lookup_intern_package(*self).budget.set_config(config);
+    }
+
+    /// Clears the diagnostics reported so far, for the packages that are compiled many times in
+    /// the same database, like the one of the REPL.
+    pub fn clear_diagnostics(&self, db: &dyn AstDatabase) {
+        let data = db.lookup_intern_package(*self);
+
+        data.errors.write().unwrap().clear();
+        data.budget.reset();
+    }
+
+    /// Clears the diagnostics reported in the file, before it's analyzed again, like when it's
+    /// edited in the language server. The diagnostics of the other files are kept, as their
+    /// queries aren't executed again.
+    pub fn clear_file_diagnostics(&self, db: &dyn AstDatabase, vfs_file: VfsFile) {
+        let path = db.lookup_intern_vfs_file(vfs_file).id.path;
+        let data = db.lookup_intern_package(*self);
+
+        data.errors
+            .write()
+            .unwrap()
+            .retain(|diagnostic| diagnostic.message.span.file.as_ref() != Some(&path));
+    }
+
+    pub fn budget(&self, db: &dyn AstDatabase) -> Arc<ErrorBudget> {
+        db.lookup_intern_package(*self).budget
+    }
+
+    pub fn edition(&self, db: &dyn AstDatabase) -> Edition {
+        db.lookup_intern_package(*self).edition
+    }
+
+    pub fn lowering_options(&self, db: &dyn AstDatabase) -> LoweringOptions {
+        db.lookup_intern_package(*self).lowering
+    }
 }
 
 pub trait HasDiagnostic {
@@ -68,16 +191,31 @@ impl<E: Clone + Send + Sync + InternalError + 'static> HasDiagnostic for Diagnos
     }
 }
 
+impl DiagnosticSink for PackageData {
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>) {
+        let Some(diagnostic) = self.budget.admit(diagnostic) else {
+            return;
+        };
+
+        self.errors.emit(diagnostic.clone());
+        self.sinks.emit(diagnostic);
+    }
+}
+
 impl Reports for PackageData {
-    fn errors(&self) -> Arc<RwLock<Vec<Diagnostic<BoxInternalError>>>> {
-        self.errors.clone()
+    fn sink(&self) -> &dyn DiagnosticSink {
+        self
     }
 }
 
+/// The packages are interned by their name, version and options, so the packages with other
+/// options, like the same sources lowered with other [LoweringOptions], are different packages.
 impl Hash for PackageData {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.name.hash(state);
         self.version.hash(state);
+        self.edition.hash(state);
+        self.lowering.hash(state);
     }
 }
 
@@ -86,5 +224,8 @@ impl Eq for PackageData {}
 impl PartialEq for PackageData {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name
+            && self.version == other.version
+            && self.edition == other.edition
+            && self.lowering == other.lowering
     }
 }
@@ -0,0 +1,50 @@
+//! The prelude is the module imported into every file automatically, so the basic types, like
+//! `Nat`, `Bool` and `IO`, don't need to be imported explicitly. The files can opt out of it with
+//! the `#no_prelude` command.
+
+use asena_ast::{AsenaFile, Decl};
+
+use crate::{db::AstDatabase, vfs::VfsFile, ModuleRef};
+
+/// The name of the prelude module, if it isn't configured in the [BuildSystem].
+///
+/// [BuildSystem]: crate::build_system::BuildSystem
+pub const DEFAULT_PRELUDE: &str = "Prelude";
+
+/// The command that opts the file out of the prelude.
+pub const NO_PRELUDE: &str = "no_prelude";
+
+/// Checks if the file has the `#no_prelude` command.
+pub fn has_no_prelude(file: &AsenaFile) -> bool {
+    file.declarations().iter().any(|decl| match decl {
+        Decl::Command(command) => command.is_command(NO_PRELUDE),
+        _ => false,
+    })
+}
+
+/// Imports the prelude into the scope of the file, unless the file is the prelude itself, it has
+/// opted out of it, or the prelude module isn't loaded.
+pub fn import_prelude(db: &dyn AstDatabase, vfs_file: VfsFile, file: &AsenaFile) {
+    if has_no_prelude(file) {
+        return;
+    }
+
+    let Some(name) = db.build_system().prelude() else {
+        return;
+    };
+
+    let module = db
+        .global_scope()
+        .read()
+        .unwrap()
+        .modules
+        .get(&name)
+        .cloned();
+
+    match module {
+        Some(ModuleRef::Found(prelude)) if prelude != vfs_file => {
+            db.add_path_dep(vfs_file, ModuleRef::Found(prelude));
+        }
+        _ => {}
+    }
+}
@@ -1,74 +1,56 @@
-use std::hash::Hash;
+use std::io::Write;
 
 use ariadne::{Color, Config, LabelAttach};
-use asena_report::{BoxInternalError, Diagnostic, DiagnosticKind};
-use im::HashSet;
+use asena_report::{aggregate_diagnostics, BoxInternalError, Diagnostic, DiagnosticKind};
 use itertools::Itertools;
 
 use crate::{db::AstDatabase, package::PackageData};
 
-#[derive(Debug, Clone)]
-struct OrdDiagnostic {
-    order: usize,
-    diagnostic: Diagnostic<BoxInternalError>,
-}
-
-impl Hash for OrdDiagnostic {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.diagnostic.hash(state);
-    }
-}
-
-impl PartialEq for OrdDiagnostic {
-    fn eq(&self, other: &Self) -> bool {
-        self.diagnostic == other.diagnostic
+impl PackageData {
+    pub fn print_diagnostics(&self, db: &dyn AstDatabase) {
+        self.write_diagnostics(db, true, &mut std::io::stderr())
+            .unwrap();
     }
-}
 
-impl Eq for OrdDiagnostic {}
+    /// Renders the diagnostics into a string without colors, it's useful for snapshot tests,
+    /// and for clients that can't handle the terminal escape codes.
+    pub fn render_diagnostics(&self, db: &dyn AstDatabase) -> String {
+        let mut buf = Vec::new();
+        self.write_diagnostics(db, false, &mut buf).unwrap();
 
-impl PartialOrd for OrdDiagnostic {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.order.partial_cmp(&other.order)
+        String::from_utf8_lossy(&buf).into_owned()
     }
-}
 
-impl Ord for OrdDiagnostic {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.order.cmp(&other.order)
-    }
-}
-
-impl PackageData {
-    pub fn print_diagnostics(&self, db: &dyn AstDatabase) {
+    pub fn write_diagnostics<W: Write>(
+        &self,
+        db: &dyn AstDatabase,
+        color: bool,
+        out: &mut W,
+    ) -> std::io::Result<()> {
         use ariadne::{ColorGenerator, Report, ReportKind, Source};
 
-        let errors = self.errors.read().unwrap();
+        // The diagnostics of the files are reported in no particular order, so they're sorted,
+        // and rendered together, with a report for each file.
+        let errors = aggregate_diagnostics(self.errors.read().unwrap().iter().cloned());
 
         let groups = errors
-            .iter()
+            .into_iter()
             .group_by(|diagnostic| diagnostic.message.span.file.clone().unwrap_or_default());
 
         let mut colors = ColorGenerator::new();
 
         for (file, diagnostics) in &groups {
             let module_ref = db.path_module(file.clone());
-            let vfs_file = db.vfs_file(module_ref);
+            let Some(vfs_file) = db.vfs_file(module_ref) else {
+                continue;
+            };
             let text = db.source(vfs_file);
 
-            let errors = diagnostics
-                .cloned()
-                .enumerate()
-                .map(|(order, diagnostic)| OrdDiagnostic { order, diagnostic })
-                .collect::<HashSet<OrdDiagnostic>>();
-
-            let labels = errors
-                .iter()
-                .cloned()
-                .sorted_by(|d, n| d.order.cmp(&n.order))
-                .map(|d| Self::create_new_label(d.diagnostic, &mut colors));
-
-            let errors = errors.len();
+            let diagnostics = diagnostics.collect::<Vec<_>>();
+            let errors = diagnostics.len();
+            let labels = diagnostics
+                .into_iter()
+                .map(|diagnostic| Self::create_new_label(diagnostic, &mut colors));
 
             Report::build(ReportKind::Error, (), 0)
                 .with_code("EFFF")
@@ -76,6 +58,7 @@ impl PackageData {
                 .with_labels(labels)
                 .with_config(
                     Config::default()
+                        .with_color(color)
                         .with_tab_width(2)
                         .with_cross_gap(true)
                         .with_label_attach(LabelAttach::Start)
@@ -83,9 +66,14 @@ impl PackageData {
                         .with_underlines(false),
                 )
                 .finish()
-                .eprint(Source::from(text.as_ref()))
-                .unwrap();
+                .write(Source::from(text.as_ref()), &mut *out)?;
+        }
+
+        if let Some(summary) = self.budget.overflow_summary() {
+            writeln!(out, "{summary}")?;
         }
+
+        Ok(())
     }
 
     fn create_new_label(
@@ -105,6 +93,7 @@ impl PackageData {
             DiagnosticKind::HardError
             | DiagnosticKind::Error
             | DiagnosticKind::InternalError
+            | DiagnosticKind::ResolutionError
             | DiagnosticKind::LoweringError => Color::Red,
             _ => colors.next(),
         };
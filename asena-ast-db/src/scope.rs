@@ -18,6 +18,10 @@ pub enum ScopeKind {
     #[default]
     Global,
     File(Arc<VfsFileData>),
+
+    /// The scopes of the local bindings, like the parameters of a lambda, they're always
+    /// children of other scopes.
+    Local,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -45,28 +49,151 @@ impl PartialEq for ScopeRef {
 
 impl Eq for ScopeRef {}
 
+impl ScopeRef {
+    pub fn new(data: ScopeData) -> Self {
+        Self(Arc::new(RwLock::new(data)))
+    }
+
+    /// Creates a local child scope, it sees the bindings of this scope, but the new bindings of
+    /// the child don't leak into this scope. The maps are persistent, so it's a cheap copy.
+    pub fn child(&self) -> ScopeRef {
+        let data = self.read().unwrap().clone();
+
+        ScopeRef::new(ScopeData {
+            kind: ScopeKind::Local,
+            parent: Some(self.clone()),
+            ..data
+        })
+    }
+
+    /// Creates the root scope of the file, it sees the bindings of this scope, and the ones
+    /// imported into the file, like the `use` declarations and the prelude, that shadow them.
+    pub fn file(&self, file: &VfsFileData) -> ScopeRef {
+        let kind = ScopeKind::File(Arc::new(file.clone()));
+        let data = self.read().unwrap().clone();
+        let imports = file.read_scope();
+
+        ScopeRef::new(ScopeData {
+            kind,
+            types: imports.types.clone().union(data.types),
+            constructors: imports.constructors.clone().union(data.constructors),
+            functions: imports.functions.clone().union(data.functions),
+            parent: Some(self.clone()),
+            ..data
+        })
+    }
+
+    /// Takes a snapshot of the scope, the bindings added to the scope later aren't visible in
+    /// the snapshot, it's stored in the nodes for the completion, and the typechecker.
+    pub fn snapshot(&self) -> ScopeRef {
+        self.read().unwrap().fork()
+    }
+
+    /// Iterates over the parent chain, from the parent of this scope to the root one.
+    pub fn ancestors(&self) -> impl Iterator<Item = ScopeRef> {
+        let parent = self.read().unwrap().parent.clone();
+
+        std::iter::successors(parent, |scope| scope.read().unwrap().parent.clone())
+    }
+}
+
 #[derive(Default, Debug, Clone, Eq, PartialEq, Hash)]
 pub struct ScopeData {
     pub kind: ScopeKind,
     pub types: im::HashMap<FunctionId, DefWithId>,
-    pub constructors: im::HashMap<FunctionId, DefWithId>,
+    pub constructors: im::HashMap<FunctionId, VariantData>,
     pub functions: im::HashMap<FunctionId, DefWithId>,
     pub variables: im::HashMap<FunctionId, usize>,
 
     pub modules: im::HashMap<String, ModuleRef>,
     pub paths: im::HashMap<PathBuf, ModuleRef>,
+
+    /// The scope this scope was created from, the bindings of it are copied into this scope, so
+    /// the lookups don't need to walk the chain.
+    pub parent: Option<ScopeRef>,
+}
+
+/// The data of a resolved enum variant, the fields are kept so the patterns can be checked
+/// against the arity of the variant when they're resolved.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct VariantData {
+    pub def: DefWithId,
+    pub fields: Vec<Expr>,
+}
+
+impl VariantData {
+    /// The amount of arguments the variant is applied to.
+    pub fn arity(&self) -> usize {
+        self.fields.len()
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum VariantResolution {
     None,
     Binding(Box<Lexeme<Local>>),
-    Variant(DefWithId),
+    Variant(VariantData),
 }
 
 impl ScopeData {
     pub fn fork(&self) -> ScopeRef {
-        ScopeRef(Arc::new(RwLock::new(self.clone())))
+        ScopeRef::new(self.clone())
+    }
+
+    /// Returns the amount of bindings of the scope, and the approximate bytes of their entries,
+    /// the persistent maps share the entries with the scopes they were forked from, so the bytes
+    /// of the forks are over estimated.
+    pub fn memory_usage(&self) -> (usize, usize) {
+        use std::mem::size_of;
+
+        let entries = [
+            (self.types.len(), size_of::<(FunctionId, DefWithId)>()),
+            (
+                self.constructors.len(),
+                size_of::<(FunctionId, VariantData)>(),
+            ),
+            (self.functions.len(), size_of::<(FunctionId, DefWithId)>()),
+            (self.variables.len(), size_of::<(FunctionId, usize)>()),
+            (self.modules.len(), size_of::<(String, ModuleRef)>()),
+            (self.paths.len(), size_of::<(PathBuf, ModuleRef)>()),
+        ];
+
+        entries
+            .into_iter()
+            .fold((0, 0), |(count, bytes), (len, size)| {
+                (count + len, bytes + len * size)
+            })
+    }
+
+    /// Enumerates the values visible in the scope, including the ones of the parents.
+    pub fn visible_values(&self) -> impl Iterator<Item = (&FunctionId, &DefWithId)> {
+        self.functions.iter()
+    }
+
+    /// Enumerates the values bound by the local scopes, like the parameters, and the pattern
+    /// bindings, that aren't visible in the nearest file or global scope. The locals that shadow
+    /// the global values are included.
+    pub fn local_values(&self) -> Vec<(FunctionId, DefWithId)> {
+        let Some(parent) = &self.parent else {
+            return vec![];
+        };
+
+        let root = match self.kind {
+            ScopeKind::Local => std::iter::once(parent.clone())
+                .chain(parent.ancestors())
+                .find(|scope| scope.read().unwrap().kind != ScopeKind::Local),
+            _ => return vec![],
+        };
+        let root = root.map(|scope| scope.read().unwrap().functions.clone());
+
+        self.functions
+            .iter()
+            .filter(|(name, def)| match &root {
+                Some(root) => root.get(*name) != Some(*def),
+                None => true,
+            })
+            .map(|(name, def)| (name.clone(), *def))
+            .collect()
     }
 
     pub fn create_enum<P>(&mut self, db: &dyn AstDatabase, decl: &Enum, prefix: P)
@@ -80,10 +207,12 @@ impl ScopeData {
 
         for (name, variant) in decl.constructors() {
             let name = FunctionId::optional_path(prefix.clone(), name.clone());
-            let variant = DefWithId::new(db, variant.name(), variant.location().into_owned());
+            let def = DefWithId::new(db, variant.name(), variant.location().into_owned());
+            let fields = variant.fields();
 
-            self.constructors.insert(name.clone(), variant);
-            self.functions.insert(name.clone(), variant);
+            self.constructors
+                .insert(name.clone(), VariantData { def, fields });
+            self.functions.insert(name.clone(), def);
         }
     }
 
@@ -161,7 +290,7 @@ impl ScopeData {
 
     pub fn find_type_constructor(&self, name: &impl GlobalName) -> VariantResolution {
         match self.constructors.get(&name.to_fn_id()) {
-            Some(variant) => VariantResolution::Variant(*variant),
+            Some(variant) => VariantResolution::Variant(variant.clone()),
 
             // if it is not a constructor, it is a variable binding: Vec.cons x xs
             //                                                                ^ ^^
@@ -174,13 +303,35 @@ impl ScopeData {
         }
     }
 
+    /// Imports all the declarations of the file, including the ones that aren't exported, it's
+    /// used to make the declarations of the file visible to itself.
     pub fn import<'a, P>(&mut self, db: &dyn AstDatabase, file: VfsFile, prefix: P)
     where
         P: Into<Option<FunctionId>> + Clone + 'a,
+    {
+        self.import_decls(db, &db.items(file), prefix)
+    }
+
+    /// Imports the declarations exported by the file, like the `use` declarations do, as the
+    /// other declarations are private to the file.
+    pub fn import_exports<'a, P>(&mut self, db: &dyn AstDatabase, file: VfsFile, prefix: P)
+    where
+        P: Into<Option<FunctionId>> + Clone + 'a,
+    {
+        self.import_decls(db, &db.exports(file), prefix)
+    }
+
+    fn import_decls<'a, P>(
+        &mut self,
+        db: &dyn AstDatabase,
+        decls: &im::HashMap<FunctionId, Arc<Decl>>,
+        prefix: P,
+    ) where
+        P: Into<Option<FunctionId>> + Clone + 'a,
     {
         let prefix: Option<_> = prefix.into();
-        for (name, decl) in db.items(file).iter() {
-            let name = FunctionId::optional_path(prefix.clone(), name.clone());
+        for (item, decl) in decls.iter() {
+            let name = FunctionId::optional_path(prefix.clone(), item.clone());
 
             match decl.borrow() {
                 Decl::Signature(decl) => {
@@ -196,6 +347,14 @@ impl ScopeData {
                 Decl::Trait(ref decl) => {
                     self.create_trait(db, decl, prefix.clone());
                 }
+                Decl::Destructure(ref decl) => {
+                    let bindings = decl.pattern().bindings();
+                    if let Some(binding) = bindings.iter().find(|b| b.to_fn_id() == *item) {
+                        let location = binding.location().into_owned();
+                        let def = DefWithId::new(db, binding.clone(), location);
+                        self.functions.insert(name.clone(), def);
+                    }
+                }
                 Decl::Assign(_) | Decl::Instance(_) => {}
                 Decl::Command(_) | Decl::Use(_) | Decl::Error => {}
             }
@@ -211,3 +370,76 @@ impl VariantResolution {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use salsa::{InternId, InternKey};
+
+    use super::*;
+
+    fn def(id: u32) -> DefWithId {
+        DefWithId::from_intern_id(InternId::from(id))
+    }
+
+    fn bind(scope: &ScopeRef, name: &str, def: DefWithId) {
+        let mut scope = scope.write().unwrap();
+        scope.functions.insert(FunctionId::new(name), def);
+    }
+
+    #[test]
+    fn scope_shadowing_works() {
+        let file = ScopeRef::default();
+        bind(&file, "x", def(0));
+        bind(&file, "f", def(1));
+
+        let lam = file.child();
+        bind(&lam, "x", def(2));
+        let case = lam.child();
+        bind(&case, "y", def(3));
+
+        // The locals that shadow the global values are local values too, and the child bindings
+        // don't leak into the parents.
+        let scope = case.read().unwrap();
+        let mut locals = scope.local_values();
+        locals.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        assert_eq!(
+            locals,
+            vec![
+                (FunctionId::new("x"), def(2)),
+                (FunctionId::new("y"), def(3))
+            ]
+        );
+        assert_eq!(scope.functions.get(&FunctionId::new("f")), Some(&def(1)));
+        assert_eq!(
+            lam.read().unwrap().functions.get(&FunctionId::new("y")),
+            None
+        );
+        assert_eq!(
+            file.read().unwrap().functions.get(&FunctionId::new("x")),
+            Some(&def(0))
+        );
+    }
+
+    #[test]
+    fn scope_ancestors_works() {
+        let file = ScopeRef::default();
+        let lam = file.child();
+        let case = lam.child();
+
+        assert_eq!(
+            case.ancestors().collect::<Vec<_>>(),
+            vec![lam.clone(), file]
+        );
+
+        // The snapshot has the bindings of the scope when it's taken, but not the later ones,
+        // and it keeps the parents of the scope.
+        bind(&case, "x", def(0));
+        let snapshot = case.snapshot();
+        bind(&case, "y", def(1));
+
+        let data = snapshot.read().unwrap();
+        assert_eq!(data.functions.get(&FunctionId::new("x")), Some(&def(0)));
+        assert_eq!(data.functions.get(&FunctionId::new("y")), None);
+        assert_eq!(snapshot.ancestors().next(), Some(lam));
+    }
+}
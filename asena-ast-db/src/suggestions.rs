@@ -0,0 +1,63 @@
+//! The edit distance between the names, it's used to suggest the names close to the ones that
+//! couldn't be found, like the unresolved names, and the modules that aren't loaded.
+
+use itertools::Itertools;
+
+/// The maximum amount of suggestions attached to a diagnostic.
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Computes the Levenshtein distance between the strings, by their characters, it's the amount of
+/// insertions, deletions and substitutions needed to turn one into the other.
+pub fn levenshtein(lhs: &str, rhs: &str) -> usize {
+    let rhs = rhs.chars().collect_vec();
+    let mut row = (0..=rhs.len()).collect_vec();
+
+    for (i, lhs_char) in lhs.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let substitution = previous + usize::from(lhs_char != *rhs_char);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[rhs.len()]
+}
+
+/// Returns the candidates that are a typo away from the name, the closest first. A candidate is
+/// close if its distance is at most a third of the length of the name, so the short names only
+/// match the single typos.
+pub fn closest_names<'a, I>(name: &str, candidates: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let threshold = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .unique()
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .sorted()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_works() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(
+            closest_names("Maybee", ["Maybe", "Nat", "Mayo"]),
+            vec!["Maybe"]
+        );
+    }
+}
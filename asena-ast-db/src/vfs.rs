@@ -5,11 +5,17 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use dashmap::DashMap;
+
 use crate::{db::AstDatabase, package::Package, scope::ScopeData};
 
 #[derive(Debug, Default)]
 pub struct FileSystem {
     pub base_dir: Option<PathBuf>,
+
+    /// Sources that aren't backed by the disk, like the module of the REPL, indexed by the file
+    /// name. They take precedence over the files in the disk.
+    pub memory: DashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord)]
@@ -66,19 +72,68 @@ pub struct VfsPath {
 
 impl FileSystem {
     pub fn read_file(&self, path: &str) -> Option<String> {
-        let result = match self.base_dir {
-            Some(ref base_dir) => {
-                std::fs::read_to_string(base_dir.join(path).with_extension("ase"))
-            }
-            None => std::fs::read_to_string(format!("{path}.ase")),
-        };
-
-        result
+        if let Some(source) = self.memory.get(path) {
+            return Some(source.clone());
+        }
+
+        // There's no filesystem in the browser, only the sources in the memory are available.
+        if cfg!(target_arch = "wasm32") {
+            return None;
+        }
+
+        std::fs::read_to_string(self.disk_path(path))
             .unwrap_or_else(|_| {
                 panic!("Failed to read file: {}", path);
             })
             .into()
     }
+
+    /// Returns the path of the module in the disk, it's where [FileSystem::read_file] reads it
+    /// from, if it isn't in the memory.
+    pub fn disk_path(&self, name: &str) -> PathBuf {
+        match self.base_dir {
+            Some(ref base_dir) => base_dir.join(name).with_extension("ase"),
+            None => PathBuf::from(format!("{name}.ase")),
+        }
+    }
+
+    /// Returns the locations searched for the module, in the order they're searched, the memory
+    /// is represented as `<memory>/Name`.
+    pub fn search_paths(&self, name: &str) -> Vec<PathBuf> {
+        let memory = PathBuf::from("<memory>").join(name);
+        if cfg!(target_arch = "wasm32") {
+            return vec![memory];
+        }
+
+        vec![memory, self.disk_path(name)]
+    }
+
+    /// Enumerates the names of the modules that can be read, the ones in the memory, and the
+    /// `.ase` files in the base directory, sorted and without duplicates.
+    pub fn modules(&self) -> Vec<String> {
+        let mut modules = self
+            .memory
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect::<Vec<_>>();
+
+        if !cfg!(target_arch = "wasm32") {
+            let base_dir = self.base_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+            let entries = std::fs::read_dir(base_dir).into_iter().flatten().flatten();
+            modules.extend(entries.filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "ase" {
+                    return None;
+                }
+
+                Some(path.file_stem()?.to_str()?.to_string())
+            }));
+        }
+
+        modules.sort();
+        modules.dedup();
+        modules
+    }
 }
 
 impl VfsFileData {
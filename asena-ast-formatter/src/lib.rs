@@ -1 +1,260 @@
+//! The formatter of the source code. It only normalizes the layout of the files for now: the
+//! trailing whitespace is removed, the indentation tabs are expanded, the consecutive blank lines
+//! are merged, and the file ends with a single line break. The lines inside of the string
+//! literals are kept as they are.
+//!
+//! The lambdas and the arrows can be respelled too, see [FormatOptions].
 
+use asena_leaf::token::kind::TokenKind::*;
+use asena_lexer::Lexer;
+
+/// The amount of spaces an indentation tab is expanded to.
+pub const TAB_WIDTH: usize = 2;
+
+/// How the symbols that have both an ascii and an unicode spelling are emitted, the lexer reads
+/// both of them as the same token.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Spelling {
+    /// Keeps the spelling of the source.
+    #[default]
+    Keep,
+
+    /// Like `\x -> x`.
+    Ascii,
+
+    /// Like `λx → x`.
+    Unicode,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    /// The spelling of the lambdas, `\` or `λ`.
+    pub lambda: Spelling,
+
+    /// The spelling of the arrows, `->`, `<-` and `=>`, or `→`, `←` and `⇒`.
+    pub arrow: Spelling,
+}
+
+/// Formats the source code, the formatting is idempotent, so formatting a formatted source
+/// doesn't change it.
+pub fn format(source: &str) -> String {
+    format_with(source, FormatOptions::default())
+}
+
+/// Formats the source code, with the spellings of the given options.
+pub fn format_with(source: &str, options: FormatOptions) -> String {
+    let source = respell(source, options);
+    let mut formatted = String::with_capacity(source.len());
+    let mut state = StringState::Outside;
+    let mut blank_lines = 0;
+
+    for line in source.lines() {
+        // The line continues a string literal, so its whitespace is a part of the string.
+        if state != StringState::Outside {
+            state = ends_in_string(line, state);
+            formatted.push_str(line);
+            formatted.push('\n');
+            continue;
+        }
+
+        state = ends_in_string(line, StringState::Outside);
+
+        let line = match state {
+            StringState::Outside => line.trim_end(),
+            _ => line,
+        };
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        // The blank lines at the start of the file are removed.
+        if blank_lines > 0 && !formatted.is_empty() {
+            formatted.push('\n');
+        }
+        blank_lines = 0;
+
+        let content = line.trim_start_matches([' ', '\t']);
+        let indentation = line[..line.len() - content.len()]
+            .chars()
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum::<usize>();
+
+        formatted.push_str(&" ".repeat(indentation));
+        formatted.push_str(content);
+        formatted.push('\n');
+    }
+
+    formatted
+}
+
+/// Rewrites the lambdas and the arrows with the spellings of the options, the strings and the
+/// comments aren't tokens, so they're kept as they are.
+fn respell(source: &str, options: FormatOptions) -> String {
+    if options == FormatOptions::default() {
+        return source.to_string();
+    }
+
+    let mut respelled = String::with_capacity(source.len());
+    let mut last = 0;
+
+    for token in Lexer::new(None, source).tokens {
+        let (spelling, ascii, unicode) = match token.kind {
+            Symbol if token.text == "\\" => (options.lambda, "\\", "λ"),
+            RightArrow => (options.arrow, "->", "→"),
+            LeftArrow => (options.arrow, "<-", "←"),
+            DoubleArrow => (options.arrow, "=>", "⇒"),
+            _ => continue,
+        };
+
+        let text = match spelling {
+            Spelling::Keep => continue,
+            Spelling::Ascii => ascii,
+            Spelling::Unicode => unicode,
+        };
+
+        let range = &token.span.range;
+        respelled.push_str(&source[last..range.start()]);
+        respelled.push_str(text);
+        last = range.end();
+    }
+
+    respelled.push_str(&source[last..]);
+    respelled
+}
+
+/// The string literal that a line ends inside of, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StringState {
+    Outside,
+
+    /// Like `"..."`, the escaped quotes don't end it.
+    Plain,
+
+    /// Like `r"..."`, it doesn't have escapes, so it ends at the first quote.
+    Raw,
+
+    /// Like `r#"..."#`, it ends at the first `"#`.
+    RawHashed,
+}
+
+/// Returns the string literal the line ends inside of, starting in the given one, the escaped
+/// quotes and the character literals don't end the strings, but the raw strings don't have
+/// escapes, like `r"C:\"`.
+fn ends_in_string(line: &str, starts_in: StringState) -> StringState {
+    let mut state = starts_in;
+    let mut previous = None;
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let rest = &line[index..];
+        // The `r` of the raw strings isn't the last letter of a name, like in `bar"`.
+        let raw = c == 'r' && !previous.is_some_and(|c: char| c.is_alphanumeric() || c == '_');
+
+        match state {
+            StringState::Outside => match c {
+                '"' => state = StringState::Plain,
+                _ if raw && rest.starts_with("r#\"") => {
+                    chars.nth(1);
+                    state = StringState::RawHashed;
+                }
+                _ if raw && rest.starts_with("r\"") => {
+                    chars.next();
+                    state = StringState::Raw;
+                }
+                '\'' => {
+                    // Skips the character literals, like `'"'` and `'\''`.
+                    if chars.next().map(|(_, c)| c) == Some('\\') {
+                        chars.next();
+                    }
+                    chars.next_if(|(_, c)| *c == '\'');
+                }
+                '/' if rest.starts_with("//") => return StringState::Outside,
+                _ => {}
+            },
+            StringState::Plain => match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => state = StringState::Outside,
+                _ => {}
+            },
+            StringState::Raw if c == '"' => state = StringState::Outside,
+            StringState::RawHashed if rest.starts_with("\"#") => {
+                chars.next();
+                state = StringState::Outside;
+            }
+            StringState::Raw | StringState::RawHashed => {}
+        }
+
+        previous = Some(c);
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn format_works() {
+        let source = "\n\nMain {\n\tprintln \"hello  \"   \n\n\n  1\n}";
+
+        assert_eq!(
+            super::format(source),
+            "Main {\n  println \"hello  \"\n\n  1\n}\n"
+        );
+        assert_eq!(super::format(&super::format(source)), super::format(source));
+    }
+
+    #[test]
+    fn raw_string_works() {
+        // The backslash doesn't escape the quote of the raw string, so it ends in the line.
+        let source = "Main {\n  r\"C:\\\"   \n  r#\"say \"hi\"   \n\"#\n}";
+
+        assert_eq!(
+            super::format(source),
+            "Main {\n  r\"C:\\\"\n  r#\"say \"hi\"   \n\"#\n}\n"
+        );
+    }
+
+    #[test]
+    fn ends_in_string_works() {
+        use super::{ends_in_string, StringState::*};
+
+        assert_eq!(ends_in_string(r#"x = "a\" b"#, Outside), Plain);
+        assert_eq!(ends_in_string(r#"x = r"C:\""#, Outside), Outside);
+        assert_eq!(ends_in_string(r#"x = r"C:\"#, Outside), Raw);
+        assert_eq!(ends_in_string(r##"x = r#"say "hi""#"##, Outside), Outside);
+        assert_eq!(ends_in_string(r##"x = r#"say "hi""##, Outside), RawHashed);
+        assert_eq!(ends_in_string(r##""# // r#""##, RawHashed), Outside);
+        assert_eq!(ends_in_string(r#"" // r""#, Raw), Outside);
+
+        // The `r` of a name doesn't start a raw string, so the backslash escapes the quote.
+        assert_eq!(ends_in_string(r#"bar"C:\""#, Outside), Plain);
+    }
+
+    #[test]
+    fn spelling_works() {
+        use super::{format_with, FormatOptions, Spelling};
+
+        let source = "Main {\n  λx → \\y -> \"->\" // ->\n}\n";
+        let unicode = FormatOptions {
+            lambda: Spelling::Unicode,
+            arrow: Spelling::Unicode,
+        };
+        let ascii = FormatOptions {
+            lambda: Spelling::Ascii,
+            arrow: Spelling::Ascii,
+        };
+
+        assert_eq!(super::format(source), source);
+        assert_eq!(
+            format_with(source, unicode),
+            "Main {\n  λx → λy → \"->\" // ->\n}\n"
+        );
+        assert_eq!(
+            format_with(source, ascii),
+            "Main {\n  \\x -> \\y -> \"->\" // ->\n}\n"
+        );
+    }
+}
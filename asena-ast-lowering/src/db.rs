@@ -10,6 +10,7 @@ use asena_hir::{
     value::HirValue,
 };
 use asena_leaf::ast::{AstParam, Located};
+use asena_report::profile::{self, ProfilePhase};
 use im::{HashMap, HashSet};
 
 use crate::stmt::Instr;
@@ -50,18 +51,38 @@ pub trait AstLowerrer: AstDatabase + HirInterner {
 }
 
 fn hir_file(db: &dyn AstLowerrer, file: AstParam<AsenaFile>) -> InternalAsenaFile {
+    let _arena = db.node_arena().enter();
+    profile::phase(ProfilePhase::Lowering, || lower_file(db, file))
+}
+
+fn lower_file(db: &dyn AstLowerrer, file: AstParam<AsenaFile>) -> InternalAsenaFile {
+    let _span = tracing::debug_span!("lower_file", location = %file.location()).entered();
+
     let mut declarations = HashSet::new();
     let mut signatures = HashMap::new();
+    let mut clauses = HashMap::new();
+    let mut derives = vec![];
+
+    // The signatures are lowered first, so the clauses that come before their signatures are
+    // grouped with them, instead of being replaced by them.
+    let (signature_decls, other_decls): (Vec<_>, Vec<_>) = file
+        .declarations()
+        .into_iter()
+        .partition(|decl| matches!(decl, Decl::Signature(_)));
 
-    for decl in file.declarations() {
+    for decl in signature_decls.into_iter().chain(other_decls) {
         match decl {
             Decl::Error => {}
             Decl::Use(_) => {}
+            Decl::Command(command) if command.is_command(crate::derive::DERIVE) => {
+                derives.push(command);
+            }
             Decl::Command(_) => {
                 // TODO: handle commands
             }
-            Decl::Assign(ref decl) => crate::make_assign(db, &mut signatures, decl),
+            Decl::Assign(ref decl) => crate::make_assign(db, &mut signatures, &mut clauses, decl),
             Decl::Signature(ref decl) => crate::make_signature(db, &mut signatures, decl),
+            Decl::Destructure(ref decl) => crate::make_destructure(db, &mut signatures, decl),
             Decl::Class(class_decl) => {
                 declarations.insert(db.hir_class(class_decl.into()));
             }
@@ -77,6 +98,13 @@ fn hir_file(db: &dyn AstLowerrer, file: AstParam<AsenaFile>) -> InternalAsenaFil
         };
     }
 
+    crate::check_arities(db, &signatures, &clauses);
+
+    // The derived methods are added to the declarations, so they're lowered after all of them.
+    for command in derives {
+        crate::derive::make_derive(db, &mut declarations, &command);
+    }
+
     for (span, group) in signatures.values().cloned() {
         let top_level = db.intern_top_level(HirTopLevelData {
             kind: HirTopLevelKind::from(group),
@@ -90,11 +118,12 @@ fn hir_file(db: &dyn AstLowerrer, file: AstParam<AsenaFile>) -> InternalAsenaFil
 
     let module = db.location_file(file.location().into_owned());
     let file = db.vfs_file(module.clone());
+    tracing::debug!(declarations = declarations.len(), "lowered the file");
 
     InternalAsenaFile {
         path: module,
-        content: db.source(file),
-        tree: db.cst(file),
+        content: file.map(|file| db.source(file)).unwrap_or_default(),
+        tree: file.map(|file| db.cst(file)).unwrap_or_default(),
         declarations,
     }
 }
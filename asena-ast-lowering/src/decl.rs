@@ -1,23 +1,30 @@
-use asena_ast::{traits::global_decl::GlobalDecl, GlobalName, Method, Parameter, Typed};
+use asena_ast::{traits::global_decl::GlobalDecl, AssociatedType, GlobalName, Method, Typed};
 use asena_ast_db::package::HasDiagnostic;
 use asena_hir::top_level::{
-    data::{HirDeclaration, HirParameterData, HirParameterKind, HirSignature},
+    data::{HirAssociatedType, HirDeclaration, HirParameterData, HirParameterKind, HirSignature},
     HirBindingGroup,
 };
+use asena_hir::Name;
 use asena_report::WithError;
 use im::hashset;
 
-use crate::{db::AstLowerrer, error::AstLoweringError::*};
+use crate::{db::AstLowerrer, error::AstLoweringError::*, pattern::build_patterns};
 
 pub mod class;
 pub mod r#enum;
 pub mod instance;
 pub mod r#trait;
 
+/// Lowers the parameters of the declaration, in the order they're declared. The instance
+/// arguments, like `[Monad m]`, are lowered into implicit parameters with fresh names.
 pub fn compute_parameters(db: &dyn AstLowerrer, decl: &impl GlobalDecl) -> Vec<HirParameterKind> {
     let mut parameters = Vec::new();
-    for (name, parameter) in Parameter::compute_parameters(decl.parameters()) {
-        let name = db.intern_name(name.to_string());
+    for (index, parameter) in decl.parameters().iter().enumerate() {
+        let name = if parameter.is_instance() {
+            Name::intern(&format!("$instance{index}"))
+        } else {
+            parameter.name().to_fn_id().symbol()
+        };
         let data = HirParameterData {
             name,
             parameter_type: match parameter.parameter_type() {
@@ -31,9 +38,10 @@ pub fn compute_parameters(db: &dyn AstLowerrer, decl: &impl GlobalDecl) -> Vec<H
                 parameters.push(HirParameterKind::This);
             }
             // a self parameter cannot be implicit
-            _ if parameter.is_self() && !parameter.explicit() => {
-                parameter.fail(SelfParameterBayMeExplicitError).push(db)
-            }
+            _ if parameter.is_self() && !parameter.explicit() => parameter
+                .clone()
+                .fail(SelfParameterBayMeExplicitError)
+                .push(db),
             // This is the inverse, for explicit being the default case, if the parameter is
             // with some error and explicit is buggy, then it will be explicit.
             _ if !parameter.explicit() => {
@@ -50,8 +58,9 @@ pub fn compute_parameters(db: &dyn AstLowerrer, decl: &impl GlobalDecl) -> Vec<H
 pub fn compute_methods(db: &dyn AstLowerrer, methods: Vec<Method>) -> im::HashSet<HirBindingGroup> {
     let mut groups = hashset![];
     for method in methods {
-        let name = db.intern_name(method.name().to_fn_id().to_string());
+        let name = method.name().to_fn_id().symbol();
         let parameters = compute_parameters(db, &method);
+        let patterns = build_patterns(db, parameters.clone());
         let return_type = match method.return_type() {
             Typed::Infer => None,
             Typed::Explicit(expr) => Some(db.hir_type(expr.into())),
@@ -63,8 +72,8 @@ pub fn compute_methods(db: &dyn AstLowerrer, methods: Vec<Method>) -> im::HashSe
                 parameters,
                 return_type,
             },
-            declarations: hashset![HirDeclaration {
-                patterns: vec![],
+            declarations: vec![HirDeclaration {
+                patterns,
                 value: db.hir_block(method.body().into()),
             }],
         };
@@ -72,3 +81,29 @@ pub fn compute_methods(db: &dyn AstLowerrer, methods: Vec<Method>) -> im::HashSe
     }
     groups
 }
+
+/// Lowers the associated types of the declaration, the values are the definitions of them in the
+/// instances.
+pub fn compute_associated_types(
+    db: &dyn AstLowerrer,
+    associated_types: Vec<AssociatedType>,
+) -> im::HashMap<Name, HirAssociatedType> {
+    let mut types = im::HashMap::new();
+    for associated_type in associated_types {
+        let name = associated_type.name().to_fn_id().symbol();
+        if types.contains_key(&name) {
+            associated_type
+                .clone()
+                .fail(DuplicatedAssociatedTypeDefinitionError)
+                .push(db);
+        }
+
+        let value = match associated_type.value() {
+            Typed::Infer => None,
+            Typed::Explicit(type_expr) => Some(db.hir_type(type_expr.into())),
+        };
+
+        types.insert(name, HirAssociatedType { name, value });
+    }
+    types
+}
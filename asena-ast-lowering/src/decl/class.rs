@@ -1,8 +1,8 @@
-use asena_ast::{Class, Field, GlobalName, Typed};
+use asena_ast::{Class, Field, GlobalName, Typed, Where};
 use asena_ast_db::package::HasDiagnostic;
 use asena_hir::{
     hir_type::HirType,
-    top_level::{data::HirSignature, HirTopLevel, HirTopLevelData, HirTopLevelStruct},
+    top_level::{data::HirSignature, HirTopLevel, HirTopLevelClass, HirTopLevelData},
     Name,
 };
 use asena_leaf::ast::AstParam;
@@ -11,18 +11,20 @@ use im::HashMap;
 
 use crate::{db::AstLowerrer, error::AstLoweringError::*, make_location};
 
-use super::{compute_methods, compute_parameters};
+use super::{compute_associated_types, compute_methods, compute_parameters};
 
 pub fn lower_class(db: &dyn AstLowerrer, decl: AstParam<Class>) -> HirTopLevel {
     let span = make_location(db, &decl);
-    let name = db.intern_name(decl.name().to_fn_id().to_string());
-    let kind = HirTopLevelStruct {
+    let name = decl.name().to_fn_id().symbol();
+    let kind = HirTopLevelClass {
         signature: HirSignature {
             name,
             parameters: compute_parameters(db, &decl.data),
             return_type: None, // class can not be gadt
         },
         fields: lower_fields(db, decl.fields()),
+        superclasses: lower_superclasses(db, decl.where_clause()),
+        associated_types: compute_associated_types(db, decl.associated_types()),
         groups: compute_methods(db, decl.methods()),
     };
 
@@ -34,10 +36,27 @@ pub fn lower_class(db: &dyn AstLowerrer, decl: AstParam<Class>) -> HirTopLevel {
     })
 }
 
+/// Lowers the constraints of the `where` clause into the superclasses of the class, the
+/// constraints are type expressions, like `Functor m`.
+pub fn lower_superclasses(db: &dyn AstLowerrer, where_clause: Option<Where>) -> Vec<HirType> {
+    let Some(where_clause) = where_clause else {
+        return vec![];
+    };
+
+    where_clause
+        .constraints()
+        .into_iter()
+        .filter_map(|constraint| match constraint.value() {
+            Typed::Infer => None,
+            Typed::Explicit(type_expr) => Some(db.hir_type(type_expr.into())),
+        })
+        .collect()
+}
+
 pub fn lower_fields(db: &dyn AstLowerrer, fields: Vec<Field>) -> HashMap<Name, HirType> {
     let mut map = HashMap::new();
     for field in fields {
-        let name = db.intern_name(field.name().to_fn_id().to_string());
+        let name = field.name().to_fn_id().symbol();
         match field.field_type() {
             // a field cannot be infer
             Typed::Infer => field.fail(FieldTypeCanNotBeInferError).push(db),
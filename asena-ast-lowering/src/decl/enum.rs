@@ -20,7 +20,7 @@ use super::{compute_methods, compute_parameters};
 
 pub fn lower_enum(db: &dyn AstLowerrer, decl: AstParam<Enum>) -> HirTopLevel {
     let span = make_location(db, &decl);
-    let name = db.intern_name(decl.name().to_fn_id().to_string());
+    let name = decl.name().to_fn_id().symbol();
     let kind = HirTopLevelEnum {
         signature: HirSignature {
             name,
@@ -46,10 +46,10 @@ pub fn lower_variants(db: &dyn AstLowerrer, decl: &Enum) -> HashMap<Name, HirVar
     let mut map = HashMap::new();
 
     let variants = decl.variants();
-    let enum_name = db.intern_name(decl.name().to_fn_id().to_string());
+    let enum_name = decl.name().to_fn_id().symbol();
 
     for variant in variants {
-        let name = db.intern_name(variant.name().to_fn_id().to_string());
+        let name = variant.name().to_fn_id().symbol();
         let variant_type = match variant {
             Variant::Error => HirType::error(db),
             Variant::TypeVariant(type_variant) => match type_variant.value() {
@@ -1,23 +1,56 @@
-use asena_ast::{Instance, Typed};
+use asena_ast::{Expr, FunctionId, GlobalName, Instance, MethodImpl, Trait, Typed};
+use asena_ast_db::{definition::DefinitionKind, package::HasDiagnostic, ModuleRef};
 use asena_hir::{
     hir_type::HirType,
-    top_level::{HirTopLevel, HirTopLevelData, HirTopLevelInstance},
+    top_level::{
+        data::{HirAssociatedType, HirDeclaration},
+        HirBindingGroup, HirTopLevel, HirTopLevelData, HirTopLevelInstance, HirTopLevelKind,
+        HirTopLevelTrait,
+    },
+    Name,
 };
-use asena_leaf::ast::AstParam;
+use asena_leaf::ast::{AstParam, Located};
+use asena_report::WithError;
+use im::HashMap;
+use itertools::Itertools;
 
-use crate::{db::AstLowerrer, make_location};
+use crate::{db::AstLowerrer, error::AstLoweringError::*, make_location, new_default_group};
 
-use super::{compute_methods, compute_parameters};
+use super::{compute_associated_types, compute_methods, compute_parameters};
 
 pub fn lower_instance(db: &dyn AstLowerrer, decl: AstParam<Instance>) -> HirTopLevel {
     let span = make_location(db, &decl);
+    let (trait_name, arguments) = match decl.gadt_type() {
+        Typed::Infer => (None, vec![]),
+        Typed::Explicit(type_expr) => lower_head(db, type_expr),
+    };
+
+    let mut groups = lower_groups(db, &decl.data);
+
+    let mut associated_types = compute_associated_types(db, decl.associated_types());
+
+    let trait_decl = trait_name
+        .as_ref()
+        .and_then(|name| find_trait(db, &decl.data, name))
+        .map(|trait_decl| {
+            db.lookup_intern_top_level(db.hir_trait(trait_decl.into()))
+                .kind
+        });
+    if let Some(HirTopLevelKind::Trait(trait_decl)) = trait_decl {
+        inherit_defaults(db, &decl.data, &trait_decl, &mut groups);
+        inherit_associated_types(db, &decl.data, &trait_decl, &mut associated_types);
+    }
+
     let kind = HirTopLevelInstance {
         parameters: compute_parameters(db, &decl.data),
+        trait_name: trait_name.map(|name| name.symbol()),
+        arguments,
         signature: match decl.gadt_type() {
             Typed::Infer => HirType::error(db),
             Typed::Explicit(type_expr) => db.hir_type(type_expr.into()),
         },
-        groups: compute_methods(db, decl.methods()),
+        associated_types,
+        groups,
     };
 
     db.intern_top_level(HirTopLevelData {
@@ -27,3 +60,168 @@ pub fn lower_instance(db: &dyn AstLowerrer, decl: AstParam<Instance>) -> HirTopL
         span,
     })
 }
+
+/// Splits the head of the instance into the trait and its arguments, like `Functor Maybe` into
+/// `Functor` and `[Maybe]`.
+fn lower_head(db: &dyn AstLowerrer, head: Expr) -> (Option<FunctionId>, Vec<HirType>) {
+    let mut arguments = vec![];
+    let mut callee = head;
+    while let Expr::App(app) = callee {
+        arguments.push(db.hir_type(app.argument().into()));
+        callee = app.callee();
+    }
+    arguments.reverse();
+
+    let trait_name = match callee {
+        Expr::LocalExpr(local) => Some(local.to_fn_id()),
+        _ => None,
+    };
+
+    (trait_name, arguments)
+}
+
+/// Finds the trait of the head of the instance by its name, in the package of the instance.
+fn find_trait(db: &dyn AstLowerrer, decl: &Instance, name: &FunctionId) -> Option<Trait> {
+    let ModuleRef::Found(file) = db.location_file(decl.location().into_owned()) else {
+        return None;
+    };
+    let package = db.lookup_intern_vfs_file(file).pkg;
+
+    db.package_definitions(package)
+        .iter()
+        .filter(|definition| definition.kind == DefinitionKind::Trait)
+        .find(|definition| &definition.name == name)
+        .and_then(|definition| definition.decl.as_trait())
+}
+
+/// Checks that the instance implements every method of the trait, the methods that it doesn't
+/// implement are inherited from the defaults of the trait, and the ones without a default are
+/// reported.
+fn inherit_defaults(
+    db: &dyn AstLowerrer,
+    decl: &Instance,
+    trait_decl: &HirTopLevelTrait,
+    groups: &mut im::HashSet<HirBindingGroup>,
+) {
+    let implemented = groups
+        .iter()
+        .map(|group| group.signature.name)
+        .collect::<im::HashSet<_>>();
+
+    let unimplemented = trait_decl
+        .groups
+        .keys()
+        .filter(|name| !implemented.contains(name))
+        .sorted_by_key(|name| name.as_str());
+
+    for name in unimplemented {
+        match trait_decl.default_method(*name) {
+            Some(default) => {
+                groups.insert(default.clone());
+            }
+            None => {
+                let name = FunctionId(*name);
+                decl.clone()
+                    .fail(UnimplementedTraitMethodError(name))
+                    .push(db);
+            }
+        }
+    }
+}
+
+/// Checks that the instance defines every associated type of the trait, the types with a value
+/// in the trait are inherited, like the default methods.
+fn inherit_associated_types(
+    db: &dyn AstLowerrer,
+    decl: &Instance,
+    trait_decl: &HirTopLevelTrait,
+    associated_types: &mut im::HashMap<Name, HirAssociatedType>,
+) {
+    let undefined = trait_decl
+        .associated_types
+        .values()
+        .filter(|associated_type| !associated_types.contains_key(&associated_type.name))
+        .sorted_by_key(|associated_type| associated_type.name.as_str());
+
+    for associated_type in undefined {
+        if associated_type.value.is_some() {
+            associated_types.insert(associated_type.name, associated_type.clone());
+            continue;
+        }
+
+        let name = FunctionId(associated_type.name);
+        decl.clone()
+            .fail(UndefinedAssociatedTypeError(name))
+            .push(db);
+    }
+}
+
+/// Lowers the methods and the implementations of the instance, deduplicated by their names, the
+/// names that are already defined by a method are reported, and skipped. The implementations of
+/// the same name are the equations of a single method.
+fn lower_groups(db: &dyn AstLowerrer, decl: &Instance) -> im::HashSet<HirBindingGroup> {
+    let mut defined = im::HashSet::new();
+    let methods = decl
+        .methods()
+        .into_iter()
+        .filter(|method| {
+            let name = method.name().to_fn_id().symbol();
+            if defined.insert(name).is_some() {
+                method
+                    .clone()
+                    .fail(DuplicatedMethodDefinitionError)
+                    .push(db);
+                return false;
+            }
+
+            true
+        })
+        .collect_vec();
+
+    let impls = decl
+        .impls()
+        .into_iter()
+        .filter(|method_impl| {
+            let name = method_impl.name().to_fn_id().symbol();
+            if defined.contains(&name) {
+                method_impl
+                    .clone()
+                    .fail(DuplicatedMethodDefinitionError)
+                    .push(db);
+                return false;
+            }
+
+            true
+        })
+        .collect_vec();
+
+    let mut groups = compute_methods(db, methods);
+    groups.extend(lower_impls(db, impls).values().cloned());
+    groups
+}
+
+/// Groups the implementations by their names, every implementation is an equation of the
+/// method, like the top level assignments.
+fn lower_impls(db: &dyn AstLowerrer, impls: Vec<MethodImpl>) -> HashMap<Name, HirBindingGroup> {
+    let mut groups = HashMap::new();
+
+    for method_impl in impls {
+        let name = method_impl.name().to_fn_id().symbol();
+        let patterns = method_impl
+            .patterns()
+            .into_iter()
+            .map(|pattern| db.hir_pattern(pattern.into()))
+            .collect_vec();
+
+        let group = groups
+            .entry(name)
+            .or_insert_with(|| new_default_group(name));
+
+        group.declarations.push(HirDeclaration {
+            patterns,
+            value: db.hir_value(method_impl.body().into()),
+        });
+    }
+
+    groups
+}
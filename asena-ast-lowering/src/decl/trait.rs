@@ -7,19 +7,19 @@ use asena_hir::top_level::{
 use asena_hir::Name;
 use asena_leaf::ast::AstParam;
 use asena_report::WithError;
-use im::{hashset, HashMap};
+use im::HashMap;
 
 use crate::make_location;
 use crate::pattern::build_patterns;
 use crate::{db::AstLowerrer, error::AstLoweringError::*};
 
-use super::compute_parameters;
+use super::{compute_associated_types, compute_parameters};
 
 type Methods = HashMap<Name, HirBindingGroup>;
 
 pub fn lower_trait(db: &dyn AstLowerrer, decl: AstParam<Trait>) -> HirTopLevel {
     let span = make_location(db, &decl);
-    let name = db.intern_name(decl.name().to_fn_id().to_string());
+    let name = decl.name().to_fn_id().symbol();
 
     let methods = compute_abstract_fields(db, decl.fields());
     let kind = HirTopLevelTrait {
@@ -28,6 +28,7 @@ pub fn lower_trait(db: &dyn AstLowerrer, decl: AstParam<Trait>) -> HirTopLevel {
             parameters: compute_parameters(db, &decl.data),
             return_type: None,
         },
+        associated_types: compute_associated_types(db, decl.associated_types()),
         groups: defaults(db, methods, decl.default_methods()),
     };
 
@@ -43,7 +44,7 @@ fn compute_abstract_fields(db: &dyn AstLowerrer, fields: Vec<Field>) -> Methods
     let mut methods = HashMap::new();
 
     for field in fields {
-        let name = db.intern_name(field.name().to_fn_id().to_string());
+        let name = field.name().to_fn_id().symbol();
         if methods.get(&name).is_some() {
             field
                 .clone()
@@ -62,7 +63,7 @@ fn compute_abstract_fields(db: &dyn AstLowerrer, fields: Vec<Field>) -> Methods
                 parameters: vec![],
                 return_type,
             },
-            declarations: hashset![],
+            declarations: vec![],
         };
 
         methods.insert(name, method);
@@ -73,7 +74,7 @@ fn compute_abstract_fields(db: &dyn AstLowerrer, fields: Vec<Field>) -> Methods
 
 fn defaults(db: &dyn AstLowerrer, mut methods: Methods, defaults: Vec<DefaultMethod>) -> Methods {
     for method in defaults {
-        let name = db.intern_name(method.name().to_fn_id().to_string());
+        let name = method.name().to_fn_id().symbol();
         let parameters = compute_parameters(db, &method);
         let group = methods.entry(name).or_insert(HirBindingGroup {
             signature: HirSignature {
@@ -81,10 +82,10 @@ fn defaults(db: &dyn AstLowerrer, mut methods: Methods, defaults: Vec<DefaultMet
                 parameters: parameters.clone(),
                 return_type: None,
             },
-            declarations: hashset![],
+            declarations: vec![],
         });
 
-        group.declarations.insert(HirDeclaration {
+        group.declarations.push(HirDeclaration {
             patterns: build_patterns(db, parameters),
             value: db.hir_block(method.body().into()),
         });
@@ -0,0 +1,330 @@
+//! The built-in `#derive` command, it generates the common methods of an enum or a struct from
+//! its HIR, like `#derive Maybe, Eq, Show` adds to the methods of `Maybe`:
+//!
+//! ```asena
+//! eq (lhs: Maybe a) (rhs: Maybe a) : Bool { ... }
+//! show (value: Maybe a) : String { ... }
+//! ```
+//!
+//! The methods are generated from the constructors of the [HirTopLevelEnum], or from the single
+//! constructor of the [HirTopLevelStruct], with its fields in the order of their names. The
+//! functions they call, like `==` and `show`, are referenced by name, like the desugaring of the
+//! do-notation.
+
+use asena_ast::command::{CommandError, Result};
+use asena_ast::{Command, GlobalName, LocalExpr};
+use asena_ast_db::package::HasDiagnostic;
+use asena_hir::expr::data::{HirBranch, HirMatchCase, HirMatchKind};
+use asena_hir::expr::{HirExprKind, HirExprLiteral, HirExprMatch};
+use asena_hir::hir_type::data::{HirTypeArgument, HirTypeFunction};
+use asena_hir::hir_type::{HirType, HirTypeApp, HirTypeData, HirTypeKind};
+use asena_hir::literal::{HirLiteral, HirString};
+use asena_hir::pattern::{HirPattern, HirPatternConstructor, HirPatternData, HirPatternKind};
+use asena_hir::top_level::data::{
+    HirDeclaration, HirParameterData, HirParameterKind, HirSignature,
+};
+use asena_hir::top_level::{HirBindingGroup, HirTopLevel, HirTopLevelKind};
+use asena_hir::value::HirValue;
+use asena_hir::{HirLoc, Name};
+use asena_report::WithError;
+use im::HashSet;
+use itertools::Itertools;
+
+use crate::do_notation::{call, reference};
+use crate::{db::AstLowerrer, make_expr, make_location, pattern::build_patterns};
+
+/// The name of the command.
+pub const DERIVE: &str = "derive";
+
+/// The structural equality, it compares the constructors, and then their fields with `==`.
+pub const EQ: &str = "Eq";
+
+/// The string rendering, it renders the constructor name, and then the fields with `show`.
+pub const SHOW: &str = "Show";
+
+/// A constructor of the derived type, with the number of its fields.
+type Constructor = (Name, usize);
+
+/// Adds the methods of the `#derive` command to the enum or the struct of the declarations, the
+/// errors are reported on the command.
+pub fn make_derive(
+    db: &dyn AstLowerrer,
+    declarations: &mut HashSet<HirTopLevel>,
+    command: &Command,
+) {
+    if let Err(err) = derive_into(db, declarations, command) {
+        command.name().fail(err).push(db);
+    }
+}
+
+fn derive_into(
+    db: &dyn AstLowerrer,
+    declarations: &mut HashSet<HirTopLevel>,
+    command: &Command,
+) -> Result<()> {
+    let target = command.at::<LocalExpr>(0)?.to_fn_id();
+    let top_level = declarations
+        .iter()
+        .copied()
+        .find(
+            |top_level| match db.lookup_intern_top_level(*top_level).kind {
+                HirTopLevelKind::Enum(enum_decl) => enum_decl.signature.name == target.symbol(),
+                HirTopLevelKind::Struct(struct_decl) => {
+                    struct_decl.signature.name == target.symbol()
+                }
+                _ => false,
+            },
+        )
+        .ok_or_else(|| CommandError::UnresolvedEnum {
+            name: target.to_string(),
+        })?;
+
+    let span = make_location(db, command);
+    let mut data = db.lookup_intern_top_level(top_level);
+    for index in 1..command.arguments().len() {
+        let name = command.at::<LocalExpr>(index)?.to_fn_id();
+        let group = derive_group(db, &data.kind, name.as_str(), span.clone())?;
+
+        match data.kind {
+            HirTopLevelKind::Enum(ref mut enum_decl) => enum_decl.groups.insert(group),
+            HirTopLevelKind::Struct(ref mut struct_decl) => struct_decl.groups.insert(group),
+            _ => unreachable!("the derived declaration is an enum or a struct"),
+        };
+    }
+
+    declarations.remove(&top_level);
+    declarations.insert(db.intern_top_level(data));
+
+    Ok(())
+}
+
+/// Generates the method of the derived name for the declaration, that must be an enum or a
+/// struct, the generated code is located at the span.
+pub fn derive_group(
+    db: &dyn AstLowerrer,
+    kind: &HirTopLevelKind,
+    name: &str,
+    span: HirLoc,
+) -> Result<HirBindingGroup> {
+    let (signature, constructors) = match kind {
+        HirTopLevelKind::Enum(enum_decl) => {
+            let constructors = enum_decl
+                .variants
+                .values()
+                .map(|variant| (variant.name, arity(db, variant.variant_type)))
+                .sorted_by_key(|(name, _)| name.as_str())
+                .collect_vec();
+
+            (&enum_decl.signature, constructors)
+        }
+        HirTopLevelKind::Struct(struct_decl) => {
+            let fields = struct_decl.fields.len();
+
+            (
+                &struct_decl.signature,
+                vec![(struct_decl.signature.name, fields)],
+            )
+        }
+        _ => unreachable!("only the enums and the structs can be derived"),
+    };
+
+    let self_type = self_type(db, signature);
+    let (name, parameters, return_type, value) = match name {
+        EQ => (
+            "eq",
+            vec!["lhs", "rhs"],
+            "Bool",
+            derive_eq(db, &constructors, span.clone()),
+        ),
+        SHOW => (
+            "show",
+            vec!["value"],
+            "String",
+            derive_show(db, &constructors, span.clone()),
+        ),
+        _ => {
+            return Err(CommandError::UnsupportedDerive {
+                name: name.to_string(),
+            })
+        }
+    };
+
+    let parameters = parameters
+        .into_iter()
+        .map(|parameter| {
+            HirParameterKind::Explicit(HirParameterData {
+                name: Name::intern(parameter),
+                parameter_type: Some(self_type),
+            })
+        })
+        .collect_vec();
+
+    Ok(HirBindingGroup {
+        signature: HirSignature {
+            name: Name::intern(name),
+            parameters: parameters.clone(),
+            return_type: Some(HirType::constructor(db, Name::intern(return_type))),
+        },
+        declarations: vec![HirDeclaration {
+            patterns: build_patterns(db, parameters),
+            value,
+        }],
+    })
+}
+
+/// The number of the fields of the variant, that are the parameters of its function type.
+fn arity(db: &dyn AstLowerrer, variant_type: HirType) -> usize {
+    match db.lookup_intern_type(variant_type).kind {
+        HirTypeKind::Forall(forall) => arity(db, forall.value),
+        HirTypeKind::App(HirTypeApp {
+            callee: HirTypeFunction::Pi,
+            arguments,
+        }) => match arguments.last() {
+            Some(HirTypeArgument::Type(value)) => 1 + arity(db, *value),
+            _ => 1,
+        },
+        _ => 0,
+    }
+}
+
+/// The type of the declaration, applied to its parameters, like `Maybe a`.
+fn self_type(db: &dyn AstLowerrer, signature: &HirSignature) -> HirType {
+    let callee = HirType::constructor(db, signature.name);
+    let arguments = signature
+        .parameters
+        .iter()
+        .filter_map(|parameter| match parameter {
+            HirParameterKind::Explicit(data) | HirParameterKind::Implicit(data) => {
+                Some(HirTypeArgument::Type(HirType::variable(db, data.name)))
+            }
+            _ => None,
+        })
+        .collect_vec();
+
+    if arguments.is_empty() {
+        return callee;
+    }
+
+    let kind = HirTypeKind::from(HirTypeApp {
+        callee: HirTypeFunction::Type(callee),
+        arguments,
+    });
+
+    db.intern_type(HirTypeData {
+        kind,
+        span: Default::default(),
+    })
+}
+
+fn derive_eq(db: &dyn AstLowerrer, constructors: &[Constructor], span: HirLoc) -> HirValue {
+    let cases = constructors
+        .iter()
+        .map(|&(constructor, arity)| {
+            let fields = (0..arity)
+                .map(|nth| {
+                    let lhs = field(db, "x", nth, span.clone());
+                    let rhs = field(db, "y", nth, span.clone());
+
+                    call(db, "==", vec![lhs, rhs], span.clone())
+                })
+                .reduce(|acc, next| call(db, "&&", vec![acc, next], span.clone()))
+                .unwrap_or_else(|| HirValue::of_bool(db, true));
+
+            let mut cases = vec![case(pattern(db, constructor, arity, "y", &span), fields)];
+
+            // The other constructors are different, it's only needed if there are others.
+            if constructors.len() > 1 {
+                cases.push(case(HirPattern::wildcard(db), HirValue::of_bool(db, false)));
+            }
+
+            let rhs = reference(db, Name::intern("rhs"), span.clone());
+            let value = match_value(db, rhs, cases, span.clone());
+
+            case(pattern(db, constructor, arity, "x", &span), value)
+        })
+        .collect_vec();
+
+    let lhs = reference(db, Name::intern("lhs"), span.clone());
+    match_value(db, lhs, cases, span)
+}
+
+fn derive_show(db: &dyn AstLowerrer, constructors: &[Constructor], span: HirLoc) -> HirValue {
+    let cases = constructors
+        .iter()
+        .map(|&(constructor, arity)| {
+            let name = string(db, constructor.as_str(), span.clone());
+            let rendered = (0..arity).fold(name, |acc, nth| {
+                let value = field(db, "x", nth, span.clone());
+                let shown = call(db, "show", vec![value], span.clone());
+                let separator = string(db, " ", span.clone());
+                let rest = call(db, "concat", vec![separator, shown], span.clone());
+
+                call(db, "concat", vec![acc, rest], span.clone())
+            });
+
+            case(pattern(db, constructor, arity, "x", &span), rendered)
+        })
+        .collect_vec();
+
+    let value = reference(db, Name::intern("value"), span.clone());
+    match_value(db, value, cases, span)
+}
+
+/// The pattern of the constructor, binding its fields with the prefix, like `Just x0`.
+fn pattern(
+    db: &dyn AstLowerrer,
+    name: Name,
+    arity: usize,
+    prefix: &str,
+    span: &HirLoc,
+) -> HirPattern {
+    let arguments = (0..arity)
+        .map(|nth| HirPattern::name(db, Name::intern(&format!("{prefix}{nth}"))))
+        .collect();
+
+    let kind = HirPatternKind::from(HirPatternConstructor {
+        constructor_name: name,
+        arguments,
+    });
+
+    db.intern_pattern(HirPatternData {
+        kind,
+        span: span.clone(),
+    })
+}
+
+fn field(db: &dyn AstLowerrer, prefix: &str, nth: usize, span: HirLoc) -> HirValue {
+    reference(db, Name::intern(&format!("{prefix}{nth}")), span)
+}
+
+fn string(db: &dyn AstLowerrer, value: &str, span: HirLoc) -> HirValue {
+    let literal = HirLiteral::String(HirString {
+        value: value.to_string(),
+        name: None,
+    });
+    let kind = HirExprKind::from(HirExprLiteral(literal));
+
+    HirValue::of_expr(db, make_expr(db, kind, span))
+}
+
+fn case(pattern: HirPattern, value: HirValue) -> HirMatchCase {
+    HirMatchCase {
+        pattern,
+        value: HirBranch::Expr(value),
+    }
+}
+
+fn match_value(
+    db: &dyn AstLowerrer,
+    scrutinee: HirValue,
+    cases: Vec<HirMatchCase>,
+    span: HirLoc,
+) -> HirValue {
+    let kind = HirExprKind::from(HirExprMatch {
+        scrutinee,
+        cases,
+        kind: HirMatchKind::Match,
+    });
+
+    HirValue::of_expr(db, make_expr(db, kind, span))
+}
@@ -0,0 +1,186 @@
+//! The desugaring of the do-notation. The blocks with `<-` statements are desugared into the
+//! calls of the functions named [BIND] and [PURE] in the scope of the block, so any type that
+//! provides them can be used with the do-notation, like a `Maybe`:
+//!
+//! ```asena
+//! Twice (m: Maybe Int32) : Maybe Int32 {
+//!   x <- m
+//!   let y = x + x
+//!   return y
+//! }
+//! ```
+//!
+//! Is lowered into `bind m (\x -> { let y = x + x; pure y })`. The other statements, that aren't
+//! the last one, are sequenced with [BIND] too, ignoring their values, and the `let` statements
+//! are kept in the block of the statements after them.
+
+use asena_ast::{Ask, Stmt};
+use asena_ast_resolver::ScopeKey;
+use asena_hir::{
+    expr::data::{HirMatchCase, HirMatchKind},
+    pattern::HirPatternKind,
+    stmt::{HirStmtKind, HirStmtValue},
+};
+use asena_leaf::ast::Node;
+
+use crate::db::AstLowerrer;
+
+use super::*;
+
+/// The name of the function that `<-` is desugared into, its type is like
+/// `m a -> (a -> m b) -> m b`.
+pub const BIND: &str = "bind";
+
+/// The name of the function that `return` is desugared into, its type is like `a -> m a`.
+pub const PURE: &str = "pure";
+
+/// Returns if the block is a do block, that is, if it has a `<-` statement.
+pub fn is_do_block(block: &[Stmt]) -> bool {
+    block.iter().any(|stmt| matches!(stmt, Stmt::Ask(_)))
+}
+
+pub fn lower_do_block(db: &dyn AstLowerrer, block: &[Stmt]) -> HirValue {
+    check_operations(db, block);
+
+    desugar(db, block)
+}
+
+/// Reports the statements that are desugared into an operation that isn't in the scope of the
+/// block, the scope is the one of the first `<-` statement.
+fn check_operations(db: &dyn AstLowerrer, block: &[Stmt]) {
+    let Some(Stmt::Ask(first)) = block.iter().find(|stmt| matches!(stmt, Stmt::Ask(_))) else {
+        return;
+    };
+    let snapshot = first.key(ScopeKey);
+    let Some(ref scope) = *snapshot else {
+        return;
+    };
+    let scope = scope.read().unwrap();
+
+    for stmt in block {
+        let (name, error) = match stmt {
+            Stmt::Ask(_) => (BIND, UnresolvedBindError),
+            Stmt::Return(_) => (PURE, UnresolvedPureError),
+            _ => continue,
+        };
+
+        if !scope.functions.contains_key(&FunctionId::new(name)) {
+            stmt.clone().fail(error).push(db);
+        }
+    }
+}
+
+fn desugar(db: &dyn AstLowerrer, stmts: &[Stmt]) -> HirValue {
+    let Some((stmt, rest)) = stmts.split_first() else {
+        return HirValue::unit(db);
+    };
+
+    let span = make_location(db, stmt);
+    match stmt {
+        Stmt::Ask(ask) => {
+            let value = db.hir_value(ask.value().into());
+            let then = continuation(db, ask, desugar(db, rest));
+
+            call(db, BIND, vec![value, then], span)
+        }
+        Stmt::LetStmt(_) => {
+            let (stmt, _) = db.hir_stmt(stmt.clone().into());
+            let kind = HirValueKind::from(HirValueBlock {
+                instructions: vec![stmt],
+                value: desugar(db, rest),
+            });
+
+            db.intern_value(HirValueData { kind, span })
+        }
+        _ => {
+            let value = match stmt {
+                Stmt::Return(_) => call(db, PURE, vec![statement_value(db, stmt)], span.clone()),
+                _ => statement_value(db, stmt),
+            };
+
+            if rest.is_empty() {
+                return value;
+            }
+
+            // The value of the statement is ignored, like `m >> rest` in Haskell.
+            let name = Name::intern("$do");
+            let then = lambda(db, name, desugar(db, rest), span.clone());
+
+            call(db, BIND, vec![value, then], span)
+        }
+    }
+}
+
+/// Lowers the rest of the block into the function that [BIND] calls, the pattern of the `<-`
+/// binds the parameter of it, and the patterns that aren't names are matched against it.
+fn continuation(db: &dyn AstLowerrer, ask: &Ask, value: HirValue) -> HirValue {
+    let span = make_location(db, ask);
+    let pattern = db.hir_pattern(ask.pattern().into());
+
+    let (name, value) = match db.lookup_intern_pattern(pattern).kind {
+        HirPatternKind::Name(pattern) => (pattern.name, value),
+        _ => {
+            let name = Name::intern("$do");
+            let kind = HirExprKind::from(HirExprMatch {
+                scrutinee: reference(db, name, span.clone()),
+                cases: vec![HirMatchCase {
+                    pattern,
+                    value: HirBranch::Expr(value),
+                }],
+                kind: HirMatchKind::Match,
+            });
+
+            (
+                name,
+                HirValue::of_expr(db, make_expr(db, kind, span.clone())),
+            )
+        }
+    };
+
+    lambda(db, name, value, span)
+}
+
+/// Returns the value of the statement, the `return` statements are lowered into their values.
+fn statement_value(db: &dyn AstLowerrer, stmt: &Stmt) -> HirValue {
+    let (stmt, value) = db.hir_stmt(stmt.clone().into());
+    if let Some(value) = value {
+        return value;
+    }
+
+    match db.lookup_intern_stmt(stmt).kind {
+        HirStmtKind::Value(HirStmtValue(value)) => value,
+        _ => HirValue::error(db),
+    }
+}
+
+pub(crate) fn call(
+    db: &dyn AstLowerrer,
+    name: &str,
+    arguments: Vec<HirValue>,
+    span: HirLoc,
+) -> HirValue {
+    let callee = reference(db, Name::intern(name), span.clone());
+    let kind = HirExprKind::from(HirExprCall {
+        callee: HirCallee::Value(callee),
+        arguments,
+        as_dsl: None,
+        spine: vec![span.clone()],
+    });
+
+    HirValue::of_expr(db, make_expr(db, kind, span))
+}
+
+fn lambda(db: &dyn AstLowerrer, name: Name, value: HirValue, span: HirLoc) -> HirValue {
+    let kind = HirExprKind::from(HirExprLam {
+        parameters: vec![name],
+        value,
+    });
+
+    HirValue::of_expr(db, make_expr(db, kind, span))
+}
+
+pub(crate) fn reference(db: &dyn AstLowerrer, name: Name, span: HirLoc) -> HirValue {
+    let kind = HirExprKind::from(HirExprReference { name });
+
+    HirValue::of_expr(db, make_expr(db, kind, span))
+}
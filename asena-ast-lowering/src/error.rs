@@ -1,3 +1,4 @@
+use asena_ast::FunctionId;
 use asena_report::{DiagnosticKind, InternalError};
 use thiserror::Error;
 
@@ -36,6 +37,45 @@ pub enum AstLoweringError {
 
     #[error("type exporessions aren't supported yet")]
     UnsupportedTypeExprsError,
+
+    #[error("only signed numbers can be negated")]
+    NegatedUnsignedLiteralError,
+
+    #[error("array types should have exactly one item, the type of the items")]
+    ArrayTypeShouldHaveOneItemError,
+
+    #[error("expression too deeply nested")]
+    ExprTooDeeplyNestedError,
+
+    #[error("`<-` is desugared into `bind`, but there isn't a `bind` function in the scope")]
+    UnresolvedBindError,
+
+    #[error("`return` is desugared into `pure`, but there isn't a `pure` function in the scope")]
+    UnresolvedPureError,
+
+    #[error("the instance doesn't implement `{0}`, and the trait doesn't have a default for it")]
+    UnimplementedTraitMethodError(FunctionId),
+
+    #[error("duplicate associated type definition")]
+    DuplicatedAssociatedTypeDefinitionError,
+
+    #[error("the instance doesn't define the associated type `{0}` of the trait")]
+    UndefinedAssociatedTypeError(FunctionId),
+
+    #[error("the clauses of `{0}` should have {1} patterns, but this one has {2}")]
+    ClauseArityMismatchError(FunctionId, usize, usize),
+
+    #[error("the ends of the range patterns should be integers")]
+    RangePatternNotIntegerError,
+
+    #[error("the range pattern is empty, its start is greater than its end")]
+    EmptyRangePatternError,
+
+    #[error("the literal `{0}` doesn't fit in `{1}`")]
+    IntegerOverflowError(u128, &'static str),
+
+    #[error("the literal `{0}` doesn't fit in `{1}`, it's wrapped around to `{2}`")]
+    IntegerOverflowWarning(u128, &'static str, i128),
 }
 
 impl AstLoweringError {
@@ -53,6 +93,9 @@ impl InternalError for AstLoweringError {
     }
 
     fn kind(&self) -> DiagnosticKind {
-        DiagnosticKind::LoweringError
+        match self {
+            Self::IntegerOverflowWarning(..) => DiagnosticKind::Warning,
+            _ => DiagnosticKind::LoweringError,
+        }
     }
 }
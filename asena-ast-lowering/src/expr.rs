@@ -1,45 +1,66 @@
-use asena_ast::{Ann, App, Array, Dsl, If, Lam, Let, LocalExpr, Match};
-use asena_ast_db::package::HasDiagnostic;
+use std::sync::Arc;
+
+use asena_ast::{
+    Ann, App, Array, Dsl, Help, If, Interpolation, Lam, LeftSection, Let, Literal, LocalExpr,
+    Match, OperatorSection, RightSection, Unary,
+};
+use asena_ast_db::{def::DefWithId, package::HasDiagnostic};
+use asena_ast_resolver::{ExprResolution, ExprResolutionKey, HoleBindingsKey};
 use asena_hir::{
     expr::data::{HirDsl, HirMatchCase, HirMatchKind},
+    intrinsic::HirIntrinsic,
     pattern::HirPattern,
     stmt::HirStmt,
 };
 use asena_report::WithError;
 
-use crate::{db::AstLowerrer, literal::make_literal};
+use crate::{
+    db::AstLowerrer,
+    literal::{lower_literal, make_literal},
+};
 
 use super::*;
 
 pub struct ExprLowering<'a> {
     pub db: &'a dyn AstLowerrer,
     pub instructions: Vec<HirStmt>,
+
+    /// How many values the expression is nested in.
+    pub depth: usize,
 }
 
 impl<'a> ExprLowering<'a> {
-    pub fn new(db: &'a dyn AstLowerrer) -> Self {
+    pub fn new(db: &'a dyn AstLowerrer, depth: usize) -> Self {
         ExprLowering {
             db,
             instructions: vec![],
+            depth,
         }
     }
 
+    /// Lowers the value nested in the expression, one value deeper than it.
+    fn lower(&self, value: impl Into<AstParam<Expr>>) -> HirValue {
+        crate::lower_nested_value(self.db, value.into(), self.depth + 1)
+    }
+
     pub fn make(&mut self, expr: Expr) -> HirExpr {
         let kind = match expr {
             Expr::Group(ref group) => HirExprKind::from(HirExprGroup {
-                value: self.db.hir_value(group.value().into()),
-            }),
-            Expr::Help(ref expr) => HirExprKind::from(HirExprHelp {
-                value: self.db.hir_value(expr.value().into()),
+                value: self.lower(group.value()),
             }),
+            Expr::Help(ref expr) => self.make_help(expr),
             Expr::LiteralExpr(ref expr) => {
-                let literal = make_literal(expr.literal().data().clone());
+                let literal = lower_literal(self.db, expr, expr.literal().data().clone());
 
                 HirExprKind::from(HirExprLiteral(literal))
             }
 
             Expr::Error => HirExprKind::Error,
-            Expr::SelfExpr(_) => HirExprKind::This,
+            // The `self` outside of the methods is reported by the resolver.
+            Expr::SelfExpr(ref expr) => match *expr.key(ExprResolutionKey) {
+                ExprResolution::Unresolved => HirExprKind::Error,
+                _ => HirExprKind::This,
+            },
             Expr::Unit(_) => HirExprKind::Unit,
             Expr::Dsl(ref expr) => self.make_dsl(expr),
             Expr::Let(ref expr) => self.make_let(expr),
@@ -51,9 +72,14 @@ impl<'a> ExprLowering<'a> {
             Expr::If(ref expr) => self.make_if(expr),
             Expr::Match(ref expr) => self.make_match(expr),
             Expr::Lam(ref expr) => self.make_lam(expr),
+            Expr::LeftSection(ref expr) => self.make_left_section(expr),
+            Expr::RightSection(ref expr) => self.make_right_section(expr),
+            Expr::OperatorSection(ref expr) => self.make_operator_section(expr),
+            Expr::Interpolation(ref expr) => self.make_interpolation(expr),
+            Expr::Unary(ref expr) => self.make_unary(expr),
 
             // dependent types unsupported syntax
-            ref expr @ Expr::Qual(_) | ref expr @ Expr::Pi(_) | ref expr @ Expr::Sigma(_) => {
+            ref expr @ (Expr::Qual(_) | Expr::Pi(_) | Expr::Sigma(_) | Expr::Forall(_)) => {
                 expr.clone()
                     .fail(UnsupportedDependentTypesError)
                     .push(self.db);
@@ -62,14 +88,27 @@ impl<'a> ExprLowering<'a> {
             }
         };
 
-        self.db.intern_expr(HirExprData {
-            kind,
-            span: make_location(self.db, &expr),
-        })
+        make_expr(self.db, kind, make_location(self.db, &expr))
     }
 
-    fn make_let(&self, _expr: &Let) -> HirExprKind {
-        todo!("lowering let expressions is not yet implemented")
+    /// Lowers the let expression into a match with a single case, like:
+    ///
+    /// ```haskell
+    /// let (Pair a b) = pair in a + b
+    /// -- is lowered into
+    /// match pair { Pair a b => a + b }
+    /// ```
+    fn make_let(&self, expr: &Let) -> HirExprKind {
+        let case = HirMatchCase {
+            pattern: self.db.hir_pattern(expr.pat().into()),
+            value: HirBranch::Expr(self.lower(expr.in_value())),
+        };
+
+        HirExprKind::from(HirExprMatch {
+            scrutinee: self.lower(expr.value()),
+            cases: vec![case],
+            kind: HirMatchKind::Match,
+        })
     }
 
     fn make_dsl(&self, expr: &Dsl) -> HirExprKind {
@@ -78,79 +117,403 @@ impl<'a> ExprLowering<'a> {
                 HirExprKind::Call(call) => call,
                 _ => unreachable!(),
             },
-            _ => HirExprCall {
-                // TODO: handle Do, etc
-                callee: HirCallee::Value(self.db.hir_value(expr.callee().into())),
+            callee => HirExprCall {
+                callee: self.make_app_callee(callee),
                 arguments: vec![],
                 as_dsl: None,
+                spine: vec![],
             },
         };
 
+        let parameters = expr
+            .parameters()
+            .iter()
+            .map(|parameter| parameter.name().to_fn_id().symbol())
+            .collect();
+
         hir_call.as_dsl = Some(HirDsl {
-            parameters: vec![], // TODO
+            parameters,
             value: self.db.hir_block(expr.block().into()),
         });
 
         HirExprKind::from(hir_call)
     }
 
+    /// Lowers the name into a reference, the variants, like `Nothing`, are lowered into a call
+    /// to their constructor without arguments.
     fn make_local(&self, expr: &LocalExpr) -> HirExprKind {
-        let name = self.db.intern_name(expr.to_fn_id().to_string());
+        let name = expr.to_fn_id().symbol();
+
+        if let ExprResolution::Variant(_) = *expr.key(ExprResolutionKey) {
+            return HirExprKind::from(HirExprCall {
+                callee: HirCallee::Constructor(name),
+                arguments: vec![],
+                as_dsl: None,
+                spine: vec![],
+            });
+        }
 
         HirExprKind::from(HirExprReference { name })
     }
 
+    /// Lowers the help expression, if it's an unbound name, like `?foo`, it's a hole, that the
+    /// value should be filled in.
+    fn make_help(&self, help: &Help) -> HirExprKind {
+        let bindings = help
+            .key(HoleBindingsKey)
+            .iter()
+            .map(FunctionId::symbol)
+            .collect();
+
+        match help.value() {
+            Expr::LocalExpr(ref local)
+                if matches!(*local.key(ExprResolutionKey), ExprResolution::Unresolved) =>
+            {
+                let hole = local.to_fn_id().symbol();
+
+                HirExprKind::from(HirExprHelp {
+                    hole: Some(hole),
+                    value: None,
+                    bindings,
+                })
+            }
+            value => HirExprKind::from(HirExprHelp {
+                hole: None,
+                value: Some(self.lower(value)),
+                bindings,
+            }),
+        }
+    }
+
     fn make_ann(&self, expr: &Ann) -> HirExprKind {
-        let value = self.db.hir_value(expr.value().into());
+        let value = self.lower(expr.value());
         let against = self.db.hir_type(expr.against().into());
 
         HirExprKind::from(HirExprAnn { value, against })
     }
 
     fn make_array(&self, array: &Array) -> HirExprKind {
-        let items = array
-            .items()
-            .into_iter()
-            .map(|e| self.db.hir_value(e.into()))
-            .collect();
+        let items = array.items().into_iter().map(|e| self.lower(e)).collect();
 
         HirExprKind::from(HirExprArray { items })
     }
 
+    /// Lowers an application spine, like `f a b c`, into a single call with all the arguments,
+    /// instead of a call for each application.
     fn make_app(&self, app: &App) -> HirExprKind {
-        let callee = self.db.hir_value(app.callee().into());
-        let argument = self.db.hir_value(app.argument().into());
+        let mut arguments = vec![self.lower(app.argument())];
+        let mut spine = vec![make_location(self.db, app)];
+        let mut callee = app.callee();
+
+        while let Expr::App(ref inner) = callee {
+            arguments.push(self.lower(inner.argument()));
+            spine.push(make_location(self.db, inner));
+            callee = inner.callee();
+        }
+
+        arguments.reverse();
+        spine.reverse();
 
         HirExprKind::from(HirExprCall {
-            callee: HirCallee::Value(callee),
-            arguments: vec![argument],
+            callee: self.make_app_callee(callee),
+            arguments,
             as_dsl: None,
+            spine,
         })
     }
 
-    fn make_infix(&self, infix: &Infix) -> HirExprKind {
-        let lhs = self.db.hir_value(infix.lhs().into());
-        let rhs = self.db.hir_value(infix.rhs().into());
-
-        let callee = match infix.fn_id().as_str() {
-            "+" => HirCallee::Add,
-            "-" => HirCallee::Sub,
-            "*" => HirCallee::Mul,
-            "/" => HirCallee::Div,
-            fn_id => self.make_callee(infix, fn_id),
+    /// Classifies the callee of an application by its resolution: the runner of the do blocks,
+    /// the constructors, like `Just 1`, the intrinsics, that are called directly, like
+    /// `println "hello"`, the methods, like `Point.show p`, and the other values.
+    fn make_app_callee(&self, callee: Expr) -> HirCallee {
+        let Expr::LocalExpr(ref local) = callee else {
+            return HirCallee::Value(self.lower(callee));
         };
 
+        match *local.key(ExprResolutionKey) {
+            ExprResolution::Do => HirCallee::Do,
+            ExprResolution::Variant(_) => HirCallee::Constructor(local.to_fn_id().symbol()),
+            ExprResolution::Method(_) => HirCallee::Method(self.lower(callee)),
+            ExprResolution::Resolved(def) => match HirIntrinsic::from_def(self.db, def) {
+                Some(intrinsic) => HirCallee::Intrinsic(intrinsic),
+                None => HirCallee::Value(self.lower(callee)),
+            },
+            ExprResolution::Unresolved => HirCallee::Value(self.lower(callee)),
+        }
+    }
+
+    fn make_infix(&self, infix: &Infix) -> HirExprKind {
+        let lhs = self.lower(infix.lhs());
+        let rhs = self.lower(infix.rhs());
+
+        let resolution = infix.key(ExprResolutionKey);
+        let callee = self.make_operator(infix, infix.fn_id().symbol(), resolution);
+
+        match callee {
+            HirCallee::Intrinsic(HirIntrinsic::And) => return self.make_and(lhs, rhs),
+            HirCallee::Intrinsic(HirIntrinsic::Or) => return self.make_or(lhs, rhs),
+            HirCallee::Intrinsic(HirIntrinsic::Pipe) => return self.make_pipe(infix, lhs, rhs),
+            HirCallee::Intrinsic(HirIntrinsic::Compose | HirIntrinsic::ComposeLeft) => {
+                return self.make_compose(infix, lhs, rhs)
+            }
+            HirCallee::Intrinsic(HirIntrinsic::ComposeRight) => {
+                return self.make_compose(infix, rhs, lhs)
+            }
+            _ => {}
+        }
+
         HirExprKind::from(HirExprCall {
             callee,
             arguments: vec![lhs, rhs],
             as_dsl: None,
+            spine: vec![],
+        })
+    }
+
+    /// Lowers `x |> f` into `f x`, so `x |> f |> g` is lowered into `g (f x)`, the argument keeps
+    /// its own span, and the application is spanned by the whole pipe.
+    fn make_pipe(&self, infix: &Infix, argument: HirValue, function: HirValue) -> HirExprKind {
+        HirExprKind::from(HirExprCall {
+            callee: HirCallee::Value(function),
+            arguments: vec![argument],
+            as_dsl: None,
+            spine: vec![make_location(self.db, infix)],
+        })
+    }
+
+    /// Lowers `f . g` and `f << g` into `\$compose -> f (g $compose)`, and `g >> f` into the same
+    /// lambda, with the functions swapped.
+    fn make_compose(&self, infix: &Infix, outer: HirValue, inner: HirValue) -> HirExprKind {
+        let span = make_location(self.db, infix);
+        let name = Name::intern("$compose");
+        let parameter = self.db.intern_expr(HirExprData {
+            kind: HirExprKind::from(HirExprReference { name }),
+            span: span.clone(),
+        });
+
+        let inner_call = self.db.intern_expr(HirExprData {
+            kind: HirExprKind::from(HirExprCall {
+                callee: HirCallee::Value(inner),
+                arguments: vec![HirValue::of_expr(self.db, parameter)],
+                as_dsl: None,
+                spine: vec![span.clone()],
+            }),
+            span: span.clone(),
+        });
+
+        let outer_call = self.db.intern_expr(HirExprData {
+            kind: HirExprKind::from(HirExprCall {
+                callee: HirCallee::Value(outer),
+                arguments: vec![HirValue::of_expr(self.db, inner_call)],
+                as_dsl: None,
+                spine: vec![span.clone()],
+            }),
+            span,
+        });
+
+        HirExprKind::from(HirExprLam {
+            parameters: vec![name],
+            value: HirValue::of_expr(self.db, outer_call),
+        })
+    }
+
+    /// Lowers `a && b` into `if a then b else false`, so `b` is only evaluated if `a` is true.
+    fn make_and(&self, lhs: HirValue, rhs: HirValue) -> HirExprKind {
+        let false_value = HirValue::of_bool(self.db, false);
+
+        self.make_short_circuit(lhs, rhs, false_value)
+    }
+
+    /// Lowers `a || b` into `if a then true else b`, so `b` is only evaluated if `a` is false.
+    fn make_or(&self, lhs: HirValue, rhs: HirValue) -> HirExprKind {
+        let true_value = HirValue::of_bool(self.db, true);
+
+        self.make_short_circuit(lhs, true_value, rhs)
+    }
+
+    fn make_short_circuit(
+        &self,
+        cond: HirValue,
+        then: HirValue,
+        otherwise: HirValue,
+    ) -> HirExprKind {
+        HirExprKind::from(HirExprMatch {
+            scrutinee: cond,
+            cases: vec![
+                HirMatchCase {
+                    pattern: HirPattern::new_true(self.db),
+                    value: HirBranch::Expr(then),
+                },
+                HirMatchCase {
+                    pattern: HirPattern::new_false(self.db),
+                    value: HirBranch::Expr(otherwise),
+                },
+            ],
+            kind: HirMatchKind::If,
+        })
+    }
+
+    /// Lowers `-x` into a negation call, the negated literals, like `-1`, are folded into signed
+    /// literals instead.
+    fn make_unary(&self, unary: &Unary) -> HirExprKind {
+        let Expr::LiteralExpr(literal) = unary.value() else {
+            return HirExprKind::from(HirExprCall {
+                callee: HirCallee::Intrinsic(HirIntrinsic::Neg),
+                arguments: vec![self.lower(unary.value())],
+                as_dsl: None,
+                spine: vec![],
+            });
+        };
+
+        match literal.literal().data().negate() {
+            Some(negated) => HirExprKind::from(HirExprLiteral(make_literal(negated))),
+            None => {
+                unary
+                    .clone()
+                    .fail(NegatedUnsignedLiteralError)
+                    .push(self.db);
+
+                HirExprKind::Error
+            }
+        }
+    }
+
+    /// Lowers `(2 *)` into `\$section -> 2 * $section`.
+    fn make_left_section(&self, section: &LeftSection) -> HirExprKind {
+        let lhs = self.lower(section.lhs());
+
+        let resolution = section.key(ExprResolutionKey);
+
+        self.make_section(
+            section,
+            section.fn_id().symbol(),
+            resolution,
+            &["$section"],
+            |rhs| [vec![lhs], rhs].concat(),
+        )
+    }
+
+    /// Lowers `(+ 1)` into `\$section -> $section + 1`.
+    fn make_right_section(&self, section: &RightSection) -> HirExprKind {
+        let rhs = self.lower(section.rhs());
+
+        let resolution = section.key(ExprResolutionKey);
+
+        self.make_section(
+            section,
+            section.fn_id().symbol(),
+            resolution,
+            &["$section"],
+            |lhs| [lhs, vec![rhs]].concat(),
+        )
+    }
+
+    /// Lowers `(+)` into `\$lhs $rhs -> $lhs + $rhs`, so the operator can be called in the prefix
+    /// position like any other function.
+    fn make_operator_section(&self, section: &OperatorSection) -> HirExprKind {
+        let resolution = section.key(ExprResolutionKey);
+        self.make_section(
+            section,
+            section.fn_id().symbol(),
+            resolution,
+            &["$lhs", "$rhs"],
+            |values| values,
+        )
+    }
+
+    /// Lowers the sections into lambdas with the given parameters, the closure builds the
+    /// arguments of the operator from the references to the parameters.
+    fn make_section<F>(
+        &self,
+        section: &impl Located,
+        fn_id: Name,
+        resolution: Arc<ExprResolution>,
+        parameters: &[&str],
+        arguments: F,
+    ) -> HirExprKind
+    where
+        F: FnOnce(Vec<HirValue>) -> Vec<HirValue>,
+    {
+        let span = make_location(self.db, section);
+        let names = parameters
+            .iter()
+            .map(|parameter| Name::intern(parameter))
+            .collect::<Vec<_>>();
+        let values = names
+            .iter()
+            .map(|&name| {
+                let parameter = self.db.intern_expr(HirExprData {
+                    kind: HirExprKind::from(HirExprReference { name }),
+                    span: span.clone(),
+                });
+
+                HirValue::of_expr(self.db, parameter)
+            })
+            .collect();
+
+        let call = self.db.intern_expr(HirExprData {
+            kind: HirExprKind::from(HirExprCall {
+                callee: self.make_operator(section, fn_id, resolution),
+                arguments: arguments(values),
+                as_dsl: None,
+                spine: vec![],
+            }),
+            span,
+        });
+
+        HirExprKind::from(HirExprLam {
+            parameters: names,
+            value: HirValue::of_expr(self.db, call),
         })
     }
 
+    /// Lowers `"hello {name}!"` into `concat (concat "hello " name) "!"`, the empty chunks are
+    /// skipped, so `"{name}"` is lowered just into `(name)`.
+    fn make_interpolation(&self, interpolation: &Interpolation) -> HirExprKind {
+        let mut operands = vec![];
+        let mut values = interpolation.values().into_iter();
+        for chunk in interpolation.chunks() {
+            if !matches!(chunk.data(), Literal::String(text) if text.is_empty()) {
+                let literal = self.db.intern_expr(HirExprData {
+                    kind: HirExprKind::from(HirExprLiteral(make_literal(chunk.data().clone()))),
+                    span: make_location(self.db, &chunk),
+                });
+
+                operands.push(HirValue::of_expr(self.db, literal));
+            }
+
+            if let Some(value) = values.next() {
+                operands.push(self.lower(value));
+            }
+        }
+
+        let span = make_location(self.db, interpolation);
+        let mut operands = operands.into_iter();
+        let Some(mut value) = operands.next() else {
+            return HirExprKind::from(HirExprLiteral(make_literal(Literal::String(String::new()))));
+        };
+
+        for operand in operands {
+            let concat = self.db.intern_expr(HirExprData {
+                kind: HirExprKind::from(HirExprCall {
+                    callee: HirCallee::Intrinsic(HirIntrinsic::Concat),
+                    arguments: vec![value, operand],
+                    as_dsl: None,
+                    spine: vec![],
+                }),
+                span: span.clone(),
+            });
+
+            value = HirValue::of_expr(self.db, concat);
+        }
+
+        HirExprKind::from(HirExprGroup { value })
+    }
+
     fn make_if(&self, expr: &If) -> HirExprKind {
         HirExprKind::from(HirExprMatch {
-            scrutinee: self.db.hir_value(expr.cond().into()),
-            cases: hashset![
+            scrutinee: self.lower(expr.cond()),
+            cases: vec![
                 HirMatchCase {
                     pattern: HirPattern::new_true(self.db),
                     value: self.db.hir_branch(expr.then_branch().into()),
@@ -158,14 +521,14 @@ impl<'a> ExprLowering<'a> {
                 HirMatchCase {
                     pattern: HirPattern::new_false(self.db),
                     value: self.db.hir_branch(expr.else_branch().into()),
-                }
+                },
             ],
             kind: HirMatchKind::If,
         })
     }
 
     fn make_match(&self, expr: &Match) -> HirExprKind {
-        let scrutinee = self.db.hir_value(expr.scrutinee().into());
+        let scrutinee = self.lower(expr.scrutinee());
         let cases = expr
             .cases()
             .into_iter()
@@ -179,20 +542,82 @@ impl<'a> ExprLowering<'a> {
         })
     }
 
+    /// Lowers a lambda expression, the pattern parameters are replaced by fresh names, and the
+    /// body is wrapped in a match for each of them, like:
+    ///
+    /// ```haskell
+    /// \(Just x) y -> x + y
+    /// -- is lowered into
+    /// \$lam0 y -> match $lam0 { Just x => x + y }
+    /// ```
     fn make_lam(&self, expr: &Lam) -> HirExprKind {
-        let value = self.db.hir_value(expr.value().into());
+        let mut value = self.lower(expr.value());
+        let mut patterns = vec![];
         let parameters = expr
             .parameters()
             .iter()
-            .map(|parameter| self.db.intern_name(parameter.name().to_fn_id().to_string()))
+            .enumerate()
+            .map(|(index, parameter)| match parameter.pattern() {
+                Some(pattern) => {
+                    let name = Name::intern(&format!("$lam{index}"));
+                    patterns.push((name, pattern));
+                    name
+                }
+                None => parameter.name().to_fn_id().symbol(),
+            })
             .collect_vec();
 
+        for (name, pattern) in patterns.into_iter().rev() {
+            let span = make_location(self.db, &pattern);
+            let scrutinee = self.db.intern_expr(HirExprData {
+                kind: HirExprKind::from(HirExprReference { name }),
+                span: span.clone(),
+            });
+            let case = HirMatchCase {
+                pattern: self.db.hir_pattern(pattern.into()),
+                value: HirBranch::Expr(value),
+            };
+            let match_expr = self.db.intern_expr(HirExprData {
+                kind: HirExprKind::from(HirExprMatch {
+                    scrutinee: HirValue::of_expr(self.db, scrutinee),
+                    cases: vec![case],
+                    kind: HirMatchKind::Match,
+                }),
+                span,
+            });
+
+            value = HirValue::of_expr(self.db, match_expr);
+        }
+
         HirExprKind::from(HirExprLam { parameters, value })
     }
 
-    fn make_callee(&self, infix: &Infix, fn_id: &str) -> HirCallee {
-        let span = make_location(self.db, infix);
-        let name = self.db.intern_name(fn_id.into());
+    /// Lowers the operator into a call to its definition, the operators that are resolved to an
+    /// intrinsic, like `+`, are called directly.
+    fn make_operator(
+        &self,
+        node: &impl Located,
+        fn_id: Name,
+        resolution: Arc<ExprResolution>,
+    ) -> HirCallee {
+        match *resolution {
+            ExprResolution::Resolved(def) | ExprResolution::Method(def) => {
+                self.make_resolved_callee(node, fn_id, def)
+            }
+            ExprResolution::Variant(_) => HirCallee::Constructor(fn_id),
+            ExprResolution::Unresolved | ExprResolution::Do => self.make_callee(node, fn_id),
+        }
+    }
+
+    fn make_resolved_callee(&self, node: &impl Located, fn_id: Name, def: DefWithId) -> HirCallee {
+        match HirIntrinsic::from_def(self.db, def) {
+            Some(intrinsic) => HirCallee::Intrinsic(intrinsic),
+            None => self.make_callee(node, fn_id),
+        }
+    }
+
+    fn make_callee(&self, node: &impl Located, name: Name) -> HirCallee {
+        let span = make_location(self.db, node);
 
         let expr = self.db.intern_expr(HirExprData {
             kind: HirExprKind::from(HirExprReference { name }),
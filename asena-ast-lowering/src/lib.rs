@@ -1,10 +1,10 @@
 #![feature(trait_upcasting)]
 
 use asena_ast::*;
-use asena_ast_db::package::HasDiagnostic;
-use asena_hir::expr::data::HirBranch;
+use asena_ast_db::package::{HasDiagnostic, LoweringOptions};
+use asena_hir::expr::data::{HirBranch, HirMatchCase, HirMatchKind};
 use asena_hir::expr::{data::HirCallee, *};
-use asena_hir::top_level::data::{HirDeclaration, HirSignature};
+use asena_hir::top_level::data::{HirDeclaration, HirParameterKind, HirSignature};
 use asena_hir::top_level::HirBindingGroup;
 use asena_hir::{literal::*, Name};
 use asena_hir::{value::*, HirLoc};
@@ -14,24 +14,31 @@ use db::AstLowerrer;
 use decl::compute_parameters;
 use error::AstLoweringError::*;
 use expr::ExprLowering;
-use im::{hashset, HashMap};
+use im::HashMap;
 use itertools::Itertools;
 
 use crate::pattern::build_patterns;
 
 pub mod db;
 pub mod decl;
+pub mod derive;
+pub mod do_notation;
 pub mod error;
 pub mod expr;
 pub mod literal;
+pub mod macros;
 pub mod pattern;
 pub mod stmt;
 pub mod types;
 
 type Signatures = HashMap<Name, (HirLoc, HirBindingGroup)>;
 
+/// The locations of the clauses of the functions, with the number of their patterns, they're
+/// checked after the whole file is lowered, as the signature can come after the clauses.
+type Clauses = HashMap<Name, Vec<(HirLoc, usize)>>;
+
 pub(crate) fn make_signature(db: &dyn AstLowerrer, signatures: &mut Signatures, decl: &Signature) {
-    let name = db.intern_name(decl.name().to_fn_id().to_string());
+    let name = decl.name().to_fn_id().symbol();
     let span = make_location(db, decl);
 
     if let Some((loc, _)) = signatures.get(&name) {
@@ -45,12 +52,12 @@ pub(crate) fn make_signature(db: &dyn AstLowerrer, signatures: &mut Signatures,
         Some(body) => {
             let patterns = build_patterns(db, parameters.clone());
 
-            hashset![HirDeclaration {
+            vec![HirDeclaration {
                 patterns,
                 value: db.hir_block(body.into()),
             }]
         }
-        None => hashset![],
+        None => vec![],
     };
     let return_type = match decl.return_type() {
         Typed::Infer => None,
@@ -69,8 +76,20 @@ pub(crate) fn make_signature(db: &dyn AstLowerrer, signatures: &mut Signatures,
     signatures.insert(name, (span, group));
 }
 
-pub(crate) fn make_assign(db: &dyn AstLowerrer, signatures: &mut Signatures, decl: &Assign) {
-    let name = db.intern_name(decl.name().to_fn_id().to_string());
+/// Lowers the clause of the function, the clauses with the same name are grouped into the same
+/// [HirBindingGroup], in the order they're declared, like:
+///
+/// ```haskell
+/// Fact Zero = 1
+/// Fact (Succ n) = n * Fact n
+/// ```
+pub(crate) fn make_assign(
+    db: &dyn AstLowerrer,
+    signatures: &mut Signatures,
+    clauses: &mut Clauses,
+    decl: &Assign,
+) {
+    let name = decl.name().to_fn_id().symbol();
     let span = make_location(db, decl);
 
     let patterns = decl
@@ -80,16 +99,85 @@ pub(crate) fn make_assign(db: &dyn AstLowerrer, signatures: &mut Signatures, dec
         .map(|next| db.hir_pattern(next.into()))
         .collect_vec();
 
+    clauses
+        .entry(name)
+        .or_default()
+        .push((span.clone(), patterns.len()));
+
     let (_, group) = signatures
         .entry(name)
         .or_insert_with(|| (span, new_default_group(name)));
 
-    group.declarations.insert(HirDeclaration {
+    group.declarations.push(HirDeclaration {
         patterns,
         value: db.hir_value(decl.body().into()),
     });
 }
 
+/// Lowers the destructuring into a declaration for each name of the pattern, that matches the
+/// value against the pattern, like `(Pair x y) = f 1` is lowered into
+/// `x = match f 1 { Pair x y => x }`, and `y` likewise.
+pub(crate) fn make_destructure(
+    db: &dyn AstLowerrer,
+    signatures: &mut Signatures,
+    decl: &Destructure,
+) {
+    let span = make_location(db, decl);
+    let pattern = db.hir_pattern(decl.pattern().into());
+    let value = db.hir_value(decl.value().into());
+
+    for name in pattern.bindings(db) {
+        let reference = HirExprKind::from(HirExprReference { name });
+        let reference = make_expr(db, reference, span.clone());
+        let kind = HirExprKind::from(HirExprMatch {
+            scrutinee: value,
+            cases: vec![HirMatchCase {
+                pattern,
+                value: HirBranch::Expr(HirValue::of_expr(db, reference)),
+            }],
+            kind: HirMatchKind::Match,
+        });
+
+        let (_, group) = signatures
+            .entry(name)
+            .or_insert_with(|| (span.clone(), new_default_group(name)));
+
+        group.declarations.push(HirDeclaration {
+            patterns: vec![],
+            value: HirValue::of_expr(db, make_expr(db, kind, span.clone())),
+        });
+    }
+}
+
+/// Reports the clauses whose number of patterns differs from the arity of their binding group,
+/// that is the number of explicit parameters of the signature, or if the signature doesn't have
+/// them, like `Id : a -> a`, the number of patterns of the first clause.
+pub(crate) fn check_arities(db: &dyn AstLowerrer, signatures: &Signatures, clauses: &Clauses) {
+    for (name, clauses) in clauses.iter() {
+        let Some((_, group)) = signatures.get(name) else {
+            continue;
+        };
+
+        let parameters = group
+            .signature
+            .parameters
+            .iter()
+            .filter(|parameter| !matches!(parameter, HirParameterKind::Implicit(_)))
+            .count();
+        let expected = match clauses.first() {
+            Some(_) if parameters > 0 => parameters,
+            Some((_, arity)) => *arity,
+            None => continue,
+        };
+
+        let name = FunctionId(*name);
+        for (loc, arity) in clauses.iter().filter(|(_, arity)| *arity != expected) {
+            let error = ClauseArityMismatchError(name.clone(), expected, *arity);
+            loc.clone().fail(error).push(db);
+        }
+    }
+}
+
 pub(crate) fn new_default_group(name: Name) -> HirBindingGroup {
     HirBindingGroup {
         signature: HirSignature {
@@ -97,7 +185,7 @@ pub(crate) fn new_default_group(name: Name) -> HirBindingGroup {
             parameters: vec![],
             return_type: None,
         },
-        declarations: hashset![],
+        declarations: vec![],
     }
 }
 
@@ -106,17 +194,52 @@ pub fn make_location(db: &dyn AstLowerrer, node: &impl Located) -> HirLoc {
     let file = span.file.clone();
 
     let module = db.path_module(file.unwrap_or_default());
-    let file = db.vfs_file(module);
-
     HirLoc {
         original: span,
-        file: Some(file),
+        file: db.vfs_file(module),
+    }
+}
+
+/// The lowering options of the package of the node, the nodes that aren't in a package, like the
+/// virtual ones, are lowered with the default options.
+pub fn lowering_options(db: &dyn AstLowerrer, node: &impl Located) -> LoweringOptions {
+    let file = node.location().file.clone().unwrap_or_default();
+
+    db.build_system()
+        .file_package(&file)
+        .map(|package| package.lowering_options(db))
+        .unwrap_or_default()
+}
+
+/// Interns the expression, without its location if the package of the node enables the
+/// hash-consing, see [asena_hir::hash_consing].
+pub fn make_expr(db: &dyn AstLowerrer, kind: HirExprKind, span: HirLoc) -> HirExpr {
+    if lowering_options(db, &span).hash_consing {
+        HirExpr::hash_consed(db, kind)
+    } else {
+        HirExpr::new(db, kind, span)
     }
 }
 
 pub fn lower_value(db: &dyn AstLowerrer, value: AstParam<Expr>) -> HirValue {
+    lower_nested_value(db, value, 0)
+}
+
+/// Lowers the value nested `depth` values deep, the values nested deeper than the limit of the
+/// package are lowered into errors. It isn't memoized, as the depth depends on where the value
+/// is, and not only on the value.
+pub fn lower_nested_value(db: &dyn AstLowerrer, value: AstParam<Expr>, depth: usize) -> HirValue {
     let span = make_location(db, &value);
-    let mut lowering = ExprLowering::new(db);
+    if depth >= lowering_options(db, &value).max_depth {
+        value.data.clone().fail(ExprTooDeeplyNestedError).push(db);
+
+        return db.intern_value(HirValueData {
+            kind: HirValueKind::Error,
+            span,
+        });
+    }
+
+    let mut lowering = ExprLowering::new(db, depth);
     let value = HirValueBlock {
         value: {
             let span = make_location(db, &value);
@@ -1,12 +1,57 @@
 use super::*;
 
+/// Lowers the literal of the node, checking that the integers fit in their types. The literals
+/// that overflow, like `128i8`, are reported as errors, and lowered into [HirLiteral::Error], or
+/// wrapped around if the package is configured with [LoweringOptions::wrapping_integers].
+pub fn lower_literal<N>(db: &dyn AstLowerrer, node: &N, literal: Literal) -> HirLiteral
+where
+    N: Located + Clone,
+{
+    let Some((value, type_name, wrapped)) = overflow(&literal) else {
+        return make_literal(literal);
+    };
+
+    if lowering_options(db, node).wrapping_integers {
+        let warning = IntegerOverflowWarning(value, type_name, wrapped);
+        node.clone().fail(warning).push(db);
+
+        make_literal(literal)
+    } else {
+        node.clone()
+            .fail(IntegerOverflowError(value, type_name))
+            .push(db);
+
+        HirLiteral::Error
+    }
+}
+
+/// Returns the value of the integer literal, the name of its type, and the value it's wrapped
+/// around to, if it doesn't fit in its type. The parser already rejects the values that don't fit
+/// in the bits of the types, but the signed literals admit the magnitude of their minimum value,
+/// so `-128i8` can be folded, and the integers of the HIR are bounded by [usize::MAX].
+fn overflow(literal: &Literal) -> Option<(u128, &'static str, i128)> {
+    let (value, type_name, wrapped) = match *literal {
+        Literal::Int8(n, Signed::Signed) => (n as u128, "Int8", n as i8 as i128),
+        Literal::Int16(n, Signed::Signed) => (n as u128, "Int16", n as i16 as i128),
+        Literal::Int32(n, Signed::Signed) => (n as u128, "Int32", n as i32 as i128),
+        Literal::Int64(n, Signed::Signed) => (n as u128, "Int64", n as i64 as i128),
+        Literal::Int128(n, Signed::Signed) => (n, "Int128", n as usize as i128),
+        Literal::Int128(n, Signed::Unsigned) => (n, "UInt128", n as usize as i128),
+        Literal::Nat(n) => (n, "Nat", n as usize as i128),
+        _ => return None,
+    };
+
+    (i128::try_from(value) != Ok(wrapped)).then_some((value, type_name, wrapped))
+}
+
 pub fn make_literal(literal: Literal) -> HirLiteral {
     match literal {
         Literal::Error => HirLiteral::Error,
         Literal::True => HirLiteral::Int(1, HirISize::U1, HirISign::Unsigned),
         Literal::False => HirLiteral::Int(0, HirISize::U1, HirISign::Unsigned),
         Literal::String(value) => HirLiteral::String(HirString { value, name: None }),
-        Literal::Nat(_) => todo!("lowering nat literals is not yet implemented"),
+        Literal::Char(value) => HirLiteral::Char(value),
+        Literal::Nat(value) => HirLiteral::Int(value as _, HirISize::USize, HirISign::Unsigned),
         Literal::Int8(value, Signed::Signed) => {
             HirLiteral::Int(value as _, HirISize::U8, HirISign::Signed)
         }
@@ -37,23 +82,22 @@ pub fn make_literal(literal: Literal) -> HirLiteral {
         Literal::Int128(value, Signed::Unsigned) => {
             HirLiteral::Int(value as _, HirISize::U128, HirISign::Unsigned)
         }
-        Literal::Float32(value) => {
-            let s = value.clone().to_string();
-
-            let mut split = s.split('.');
-            let integer = split.next().unwrap().parse::<usize>().unwrap();
-            let decimal = split.next().unwrap_or("0").parse::<usize>().unwrap();
-
-            HirLiteral::Decimal(HirFSize::F64, HirDecimal { integer, decimal })
-        }
-        Literal::Float64(value) => {
-            let s = value.clone().to_string();
-
-            let mut split = s.split('.');
-            let integer = split.next().unwrap().parse::<usize>().unwrap();
-            let decimal = split.next().unwrap_or("0").parse::<usize>().unwrap();
+        Literal::Float32(_, digits) => HirLiteral::Decimal(HirFSize::F64, make_decimal(digits)),
+        Literal::Float64(_, digits) => HirLiteral::Decimal(HirFSize::F64, make_decimal(digits)),
+    }
+}
 
-            HirLiteral::Decimal(HirFSize::F64, HirDecimal { integer, decimal })
-        }
+/// Splits the sign of the digits of the float literal, the digits are kept as they were written,
+/// so the HIR prints `1.50` instead of `1.5`.
+fn make_decimal(digits: String) -> HirDecimal {
+    match digits.strip_prefix('-') {
+        Some(digits) => HirDecimal {
+            negative: true,
+            digits: digits.to_string(),
+        },
+        None => HirDecimal {
+            negative: false,
+            digits,
+        },
     }
 }
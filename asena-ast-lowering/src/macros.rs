@@ -0,0 +1,42 @@
+//! The macros to declare the databases of the drivers, that share the storages of the front-end
+//! of the compiler, until the lowering into the HIR.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! asena_ast_lowering::frontend_database! {
+//!     pub struct DatabaseImpl [HirDatabaseStorage, LlirStorage] {
+//!         pub logs: Mutex<Vec<salsa::Event>>,
+//!     }
+//! }
+//!
+//! impl salsa::Database for DatabaseImpl {}
+//! ```
+
+/// Declares a database with the storages of the front-end, the extra storages, between the
+/// brackets, and the extra fields are appended to them. The `salsa::Database` isn't implemented,
+/// so the drivers can handle the events.
+#[macro_export]
+macro_rules! frontend_database {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident $([$($storage:path),* $(,)?])? {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        #[salsa::database(
+            asena_prec::db::PrecStorage,
+            asena_ast_db::db::AstDatabaseStorage,
+            $crate::db::AstLowerrerStorage,
+            asena_ast_resolver::db::AstResolverStorage,
+            asena_hir::interner::HirStorage
+            $($(, $storage)*)?
+        )]
+        #[derive(Default)]
+        $(#[$attr])*
+        $vis struct $name {
+            $vis storage: salsa::Storage<$name>,
+            $($(#[$field_attr])* $field_vis $field: $ty,)*
+        }
+    };
+}
@@ -2,7 +2,7 @@ use asena_ast::Pat;
 use asena_ast_resolver::{PatResolution, PatResolutionKey};
 use asena_hir::{pattern::*, top_level::data::HirParameterKind};
 
-use crate::{db::AstLowerrer, literal::make_literal};
+use crate::{db::AstLowerrer, literal::lower_literal};
 
 use super::*;
 
@@ -13,7 +13,7 @@ pub fn lower_pattern(db: &dyn AstLowerrer, pattern: AstParam<Pat>) -> HirPattern
         Pat::SpreadPat(_) => HirPatternKind::Spread,
         Pat::UnitPat(_) => HirPatternKind::Unit,
         Pat::ConstructorPat(ref constructor) => {
-            let constructor_name = db.intern_name(constructor.name().to_fn_id().to_string());
+            let constructor_name = constructor.name().to_fn_id().symbol();
             let arguments = constructor
                 .arguments()
                 .iter()
@@ -35,14 +35,14 @@ pub fn lower_pattern(db: &dyn AstLowerrer, pattern: AstParam<Pat>) -> HirPattern
             HirPatternKind::from(HirPatternList { items })
         }
         Pat::GlobalPat(ref pat) => {
-            let name = db.intern_name(pat.name().to_fn_id().to_string());
+            let name = pat.name().to_fn_id().symbol();
 
             match &*pat.key(PatResolutionKey) {
                 PatResolution::Variant(variant) => {
                     let data = db.lookup_intern_def(*variant);
 
                     HirPatternKind::from(HirPatternConstructor {
-                        constructor_name: db.intern_name(data.name.to_string()),
+                        constructor_name: data.name.symbol(),
                         arguments: vec![],
                     })
                 }
@@ -50,10 +50,24 @@ pub fn lower_pattern(db: &dyn AstLowerrer, pattern: AstParam<Pat>) -> HirPattern
             }
         }
         Pat::LiteralPat(ref pat) => {
-            let literal = make_literal(pat.literal().data().clone());
+            let literal = lower_literal(db, pat, pat.literal().data().clone());
 
             HirPatternKind::from(HirPatternLiteral(literal))
         }
+        Pat::RangePat(ref pat) => lower_range(db, pat),
+        Pat::OrPat(ref pat) => {
+            let alternatives = pat
+                .alternatives()
+                .iter()
+                .map(|alternative| db.hir_pattern(alternative.clone().into()))
+                .collect();
+
+            HirPatternKind::from(HirPatternOr { alternatives })
+        }
+        Pat::AsPat(ref pat) => HirPatternKind::from(HirPatternAs {
+            name: pat.name().to_fn_id().symbol(),
+            pattern: db.hir_pattern(pat.pattern().into()),
+        }),
     };
 
     db.intern_pattern(HirPatternData {
@@ -62,6 +76,33 @@ pub fn lower_pattern(db: &dyn AstLowerrer, pattern: AstParam<Pat>) -> HirPattern
     })
 }
 
+/// Lowers the range pattern, the ends should be integers, and the start can't be greater than the
+/// end, otherwise the range is empty.
+fn lower_range(db: &dyn AstLowerrer, pat: &RangePat) -> HirPatternKind {
+    let start = pat.start().literal().data().clone();
+    let end = pat.end().literal().data().clone();
+
+    // The invalid literals are already reported by the lexer.
+    if matches!(start, Literal::Error) || matches!(end, Literal::Error) {
+        return HirPatternKind::Error;
+    }
+
+    let is_integer = |literal: &Literal| literal.category() == Some(LiteralCategory::Integer);
+    if !is_integer(&start) || !is_integer(&end) {
+        pat.clone().fail(RangePatternNotIntegerError).push(db);
+        return HirPatternKind::Error;
+    }
+
+    if start.contents().parse::<i128>().ok() > end.contents().parse::<i128>().ok() {
+        pat.clone().fail(EmptyRangePatternError).push(db);
+    }
+
+    HirPatternKind::from(HirPatternRange {
+        start: lower_literal(db, &pat.start(), start),
+        end: lower_literal(db, &pat.end(), end),
+    })
+}
+
 pub fn build_patterns(db: &dyn AstLowerrer, parameters: Vec<HirParameterKind>) -> Vec<HirPattern> {
     let mut patterns = Vec::new();
     for parameter in parameters {
@@ -6,7 +6,7 @@ use asena_hir::{
     value::instr::{Block, HirInstr},
 };
 
-use crate::db::AstLowerrer;
+use crate::{db::AstLowerrer, do_notation};
 
 use super::*;
 
@@ -31,6 +31,10 @@ pub fn lower_stmt(db: &dyn AstLowerrer, stmt: AstParam<Stmt>) -> Instr {
 }
 
 pub fn lower_block(db: &dyn AstLowerrer, block: AstParam<Vec<Stmt>>) -> HirValue {
+    if do_notation::is_do_block(&block.data) {
+        return do_notation::lower_do_block(db, &block.data);
+    }
+
     let mut stmts = vec![];
     let mut last = None;
 
@@ -108,7 +112,7 @@ fn make_return(db: &dyn AstLowerrer, stmt: &Return) -> Instr {
 fn make_if(db: &dyn AstLowerrer, stmt: &IfStmt) -> HirStmtKind {
     let expr = db.intern_expr(HirExprData::from(HirExprKind::from(HirExprMatch {
         scrutinee: db.hir_value(stmt.cond().into()),
-        cases: hashset![
+        cases: vec![
             HirMatchCase {
                 pattern: HirPattern::new_true(db),
                 value: db.hir_branch(stmt.then_branch().into()),
@@ -119,7 +123,7 @@ fn make_if(db: &dyn AstLowerrer, stmt: &IfStmt) -> HirStmtKind {
                     Some(else_branch) => db.hir_branch(else_branch.into()),
                     None => HirBranch::Expr(HirValue::unit(db)),
                 },
-            }
+            },
         ],
         kind: HirMatchKind::If,
     })));
@@ -13,26 +13,74 @@ pub fn lower_type(db: &dyn AstLowerrer, expr: AstParam<Expr>) -> HirType {
 
         // unsupported types yet
         Expr::Infix(_) => raise_type_expr_error(db, &expr),
-        Expr::Array(_) => raise_type_expr_error(db, &expr),
         Expr::Dsl(_) => raise_type_expr_error(db, &expr),
         Expr::Lam(_) => raise_type_expr_error(db, &expr),
         Expr::Let(_) => raise_type_expr_error(db, &expr),
         Expr::If(_) => raise_type_expr_error(db, &expr),
         Expr::Match(_) => raise_type_expr_error(db, &expr),
         Expr::Ann(_) => raise_type_expr_error(db, &expr),
-        Expr::Qual(_) => raise_type_expr_error(db, &expr),
-        Expr::Sigma(_) => raise_type_expr_error(db, &expr),
         Expr::Help(_) => raise_type_expr_error(db, &expr),
+        Expr::LeftSection(_) => raise_type_expr_error(db, &expr),
+        Expr::RightSection(_) => raise_type_expr_error(db, &expr),
+        Expr::OperatorSection(_) => raise_type_expr_error(db, &expr),
+        Expr::Interpolation(_) => raise_type_expr_error(db, &expr),
+        Expr::Unary(_) => raise_type_expr_error(db, &expr),
         Expr::LiteralExpr(_) => raise_type_literal_error(db, &expr),
 
         //
         Expr::Group(ref group) => return db.hir_type(group.value().into()),
+        Expr::Forall(ref forall) => {
+            let variables = forall
+                .variables()
+                .iter()
+                .map(|variable| variable.to_fn_id().symbol())
+                .collect();
+
+            HirTypeKind::from(HirTypeForall {
+                variables,
+                value: db.hir_type(forall.value().into()),
+            })
+        }
+        Expr::Array(ref array) => match array.items().as_slice() {
+            [item] => HirTypeKind::from(HirTypeApp {
+                callee: HirTypeFunction::Array,
+                arguments: vec![HirTypeArgument::Type(db.hir_type(item.clone().into()))],
+            }),
+            _ => raise_array_type_error(db, &expr),
+        },
+        Expr::Qual(ref qual) => {
+            let constraint = db.hir_type(qual.lhs().into());
+            let value = db.hir_type(qual.rhs().into());
+
+            HirTypeKind::from(HirTypeApp {
+                callee: HirTypeFunction::Qual,
+                arguments: vec![
+                    HirTypeArgument::Type(constraint),
+                    HirTypeArgument::Type(value),
+                ],
+            })
+        }
+        Expr::Sigma(ref sigma) => {
+            let lhs = db.hir_type(sigma.parameter_type().into());
+            let rhs = db.hir_type(sigma.return_type().into());
+            let name = sigma.parameter_name();
+            let parameter = if name.as_str().is_empty() {
+                HirTypeArgument::Type(lhs)
+            } else {
+                HirTypeArgument::Named(name.to_fn_id().symbol(), lhs)
+            };
+
+            HirTypeKind::from(HirTypeApp {
+                callee: HirTypeFunction::Sigma,
+                arguments: vec![parameter, HirTypeArgument::Type(rhs)],
+            })
+        }
         Expr::Pi(ref pi) => {
             let lhs = db.hir_type(pi.parameter_type().into());
             let rhs = db.hir_type(pi.return_type().into());
             let parameter = match pi.parameter_name() {
                 Some(name) => {
-                    let name = db.intern_name(name.to_fn_id().to_string());
+                    let name = name.to_fn_id().symbol();
 
                     HirTypeArgument::Named(name, lhs)
                 }
@@ -54,12 +102,11 @@ pub fn lower_type(db: &dyn AstLowerrer, expr: AstParam<Expr>) -> HirType {
             })
         }
         Expr::LocalExpr(ref local) => {
-            let str = local.clone().to_fn_id().to_string();
-            let name = db.intern_name(str.clone());
+            let name = local.to_fn_id().symbol();
             let mut is_constructor = false;
 
             if_chain! {
-                if let Some(c) = str.chars().next();
+                if let Some(c) = name.chars().next();
                 if c.is_uppercase();
                 then {
                     is_constructor = true;
@@ -85,6 +132,13 @@ fn raise_type_literal_error(db: &dyn AstLowerrer, expr: &Expr) -> HirTypeKind {
     HirTypeKind::Error
 }
 
+/// The array types have exactly one item, the type of the items, like `[a]`.
+fn raise_array_type_error(db: &dyn AstLowerrer, expr: &Expr) -> HirTypeKind {
+    expr.clone().fail(ArrayTypeShouldHaveOneItemError).push(db);
+
+    HirTypeKind::Error
+}
+
 fn raise_type_expr_error(db: &dyn AstLowerrer, expr: &Expr) -> HirTypeKind {
     expr.clone().fail(UnsupportedTypeExprsError).push(db);
 
@@ -0,0 +1,489 @@
+//! Tests for the lowering into the high-level intermediate representation, every test lowers
+//! the modules in the memory, and checks the declarations and the diagnostics of them.
+
+use std::sync::{Arc, Mutex};
+
+use asena_ast_db::{db::*, fixture::*, vfs::VfsFile};
+use asena_ast_lowering::db::{AstLowerrer, AstLowerrerStorage};
+use asena_ast_lowering::derive::derive_group;
+use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
+use asena_hir::eq::HirEq;
+use asena_hir::expr::HirExprKind;
+use asena_hir::file::InternalAsenaFile;
+use asena_hir::hir_type::HirType;
+use asena_hir::interner::{HirInterner, HirStorage};
+use asena_hir::top_level::{data::HirSignature, HirTopLevelKind, HirTopLevelStruct};
+use asena_hir::value::{HirValue, HirValueExpr, HirValueKind};
+use asena_hir::{dot::HirDot, sexpr::HirSexpr, HirLoc, Name};
+use asena_prec::{db::PrecStorage, PrecDatabase};
+
+#[salsa::database(
+    PrecStorage,
+    AstDatabaseStorage,
+    AstLowerrerStorage,
+    AstResolverStorage,
+    HirStorage
+)]
+#[derive(Default)]
+struct TestDatabase {
+    storage: salsa::Storage<TestDatabase>,
+}
+
+impl salsa::Database for TestDatabase {}
+
+/// Resolves and lowers the file, the diagnostics are reported to its package.
+fn lower(db: &TestDatabase, file: VfsFile) -> InternalAsenaFile {
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+
+    db.hir_file(ast.into())
+}
+
+/// Lowers the module `Test` with the source, returns the declarations as s-expressions, and the
+/// messages of its diagnostics.
+fn lower_sexpr(source: &str) -> (String, Vec<String>) {
+    let db = TestDatabase::default();
+    let file = memory_file(&db, source);
+
+    let hir = lower(&db, file);
+    let messages = db
+        .diagnostics(file)
+        .iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .collect();
+
+    (HirSexpr::new(&db).file(hir.declarations), messages)
+}
+
+#[test]
+fn implicit_parameters_works() {
+    let (hir, messages) = lower_sexpr("Id [a: Set] [Show a] (x: a) : a { x }");
+    assert!(hir.contains("([a : Set] [$instance1 : (Show a)] (x : a))"));
+
+    // The implicit parameters are type variables in the constraints of the instances.
+    assert!(messages.contains(&"could not find the type name: `Show`".to_string()));
+    assert!(!messages.contains(&"could not find the type name: `a`".to_string()));
+}
+
+#[test]
+fn default_methods_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "trait Show (a: Set) {\n",
+        "  show: a -> String,\n",
+        "  display: a -> String,\n",
+        "  default display(x: a) : String { show x }\n",
+        "}\n",
+        "instance Show Int32 {\n",
+        "  show x = \"int\"\n",
+        "}\n",
+        "instance Show Bool {\n",
+        "  display x = \"bool\"\n",
+        "}\n",
+    );
+    let file = memory_file(&db, source);
+
+    let hir = lower(&db, file);
+
+    // The instance of `Int32` inherits `display` from the trait.
+    let display = Name::intern("display");
+    let inherits = hir.declarations.iter().any(|decl| {
+        let HirTopLevelKind::Instance(instance) = db.lookup_intern_top_level(*decl).kind else {
+            return false;
+        };
+
+        instance.dictionary().map_or(false, |dictionary| {
+            dictionary.methods.contains_key(&display)
+        }) && instance.groups.len() == 2
+    });
+    assert!(inherits);
+
+    let messages = db
+        .diagnostics(file)
+        .iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .filter(|message| message.contains("instance"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![concat!(
+            "the instance doesn't implement `show`, ",
+            "and the trait doesn't have a default for it"
+        )]
+    );
+}
+
+#[test]
+fn associated_types_works() {
+    let source = concat!(
+        "trait Collection (c: Set) {\n",
+        "  type Item\n",
+        "  first: c -> Self.Item\n",
+        "}\n",
+        "instance Collection String {\n",
+        "  type Item = Int32\n",
+        "  first x = 0\n",
+        "}\n",
+        "instance Collection Bool {\n",
+        "  first x = 0\n",
+        "}\n",
+    );
+
+    let (hir, messages) = lower_sexpr(source);
+    assert!(hir.contains("(types (Item))"));
+    assert!(hir.contains("(types (Item Int32))"));
+
+    let messages = messages
+        .into_iter()
+        .filter(|message| message.contains("Item"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["the instance doesn't define the associated type `Item` of the trait"]
+    );
+}
+
+#[test]
+fn do_notation_works() {
+    let twice = concat!(
+        "Twice (m: Maybe Int32) : Maybe Int32 {\n",
+        "  x <- m\n",
+        "  y <- m\n",
+        "  return y\n",
+        "}",
+    );
+    let maybe = concat!(
+        "enum Maybe (a: Set) {\n",
+        "  Nothing : Maybe a,\n",
+        "  Just : a -> Maybe a,\n",
+        "}\n",
+        "\n",
+        "bind (m: Maybe a) (f: a -> Maybe b) : Maybe b {\n",
+        "  match m {\n",
+        "    Nothing => Nothing,\n",
+        "    Just x => f x,\n",
+        "  }\n",
+        "}\n",
+        "\n",
+        "pure (x: a) : Maybe a { Just x }\n",
+        "\n",
+    );
+
+    let (hir, messages) = lower_sexpr(&format!("{maybe}{twice}"));
+    assert!(hir.contains("(bind m (lambda (x) (bind m (lambda (y) (pure y)))))"));
+    assert!(!messages.iter().any(|message| message.contains("desugared")));
+
+    let (_, messages) = lower_sexpr(twice);
+    let messages = messages
+        .into_iter()
+        .filter(|message| message.contains("desugared"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "`<-` is desugared into `bind`, but there isn't a `bind` function in the scope",
+            "`<-` is desugared into `bind`, but there isn't a `bind` function in the scope",
+            "`return` is desugared into `pure`, but there isn't a `pure` function in the scope",
+        ]
+    );
+}
+
+#[test]
+fn streaming_diagnostics_works() {
+    let db = TestDatabase::default();
+    let (local_pkg, files) = memory_package(&db, &[("Test", "Main { 128i8 }")]);
+
+    let streamed = Arc::new(Mutex::new(vec![]));
+    let sink = streamed.clone();
+    local_pkg.on_diagnostic(&db, move |diagnostic| {
+        sink.lock()
+            .unwrap()
+            .push(diagnostic.message.value.to_string());
+    });
+
+    let ast = db.ast(files[0]);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+
+    // Nothing is reported until the lowering, where the literal overflows.
+    assert!(streamed.lock().unwrap().is_empty());
+    db.hir_file(ast.into());
+
+    let streamed = streamed.lock().unwrap().clone();
+    assert_eq!(streamed, vec!["the literal `128` doesn't fit in `Int8`"]);
+}
+
+#[test]
+fn dot_works() {
+    let db = TestDatabase::default();
+    let source = "Double (x: Int32) : Int32 { x + x }\nMain { Double 21 }";
+    let file = memory_file(&db, source);
+
+    let hir = lower(&db, file);
+
+    let dot = HirDot::new(&db);
+    let calls = dot.call_graph(hir.declarations.clone());
+    assert!(calls.contains("  \"Main\" -> \"Double\";\n"));
+    assert!(!calls.contains("\"Double\" -> "));
+    assert!(dot.tree(hir.declarations).starts_with("digraph hir {"));
+}
+
+#[test]
+fn destructuring_works() {
+    fn is_match(db: &dyn HirInterner, value: HirValue) -> bool {
+        match db.lookup_intern_value(value).kind {
+            HirValueKind::Block(block) => is_match(db, block.value),
+            HirValueKind::Expr(HirValueExpr(expr)) => {
+                matches!(db.lookup_intern_expr(expr).kind, HirExprKind::Match(_))
+            }
+            _ => false,
+        }
+    }
+
+    let db = TestDatabase::default();
+    let source = concat!(
+        "enum Pair {\n",
+        "  MkPair : Int32 -> Int32 -> Pair,\n",
+        "}\n",
+        "(MkPair x y) = MkPair 1 2\n",
+        "Swap pair = let (MkPair a b) = pair in MkPair b x\n",
+    );
+    let file = memory_file(&db, source);
+
+    let hir = lower(&db, file);
+
+    let unresolved = db
+        .diagnostics(file)
+        .iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .filter(|message| message.contains("could not find"))
+        .collect::<Vec<_>>();
+    assert!(unresolved.is_empty(), "{unresolved:?}");
+
+    // Every name of the pattern is a declaration, that matches the value against it.
+    let mut names = hir
+        .declarations
+        .iter()
+        .filter_map(|decl| match db.lookup_intern_top_level(*decl).kind {
+            HirTopLevelKind::BindingGroup(group) => {
+                let declaration = group.declarations.iter().next()?;
+                let is_match = is_match(&db, declaration.value);
+
+                Some((group.signature.name.to_string(), is_match))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            ("Swap".to_string(), true),
+            ("x".to_string(), true),
+            ("y".to_string(), true),
+        ]
+    );
+}
+
+#[test]
+fn clause_arity_mismatch_works() {
+    let source = concat!(
+        "Add 0 y = y\n",
+        "Add x = x\n",
+        "Inc (x: Int32) : Int32\n",
+        "Inc x y = x\n",
+    );
+
+    let (_, messages) = lower_sexpr(source);
+    let mut messages = messages
+        .into_iter()
+        .filter(|message| message.contains("clauses"))
+        .collect::<Vec<_>>();
+    messages.sort();
+
+    // The arity of `Inc` is the one of its signature, instead of its first clause.
+    assert_eq!(
+        messages,
+        vec![
+            "the clauses of `Add` should have 2 patterns, but this one has 1".to_string(),
+            "the clauses of `Inc` should have 1 patterns, but this one has 2".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn constructor_references_works() {
+    let source = concat!(
+        "enum Maybe (a: Set) {\n",
+        "  Nothing : Maybe a,\n",
+        "  Just : a -> Maybe a,\n",
+        "}\n",
+        "\n",
+        "Wrap (x: Int32) : Maybe Int32 { Just x }\n",
+        "Empty : Maybe Int32 { Nothing }\n",
+        "Apply (f: Int32 -> Maybe Int32) : Maybe Int32 { f 1 }\n",
+    );
+
+    let (hir, _) = lower_sexpr(source);
+
+    // The variants are applied to their constructors, even without arguments, and the
+    // other names are still references.
+    assert!(hir.contains("(Just x)"));
+    assert!(hir.contains("(Nothing)"));
+    assert!(hir.contains("(f 1)"));
+}
+
+#[test]
+fn derive_works() {
+    let maybe = "enum Maybe (a: Set) {\n  Nothing : Maybe a,\n  Just : a -> Maybe a,\n}\n";
+
+    // The methods are added to the enum, and its constructors are matched in the order of
+    // their names.
+    let (hir, _) = lower_sexpr(&format!("{maybe}#derive Maybe, Eq, Show"));
+    assert!(hir.contains("(fun eq ((lhs : (Maybe a)) (rhs : (Maybe a))) Bool"));
+    assert!(hir.contains("((Just x0) (match/Match rhs ((Just y0) (== x0 y0))"));
+    assert!(hir.contains(r#"((Just x0) (concat "Just" (concat " " (show x0))))"#));
+
+    let (_, messages) = lower_sexpr(&format!("{maybe}#derive Maybe, Ord"));
+    assert!(messages.contains(&"can't derive Ord, only Eq and Show can be derived".to_string()));
+
+    let (_, messages) = lower_sexpr(&format!("{maybe}#derive Pair, Eq"));
+    assert!(messages.contains(&"can't find the enum or the struct Pair in the file".to_string()));
+}
+
+#[test]
+fn derive_struct_works() {
+    let db = TestDatabase::default();
+    let int32 = HirType::constructor(&db, Name::intern("Int32"));
+    let point = HirTopLevelKind::from(HirTopLevelStruct {
+        signature: HirSignature {
+            name: Name::intern("Point"),
+            parameters: vec![],
+            return_type: None,
+        },
+        fields: im::hashmap! { Name::intern("x") => int32, Name::intern("y") => int32 },
+        groups: Default::default(),
+    });
+
+    // The struct has a single constructor, with its fields.
+    let group = derive_group(&db, &point, "Show", HirLoc::default()).unwrap();
+    assert_eq!(
+        HirSexpr::new(&db).binding_group(&group),
+        concat!(
+            "(fun show ((value : Point)) String (= (value) (match/Match value ((Point x0 x1) ",
+            r#"(concat (concat "Point" (concat " " (show x0))) (concat " " (show x1)))))))"#,
+        )
+    );
+}
+
+#[test]
+fn hir_eq_works() {
+    let db = TestDatabase::default();
+    let file = memory_file(&db, "A { 1 + 2 }\nB { 1 + 2 }\nC { 1 + 3 }");
+
+    let hir = lower(&db, file);
+    let value = |name: &str| {
+        hir.declarations
+            .iter()
+            .find_map(|decl| match db.lookup_intern_top_level(*decl).kind {
+                HirTopLevelKind::BindingGroup(group) if group.signature.name.as_str() == name => {
+                    Some(group.declarations.iter().next()?.value)
+                }
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    // The values are at different locations, so they're interned with different ids, but they're
+    // structurally the same.
+    assert_ne!(value("A"), value("B"));
+    assert!(value("A").hir_eq(&value("B"), &db));
+    assert!(!value("A").hir_eq(&value("C"), &db));
+}
+
+#[test]
+fn class_superclasses_works() {
+    let source = concat!(
+        "trait Eq (a: Set) {\n",
+        "  eq: a -> a -> Bool\n",
+        "}\n",
+        "class Ord (a: Set) where Eq a {\n",
+        "  value: a\n",
+        "}\n",
+        "class Point {\n",
+        "  x: Int32\n",
+        "}\n",
+    );
+
+    let (hir, messages) = lower_sexpr(source);
+    assert!(hir.contains("(class Ord ((a : Set)) _ (superclasses (Eq a))"));
+    assert!(hir.contains("(class Point () _ (superclasses)"));
+    assert!(!messages.iter().any(|message| message.contains("`Eq`")));
+}
+
+#[test]
+fn type_exprs_works() {
+    let source = concat!(
+        "Pair (p: Σ [x: Int32] -> Int32) : Int32 { 0 }\n",
+        "Shown [a: Set] (x: Show a => a) : a { x }\n",
+        "Items (xs: [Int32]) : [Int32] { xs }\n",
+        "Tuple (xs: [Int32, Int32]) : Int32 { 0 }\n",
+    );
+
+    let (hir, messages) = lower_sexpr(source);
+    assert!(hir.contains("(p : (** (x : Int32) Int32))"));
+    assert!(hir.contains("(x : (=> (Show a) a))"));
+    assert!(hir.contains("((xs : ([] Int32))) ([] Int32)"));
+
+    let messages = messages
+        .into_iter()
+        .filter(|message| message.contains("array"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["array types should have exactly one item, the type of the items"]
+    );
+}
+
+#[test]
+fn duplicated_instance_methods_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "trait Show (a: Set) {\n",
+        "  display: a -> String,\n",
+        "  default display(x: a) : String { \"default\" }\n",
+        "}\n",
+        "instance Show Bool {\n",
+        "  fun display(x: Bool) : String { \"method\" }\n",
+        "  display x = \"impl\"\n",
+        "}\n",
+    );
+    let file = memory_file(&db, source);
+
+    let hir = lower(&db, file);
+    let groups = hir
+        .declarations
+        .iter()
+        .find_map(|decl| match db.lookup_intern_top_level(*decl).kind {
+            HirTopLevelKind::Instance(instance) => Some(instance.groups),
+            _ => None,
+        })
+        .unwrap();
+
+    // The method is emitted once, and the default of the trait isn't inherited.
+    assert_eq!(groups.len(), 1);
+    let group = groups.iter().next().unwrap();
+    assert_eq!(group.signature.name, Name::intern("display"));
+    assert_eq!(group.declarations.len(), 1);
+
+    let messages = db
+        .diagnostics(file)
+        .iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .filter(|message| message.contains("duplicate"))
+        .collect::<Vec<_>>();
+    assert_eq!(messages, vec!["duplicate method definition"]);
+}
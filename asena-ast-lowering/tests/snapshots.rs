@@ -0,0 +1,310 @@
+//! Snapshot tests for the front-end of the compiler, every `.ase` file in the `testdata/`
+//! directory is parsed, resolved and lowered, and the results are compared with the `.snap`
+//! file with the same name.
+//!
+//! The missing snapshots fail the tests, to write them, or to update the existing ones, run the
+//! tests with `UPDATE_SNAPSHOTS=1`, and commit the `.snap` files.
+
+use std::{path::PathBuf, sync::Arc};
+
+use asena_ast::{Command, Infix};
+use asena_ast_db::code_action::{apply_code_actions, code_actions};
+use asena_ast_db::{ast_id::AstId, commands::parse_expansion, db::AstDatabase, package::*, vfs::*};
+use asena_ast_lowering::db::{AstLowerrer, AstLowerrerStorage};
+use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
+use asena_hir::{hash_consing, interner::HirStorage, sexpr::HirSexpr};
+use asena_leaf::ast::{Located, Node};
+use asena_leaf::token::token_set::HasTokens;
+use asena_prec::{db::PrecStorage, PrecDatabase};
+
+#[salsa::database(
+    PrecStorage,
+    AstDatabaseStorage,
+    AstLowerrerStorage,
+    AstResolverStorage,
+    HirStorage
+)]
+#[derive(Default)]
+struct TestDatabase {
+    storage: salsa::Storage<TestDatabase>,
+}
+
+impl salsa::Database for TestDatabase {}
+
+fn testdata() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata")
+}
+
+/// Loads the file of the `testdata/` directory with the name into its own package.
+fn testdata_file(db: &TestDatabase, name: &str) -> (Package, VfsFile) {
+    let fs = FileSystem {
+        base_dir: Some(testdata()),
+        ..Default::default()
+    };
+    let local_pkg = Package::new(db, "Local", "0.0.0", Arc::new(fs));
+    let path = format!("./{name}.ase");
+    let file = VfsFileData::new(db, name, path.as_str().into(), local_pkg);
+
+    db.global_scope().write().unwrap().import(db, file, None);
+
+    (local_pkg, file)
+}
+
+fn render_snapshot(name: &str) -> String {
+    let db = TestDatabase::default();
+    let (local_pkg, file) = testdata_file(&db, name);
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+
+    let tree = format!("{:#?}", &*ast);
+    let hir = db.hir_file(ast.into());
+
+    let hir = HirSexpr::new(&db).file(hir.declarations);
+    let diagnostics = db.lookup_intern_package(local_pkg).render_diagnostics(&db);
+
+    format!(
+        "---- tree ----\n{tree}\n\
+         ---- hir ----\n{hir}\n\
+         ---- diagnostics ----\n{diagnostics}"
+    )
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = testdata().join(name).with_extension("snap");
+    let update = std::env::var("UPDATE_SNAPSHOTS").map_or(false, |value| value == "1");
+    if update {
+        std::fs::write(&path, actual).expect("failed to write the snapshot");
+        return;
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(expected) => assert!(
+            expected == actual,
+            "snapshot `{name}` does not match, rerun with UPDATE_SNAPSHOTS=1 to update it:\n\
+             ---- expected ----\n{expected}\n---- actual ----\n{actual}"
+        ),
+        Err(_) => panic!(
+            "snapshot `{name}` is missing, rerun with UPDATE_SNAPSHOTS=1 to write it:\n\
+             ---- actual ----\n{actual}"
+        ),
+    }
+}
+
+#[test]
+fn snapshots_works() {
+    let mut entries = std::fs::read_dir(testdata())
+        .expect("failed to read the testdata directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "ase"))
+        .collect::<Vec<_>>();
+
+    entries.sort();
+
+    for path in entries {
+        let name = path.file_stem().unwrap().to_str().unwrap();
+        let actual = render_snapshot(name);
+
+        assert_snapshot(name, &actual);
+    }
+}
+
+/// Lowers the file of the `testdata/` directory, returns the messages of its diagnostics, with
+/// the source code at their spans.
+fn testdata_diagnostics(name: &str) -> Vec<(String, String)> {
+    let db = TestDatabase::default();
+    let (_, file) = testdata_file(&db, name);
+    let source = db.source(file);
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+    db.hir_file(ast.into());
+
+    db.diagnostics(file)
+        .iter()
+        .map(|diagnostic| {
+            let span = diagnostic.message.span.range.clone().into_ranged().unwrap();
+
+            (
+                diagnostic.message.value.to_string(),
+                source[span].to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn pattern_arity_diagnostics_works() {
+    let diagnostics = testdata_diagnostics("pattern_arity")
+        .into_iter()
+        .filter(|(message, _)| message.contains("pattern"))
+        .collect::<Vec<_>>();
+
+    // The spans of the patterns can reach the next token, so only their starts are checked
+    let expected = [
+        (
+            "the constructor `Just` has 1 fields, but the pattern applies 2 arguments",
+            "y",
+        ),
+        (
+            "expected a char pattern, but found a string literal",
+            "\"+\"",
+        ),
+        (
+            "expected a integer pattern, but found a char literal",
+            "'a'",
+        ),
+        (
+            "expected a integer pattern, but found a char literal",
+            "'a'",
+        ),
+    ];
+
+    assert_eq!(diagnostics.len(), expected.len(), "{diagnostics:#?}");
+    for ((message, text), (expected_message, expected_text)) in diagnostics.iter().zip(expected) {
+        assert_eq!(message, expected_message);
+        assert!(text.starts_with(expected_text), "{text:?} at {message}");
+    }
+}
+
+#[test]
+fn unresolved_diagnostics_works() {
+    let diagnostics = testdata_diagnostics("unresolved")
+        .into_iter()
+        .filter(|(message, _)| message.starts_with("could not find"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        diagnostics,
+        vec![(
+            "could not find the value name: `unknown`".to_string(),
+            "unknown".to_string()
+        )]
+    );
+}
+
+/// Lowers the module with the lowering options, returns the rendered diagnostics.
+fn lower_module_with(source: &str, options: LoweringOptions) -> String {
+    lower_module_in(&TestDatabase::default(), source, options)
+}
+
+fn lower_module_in(db: &TestDatabase, source: &str, options: LoweringOptions) -> String {
+    let fs = FileSystem::default();
+    fs.memory.insert("Test".into(), source.into());
+
+    let vfs = Arc::new(fs);
+    let local_pkg = Package::with_options(db, "Local", "0.0.0", vfs, Default::default(), options);
+    let file = VfsFileData::new(db, "Test", "./Test.ase".into(), local_pkg);
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+    db.hir_file(ast.into());
+
+    db.lookup_intern_package(local_pkg).render_diagnostics(db)
+}
+
+#[test]
+fn lowering_depth_works() {
+    // The infix chain is parsed in a loop, but it's lowered into nested values
+    let code = format!("Main {{ {}1 }}", "1 + ".repeat(64));
+
+    let shallow = LoweringOptions {
+        max_depth: 16,
+        ..Default::default()
+    };
+    assert!(lower_module_with(&code, shallow).contains("too deeply nested"));
+
+    let default = LoweringOptions::default();
+    assert!(!lower_module_with(&code, default).contains("too deeply nested"));
+}
+
+#[test]
+fn lowering_hash_consing_works() {
+    let code = "Id x = x\nMain { Id Id }";
+
+    let db = TestDatabase::default();
+    lower_module_in(&db, code, LoweringOptions::default());
+    assert_eq!(hash_consing::take_statistics(&db).requests, 0);
+
+    let db = TestDatabase::default();
+    let options = LoweringOptions {
+        hash_consing: true,
+        ..Default::default()
+    };
+    lower_module_in(&db, code, options);
+    assert!(hash_consing::take_statistics(&db).hits > 0);
+}
+
+#[test]
+fn expansion_origin_works() {
+    let db = TestDatabase::default();
+
+    let fs = FileSystem::default();
+    fs.memory.insert("Test".into(), "#answer".into());
+
+    let local_pkg = Package::new(&db, "Local", "0.0.0", Arc::new(fs));
+    let file = VfsFileData::new(&db, "Test", "./Test.ase".into(), local_pkg);
+    let command = db.ast(file).descendants_of::<Command>().next().unwrap();
+
+    // The expanded declarations are located at the command, and derived from it
+    let declarations = parse_expansion(&command, "Answer { 42 }").unwrap();
+    let tree = declarations[0].clone().unwrap();
+    assert_eq!(tree.location(), command.location());
+    assert_eq!(tree.origin().as_ref(), Some(command.location().as_ref()));
+    assert!(tree
+        .tokens()
+        .iter()
+        .all(|token| token.span == *command.location()));
+}
+
+#[test]
+fn ast_ids_works() {
+    let db = TestDatabase::default();
+
+    let fs = FileSystem::default();
+    fs.memory.insert("Test".into(), "Main { 1 + 2 }".into());
+
+    let local_pkg = Package::new(&db, "Local", "0.0.0", Arc::new(fs));
+    let file = VfsFileData::new(&db, "Test", "./Test.ase".into(), local_pkg);
+
+    let ids = db.ast_ids(file);
+    let infix = db.ast(file).descendants_of::<Infix>().next().unwrap();
+
+    let id = ids.id_of(&infix).unwrap();
+    assert_eq!(ids.get(id).map(|node| node.kind()), Some(infix.kind()));
+    assert_eq!(ids.id_of(ids.get(AstId(0)).unwrap()), Some(AstId(0)));
+}
+
+#[test]
+fn code_actions_works() {
+    let db = TestDatabase::default();
+
+    let fs = FileSystem::default();
+    fs.memory.insert(
+        "Math".into(),
+        "pub Double (x: Int32) : Int32 { x + x }".into(),
+    );
+    fs.memory.insert("Test".into(), "Main { Double 21 }".into());
+
+    let local_pkg = Package::new(&db, "Local", "0.0.0", Arc::new(fs));
+    VfsFileData::new(&db, "Math", "./Math.ase".into(), local_pkg);
+    let file = VfsFileData::new(&db, "Test", "./Test.ase".into(), local_pkg);
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    db.ast_resolved_file(ast.into());
+
+    let fixes = code_actions(&db, file);
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(
+        apply_code_actions(&db, file, &fixes),
+        "use Math\nMain { Double 21 }"
+    );
+}
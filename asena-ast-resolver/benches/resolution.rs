@@ -0,0 +1,47 @@
+//! Benchmarks for the resolution, that memoizes the scopes and the resolutions in the metadata
+//! of the nodes, run with `cargo bench -p asena-ast-resolver`. The arenas of the databases are
+//! in the default storage mode, to benchmark the inline metadata, run them with
+//! `--features asena-leaf/inline-metadata`.
+
+#![feature(test)]
+
+extern crate test;
+
+use std::sync::Arc;
+
+use asena_ast::AsenaFile;
+use asena_ast_db::{db::AstDatabase, package::*, vfs::*};
+use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
+use asena_prec::{db::PrecStorage, PrecDatabase};
+use test::Bencher;
+
+#[salsa::database(PrecStorage, AstDatabaseStorage, AstResolverStorage)]
+#[derive(Default)]
+struct BenchDatabase {
+    storage: salsa::Storage<BenchDatabase>,
+}
+
+impl salsa::Database for BenchDatabase {}
+
+#[bench]
+fn bench_parse_and_resolve(b: &mut Bencher) {
+    b.iter(parse_and_resolve);
+}
+
+/// Parses and resolves the file in a new database, as the queries are memoized.
+fn parse_and_resolve() -> AsenaFile {
+    let db = BenchDatabase::default();
+
+    let fs = FileSystem::default();
+    let source = include_str!("../../asena-grammar/tests/simple.ase");
+    fs.memory.insert("Simple".into(), source.into());
+
+    let local_pkg = Package::new(&db, "Local", "0.0.0", Arc::new(fs));
+    let file = VfsFileData::new(&db, "Simple", "./Simple.ase".into(), local_pkg);
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+
+    db.ast_resolved_file(ast.into())
+}
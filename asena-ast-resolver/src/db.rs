@@ -1,6 +1,7 @@
 use asena_ast::AsenaFile;
-use asena_ast_db::db::AstDatabase;
+use asena_ast_db::{db::AstDatabase, prelude::import_prelude};
 use asena_leaf::ast::{AstParam, Located};
+use asena_report::profile::{self, ProfilePhase};
 
 use crate::decl::AstResolver;
 
@@ -10,15 +11,30 @@ pub trait AstResolverDatabase: AstDatabase {
 }
 
 fn ast_resolved_file(db: &dyn AstResolverDatabase, ast: AstParam<AsenaFile>) -> AsenaFile {
+    let _arena = db.node_arena().enter();
+    profile::phase(ProfilePhase::Resolution, || resolve_file(db, ast))
+}
+
+fn resolve_file(db: &dyn AstResolverDatabase, ast: AstParam<AsenaFile>) -> AsenaFile {
     let module = db.location_file(ast.location().into_owned());
-    let file = db.vfs_file(module);
-    ast.data.walks(AstResolver {
-        db,
-        file,
-        binding_groups: Default::default(),
-        class_declarations: Default::default(),
-        enum_declarations: Default::default(),
-        instance_declarations: Default::default(),
-        trait_declarations: Default::default(),
-    })
+    // The file that isn't in the global scope is reported by `location_file`.
+    let Some(file) = db.vfs_file(module) else {
+        return ast.data.clone();
+    };
+    let name = db.lookup_intern_vfs_file(file).name.clone();
+    let _span = tracing::debug_span!("resolve_file", module = %name).entered();
+
+    // The shapes are validated on the original tree, before the resolution changes it.
+    asena_ast_validate::validate_file(db, &ast.data);
+
+    import_prelude(db, file, &ast.data);
+
+    // The names are declared after the prelude is imported, so they shadow the prelude ones.
+    let resolver = AstResolver::new(db, file);
+    resolver.declare_items();
+
+    let resolved = ast.data.walks(resolver);
+    crate::signatures::check_signatures(db, &resolved);
+
+    resolved
 }
@@ -1,5 +1,6 @@
-use asena_ast_db::db::AstDatabase;
+use asena_ast_db::{db::AstDatabase, def::DefWithId, package::HasDiagnostic, ModuleRef};
 use asena_leaf::ast::Located;
+use asena_report::WithError;
 use asena_span::Spanned;
 
 use crate::{scopes::*, *};
@@ -17,6 +18,10 @@ pub struct AstResolver<'db> {
     pub class_declarations: im::HashMap<FunctionId, Class>,
     pub trait_declarations: im::HashMap<FunctionId, Trait>,
     pub instance_declarations: Vec<Instance>, // TODO: change to hashset
+
+    /// The associated types of the trait, the class, or the instance that is being resolved,
+    /// they're bound as `Self.Item` in the scopes of its declarations.
+    pub associated_types: im::HashMap<FunctionId, DefWithId>,
 }
 
 impl<'db> AstResolver<'db> {
@@ -29,22 +34,63 @@ impl<'db> AstResolver<'db> {
             class_declarations: Default::default(),
             trait_declarations: Default::default(),
             instance_declarations: Default::default(),
+            associated_types: Default::default(),
         }
     }
 
-    pub fn resolve_method(&mut self, method: Method) {
-        let mut resolver = ScopeResolver::new(method.name(), Level::Value, self);
+    /// Declares the top-level names of the file in its scope, it's the first pass of the
+    /// resolution, so the bodies resolved in the second pass can reference the declarations that
+    /// come later in the file.
+    pub fn declare_items(&self) {
+        let data = self.db.lookup_intern_vfs_file(self.file);
+        let mut scope = data.scope.write().unwrap();
+
+        scope.import(self.db, self.file, None);
+    }
+
+    /// The root scope of the declarations of the file, with the global bindings and the ones
+    /// imported into the file.
+    pub fn file_scope(&self) -> ScopeRef {
+        let file = self.db.lookup_intern_vfs_file(self.file);
+
+        self.db.global_scope().file(&file)
+    }
+
+    /// The scope of a declaration, with the associated types of the enclosing trait, class, or
+    /// instance, if there's one.
+    pub fn declaration_scope(&self) -> ScopeRef {
+        let scope = self.file_scope().child();
+        scope
+            .write()
+            .unwrap()
+            .types
+            .extend(self.associated_types.clone());
+
+        scope
+    }
 
-        for (name, parameter) in Parameter::compute_parameters(method.parameters()) {
-            let mut scope = resolver.local_scope.write().unwrap();
-            let def = DefWithId::new(
-                resolver.owner.db,
-                parameter.name(),
-                parameter.location().into_owned(),
-            );
+    /// Binds the associated types as `Self.Item`, for the declarations resolved until they're
+    /// cleared by [Self::clear_associated_types].
+    pub fn bind_associated_types(&mut self, associated_types: &[AssociatedType]) {
+        for associated_type in associated_types {
+            let name = FunctionId::new(&format!("Self.{}", associated_type.name().to_fn_id()));
+            let location = associated_type.location().into_owned();
+            let def = DefWithId::new(self.db, associated_type.name(), location);
 
-            scope.functions.insert(name, def);
+            self.associated_types.insert(name, def);
         }
+    }
+
+    pub fn clear_associated_types(&mut self) {
+        self.associated_types.clear();
+    }
+
+    pub fn resolve_method(&mut self, method: Method) {
+        let mut resolver = ScopeResolver::new(method.name(), Level::Value, self);
+        let name = method.name().to_fn_id();
+        resolver.receiver = Receiver::of_method(resolver.owner.db, name, &method.parameters());
+
+        resolver.bind_parameters(&method.parameters());
 
         resolver.listens(method.return_type());
         resolver.listens(method.body());
@@ -52,17 +98,10 @@ impl<'db> AstResolver<'db> {
 
     pub fn resolve_default_method(&mut self, method: DefaultMethod) {
         let mut resolver = ScopeResolver::new(method.name(), Level::Value, self);
+        let name = method.name().to_fn_id();
+        resolver.receiver = Receiver::of_method(resolver.owner.db, name, &method.parameters());
 
-        for (name, parameter) in Parameter::compute_parameters(method.parameters()) {
-            let mut scope = resolver.local_scope.write().unwrap();
-            let def = DefWithId::new(
-                resolver.owner.db,
-                parameter.name(),
-                parameter.location().into_owned(),
-            );
-
-            scope.functions.insert(name, def);
-        }
+        resolver.bind_parameters(&method.parameters());
 
         resolver.listens(method.return_type());
         resolver.listens(method.body());
@@ -74,6 +113,22 @@ impl<'ctx> AsenaVisitor<()> for AstResolver<'ctx> {
         let fn_id = Spanned::new(value.location().into_owned(), value.to_fn_id());
         let module_ref = self.db.module_ref(fn_id);
 
+        // The module that isn't found is already reported by `module_ref`.
+        let ModuleRef::Found(module) = module_ref else {
+            return;
+        };
+
+        if let Some(cycle) = self.db.import_cycle(self.file, module) {
+            let path = cycle
+                .into_iter()
+                .map(|module| self.db.lookup_intern_vfs_file(module).name)
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            value.fail(ImportCycleError(path)).push(self.db);
+            return;
+        }
+
         self.db.add_path_dep(self.file, module_ref);
     }
 
@@ -87,16 +142,7 @@ impl<'ctx> AsenaVisitor<()> for AstResolver<'ctx> {
         let name = signature.name();
         let mut resolver = ScopeResolver::new(name, Level::Value, self);
 
-        for (name, parameter) in Parameter::compute_parameters(signature.parameters()) {
-            let mut scope = resolver.local_scope.write().unwrap();
-            let def = DefWithId::new(
-                resolver.owner.db,
-                parameter.name(),
-                parameter.location().into_owned(),
-            );
-
-            scope.functions.insert(name, def);
-        }
+        resolver.bind_parameters(&signature.parameters());
 
         resolver.listens(signature.return_type());
         resolver.listens(signature.body());
@@ -119,6 +165,15 @@ impl<'ctx> AsenaVisitor<()> for AstResolver<'ctx> {
         resolver.listens(assign.body());
     }
 
+    /// The names of the pattern are items of the file, so they're already in scope, and the
+    /// pattern is resolved like the ones of the cases.
+    fn visit_destructure(&mut self, destructure: Destructure) {
+        let mut resolver = ScopeResolver::empty(Level::Value, self);
+
+        resolver.listens(destructure.pattern());
+        resolver.listens(destructure.value());
+    }
+
     fn visit_enum(&mut self, enum_decl: Enum) {
         self.resolve_enum_decl(enum_decl);
     }
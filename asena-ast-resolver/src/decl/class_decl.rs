@@ -15,6 +15,7 @@ impl AstResolver<'_> {
             .unwrap()
             .create_class(self.db, &class, None);
 
+        self.bind_associated_types(&class.associated_types());
         let mut resolver = ScopeResolver::new(class.name(), Level::Value, self);
 
         for (name, parameter) in Parameter::compute_parameters(class.parameters()) {
@@ -34,5 +35,6 @@ impl AstResolver<'_> {
         for method in class.methods() {
             self.resolve_method(method);
         }
+        self.clear_associated_types();
     }
 }
@@ -9,7 +9,8 @@ impl AstResolver<'_> {
     pub fn resolve_instance_decl(&mut self, instance_decl: Instance) {
         self.instance_declarations.push(instance_decl.clone());
 
-        let resolver = ScopeResolver::empty(Level::Value, self);
+        self.bind_associated_types(&instance_decl.associated_types());
+        let mut resolver = ScopeResolver::empty(Level::Value, self);
 
         for (name, parameter) in Parameter::compute_parameters(instance_decl.parameters()) {
             let mut scope = resolver.local_scope.write().unwrap();
@@ -23,8 +24,28 @@ impl AstResolver<'_> {
             scope.types.insert(name.clone(), def);
         }
 
+        for associated_type in instance_decl.associated_types() {
+            resolver.listens(associated_type.value());
+        }
+
         for method in instance_decl.methods() {
             self.resolve_method(method);
         }
+
+        for method_impl in instance_decl.impls() {
+            self.resolve_method_impl(method_impl);
+        }
+        self.clear_associated_types();
+    }
+
+    /// Resolves the implementation like an [Assign], the patterns bind the variables of the body.
+    pub fn resolve_method_impl(&mut self, method_impl: MethodImpl) {
+        let mut resolver = ScopeResolver::new(method_impl.name(), Level::Value, self);
+
+        for pat in method_impl.patterns() {
+            resolver.listens(pat);
+        }
+
+        resolver.listens(method_impl.body());
     }
 }
@@ -16,6 +16,7 @@ impl AstResolver<'_> {
             .unwrap()
             .create_trait(self.db, &trait_decl, None);
 
+        self.bind_associated_types(&trait_decl.associated_types());
         let mut resolver = ScopeResolver::new(trait_decl.name(), Level::Value, self);
 
         for (name, parameter) in Parameter::compute_parameters(trait_decl.parameters()) {
@@ -34,5 +35,6 @@ impl AstResolver<'_> {
         for method in trait_decl.default_methods() {
             self.resolve_default_method(method);
         }
+        self.clear_associated_types();
     }
 }
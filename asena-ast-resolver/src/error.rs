@@ -17,6 +17,78 @@ pub enum ResolutionError {
 
     #[error("could not find the type constructor: `{0}`")]
     UnresolvedConstructorError(FunctionId),
+
+    #[error("`{0}` is never used")]
+    UnusedDefinitionWarning(FunctionId),
+
+    #[error("the constructor `{0}` has {1} fields, but the pattern applies {2} arguments")]
+    PatternArityMismatchError(FunctionId, usize, usize),
+
+    #[error("expected a {0} pattern, but found a {1} literal")]
+    LiteralPatternMismatchError(LiteralCategory, LiteralCategory),
+
+    #[error("`{0}` is private to its module")]
+    PrivateNameError(FunctionId),
+
+    #[error("`{0}` is defined here, without `pub`")]
+    PrivateDefinitionHint(FunctionId),
+
+    #[error("the modules use each other: {0}")]
+    ImportCycleError(String),
+
+    #[error("the module `{0}` is never used")]
+    UnusedImportWarning(FunctionId),
+
+    #[error("the match doesn't cover the constructors: {0}")]
+    NonExhaustiveMatchWarning(String),
+
+    #[error("the match doesn't cover the integers: {0}")]
+    NonExhaustiveIntegersWarning(String),
+
+    #[error("the match doesn't cover the lists with the lengths: {0}")]
+    NonExhaustiveListsWarning(String),
+
+    #[error("the case is unreachable, the pattern `{0}` is matched by a previous case")]
+    DuplicateCaseWarning(String),
+
+    #[error("did you mean `{0}`?")]
+    SimilarNameHint(FunctionId),
+
+    #[error("`self` can only be used in the methods")]
+    SelfOutsideMethodError,
+
+    #[error("the method `{0}` doesn't have the `self` parameter")]
+    SelfWithoutParameterError(FunctionId),
+
+    #[error("the block of `{0}` should have {1} parameters, but it has {2}")]
+    DslArityMismatchError(FunctionId, usize, usize),
+
+    #[error("every variant of `{0}` has a field of its own type, so its values are infinite")]
+    InfiniteEnumError(FunctionId),
+
+    #[error("`{0}` is at the left of an arrow in the field, so it isn't strictly positive")]
+    NonPositiveEnumError(FunctionId),
+
+    #[error("the name of the as-pattern should be a variable, but `{0}` is a path")]
+    AsPatternNameError(FunctionId),
+
+    #[error("the alternatives of the or-pattern bind different names: {0}")]
+    OrPatternBindingsMismatchError(String),
+
+    #[error("the list pattern can have only one rest `..`")]
+    DuplicatedSpreadPatternError,
+
+    #[error("the signature of `{0}` doesn't have a body, nor clauses that define it")]
+    SignatureWithoutDefinitionWarning(FunctionId),
+
+    #[error("`{0}` is defined without a signature")]
+    DefinitionWithoutSignatureWarning(FunctionId),
+
+    #[error("`{0}` is defined by both the body of its signature and clauses")]
+    DuplicatedDefinitionError(FunctionId),
+
+    #[error("the signature of `{0}` is here")]
+    SignatureDefinitionHint(FunctionId),
 }
 
 impl ResolutionError {
@@ -34,6 +106,16 @@ impl InternalError for ResolutionError {
     }
 
     fn kind(&self) -> asena_report::DiagnosticKind {
-        asena_report::DiagnosticKind::Error
+        match self {
+            Self::UnusedDefinitionWarning(_)
+            | Self::UnusedImportWarning(_)
+            | Self::NonExhaustiveMatchWarning(_)
+            | Self::NonExhaustiveIntegersWarning(_)
+            | Self::NonExhaustiveListsWarning(_)
+            | Self::DuplicateCaseWarning(_)
+            | Self::SignatureWithoutDefinitionWarning(_)
+            | Self::DefinitionWithoutSignatureWarning(_) => asena_report::DiagnosticKind::Warning,
+            _ => asena_report::DiagnosticKind::ResolutionError,
+        }
     }
 }
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use asena_ast::*;
 
-use asena_ast_db::scope::VariantResolution;
+use asena_ast_db::scope::{ScopeRef, VariantResolution};
 use asena_ast_db::vfs::*;
 
 use asena_leaf::ast::Lexeme;
@@ -20,12 +20,36 @@ pub mod db;
 pub mod decl;
 pub mod error;
 pub mod scopes;
+pub mod signatures;
+pub mod suggestions;
 
 #[derive(Default, Clone)]
 pub enum ExprResolution {
     #[default]
     Unresolved,
     Resolved(DefWithId),
+
+    /// The name is a variant of an enum, like `Just`, so it's lowered into a constructor, and
+    /// not into a reference to a function.
+    Variant(DefWithId),
+
+    /// The name is a method of a class or trait, like `Point.show`.
+    Method(DefWithId),
+
+    /// The name is the runner of the do blocks, like `do` in `do { x <- m; return x }`.
+    Do,
+}
+
+impl ExprResolution {
+    /// The definition that the name resolves to, be it a function, a variant or a method.
+    pub fn def(&self) -> Option<DefWithId> {
+        match self {
+            ExprResolution::Unresolved | ExprResolution::Do => None,
+            ExprResolution::Resolved(def)
+            | ExprResolution::Variant(def)
+            | ExprResolution::Method(def) => Some(*def),
+        }
+    }
 }
 
 #[derive(Default, Clone)]
@@ -54,3 +78,14 @@ ast_key! {
 ast_key! {
     pub struct PatResolutionKey : PatResolution;
 }
+
+ast_key! {
+    /// The local bindings in scope at a hole, sorted by their names.
+    pub struct HoleBindingsKey : Vec<FunctionId>;
+}
+
+ast_key! {
+    /// The snapshot of the scope at a binder, like a lambda or a `<-`, before its names are bound,
+    /// it's [None] if the binder wasn't resolved.
+    pub struct ScopeKey : Option<ScopeRef>;
+}
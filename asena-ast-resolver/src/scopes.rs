@@ -1,25 +1,80 @@
-use crate::{decl::AstResolver, *};
-use asena_ast_db::{def::Def, package::HasDiagnostic, scope::ScopeRef};
-use asena_leaf::ast::Located;
-use asena_report::WithError;
+use std::collections::BTreeSet;
+
+use crate::{decl::AstResolver, suggestions::suggest, *};
+use asena_ast_db::{
+    code_action::import_fix,
+    db::AstDatabase,
+    def::Def,
+    definition::{find_private_definition, DefinitionKind},
+    package::HasDiagnostic,
+    scope::{ScopeData, ScopeRef, VariantData},
+};
+use asena_leaf::ast::{GreenTree, Located, Node};
+use asena_report::{Diagnostic, WithError};
+use asena_span::Spanned;
+use itertools::Itertools;
 
 pub enum Level {
     Type,
     Value,
 }
 
+/// The name of the runner of the do blocks, like `do { x <- m; return x }`, it's a keyword since
+/// the 2024 edition, and before it, it's the runner unless there's a function with the same name
+/// in the scope.
+pub const DO: &str = "do";
+
+/// The receiver of the declaration that is being resolved, the `self` expressions are resolved
+/// to it.
+#[derive(Clone)]
+pub enum Receiver {
+    /// The declaration isn't a method.
+    None,
+
+    /// The method doesn't have the `self` parameter.
+    Missing(FunctionId),
+
+    /// The `self` parameter of the method.
+    Parameter(DefWithId),
+}
+
+impl Receiver {
+    pub fn of_method(db: &dyn AstDatabase, name: FunctionId, parameters: &[Parameter]) -> Self {
+        match parameters.iter().find(|parameter| parameter.is_self()) {
+            Some(parameter) => {
+                let location = parameter.location().into_owned();
+
+                Receiver::Parameter(DefWithId::new(db, parameter.name(), location))
+            }
+            None => Receiver::Missing(name),
+        }
+    }
+}
+
 pub struct ScopeResolver<'db, 'ctx> {
     pub local_scope: ScopeRef,
     pub frames: Vec<ScopeRef>,
     pub level: Level,
     pub owner: &'ctx mut AstResolver<'db>,
+    pub receiver: Receiver,
+
+    /// If the next local expression is the name of a hole, like `foo` in `?foo`, that shouldn't
+    /// be reported if it's unbound.
+    pub hole: bool,
+
+    /// If the next local expression is the callee of a block, like `do` in `do { ... }`, that is
+    /// the runner of the do blocks if it's unbound.
+    pub runner: bool,
+
+    /// The declared types of the parameters, like `Int32` in `(x: Int32)`, so the scrutinees of
+    /// the matches can be checked against them.
+    pub declared_types: im::HashMap<DefWithId, Expr>,
 }
 
 impl<'db, 'ctx> ScopeResolver<'db, 'ctx> {
     pub fn new(name: BindingId, level: Level, resolver: &'ctx mut AstResolver<'db>) -> Self {
-        let global_scope = resolver.db.global_scope();
         let local_scope = {
-            let named_scope = global_scope.read().unwrap().fork();
+            let named_scope = resolver.declaration_scope();
             let mut scope = named_scope.write().unwrap();
             scope.variables.insert(name.to_fn_id(), 0);
             named_scope.clone()
@@ -30,18 +85,25 @@ impl<'db, 'ctx> ScopeResolver<'db, 'ctx> {
             frames: vec![local_scope],
             level,
             owner: resolver,
+            receiver: Receiver::None,
+            hole: false,
+            runner: false,
+            declared_types: im::HashMap::new(),
         }
     }
 
     pub fn empty(level: Level, resolver: &'ctx mut AstResolver<'db>) -> Self {
-        let global_scope = resolver.db.global_scope();
-        let local_scope = global_scope.read().unwrap().fork();
+        let local_scope = resolver.declaration_scope();
 
         Self {
             local_scope: local_scope.clone(),
             frames: vec![local_scope],
             level,
             owner: resolver,
+            receiver: Receiver::None,
+            hole: false,
+            runner: false,
+            declared_types: im::HashMap::new(),
         }
     }
 
@@ -51,12 +113,319 @@ impl<'db, 'ctx> ScopeResolver<'db, 'ctx> {
             .cloned()
             .unwrap_or_else(|| self.owner.db.global_scope())
     }
+
+    /// Binds the named parameters in the local scope, the implicit ones are type variables too,
+    /// like `a` in `[a: Set]`. The constraints of the instance arguments, like `Monad m` in
+    /// `[Monad m]`, are resolved after them, so they can use the type variables.
+    pub fn bind_parameters(&mut self, parameters: &[Parameter]) {
+        for (name, parameter) in Parameter::compute_parameters(parameters.to_vec()) {
+            let location = parameter.location().into_owned();
+            let def = DefWithId::new(self.owner.db, parameter.name(), location);
+
+            if let Typed::Explicit(type_expr) = parameter.parameter_type() {
+                self.declared_types.insert(def, type_expr);
+            }
+
+            let mut scope = self.local_scope.write().unwrap();
+            if !parameter.explicit() {
+                scope.types.insert(name.clone(), def);
+            }
+            scope.functions.insert(name, def);
+        }
+
+        for parameter in parameters
+            .iter()
+            .filter(|parameter| parameter.is_instance())
+        {
+            self.listens(parameter.parameter_type());
+        }
+    }
+
+    /// Returns the declared type of the expression, if it's annotated, like `x : Int32`, or if
+    /// it's a parameter with an explicit type.
+    fn declared_type(&mut self, expr: &Expr) -> Option<Expr> {
+        match expr {
+            Expr::Ann(ann) => Some(ann.against()),
+            Expr::Group(group) => self.declared_type(&group.value()),
+            Expr::LocalExpr(local) => {
+                let scope = self.last_scope();
+                let def = *scope.read().unwrap().functions.get(&local.to_fn_id())?;
+
+                self.declared_types.get(&def).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Stores a snapshot of the scope in the binder, before its names are bound, so the bindings
+    /// visible at it can be enumerated after the resolution, like in the completion. The scopes
+    /// are persistent, so it's cheap, but it's only taken at the binders, and not at every
+    /// expression.
+    fn snapshot(&mut self, node: &GreenTree) {
+        let snapshot = self.last_scope().snapshot();
+
+        node.dynamic(ScopeKey, Some(snapshot));
+    }
+
+    /// Checks that the pattern applies the variant to all of its fields, like `Just x`, and not
+    /// `Just x y`, so the mismatch is reported at the pattern instead of when it's type checked.
+    /// The extra sub-patterns are reported from the first one, like `y` in `Just x y`.
+    fn check_arguments(
+        &self,
+        node: &impl Located,
+        name: FunctionId,
+        variant: &VariantData,
+        arguments: &[Pat],
+    ) {
+        let arity = variant.arity();
+        let error = PatternArityMismatchError(name, arity, arguments.len());
+
+        match arguments.get(arity) {
+            Some(extra) => extra.clone().fail(error).push(self.owner.db),
+            None if arguments.len() < arity => {
+                Diagnostic::of(node.location().into_owned(), error).push(self.owner.db)
+            }
+            None => {}
+        }
+
+        for (field, argument) in variant.fields.iter().zip(arguments) {
+            let Pat::LiteralPat(pat) = argument else {
+                continue;
+            };
+
+            if let Some(expected) = type_category(field) {
+                self.check_literal(pat, expected);
+            }
+        }
+    }
+
+    /// Checks that the literal pattern has the expected category, like a string in a field of
+    /// type `String`, the errors are already reported by the lexer.
+    fn check_literal(&self, pat: &LiteralPat, expected: LiteralCategory) {
+        let Some(found) = pat.literal().category() else {
+            return;
+        };
+
+        if found != expected {
+            pat.clone()
+                .fail(LiteralPatternMismatchError(expected, found))
+                .push(self.owner.db);
+        }
+    }
+
+    /// Binds the name of the pattern in the current scope, like `x` in `Just x`.
+    fn bind_pattern_name(&mut self, name: Lexeme<Local>) {
+        let scope = self.last_scope();
+        let mut scope = scope.write().unwrap();
+
+        let location = name.location().into_owned();
+        let local = name.to_fn_id();
+        let def = DefWithId::new(self.owner.db, name, location);
+
+        scope.functions.insert(local, def);
+    }
+
+    /// Reports the name if it's defined in another module, that doesn't export it, with the
+    /// definition site of it. Returns if the name was reported.
+    fn check_private(&self, value: &LocalExpr) -> bool {
+        let name = value.to_fn_id();
+        let db = self.owner.db;
+        let Some(definition) = find_private_definition(db, self.owner.file, &name) else {
+            return false;
+        };
+
+        let hint = Spanned::new(
+            definition.name_location,
+            PrivateDefinitionHint(definition.name),
+        );
+
+        value
+            .segments()
+            .fail(PrivateNameError(name))
+            .add_child(hint)
+            .push(db);
+
+        true
+    }
+
+    /// Returns the names similar to the unresolved one, among the names of the level visible in
+    /// the scope, and the ones that can be imported from the other modules of the package.
+    fn similar_names(&mut self, name: &FunctionId) -> Vec<FunctionId> {
+        let db = self.owner.db;
+        let file = self.owner.file;
+
+        let mut candidates = {
+            let scope = self.last_scope();
+            let scope = scope.read().unwrap();
+            match self.level {
+                Level::Type => scope.types.keys().cloned().collect::<Vec<_>>(),
+                Level::Value => scope.functions.keys().cloned().collect::<Vec<_>>(),
+            }
+        };
+
+        let package = db.lookup_intern_vfs_file(file).pkg;
+        candidates.extend(
+            db.package_definitions(package)
+                .iter()
+                .filter(|definition| definition.file != file)
+                .filter(|definition| match self.level {
+                    Level::Type => !matches!(
+                        definition.kind,
+                        DefinitionKind::Signature | DefinitionKind::Assign
+                    ),
+                    Level::Value => matches!(
+                        definition.kind,
+                        DefinitionKind::Signature | DefinitionKind::Assign
+                    ),
+                })
+                .filter(|definition| db.exports(definition.file).contains_key(&definition.name))
+                .map(|definition| definition.name.clone()),
+        );
+
+        suggestions::similar_names(name, candidates.iter())
+    }
+
+    /// Returns the constructors similar to the unresolved one, among the ones visible in the
+    /// scope.
+    fn similar_constructors(&mut self, name: &FunctionId) -> Vec<FunctionId> {
+        let scope = self.last_scope();
+        let scope = scope.read().unwrap();
+
+        suggestions::similar_names(name, scope.constructors.keys())
+    }
+
+    /// Resolves the operator of an infix application or section, like any other function, as
+    /// the built-in operators are defined in the global scope too.
+    fn resolve_operator(&mut self, node: &impl Located, fn_id: FunctionId) -> ExprResolution {
+        // The operators in the types aren't functions, so they aren't resolved.
+        if let Level::Type = self.level {
+            return ExprResolution::Unresolved;
+        }
+
+        let resolved = self
+            .last_scope()
+            .read()
+            .unwrap()
+            .functions
+            .get(&fn_id)
+            .cloned();
+        match resolved {
+            Some(def) => ExprResolution::Resolved(def),
+            None => {
+                let similar = self.similar_names(&fn_id);
+
+                let location = node.location().into_owned();
+                let diagnostic = Diagnostic::of(location, UnresolvedNameError(fn_id));
+                suggest(diagnostic, similar).push(self.owner.db);
+
+                ExprResolution::Unresolved
+            }
+        }
+    }
+
+    /// Checks that the parameters of the block are the ones of the function that the callee
+    /// expects in its place, like `forEach xs { x -> ... }`, with the signature
+    /// `forEach (xs: List a) (f: a -> Unit) : Unit`. The blocks without parameters ignore the
+    /// parameters of the function, so they aren't checked.
+    fn check_dsl_arity(&self, dsl: &Dsl) {
+        let parameters = dsl.parameters();
+        if parameters.is_empty() {
+            return;
+        }
+
+        let mut arguments = 0;
+        let mut callee = dsl.callee();
+        while let Expr::App(ref app) = callee {
+            arguments += 1;
+            callee = app.callee();
+        }
+
+        let Expr::LocalExpr(ref local) = callee else {
+            return;
+        };
+        let (ExprResolution::Resolved(def) | ExprResolution::Method(def)) =
+            *local.key(ExprResolutionKey)
+        else {
+            return;
+        };
+        let Some(arity) = block_arity(self.owner.db, self.owner.file, def, arguments) else {
+            return;
+        };
+
+        if arity != parameters.len() {
+            let error = DslArityMismatchError(local.to_fn_id(), arity, parameters.len());
+            match parameters.get(arity) {
+                Some(extra) => extra.clone().fail(error).push(self.owner.db),
+                None => Diagnostic::of(dsl.location().into_owned(), error).push(self.owner.db),
+            }
+        }
+    }
+}
+
+/// Returns if the qualified name is a method of a class or trait in the scope, like `Point.show`,
+/// the methods are defined in the functions with the name of their type as the prefix.
+fn is_method(scope: &ScopeData, value: &LocalExpr) -> bool {
+    let segments = value.segments();
+    if segments.len() < 2 {
+        return false;
+    }
+
+    let prefix = segments
+        .iter()
+        .take(segments.len() - 1)
+        .map(|segment| segment.as_str())
+        .join(".");
+    scope.types.contains_key(&FunctionId::new(&prefix))
+}
+
+/// Returns the number of parameters of the function type that the function expects at the
+/// explicit parameter of the position, if the function has a signature in the package, and the
+/// parameter is a function.
+fn block_arity(
+    db: &dyn AstDatabase,
+    file: VfsFile,
+    def: DefWithId,
+    position: usize,
+) -> Option<usize> {
+    let defined_at = db.lookup_intern_def(def).defined_at;
+    let package = db.lookup_intern_vfs_file(file).pkg;
+    let signature = db
+        .package_definitions(package)
+        .iter()
+        .filter(|definition| definition.kind == DefinitionKind::Signature)
+        .find(|definition| definition.location.contains(&defined_at))
+        .and_then(|definition| definition.decl.as_signature())?;
+
+    let parameter = signature
+        .parameters()
+        .iter()
+        .filter(|parameter| parameter.explicit())
+        .nth(position)
+        .cloned()?;
+    let Typed::Explicit(mut value) = parameter.parameter_type() else {
+        return None;
+    };
+
+    let mut arity = 0;
+    loop {
+        match value {
+            Expr::Group(ref group) => value = group.value(),
+            Expr::Pi(ref pi) => {
+                arity += 1;
+                value = pi.return_type();
+            }
+            _ => break,
+        }
+    }
+
+    (arity > 0).then_some(arity)
 }
 
 impl AsenaListener for ScopeResolver<'_, '_> {
     // >>> Enter/Exit scope abstractions
     fn enter_pi(&mut self, pi: asena_ast::Pi) {
-        let scope = self.last_scope().read().unwrap().fork();
+        self.snapshot(&pi);
+        let scope = self.last_scope().child();
         if let Some(name) = pi.parameter_name() {
             let value = pi.parameter_type();
             let local = name.to_fn_id();
@@ -73,8 +442,55 @@ impl AsenaListener for ScopeResolver<'_, '_> {
         self.frames.pop();
     }
 
-    fn enter_case(&mut self, _: Case) {
-        let scope = self.last_scope().read().unwrap().fork();
+    /// The variables of the forall are bound in the types of the scope, so they don't shadow the
+    /// values with the same names.
+    fn enter_forall(&mut self, forall: asena_ast::Forall) {
+        self.snapshot(&forall);
+        let scope = self.last_scope().child();
+        for variable in forall.variables() {
+            let name = variable.to_fn_id();
+            let location = variable.location().into_owned();
+            let def = DefWithId::new(self.owner.db, variable, location);
+
+            scope.write().unwrap().types.insert(name, def);
+        }
+
+        self.frames.push(scope);
+    }
+
+    fn exit_forall(&mut self, _: asena_ast::Forall) {
+        self.frames.pop();
+    }
+
+    /// Checks that the literal patterns of the cases have the category of the scrutinee, if it's
+    /// a literal or its type is declared, like `x` in `(x: Int32)`, or otherwise of the first
+    /// literal pattern, like `'a'` and `1` can't be mixed.
+    fn enter_match(&mut self, value: Match) {
+        let literals = value
+            .cases()
+            .into_iter()
+            .filter_map(|case| case.pat().as_literal_pat())
+            .collect::<Vec<_>>();
+
+        let scrutinee = match value.scrutinee() {
+            Expr::LiteralExpr(scrutinee) => scrutinee.literal().category(),
+            scrutinee => self
+                .declared_type(&scrutinee)
+                .and_then(|type_expr| type_category(&type_expr)),
+        };
+        let expected =
+            scrutinee.or_else(|| literals.iter().find_map(|pat| pat.literal().category()));
+
+        if let Some(expected) = expected {
+            for pat in literals.iter() {
+                self.check_literal(pat, expected);
+            }
+        }
+    }
+
+    fn enter_case(&mut self, case: Case) {
+        self.snapshot(&case);
+        let scope = self.last_scope().child();
         self.frames.push(scope);
     }
 
@@ -82,8 +498,21 @@ impl AsenaListener for ScopeResolver<'_, '_> {
         self.frames.pop();
     }
 
-    fn enter_lam(&mut self, _: Lam) {
-        let scope = self.last_scope().read().unwrap().fork();
+    /// The names of the pattern are bound in the scope of the `let`, so they're visible in the
+    /// value too, like the recursive `let` of Haskell, but not after the `in` value.
+    fn enter_let(&mut self, value: Let) {
+        self.snapshot(&value);
+        let scope = self.last_scope().child();
+        self.frames.push(scope);
+    }
+
+    fn exit_let(&mut self, _: Let) {
+        self.frames.pop();
+    }
+
+    fn enter_lam(&mut self, lam: Lam) {
+        self.snapshot(&lam);
+        let scope = self.last_scope().child();
         self.frames.push(scope);
     }
 
@@ -91,6 +520,22 @@ impl AsenaListener for ScopeResolver<'_, '_> {
         self.frames.pop();
     }
 
+    /// The parameters of the block are bound in its own scope, like the ones of a lambda.
+    fn enter_dsl(&mut self, dsl: Dsl) {
+        self.snapshot(&dsl);
+        self.runner = match dsl.callee() {
+            Expr::LocalExpr(ref local) => local.to_fn_id().as_str() == DO,
+            _ => false,
+        };
+        let scope = self.last_scope().child();
+        self.frames.push(scope);
+    }
+
+    fn exit_dsl(&mut self, dsl: Dsl) {
+        self.frames.pop();
+        self.check_dsl_arity(&dsl);
+    }
+
     fn enter_typed_explicit(&mut self, _: Expr) {
         self.level = Level::Type;
     }
@@ -100,12 +545,44 @@ impl AsenaListener for ScopeResolver<'_, '_> {
     }
     // <<< Enter/Exit scope abstractions
 
+    /// The snapshot is taken before the pattern is bound, so it's the scope of the value, like
+    /// `m` in `x <- m`.
+    fn enter_ask(&mut self, ask: Ask) {
+        self.snapshot(&ask);
+    }
+
+    fn enter_let_stmt(&mut self, value: LetStmt) {
+        self.snapshot(&value);
+    }
+
+    /// Stores the local bindings in scope at the hole, so they can be reported with the expected
+    /// type of it, like the typed holes of Haskell.
+    fn enter_help(&mut self, value: Help) {
+        let scope = self.last_scope();
+        let scope = scope.read().unwrap();
+        let mut bindings = scope
+            .local_values()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        bindings.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        value.dynamic(HoleBindingsKey, bindings);
+
+        self.hole = value.value().is_local_expr();
+    }
+
     /// Just bridges to ast resolver, which will search, and report if it's bound.
     fn enter_qualified_path(&mut self, value: asena_ast::QualifiedPath) {
         self.owner.visit_qualified_path(value);
     }
 
     fn enter_lam_parameter(&mut self, value: LamParameter) {
+        // The pattern parameters are bound when the pattern is visited.
+        if value.pattern().is_some() {
+            return;
+        }
+
         let scope = self.last_scope();
         let mut scope = scope.write().unwrap();
 
@@ -114,65 +591,201 @@ impl AsenaListener for ScopeResolver<'_, '_> {
         scope.functions.insert(value.name().to_fn_id(), def);
     }
 
+    fn enter_infix(&mut self, value: Infix) {
+        let resolution = self.resolve_operator(&value, value.fn_id());
+
+        value.dynamic(ExprResolutionKey, resolution);
+    }
+
+    fn enter_left_section(&mut self, value: LeftSection) {
+        let resolution = self.resolve_operator(&value, value.fn_id());
+
+        value.dynamic(ExprResolutionKey, resolution);
+    }
+
+    fn enter_right_section(&mut self, value: RightSection) {
+        let resolution = self.resolve_operator(&value, value.fn_id());
+
+        value.dynamic(ExprResolutionKey, resolution);
+    }
+
+    fn enter_operator_section(&mut self, value: OperatorSection) {
+        let resolution = self.resolve_operator(&value, value.fn_id());
+
+        value.dynamic(ExprResolutionKey, resolution);
+    }
+
     fn enter_local_expr(&mut self, value: LocalExpr) {
-        let scope = self.last_scope();
-        let scope = scope.read().unwrap();
+        let is_hole = std::mem::take(&mut self.hole);
+        let is_runner = std::mem::take(&mut self.runner);
+
+        // The scope is cloned, so it isn't locked while the similar names are searched, as the
+        // maps are persistent, it's cheap.
+        let scope = self.last_scope().read().unwrap().clone();
         match self.level {
             Level::Type => match scope.find_type(&value) {
                 Def::WithId(id) => {
                     let resolution = TypeResolution::Resolved(id);
+                    if value.segments().len() > 1 {
+                        self.check_private(&value);
+                    }
 
                     value.dynamic(TypeResolutionKey, resolution);
                 }
+                Def::Unresolved if self.check_private(&value) => {}
                 Def::Unresolved => {
-                    value
+                    let name = value.to_fn_id();
+                    tracing::debug!(name = %name, "could not resolve the type name");
+
+                    let diagnostic = value
                         .segments()
-                        .fail(UnresolvedTypeNameError(value.to_fn_id()))
-                        .push(self.owner.db);
+                        .fail(UnresolvedTypeNameError(name.clone()))
+                        .with_fixes(import_fix(self.owner.db, self.owner.file, &name));
+                    suggest(diagnostic, self.similar_names(&name)).push(self.owner.db);
                 }
             },
             Level::Value => match scope.functions.get(&value.to_fn_id()).cloned() {
                 Some(resolved) => {
-                    value.dynamic(ExprResolutionKey, ExprResolution::Resolved(resolved));
+                    // The qualified names are resolved in the global scope, that has all the
+                    // declarations of the modules, so the visibility is checked here.
+                    if value.segments().len() > 1 {
+                        self.check_private(&value);
+                    }
+
+                    // The variants are in the functions too, so they're told apart by the
+                    // constructors, unless they're shadowed by a local with the same name.
+                    let resolution = match scope.find_type_constructor(&value) {
+                        VariantResolution::Variant(variant) if variant.def == resolved => {
+                            ExprResolution::Variant(resolved)
+                        }
+                        _ if is_method(&scope, &value) => ExprResolution::Method(resolved),
+                        _ => ExprResolution::Resolved(resolved),
+                    };
+
+                    value.dynamic(ExprResolutionKey, resolution);
                 }
+                // The unbound names of the holes are left unresolved, they're lowered into holes
+                None if is_hole => {}
+                None if is_runner => value.dynamic(ExprResolutionKey, ExprResolution::Do),
+                None if self.check_private(&value) => {}
                 None => {
-                    value
+                    let name = value.to_fn_id();
+                    tracing::debug!(name = %name, "could not resolve the value name");
+
+                    let diagnostic = value
                         .segments()
-                        .fail(UnresolvedNameError(value.to_fn_id()))
-                        .push(self.owner.db);
+                        .fail(UnresolvedNameError(name.clone()))
+                        .with_fixes(import_fix(self.owner.db, self.owner.file, &name));
+                    suggest(diagnostic, self.similar_names(&name)).push(self.owner.db);
                 }
             },
         }
     }
 
+    /// Resolves `self` to the receiver of the method, the `self` in the types is the type of the
+    /// receiver, so it isn't resolved.
+    fn enter_self_expr(&mut self, value: SelfExpr) {
+        if let Level::Type = self.level {
+            return;
+        }
+
+        match self.receiver.clone() {
+            Receiver::Parameter(def) => {
+                value.dynamic(ExprResolutionKey, ExprResolution::Resolved(def));
+            }
+            Receiver::Missing(name) => {
+                value
+                    .fail(SelfWithoutParameterError(name))
+                    .push(self.owner.db);
+            }
+            Receiver::None => value.fail(SelfOutsideMethodError).push(self.owner.db),
+        }
+    }
+
     fn enter_global_pat(&mut self, value: asena_ast::GlobalPat) {
         let name = value.name();
         let file = self.owner.file;
 
         match self.owner.db.constructor_data(value.name(), file) {
             VariantResolution::Variant(variant) => {
-                value.dynamic(PatResolutionKey, PatResolution::Variant(variant));
+                self.check_arguments(&value, name.to_fn_id(), &variant, &[]);
+
+                value.dynamic(PatResolutionKey, PatResolution::Variant(variant.def));
             }
             VariantResolution::Binding(name) => {
                 value.dynamic(PatResolutionKey, PatResolution::LocalBinding(name.clone()));
 
-                let scope = self.last_scope();
-                let mut scope = scope.write().unwrap();
-
-                let location = name.location().into_owned();
-                let local = name.to_fn_id();
-                let def = DefWithId::new(self.owner.db, *name, location);
-
-                scope.functions.insert(local, def);
+                self.bind_pattern_name(*name);
             }
             VariantResolution::None => {
                 let fn_id = name.to_fn_id();
+                let similar = self.similar_constructors(&fn_id);
 
-                name.fail(UnresolvedNameError(fn_id)).push(self.owner.db);
+                suggest(name.fail(UnresolvedNameError(fn_id)), similar).push(self.owner.db);
             }
         }
     }
 
+    fn enter_as_pat(&mut self, value: asena_ast::AsPat) {
+        let name = value.name();
+        match name.is_ident() {
+            Some(local) => self.bind_pattern_name(local),
+            None => name
+                .clone()
+                .fail(AsPatternNameError(name.to_fn_id()))
+                .push(self.owner.db),
+        }
+    }
+
+    /// Checks that all the alternatives of the or-pattern bind the same names, as the value of
+    /// the case can reference any of them.
+    fn enter_or_pat(&mut self, value: asena_ast::OrPat) {
+        let names = |pat: &Pat| {
+            pat.bindings()
+                .iter()
+                .map(|name| name.to_fn_id().to_string())
+                .collect::<BTreeSet<_>>()
+        };
+
+        let alternatives = value.alternatives();
+        let Some(expected) = alternatives.iter().next().map(names) else {
+            return;
+        };
+
+        for alternative in alternatives.iter().skip(1) {
+            let found = names(alternative);
+            if found == expected {
+                continue;
+            }
+
+            let unbound = expected
+                .symmetric_difference(&found)
+                .map(|name| format!("`{name}`"))
+                .join(", ");
+
+            alternative
+                .clone()
+                .fail(OrPatternBindingsMismatchError(unbound))
+                .push(self.owner.db);
+        }
+    }
+
+    /// Checks that the list pattern has at most one rest, like `[x, .., y]`, as the items
+    /// around two rests couldn't be placed in the list.
+    fn enter_list_pat(&mut self, value: asena_ast::ListPat) {
+        let items = value.items();
+        let spreads = items
+            .iter()
+            .filter(|item| matches!(item, Pat::SpreadPat(_)));
+
+        for spread in spreads.skip(1) {
+            spread
+                .clone()
+                .fail(DuplicatedSpreadPatternError)
+                .push(self.owner.db);
+        }
+    }
+
     fn enter_constructor_pat(&mut self, value: asena_ast::ConstructorPat) {
         let name = value.name();
         let file = self.owner.file;
@@ -186,13 +799,26 @@ impl AsenaListener for ScopeResolver<'_, '_> {
                 value.dynamic(PatResolutionKey, PatResolution::LocalBinding(name));
             }
             VariantResolution::Variant(variant) => {
-                value.dynamic(PatResolutionKey, PatResolution::Variant(variant));
+                let arguments = value.arguments();
+                self.check_arguments(&value, name.to_fn_id(), &variant, &arguments);
+
+                value.dynamic(PatResolutionKey, PatResolution::Variant(variant.def));
             }
             VariantResolution::None => {
-                name.clone()
-                    .fail(UnresolvedNameError(name.to_fn_id()))
-                    .push(self.owner.db);
+                let similar = self.similar_constructors(&name.to_fn_id());
+                let diagnostic = name.clone().fail(UnresolvedNameError(name.to_fn_id()));
+
+                suggest(diagnostic, similar).push(self.owner.db);
             }
         }
     }
 }
+
+/// Returns the literal category of the values of the type, if it's a built-in type, like `Int32`.
+fn type_category(type_expr: &Expr) -> Option<LiteralCategory> {
+    match type_expr {
+        Expr::LocalExpr(local) => LiteralCategory::of_type(local.to_fn_id().as_str()),
+        Expr::Group(group) => type_category(&group.value()),
+        _ => None,
+    }
+}
@@ -0,0 +1,69 @@
+//! Pairs the signatures of the file with the clauses that define them, the pairing is done on the
+//! whole file, so the clauses can come before their signatures, or between other declarations.
+
+use asena_ast_db::{db::AstDatabase, package::HasDiagnostic};
+use asena_leaf::ast::Located;
+use asena_report::WithError;
+use asena_span::Spanned;
+
+use crate::*;
+
+/// Reports the signatures without a body nor clauses, the clauses without a signature, and the
+/// functions that are defined by both the body of the signature and clauses.
+///
+/// The missing halves are reported as warnings, so they can be escalated into errors with the
+/// `deny` of the [asena_report::ReportConfig] of the package.
+pub fn check_signatures(db: &dyn AstDatabase, file: &AsenaFile) {
+    let mut signatures = im::HashMap::<FunctionId, Signature>::new();
+    let mut clauses = im::HashMap::<FunctionId, Vec<Assign>>::new();
+
+    for decl in file.declarations().iter() {
+        match decl {
+            Decl::Signature(signature) => {
+                signatures
+                    .entry(signature.name().to_fn_id())
+                    .or_insert_with(|| signature.clone());
+            }
+            Decl::Assign(assign) => {
+                clauses
+                    .entry(assign.name().to_fn_id())
+                    .or_default()
+                    .push(assign.clone());
+            }
+            _ => {}
+        }
+    }
+
+    for (name, signature) in signatures.iter() {
+        let has_body = signature.body().is_some();
+
+        match clauses.get(name) {
+            Some(clauses) if has_body => {
+                let location = signature.name().location().into_owned();
+                let hint = Spanned::new(location, SignatureDefinitionHint(name.clone()));
+
+                clauses[0]
+                    .name()
+                    .fail(DuplicatedDefinitionError(name.clone()))
+                    .add_child(hint)
+                    .push(db);
+            }
+            None if !has_body => {
+                signature
+                    .name()
+                    .fail(SignatureWithoutDefinitionWarning(name.clone()))
+                    .push(db);
+            }
+            _ => {}
+        }
+    }
+
+    for (name, clauses) in clauses.iter() {
+        if !signatures.contains_key(name) {
+            clauses[0]
+                .name()
+                .fail(DefinitionWithoutSignatureWarning(name.clone()))
+                .push(db);
+        }
+    }
+}
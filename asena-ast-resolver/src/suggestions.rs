@@ -0,0 +1,62 @@
+//! The "did you mean" suggestions of the unresolved names, the names visible in the scope, or
+//! importable from the package, that are close to the unresolved one by the edit distance.
+
+use asena_ast_db::suggestions::closest_names;
+use asena_report::{CodeAction, Diagnostic, TextEdit};
+use asena_span::Spanned;
+
+use crate::error::ResolutionError;
+use crate::*;
+
+/// Returns the candidates that are a typo away from the name, the closest first, see
+/// [closest_names].
+pub fn similar_names<'a, I>(name: &FunctionId, candidates: I) -> Vec<FunctionId>
+where
+    I: IntoIterator<Item = &'a FunctionId>,
+{
+    closest_names(
+        name.as_str(),
+        candidates.into_iter().map(FunctionId::as_str),
+    )
+    .into_iter()
+    .map(FunctionId::new)
+    .collect()
+}
+
+/// Attaches the names to the diagnostic of the unresolved name, as hints at its location, with
+/// the fixes that replace the name by them.
+pub fn suggest(
+    mut diagnostic: Diagnostic<ResolutionError>,
+    names: Vec<FunctionId>,
+) -> Diagnostic<ResolutionError> {
+    let location = diagnostic.message.span.clone();
+
+    for name in names {
+        let fix = CodeAction::new(
+            format!("Replace with `{name}`"),
+            vec![TextEdit::replace(location.clone(), name.to_string())],
+        );
+
+        diagnostic = diagnostic
+            .add_child(Spanned::new(location.clone(), SimilarNameHint(name)))
+            .with_fix(fix);
+    }
+
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggestions_works() {
+        let candidates = ["Double", "Doubles", "Triple", "Dbl"].map(FunctionId::new);
+        let similar = similar_names(&FunctionId::new("Doubel"), candidates.iter());
+
+        assert_eq!(
+            similar,
+            vec![FunctionId::new("Double"), FunctionId::new("Doubles")]
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! Tests for the resolution of the names, every test resolves the modules in the memory, and
+//! checks the diagnostics reported for them.
+
+use asena_ast::{command::CommandError, FunctionId};
+use asena_ast_db::commands::{parse_expansion, MAX_EXPANSION_DEPTH};
+use asena_ast_db::{db::*, fixture::*, vfs::VfsFile};
+use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
+use asena_ast_resolver::error::ResolutionError::*;
+use asena_prec::{db::PrecStorage, PrecDatabase};
+use asena_report::{DiagnosticKind, InternalError};
+
+#[salsa::database(PrecStorage, AstDatabaseStorage, AstResolverStorage)]
+#[derive(Default)]
+struct TestDatabase {
+    storage: salsa::Storage<TestDatabase>,
+}
+
+impl salsa::Database for TestDatabase {}
+
+/// Resolves the file, the diagnostics are reported to its package.
+fn resolve(db: &TestDatabase, file: VfsFile) {
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    db.ast_resolved_file(ast.into());
+}
+
+/// Resolves the module `Test` with the source, returns the messages of its diagnostics.
+fn resolve_messages(source: &str) -> Vec<String> {
+    let db = TestDatabase::default();
+    let file = memory_file(&db, source);
+
+    resolve(&db, file);
+
+    db.diagnostics(file)
+        .iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .collect()
+}
+
+/// A diagnostic of a file, with its kind, its message, and the source code at its span.
+type Reported = (DiagnosticKind, String, String);
+
+/// Loads the modules, and resolves the last one of them, returns the diagnostics of it, except
+/// the warnings.
+fn resolve_modules(modules: &[(&str, &str)]) -> Vec<Reported> {
+    resolve_modules_with(modules, |_| {})
+}
+
+/// Same as [resolve_modules], but calls `setup` with the database before loading the modules.
+fn resolve_modules_with(
+    modules: &[(&str, &str)],
+    setup: impl FnOnce(&TestDatabase),
+) -> Vec<Reported> {
+    let db = TestDatabase::default();
+    setup(&db);
+
+    let (_, files) = memory_package(&db, modules);
+    let file = *files.last().unwrap();
+    let source = db.source(file);
+
+    resolve(&db, file);
+
+    db.diagnostics(file)
+        .iter()
+        .filter(|diagnostic| diagnostic.kind != DiagnosticKind::Warning)
+        .map(|diagnostic| {
+            let span = diagnostic.message.span.range.clone().into_ranged().unwrap();
+
+            (
+                diagnostic.kind,
+                diagnostic.message.value.to_string(),
+                source[span].to_string(),
+            )
+        })
+        .collect()
+}
+
+/// The diagnostic of the error, reported at the source code.
+fn reported(error: impl InternalError, text: &str) -> Reported {
+    (error.kind(), error.to_string(), text.to_string())
+}
+
+#[test]
+fn forward_references_works() {
+    let source = "Main { Double 21 }\nDouble (x: Int32) : Int32 { x + x }";
+
+    assert!(resolve_messages(source).is_empty());
+}
+
+#[test]
+fn self_works() {
+    let source = concat!(
+        "class Person {\n",
+        "  age : Int32,\n",
+        "  fun greet(self) { self }\n",
+        "  fun shout { self }\n",
+        "}\n",
+        "\n",
+        "Main { self }",
+    );
+
+    let messages = resolve_messages(source)
+        .into_iter()
+        .filter(|message| message.contains("self"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "the method `shout` doesn't have the `self` parameter",
+            "`self` can only be used in the methods",
+        ]
+    );
+}
+
+#[test]
+fn dsl_parameters_works() {
+    let source = concat!(
+        "forEach (xs: List a) (f: a -> Unit) : Unit { () }\n",
+        "\n",
+        "Main {\n",
+        "  forEach [1, 2] { x -> x }\n",
+        "  forEach [1, 2] { x y -> y }\n",
+        "}",
+    );
+
+    let messages = resolve_messages(source)
+        .into_iter()
+        .filter(|message| message.contains("block") || message.contains("value name"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["the block of `forEach` should have 1 parameters, but it has 2"]
+    );
+}
+
+#[test]
+fn unresolved_import_works() {
+    let db = TestDatabase::default();
+    let modules = [
+        ("Nat", "pub Zero : Int32 { 0 }"),
+        ("Test", "use Natt\nMain { 1 }"),
+    ];
+    let (_, files) = memory_package(&db, &modules);
+
+    resolve(&db, files[1]);
+
+    let hints = db
+        .diagnostics(files[1])
+        .iter()
+        .flat_map(|diagnostic| diagnostic.children.iter())
+        .map(|hint| hint.message.value.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(hints.contains(&"searched at `<memory>/Natt`".to_string()));
+    assert!(hints.contains(&"a module with a similar name exists: `Nat`".to_string()));
+}
+
+#[test]
+fn or_pattern_bindings_works() {
+    let source = concat!(
+        "enum Pair {\n",
+        "  Left : Int32 -> Pair,\n",
+        "  Right : Int32 -> Pair,\n",
+        "}\n",
+        "\n",
+        "Value (pair: Pair) : Int32 {\n",
+        "  match pair {\n",
+        "    p@(Left x | Right x) => x,\n",
+        "    Left y | Right z => y,\n",
+        "  }\n",
+        "}",
+    );
+
+    let messages = resolve_messages(source)
+        .into_iter()
+        .filter(|message| message.contains("or-pattern") || message.contains("value name"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec!["the alternatives of the or-pattern bind different names: `y`, `z`"]
+    );
+}
+
+#[test]
+fn signature_pairing_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "Inc x = x + 1\n",
+        "Main { Inc 1 }\n",
+        "Inc (x: Int32) : Int32\n",
+        "Print : String -> Unit\n",
+        "Double x = x + x\n",
+        "Twice (x: Int32) : Int32 { x * 2 }\n",
+        "Twice x = x * 2\n",
+    );
+    let file = memory_file(&db, source);
+
+    resolve(&db, file);
+
+    let diagnostics = db.diagnostics(file);
+    let mut messages = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .filter(|message| message.contains("signature") || message.contains("defined"))
+        .collect::<Vec<_>>();
+    messages.sort();
+
+    // The signature of `Inc` comes after its clause, but they're still paired.
+    assert_eq!(
+        messages,
+        vec![
+            "`Double` is defined without a signature",
+            "`Twice` is defined by both the body of its signature and clauses",
+            "the signature of `Print` doesn't have a body, nor clauses that define it",
+        ]
+    );
+
+    let hints = diagnostics
+        .iter()
+        .flat_map(|diagnostic| diagnostic.children.iter())
+        .map(|hint| hint.message.value.to_string())
+        .collect::<Vec<_>>();
+    assert!(hints.contains(&"the signature of `Twice` is here".to_string()));
+}
+
+#[test]
+fn syntax_shapes_works() {
+    let source = concat!(
+        "Empty (x: Int32) : Int32 {\n",
+        "  match x {}\n",
+        "}\n",
+        "Main { match 1 { n => n } }\n",
+    );
+
+    let mut messages = resolve_messages(source)
+        .into_iter()
+        .filter(|message| message.contains("cases"))
+        .collect::<Vec<_>>();
+    messages.sort();
+
+    // The `match` of `Main` has a case, so it isn't reported.
+    assert_eq!(messages, vec!["the `match` doesn't have cases"]);
+}
+
+#[test]
+fn literal_pattern_category_works() {
+    let source = concat!(
+        "Classify (x: Int32) : Int32 {\n",
+        "  match x {\n",
+        "    'a' => 1,\n",
+        "    2 => 2,\n",
+        "    _ => 3,\n",
+        "  }\n",
+        "}",
+    );
+
+    // The category is of the declared type of `x`, and not of the first literal pattern.
+    assert_eq!(
+        resolve_messages(source),
+        vec!["expected a integer pattern, but found a char literal"]
+    );
+}
+
+#[test]
+fn forall_variant_fields_works() {
+    let source = concat!(
+        "enum Maybe (a: Set) {\n",
+        "  Just : forall b. b -> Maybe b,\n",
+        "  Nothing : Maybe a,\n",
+        "}\n",
+        "\n",
+        "Value (m: Maybe Int32) : Int32 {\n",
+        "  match m {\n",
+        "    Just x y => x,\n",
+        "    Nothing => 0,\n",
+        "  }\n",
+        "}",
+    );
+
+    let messages = resolve_messages(source)
+        .into_iter()
+        .filter(|message| message.contains("fields"))
+        .collect::<Vec<_>>();
+
+    // The forall of `Just` is peeled, so it has a single field.
+    assert_eq!(
+        messages,
+        vec!["the constructor `Just` has 1 fields, but the pattern applies 2 arguments"]
+    );
+}
+
+#[test]
+fn prelude_works() {
+    let prelude = ("Prelude", "pub Double (x: Int32) : Int32 { x + x }");
+
+    assert_eq!(
+        resolve_modules(&[prelude, ("Test", "Main { Double 21 }")]),
+        vec![]
+    );
+    assert_eq!(
+        resolve_modules(&[prelude, ("Test", "#no_prelude\nMain { Double 21 }")]),
+        vec![reported(
+            UnresolvedNameError(FunctionId::new("Double")),
+            "Double"
+        )]
+    );
+}
+
+#[test]
+fn exports_works() {
+    let math = (
+        "Math",
+        "#export Double\n\
+         Double (x: Int32) : Int32 { Twice x }\n\
+         Twice (x: Int32) : Int32 { x + x }",
+    );
+    let reexport = ("Reexport", "use Math\n#export Double");
+
+    assert_eq!(
+        resolve_modules(&[math, ("Test", "use Math\nMain { Double 21 }")]),
+        vec![]
+    );
+    assert_eq!(
+        resolve_modules(&[math, ("Test", "use Math\nMain { Twice 21 }")]),
+        vec![reported(
+            PrivateNameError(FunctionId::new("Twice")),
+            "Twice"
+        )]
+    );
+    assert_eq!(
+        resolve_modules(&[math, reexport, ("Test", "use Reexport\nMain { Double 21 }")]),
+        vec![]
+    );
+}
+
+#[test]
+fn visibility_works() {
+    let math = (
+        "Math",
+        "pub Double (x: Int32) : Int32 { Twice x }\n\
+         Twice (x: Int32) : Int32 { x + x }",
+    );
+
+    assert_eq!(
+        resolve_modules(&[math, ("Test", "use Math\nMain { Double 21 }")]),
+        vec![]
+    );
+    assert_eq!(
+        resolve_modules(&[math, ("Test", "Main { Math.Double 21 }")]),
+        vec![]
+    );
+    assert_eq!(
+        resolve_modules(&[math, ("Test", "use Math\nMain { Twice 21 }")]),
+        vec![reported(
+            PrivateNameError(FunctionId::new("Twice")),
+            "Twice"
+        )]
+    );
+    assert_eq!(
+        resolve_modules(&[math, ("Test", "Main { Math.Twice 21 }")]),
+        vec![reported(
+            PrivateNameError(FunctionId::new("Math.Twice")),
+            "Math.Twice"
+        )]
+    );
+}
+
+#[test]
+fn import_cycles_works() {
+    let cycle = |modules: &[(&str, &str)]| {
+        let error = ImportCycleError("B -> A -> B".into());
+        let (kind, _, text) = resolve_modules(modules)
+            .into_iter()
+            .find(|(_, message, _)| *message == error.to_string())
+            .expect("the import cycle should be reported");
+
+        // The span of the `use` starts at its keyword
+        assert_eq!(kind, error.kind());
+        assert!(text.starts_with("use A"));
+    };
+
+    cycle(&[
+        ("A", "use B\npub Foo { 1 }"),
+        ("B", "use A\npub Bar { Foo }"),
+    ]);
+
+    // The re-exports don't follow the cycle
+    cycle(&[("A", "use B\n#export Bar"), ("B", "use A\n#export Foo")]);
+
+    let c = ("C", "use A\npub Baz { Foo }");
+    assert_eq!(resolve_modules(&[("A", "pub Foo { 1 }"), c]), vec![]);
+}
+
+#[test]
+fn command_expansion_works() {
+    let register = |db: &TestDatabase| {
+        let build_system = db.build_system();
+        build_system.register_command("answer", |_, _, command| {
+            parse_expansion(command, "Answer { 42 }")
+        });
+        build_system.register_command("forever", |_, _, command| {
+            parse_expansion(command, "#forever")
+        });
+    };
+
+    assert_eq!(
+        resolve_modules_with(&[("Test", "#answer\nMain { Answer }")], register),
+        vec![]
+    );
+
+    // The expansions are located at the command, so the nested ones are reported on it
+    let error = CommandError::TooDeepExpansion {
+        limit: MAX_EXPANSION_DEPTH,
+    };
+    let (kind, _, text) = resolve_modules_with(&[("Test", "#forever\nMain { 1 }")], register)
+        .into_iter()
+        .find(|(_, message, _)| *message == error.to_string())
+        .expect("the nested expansion should be reported");
+
+    assert_eq!(kind, error.kind());
+    assert!(text.starts_with("#forever"));
+}
@@ -0,0 +1,37 @@
+use asena_report::{DiagnosticKind, InternalError};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ValidationError {
+    #[error("the type annotations can't be used in the patterns, annotate the value instead")]
+    AnnotationInPatternError,
+
+    #[error("the `<-` can only be used in the statements of the do blocks")]
+    AskOutsideDoError,
+
+    #[error("the cases can only be used in the `match` expressions")]
+    CaseOutsideMatchError,
+
+    #[error("the `match` doesn't have cases")]
+    EmptyMatchError,
+}
+
+impl ValidationError {
+    pub fn discriminant(&self) -> u8 {
+        // SAFETY: Because `Self` is marked `repr(u8)`, its layout is a `repr(C)` `union`
+        // between `repr(C)` structs, each of which has the `u8` discriminant as its first
+        // field, so we can read the discriminant without offsetting the pointer.
+        unsafe { *<*const _>::from(self).cast::<u8>() }
+    }
+}
+
+impl InternalError for ValidationError {
+    fn code(&self) -> u16 {
+        self.discriminant() as u16
+    }
+
+    fn kind(&self) -> DiagnosticKind {
+        DiagnosticKind::SyntaxError
+    }
+}
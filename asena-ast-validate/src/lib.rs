@@ -0,0 +1,65 @@
+//! Validates the shapes of the syntax trees that the grammar can't express, like a `match`
+//! without cases. The trees that aren't parsed, like the expansions of the commands, can break the
+//! other invariants too, like a case outside of a `match`, or a `<-` outside of a do block, so
+//! they're checked as well.
+//!
+//! The validation runs before the resolution, so the errors are reported on the original trees.
+
+use asena_ast::{AsenaFile, Case};
+use asena_ast_db::{db::AstDatabase, package::HasDiagnostic};
+use asena_leaf::ast::GreenTree;
+use asena_leaf::node::TreeKind::{self, *};
+use asena_report::WithError;
+
+use crate::error::ValidationError::{self, *};
+
+pub mod error;
+
+/// Reports the syntax shape errors of the file, see the [module](self) documentation.
+pub fn validate_file(db: &dyn AstDatabase, file: &AsenaFile) {
+    for node in file.preorder() {
+        if let Some(error) = validate_node(&node) {
+            node.clone().fail(error).push(db);
+        }
+    }
+}
+
+/// Returns the syntax shape error of the node, if any. The parent of the node must be set, like
+/// in the nodes of [GreenTree::preorder].
+pub fn validate_node(node: &GreenTree) -> Option<ValidationError> {
+    let parent = (*node.parent()).as_ref().map(|parent| parent.kind());
+
+    match node.kind() {
+        ExprAnn if parent.map(is_pattern).unwrap_or_default() => Some(AnnotationInPatternError),
+        MatchCase if parent != Some(ExprMatch) => Some(CaseOutsideMatchError),
+        StmtAsk if !parent.map(is_block).unwrap_or_default() => Some(AskOutsideDoError),
+        ExprMatch if node.filter::<Case>().get().is_empty() => Some(EmptyMatchError),
+        _ => None,
+    }
+}
+
+fn is_pattern(kind: TreeKind) -> bool {
+    matches!(
+        kind,
+        PatWildcard
+            | PatSpread
+            | PatLit
+            | PatRange
+            | PatOr
+            | PatAs
+            | PatGlobal
+            | PatConstructor
+            | PatConstructorList
+            | PatList
+            | PatGroup
+            | PatUnit
+    )
+}
+
+/// Returns if the node has the statements of a block, every block with a `<-` is a do block.
+fn is_block(kind: TreeKind) -> bool {
+    matches!(
+        kind,
+        DeclSignature | TraitDefault | ClassMethod | BranchBlock | ExprDsl | BodyDo
+    )
+}
@@ -0,0 +1,41 @@
+use asena_ast_validate::{error::ValidationError, validate_node};
+use asena_leaf::ast::GreenTree;
+use asena_leaf::node::{Child, Tree, TreeKind};
+use asena_lexer::Lexer;
+use asena_parser::Parser;
+
+fn errors_of(tree: &GreenTree) -> Vec<ValidationError> {
+    tree.preorder()
+        .filter_map(|node| validate_node(&node))
+        .collect()
+}
+
+#[test]
+fn ask_outside_do_works() {
+    let source = concat!(
+        "Twice (m: Maybe Int32) : Maybe Int32 {\n",
+        "  x <- m\n",
+        "  if x { y <- m\n return y } else { return x }\n",
+        "}\n",
+    );
+    let file = Parser::from(Lexer::new(None, source)).run(asena_grammar::file);
+    let file = GreenTree::new(file.build_tree().unwrap());
+
+    // The bodies of the functions and the blocks of the branches are do blocks.
+    assert_eq!(errors_of(&file), vec![]);
+
+    // The expansions of the commands aren't parsed, so the `<-` can be out of the blocks, like
+    // in a group.
+    let ask = Parser::from(Lexer::new(None, "x <- m")).run(asena_grammar::stmt_ask);
+    let ask = ask.build_tree().unwrap();
+    let group = ask.replace(Tree {
+        name: None,
+        kind: TreeKind::ExprGroup,
+        children: vec![ask.replace(Child::Tree(ask.value.clone()))],
+    });
+
+    assert_eq!(
+        errors_of(&GreenTree::new(group)),
+        vec![ValidationError::AskOutsideDoError]
+    );
+}
@@ -36,6 +36,8 @@ impl Do {
 }
 
 ast_enum! {
+    #[ast_walker(AsenaVisitor)]
+    #[ast_listener(AsenaListener)]
     pub enum Body {
         Value <- BodyValue,
         Do    <- BodyDo,
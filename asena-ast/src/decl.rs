@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use asena_derive::*;
 
-use asena_leaf::ast::{Cursor, Lexeme};
+use asena_leaf::ast::{Cursor, Lexeme, Node};
 use asena_leaf::ast_enum;
 use asena_leaf::node::TreeKind::*;
 
@@ -12,15 +12,19 @@ use crate::traits::global_decl::GlobalDecl;
 use crate::visitor::AsenaVisitor;
 use crate::*;
 
+pub mod associated_type;
 pub mod command;
 pub mod constraint;
 pub mod default_method;
+pub mod method_impl;
 pub mod property;
 pub mod variant;
 pub mod where_clause;
 
+pub use associated_type::*;
 pub use constraint::*;
 pub use default_method::*;
+pub use method_impl::*;
 pub use property::*;
 pub use variant::*;
 pub use where_clause::*;
@@ -49,7 +53,7 @@ impl Use {
     pub fn to_fn_id(&self) -> FunctionId {
         let mut paths = Vec::new();
         for lexeme in self.segments().iter() {
-            paths.push(lexeme.0.clone())
+            paths.push(lexeme.as_str())
         }
 
         FunctionId::new(&paths.join("."))
@@ -138,6 +142,34 @@ impl Assign {
     }
 }
 
+/// Destructure is a value-declaration that binds the names of a pattern, instead of a single
+/// name, every name is bound to the part of the value that the pattern matches.
+///
+/// # Examples
+///
+/// ```haskell
+/// (Pair x y) = Divide 10 3
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct Destructure(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl Destructure {
+    #[ast_leaf]
+    pub fn pattern(&self) -> Pat {
+        self.filter().first()
+    }
+
+    /// Holds the value that is matched against the pattern.
+    #[ast_leaf]
+    pub fn value(&self) -> Expr {
+        self.filter().first()
+    }
+}
+
 /// Command is a declaration that executes a command in the LSP, like a REPL, the goal of the
 /// language, is to have a language server as a REPL, available to execute commands.
 ///
@@ -197,6 +229,18 @@ impl Class {
         GlobalDecl::find_parameters(self)
     }
 
+    /// The superclasses of the class, like `Functor m` in `class Monad (m) where Functor m`.
+    #[ast_leaf]
+    pub fn where_clause(&self) -> Option<Where> {
+        self.filter().try_as_nth(0)
+    }
+
+    /// The associated types of the class, like `type Item`.
+    #[ast_leaf]
+    pub fn associated_types(&self) -> Vec<AssociatedType> {
+        self.filter()
+    }
+
     #[ast_leaf]
     pub fn fields(&self) -> Vec<Field> {
         self.filter()
@@ -289,6 +333,12 @@ impl Trait {
         self.filter()
     }
 
+    /// The associated types of the trait, like `type Item`.
+    #[ast_leaf]
+    pub fn associated_types(&self) -> Vec<AssociatedType> {
+        self.filter()
+    }
+
     #[ast_leaf]
     pub fn fields(&self) -> Vec<Field> {
         self.filter()
@@ -332,10 +382,37 @@ impl Instance {
         self.filter().try_as_nth(0)
     }
 
+    /// The definitions of the trait's associated types, like `type Item = Int32`.
+    #[ast_leaf]
+    pub fn associated_types(&self) -> Vec<AssociatedType> {
+        self.filter()
+    }
+
     #[ast_leaf]
     pub fn methods(&self) -> Vec<Method> {
         self.filter()
     }
+
+    /// The implementations of the trait's fields, like `pure a = Just a`.
+    #[ast_leaf]
+    pub fn impls(&self) -> Vec<MethodImpl> {
+        self.filter()
+    }
+}
+
+/// The visibility of a top-level declaration, the private declarations are only visible in the
+/// module that declares them.
+///
+/// # Examples
+///
+/// ```haskell
+/// pub Double (x: Int32) : Int32 { x + x }
+/// ```
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Visibility {
+    #[default]
+    Private,
+    Public,
 }
 
 impl Decl {
@@ -345,19 +422,29 @@ impl Decl {
         self.walk(&mut visitor::new_walker(&mut visitor));
         self
     }
+
+    /// Returns the visibility of the declaration, it's public if it starts with `pub`.
+    pub fn visibility(&self) -> Visibility {
+        if self.clone().unwrap().matches(0, TokenKind::PubKeyword) {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        }
+    }
 }
 
 ast_enum! {
     #[ast_walker(AsenaVisitor)]
     #[ast_listener(AsenaListener)]
     pub enum Decl {
-        Use       <- DeclUse,
-        Signature <- DeclSignature,
-        Assign    <- DeclAssign,
-        Command   <- DeclCommand,
-        Class     <- DeclClass,
-        Instance  <- DeclInstance,
-        Trait     <- DeclTrait,
-        Enum      <- DeclEnum,
+        Use         <- DeclUse,
+        Signature   <- DeclSignature,
+        Assign      <- DeclAssign,
+        Destructure <- DeclDestructure,
+        Command     <- DeclCommand,
+        Class       <- DeclClass,
+        Instance    <- DeclInstance,
+        Trait       <- DeclTrait,
+        Enum        <- DeclEnum,
     }
 }
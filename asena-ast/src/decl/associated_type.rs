@@ -0,0 +1,42 @@
+use asena_leaf::ast::{Leaf, Node};
+use asena_leaf::kind::TreeKind::*;
+
+use crate::*;
+
+/// An associated type node in a trait, or in a class, declares a type that the instances
+/// define, and that the signatures reference with `Self`, like `Self.Item`.
+///
+/// The syntax is like:
+/// ```haskell
+/// type Item
+/// ```
+///
+/// And the instances define it with its value, like `type Item = Int32`.
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct AssociatedType(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl AssociatedType {
+    #[ast_leaf]
+    pub fn name(&self) -> BindingId {
+        self.filter().first()
+    }
+
+    /// The value of the type, it's [Typed::Infer] in the declarations of the traits.
+    #[ast_leaf]
+    pub fn value(&self) -> Typed {
+        self.filter().first()
+    }
+}
+
+impl Leaf for AssociatedType {
+    fn make(tree: GreenTree) -> Option<Self> {
+        Some(match tree.kind() {
+            AssocType => AssociatedType::new(tree),
+            _ => return None,
+        })
+    }
+}
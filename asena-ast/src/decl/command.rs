@@ -9,6 +9,18 @@ pub type Result<T = ()> = std::result::Result<T, CommandError>;
 pub enum CommandError {
     #[error("invalid argument type: expected {expected_node_type}")]
     InvalidArgument { expected_node_type: &'static str },
+
+    #[error("the expansion of the command is nested too deep, the limit is {limit}")]
+    TooDeepExpansion { limit: usize },
+
+    #[error("the code generated by the command has {errors} syntax errors")]
+    InvalidExpansion { errors: usize },
+
+    #[error("can't find the enum or the struct {name} in the file")]
+    UnresolvedEnum { name: String },
+
+    #[error("can't derive {name}, only Eq and Show can be derived")]
+    UnsupportedDerive { name: String },
 }
 
 impl Command {
@@ -0,0 +1,44 @@
+use asena_leaf::ast::{Leaf, Node};
+use asena_leaf::kind::TreeKind::*;
+
+use crate::*;
+
+/// A method implementation node in an instance, implements a field of the trait with
+/// equations, like the top level [Assign]s.
+///
+/// The syntax is like:
+/// ```haskell
+/// pure a = Just a
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct MethodImpl(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl MethodImpl {
+    #[ast_leaf]
+    pub fn name(&self) -> BindingId {
+        self.filter().first()
+    }
+
+    #[ast_leaf]
+    pub fn patterns(&self) -> Vec<Pat> {
+        self.filter()
+    }
+
+    #[ast_leaf]
+    pub fn body(&self) -> Expr {
+        self.filter().first()
+    }
+}
+
+impl Leaf for MethodImpl {
+    fn make(tree: GreenTree) -> Option<Self> {
+        Some(match tree.kind() {
+            InstanceImpl => MethodImpl::new(tree),
+            _ => return None,
+        })
+    }
+}
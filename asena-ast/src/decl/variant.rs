@@ -66,6 +66,47 @@ impl Variant {
             Variant::Error => BindingId::default(),
         }
     }
+
+    /// The types of the fields of the variant, in the order they're applied. The fields of a
+    /// GADT-style variant are the parameters of its type, like `a` in `Just : a -> Maybe a`, and
+    /// the inferred fields are errors, as they're reported in the lowering. The foralls are
+    /// peeled, like in `Just : forall a. a -> Maybe a`.
+    pub fn fields(&self) -> Vec<Expr> {
+        match self {
+            Variant::TypeVariant(v) => {
+                let Typed::Explicit(mut value) = v.value() else {
+                    return vec![];
+                };
+
+                let mut fields = vec![];
+                loop {
+                    match value {
+                        Expr::Forall(forall) => value = forall.value(),
+                        Expr::Pi(pi) => {
+                            fields.push(pi.parameter_type());
+                            value = pi.return_type();
+                        }
+                        _ => break,
+                    }
+                }
+                fields
+            }
+            Variant::ConstructorVariant(v) => v
+                .parameters()
+                .into_iter()
+                .map(|parameter| match parameter {
+                    Typed::Infer => Expr::Error,
+                    Typed::Explicit(type_expr) => type_expr,
+                })
+                .collect(),
+            Variant::Error => vec![],
+        }
+    }
+
+    /// The amount of arguments the variant is applied to, to build a value of the enum.
+    pub fn arity(&self) -> usize {
+        self.fields().len()
+    }
 }
 
 ast_enum! {
@@ -18,6 +18,11 @@
 //! - [Sigma] TODO
 //! - [Local]
 //! - [Help]
+//! - [LeftSection]
+//! - [RightSection]
+//! - [OperatorSection]
+//! - [Interpolation]
+//! - [Unary]
 //!
 
 use std::fmt::Debug;
@@ -139,15 +144,16 @@ impl Group {
 /// ```
 ///
 /// The infix expressions can have precedence, and they have the following precedence order:
-///   - `->`, `=>`
-///   - `^`, `>>`, `<<`, `|`, `&`
-///   - `>`, `>=`, `<=`, `<`
-///   - `==`, `!=`
-///   - `||`, `&&`
-///   - `$`, `%`, `=>>`, `@`
-///   - `^^`
+///   - `.`, `>>`, `<<`
+///   - `^`, `^^`
 ///   - `*`, `/`
 ///   - `+`, `-`
+///   - `|`, `&`
+///   - `==`, `/=`, `>`, `>=`, `<=`, `<`
+///   - `&&`
+///   - `||`
+///   - `|>`
+///   - `$`, `%`, `=>>`, `@`
 ///   Being the most important the first items.
 #[derive(Default, Node, Clone, Hash, PartialEq, Eq)]
 pub struct Infix(GreenTree);
@@ -162,25 +168,9 @@ impl Debug for Infix {
     }
 }
 
-impl Listenable for Infix {
-    type Listener<'a> = &'a mut dyn AsenaListener<()>;
-
-    fn listen(&self, listener: &mut Self::Listener<'_>) {
-        self.lhs().listen(listener);
-        self.fn_id().listen(listener);
-        self.rhs().listen(listener);
-    }
-}
-
-impl Walkable for Infix {
-    type Walker<'a> = &'a mut dyn AsenaVisitor<()>;
-
-    fn walk(&self, walker: &mut Self::Walker<'_>) {
-        self.lhs().walk(walker);
-        self.fn_id().walk(walker);
-        self.rhs().walk(walker);
-    }
-}
+#[ast_walkable(AsenaVisitor, leaves(lhs, fn_id, rhs))]
+#[ast_listenable(AsenaListener, leaves(lhs, fn_id, rhs))]
+impl Infix {}
 
 impl Located for Infix {
     fn location(&self) -> std::borrow::Cow<'_, asena_span::Loc> {
@@ -227,8 +217,8 @@ impl App {
 ///
 /// The syntax is like:
 /// ```haskell
-/// something 10 42 { a, .. ->
-///
+/// something 10 42 { a b ->
+///   ...
 /// }
 /// ```
 ///
@@ -247,10 +237,10 @@ impl Dsl {
         self.filter().first()
     }
 
+    /// The parameters of the block, like `item` in `forEach items { item -> ... }`.
     #[ast_leaf]
-    pub fn parameters(&self) -> Vec<Parameter> {
-        // TODO: Implement this
-        vec![].into()
+    pub fn parameters(&self) -> Vec<LamParameter> {
+        self.filter()
     }
 
     #[ast_leaf]
@@ -344,12 +334,12 @@ impl Let {
 
     #[ast_leaf]
     pub fn value(&self) -> Expr {
-        self.filter().nth(1)
+        self.filter().first()
     }
 
     #[ast_leaf]
     pub fn in_value(&self) -> Expr {
-        self.filter().nth(2)
+        self.filter().nth(1)
     }
 }
 
@@ -478,23 +468,9 @@ impl Debug for Qual {
     }
 }
 
-impl Walkable for Qual {
-    type Walker<'a> = &'a mut dyn AsenaVisitor<()>;
-
-    fn walk(&self, walker: &mut Self::Walker<'_>) {
-        self.lhs().walk(walker);
-        self.rhs().walk(walker);
-    }
-}
-
-impl Listenable for Qual {
-    type Listener<'a> = &'a mut dyn AsenaListener<()>;
-
-    fn listen(&self, listener: &mut Self::Listener<'_>) {
-        self.lhs().listen(listener);
-        self.rhs().listen(listener);
-    }
-}
+#[ast_walkable(AsenaVisitor, leaves(lhs, rhs))]
+#[ast_listenable(AsenaListener, leaves(lhs, rhs))]
+impl Qual {}
 
 impl Located for Qual {
     fn location(&self) -> std::borrow::Cow<'_, asena_span::Loc> {
@@ -529,7 +505,7 @@ impl Pi {
             let fn_id = self
                 .named_terminal::<FunctionId>("parameter_name")
                 .as_leaf()
-                .map_token(|x, token| Local(x.to_string(), token.span.clone()));
+                .map_token(|x, token| Local(x.0, token.span.clone()));
 
             if fn_id.as_str().is_empty() {
                 return Cursor::from(None);
@@ -607,7 +583,7 @@ impl Sigma {
         let fn_id = self
             .named_terminal::<FunctionId>("parameter_name")
             .as_leaf()
-            .map_token(|x, token| Local(x.to_string(), token.span.clone()));
+            .map_token(|x, token| Local(x.0, token.span.clone()));
 
         Cursor::of(fn_id)
     }
@@ -619,7 +595,40 @@ impl Sigma {
 
     #[ast_leaf]
     pub fn return_type(&self) -> Expr {
-        self.named_at("parameter_type")
+        self.named_at("return_type")
+    }
+}
+
+/// Forall expression, is a type that is quantified over the type variables, explicitly, as the
+/// free type variables are quantified implicitly.
+///
+/// # Examples
+///
+/// The syntax is like:
+/// ```haskell
+/// forall a b. (a -> b) -> List a -> List b
+/// ```
+///
+/// It would be pretty printed to:
+/// ```haskell
+/// ∀ a b. (a -> b) -> List a -> List b
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct Forall(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl Forall {
+    #[ast_leaf]
+    pub fn variables(&self) -> Vec<Lexeme<Local>> {
+        self.filter_terminal()
+    }
+
+    #[ast_leaf]
+    pub fn value(&self) -> Expr {
+        self.filter().first()
     }
 }
 
@@ -638,6 +647,141 @@ impl Help {
     }
 }
 
+/// Left operator section, is an infix operator partially applied to its left operand, and
+/// surrounded by parenthesis.
+///
+/// # Examples
+///
+/// The syntax is like:
+/// ```haskell
+/// (2 *) -- \x -> 2 * x
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct LeftSection(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl LeftSection {
+    #[ast_leaf]
+    pub fn lhs(&self) -> Expr {
+        self.filter().first()
+    }
+
+    #[ast_leaf]
+    pub fn fn_id(&self) -> Lexeme<FunctionId> {
+        self.filter_terminal().first()
+    }
+}
+
+/// Right operator section, is an infix operator partially applied to its right operand, and
+/// surrounded by parenthesis.
+///
+/// # Examples
+///
+/// The syntax is like:
+/// ```haskell
+/// (+ 1) -- \x -> x + 1
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct RightSection(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl RightSection {
+    #[ast_leaf]
+    pub fn fn_id(&self) -> Lexeme<FunctionId> {
+        self.filter_terminal().first()
+    }
+
+    #[ast_leaf]
+    pub fn rhs(&self) -> Expr {
+        self.filter().first()
+    }
+}
+
+/// Operator section, is an infix operator without operands, surrounded by parenthesis, so it
+/// can be used in the prefix position.
+///
+/// # Examples
+///
+/// The syntax is like:
+/// ```haskell
+/// (+) 1 2 -- \x y -> x + y
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct OperatorSection(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl OperatorSection {
+    #[ast_leaf]
+    pub fn fn_id(&self) -> Lexeme<FunctionId> {
+        self.filter_terminal().first()
+    }
+}
+
+/// Interpolated string expression, is a string literal with expressions inside braces, the
+/// chunks and the values are alternated, so there's always one more chunk than values.
+///
+/// # Examples
+///
+/// The syntax is like:
+/// ```haskell
+/// "hello {name}!" -- "hello " ++ name ++ "!"
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct Interpolation(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl Interpolation {
+    #[ast_leaf]
+    pub fn chunks(&self) -> Vec<Lexeme<Literal>> {
+        self.filter_terminal()
+    }
+
+    #[ast_leaf]
+    pub fn values(&self) -> Vec<Expr> {
+        self.filter()
+    }
+}
+
+/// Unary expression, is a prefix operator applied to a single operand, it's distinct from the
+/// [Infix] minus, and only the minus operator can be used as prefix.
+///
+/// # Examples
+///
+/// The syntax is like:
+/// ```haskell
+/// -x
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct Unary(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl Unary {
+    #[ast_leaf]
+    pub fn fn_id(&self) -> Lexeme<FunctionId> {
+        self.filter_terminal().first()
+    }
+
+    #[ast_leaf]
+    pub fn value(&self) -> Expr {
+        self.filter().first()
+    }
+}
+
 impl Expr {
     /// Walks the tree using the given visitor, it will call the visitor's methods for each node
     /// in the tree.
@@ -667,7 +811,13 @@ ast_enum! {
         Qual            <- ExprQual,
         Pi              <- ExprPi,
         Sigma           <- ExprSigma,
+        Forall          <- ExprForall,
         Help            <- ExprHelp,
+        LeftSection     <- ExprLeftSection,
+        RightSection    <- ExprRightSection,
+        OperatorSection <- ExprOperatorSection,
+        Interpolation   <- ExprInterpolation,
+        Unary           <- ExprUnary,
         LocalExpr       <- ExprLocal,
         LiteralExpr     <- ExprLit,
     }
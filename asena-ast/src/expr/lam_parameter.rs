@@ -5,7 +5,8 @@ use asena_leaf::kind::TreeKind::*;
 
 use crate::*;
 
-/// A lambda expression parameter, is a parameter of a lambda expression.
+/// A lambda expression parameter, is a parameter of a lambda expression. It can be a simple
+/// name, or a pattern, like `\(Just x) -> x`, that is desugared into a match during the lowering.
 #[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
 pub struct LamParameter(GreenTree);
 
@@ -18,6 +19,11 @@ impl LamParameter {
     pub fn name(&self) -> Lexeme<Local> {
         self.filter_terminal().first()
     }
+
+    #[ast_leaf]
+    pub fn pattern(&self) -> Option<Pat> {
+        self.filter().try_as_nth(0)
+    }
 }
 
 impl Leaf for LamParameter {
@@ -3,6 +3,8 @@ use std::fmt::{Debug, Display};
 
 use asena_derive::*;
 
+use asena_interner::Symbol;
+
 use asena_leaf::ast::*;
 use asena_leaf::node::TreeKind::*;
 use asena_leaf::token::{kind::TokenKind, Token};
@@ -2,8 +2,10 @@ use super::*;
 
 /// Identifier's key to a function (everything on the language), this can be abstracted in another
 /// identifiers. Serves as a key on a graph, or the abstract syntax tree representation.
+///
+/// The identifier is interned, so it's cheap to clone, and comparing it is comparing ids.
 #[derive(Default, Clone, Hash, PartialEq, Eq)]
-pub struct FunctionId(pub String);
+pub struct FunctionId(pub Symbol);
 
 impl Located for FunctionId {
     fn location(&self) -> std::borrow::Cow<'_, Loc> {
@@ -14,17 +16,22 @@ impl Located for FunctionId {
 impl FunctionId {
     /// Creates a new [FunctionId] by a string
     pub fn new(id: &str) -> Self {
-        Self(id.into())
+        Self(Symbol::intern(id))
     }
 
     /// Gets the local's identifier as string borrow
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'static str {
         self.0.as_str()
     }
 
+    /// Gets the interned symbol of the identifier, it's the same symbol of the HIR names.
+    pub fn symbol(&self) -> Symbol {
+        self.0
+    }
+
     /// Creates a new [FunctionId] by appending a path to the current identifier
     pub fn create_path<I: Into<FunctionId>>(a: I, b: I) -> Self {
-        Self(format!("{}.{}", a.into().as_str(), b.into().as_str()))
+        Self::new(&format!("{}.{}", a.into().as_str(), b.into().as_str()))
     }
 
     pub fn optional_path<I: Clone + Into<FunctionId>>(a: Option<I>, b: I) -> Self {
@@ -48,13 +55,13 @@ impl From<&str> for FunctionId {
 impl Terminal for FunctionId {
     fn terminal(token: Spanned<Token>) -> Option<Self> {
         Some(match token.kind {
-            TokenKind::Identifier => Self(token.text.clone()),
-            TokenKind::Symbol => Self(token.text.clone()),
-            TokenKind::Dot => Self(token.text.clone()),
-            TokenKind::DoubleArrow => Self(token.text.clone()),
-            TokenKind::LeftArrow => Self(token.text.clone()),
-            TokenKind::RightArrow => Self(token.text.clone()),
-            TokenKind::SelfKeyword => Self(token.text.clone()),
+            TokenKind::Identifier => Self(token.text.symbol()),
+            TokenKind::Symbol => Self(token.text.symbol()),
+            TokenKind::Dot => Self(token.text.symbol()),
+            TokenKind::DoubleArrow => Self(token.text.symbol()),
+            TokenKind::LeftArrow => Self(token.text.symbol()),
+            TokenKind::RightArrow => Self(token.text.symbol()),
+            TokenKind::SelfKeyword => Self(token.text.symbol()),
             _ => return None,
         })
     }
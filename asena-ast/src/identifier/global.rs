@@ -30,7 +30,7 @@ pub trait GlobalName: Default + Ast {
     fn to_fn_id(&self) -> FunctionId {
         let mut paths = Vec::new();
         for lexeme in self.segments().iter() {
-            paths.push(lexeme.0.clone())
+            paths.push(lexeme.as_str())
         }
 
         FunctionId::new(&paths.join("."))
@@ -3,16 +3,16 @@ use super::*;
 /// Identifier's key to local identifier, that's not declared globally, almost everything with
 /// snake case, as a language pattern.
 #[derive(Default, Clone, Hash, PartialEq, Eq)]
-pub struct Local(pub String, pub Loc);
+pub struct Local(pub Symbol, pub Loc);
 
 impl Local {
     /// Creates a new [Local] by a string
     pub fn new(span: Loc, id: &str) -> Self {
-        Self(id.into(), span)
+        Self(Symbol::intern(id), span)
     }
 
     /// Gets the local's identifier as string borrow
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'static str {
         self.0.as_str()
     }
 
@@ -31,7 +31,7 @@ impl Local {
     }
 
     pub fn to_fn_id(&self) -> FunctionId {
-        FunctionId::new(&self.0)
+        FunctionId(self.0)
     }
 }
 
@@ -44,17 +44,8 @@ impl Located for Local {
 impl Terminal for Local {
     fn terminal(token: Spanned<Token>) -> Option<Self> {
         Some(match token.kind {
-            TokenKind::SelfKeyword => {
-                let text = token.text.clone();
-                let span = token.span;
-
-                Local::new(span, &text)
-            }
-            TokenKind::Identifier => {
-                let text = token.text.clone();
-                let span = token.span;
-
-                Local::new(span, &text)
+            TokenKind::SelfKeyword | TokenKind::DoKeyword | TokenKind::Identifier => {
+                Local(token.text.symbol(), token.span)
             }
             _ => return None,
         })
@@ -17,12 +17,13 @@ pub enum Signed {
 }
 
 /// Represents the root of the asena source code file, it contains a set of declarations.
-#[derive(Default, Node, Clone, Hash, PartialEq, Eq)]
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
 pub struct AsenaFile(GreenTree);
 
 #[ast_of]
 #[ast_debug]
 #[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
 impl AsenaFile {
     #[ast_leaf]
     pub fn declarations(&self) -> Vec<Decl> {
@@ -40,6 +41,27 @@ impl AsenaFile {
         self.walk(&mut visitor::new_walker(&mut visitor));
         self
     }
+
+    /// Listens the tree using the given listener, it will call the listener's `enter` methods
+    /// before the children of each node, and the `exit` methods after them.
+    pub fn listens<T: AsenaListener>(self, mut listener: T) -> Self {
+        listener.listens(self.clone());
+        self
+    }
+
+    /// Listens the tree using the given listener, and returns the listener back, like
+    /// [Self::walk_with].
+    pub fn listen_with<T: AsenaListener>(&self, mut listener: T) -> T {
+        listener.listens(self.clone());
+        listener
+    }
+
+    /// Walks the tree using the given visitor, and returns the visitor back, so the state it
+    /// collected can be used, like the visitors fused with [Walkers].
+    pub fn walk_with<T: AsenaVisitor<()>>(&self, mut visitor: T) -> T {
+        self.walk(&mut visitor::new_walker(&mut visitor));
+        visitor
+    }
 }
 
 pub use body::*;
@@ -54,6 +76,7 @@ pub use stmt::*;
 pub use traits::binary::*;
 pub use traits::function::*;
 pub use visitor::*;
+pub use walkers::*;
 
 pub mod body;
 pub mod decl;
@@ -66,7 +89,9 @@ pub mod parameter;
 pub mod pat;
 pub mod reporter;
 pub mod stmt;
+pub mod syntax_kinds;
 pub mod visitor;
+pub mod walkers;
 
 pub mod traits {
     pub mod binary;
@@ -13,6 +13,7 @@ use crate::{AsenaListener, AsenaVisitor};
 pub enum Literal {
     Nat(u128), // <n>n
     String(String),
+    Char(char), // '<c>'
 
     // integers
     Int8(u8, Signed),     // <n>u8
@@ -21,9 +22,10 @@ pub enum Literal {
     Int64(u64, Signed),   // <n>u64
     Int128(u128, Signed), // <n>u128
 
-    // floats
-    Float32(f32),
-    Float64(f64),
+    // floats, with the digits of the source code, so they're printed exactly like they were
+    // written, like `1.50` or `1e3`, instead of being reconstructed from the parsed values
+    Float32(f32, String),
+    Float64(f64, String),
 
     // booleans
     True,
@@ -49,11 +51,29 @@ impl Literal {
         self.contents().parse().ok()
     }
 
+    /// Negates the literal, it's used to fold the unary minus applied to literals. Returns [None]
+    /// if the literal can't be negated, like the unsigned integers.
+    pub fn negate(&self) -> Option<Literal> {
+        Some(match self {
+            Literal::Int8(n, Signed) => Literal::Int8((*n as i8).wrapping_neg() as u8, Signed),
+            Literal::Int16(n, Signed) => Literal::Int16((*n as i16).wrapping_neg() as u16, Signed),
+            Literal::Int32(n, Signed) => Literal::Int32((*n as i32).wrapping_neg() as u32, Signed),
+            Literal::Int64(n, Signed) => Literal::Int64((*n as i64).wrapping_neg() as u64, Signed),
+            Literal::Int128(n, Signed) => {
+                Literal::Int128((*n as i128).wrapping_neg() as u128, Signed)
+            }
+            Literal::Float32(f, digits) => Literal::Float32(-f, negate_digits(digits)),
+            Literal::Float64(f, digits) => Literal::Float64(-f, negate_digits(digits)),
+            _ => return None,
+        })
+    }
+
     /// Returns the contents of the literal as a string.
     pub fn contents(&self) -> String {
         match self {
             Literal::Nat(n) => n.to_string(),
             Literal::String(s) => s.to_string(),
+            Literal::Char(c) => c.to_string(),
             Literal::Int8(n, Signed::Signed) => (*n as i8).to_string(),
             Literal::Int8(n, Signed::Unsigned) => n.to_string(),
             Literal::Int16(n, Signed::Signed) => (*n as i16).to_string(),
@@ -64,13 +84,78 @@ impl Literal {
             Literal::Int64(n, Signed::Unsigned) => n.to_string(),
             Literal::Int128(n, Signed::Signed) => (*n as i128).to_string(),
             Literal::Int128(n, Signed::Unsigned) => n.to_string(),
-            Literal::Float32(f) => f.to_string(),
-            Literal::Float64(f) => f.to_string(),
+            Literal::Float32(_, digits) => digits.to_string(),
+            Literal::Float64(_, digits) => digits.to_string(),
             Literal::True => "true".to_string(),
             Literal::False => "false".to_string(),
             Literal::Error => "".to_string(),
         }
     }
+
+    /// Returns the category of the literal, or [None] if it's an error.
+    pub fn category(&self) -> Option<LiteralCategory> {
+        Some(match self {
+            Literal::Nat(_)
+            | Literal::Int8(..)
+            | Literal::Int16(..)
+            | Literal::Int32(..)
+            | Literal::Int64(..)
+            | Literal::Int128(..) => LiteralCategory::Integer,
+            Literal::Float32(..) | Literal::Float64(..) => LiteralCategory::Float,
+            Literal::String(_) => LiteralCategory::String,
+            Literal::Char(_) => LiteralCategory::Char,
+            Literal::True | Literal::False => LiteralCategory::Bool,
+            Literal::Error => return None,
+        })
+    }
+}
+
+/// Negates the digits of the float literals, like `1.5` into `-1.5`, and `-1.5` back into `1.5`.
+fn negate_digits(digits: &str) -> String {
+    match digits.strip_prefix('-') {
+        Some(digits) => digits.to_string(),
+        None => format!("-{digits}"),
+    }
+}
+
+/// The category of the literals, the literals of the same category can be matched against the same
+/// values, like `1` and `2u8`, but not `1` and `'a'`. The sizes are only checked by the types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiteralCategory {
+    Integer,
+    Float,
+    String,
+    Char,
+    Bool,
+}
+
+impl LiteralCategory {
+    /// Returns the category of the values of the built-in type with the name, like `Int32` or
+    /// `String`, or [None] if it isn't a built-in type.
+    pub fn of_type(name: &str) -> Option<Self> {
+        Some(match name {
+            "Nat" => Self::Integer,
+            "Int8" | "Int16" | "Int32" | "Int64" | "Int128" => Self::Integer,
+            "UInt8" | "UInt16" | "UInt32" | "UInt64" | "UInt128" => Self::Integer,
+            "Float32" | "Float64" => Self::Float,
+            "String" => Self::String,
+            "Char" => Self::Char,
+            "Bool" => Self::Bool,
+            _ => return None,
+        })
+    }
+}
+
+impl std::fmt::Display for LiteralCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer => write!(f, "integer"),
+            Self::Float => write!(f, "float"),
+            Self::String => write!(f, "string"),
+            Self::Char => write!(f, "char"),
+            Self::Bool => write!(f, "boolean"),
+        }
+    }
 }
 
 impl Located for Literal {
@@ -97,7 +182,7 @@ impl LexemeListenable for Literal {
 
 impl Terminal for Literal {
     fn terminal(from: Spanned<Token>) -> Option<Self> {
-        let text = from.text.clone();
+        let text = from.text.to_string();
 
         match from.kind {
             Nat => text.parse().map(Self::Nat).ok(),
@@ -111,27 +196,59 @@ impl Terminal for Literal {
             UInt64 => text.parse().map(|value| Self::Int64(value, Unsigned)).ok(),
             Int128 => text.parse().map(|value| Self::Int128(value, Signed)).ok(),
             UInt128 => text.parse().map(|value| Self::Int128(value, Unsigned)).ok(),
-            Float64 => text.parse().map(Self::Float64).ok(),
-            Float32 => text.parse().map(Self::Float32).ok(),
+            Float64 => Some(Self::Float64(text.parse().ok()?, text)),
+            Float32 => Some(Self::Float32(text.parse().ok()?, text)),
             TrueKeyword => Some(Self::True),
             FalseKeyword => Some(Self::False),
-            Str => {
+            Str if text.starts_with('r') => {
+                let hashes = text[1..].len() - text[1..].trim_start_matches('#').len();
+                let text = &text[hashes + 2..text.len() - hashes - 1];
+
+                Some(Self::String(text.to_string()))
+            }
+            // The interpolated string chunks are delimited by the quotes and the braces, so the
+            // delimiters are stripped just like in the plain strings, and the escaped braces,
+            // like `{{` and `}}`, are the braces themselves.
+            Str | StrStart | StrMiddle | StrEnd => {
                 let text = &from.text[1..(text.len() - 1)];
-                let text = text.to_string();
+                let text = text.replace("{{", "{").replace("}}", "}");
 
                 Some(Self::String(text))
             }
+            Char => unescape_char(&text[1..(text.len() - 1)]).map(Self::Char),
             _ => None,
         }
     }
 }
 
+/// Transforms the contents of a char literal, like `a` or `\n`, into the char value.
+fn unescape_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    let value = match chars.next()? {
+        '\\' => match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            value => value,
+        },
+        value => value,
+    };
+
+    if chars.next().is_some() {
+        return None;
+    }
+
+    Some(value)
+}
+
 impl Debug for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Error => write!(f, "Error"),
             Self::Nat(n) => write!(f, "{n}n"),
             Self::String(string) => write!(f, "\"{string}\""),
+            Self::Char(value) => write!(f, "{value:?}"),
             Self::Int8(i8, Signed::Signed) => write!(f, "{i8}i8"),
             Self::Int8(u8, Signed::Unsigned) => write!(f, "{u8}u8"),
             Self::Int16(i16, Signed::Signed) => write!(f, "{i16}i16"),
@@ -142,8 +259,8 @@ impl Debug for Literal {
             Self::Int64(u64, Signed::Unsigned) => write!(f, "{u64}u64"),
             Self::Int128(i128, Signed::Signed) => write!(f, "{i128}i128"),
             Self::Int128(u128, Signed::Unsigned) => write!(f, "{u128}u128"),
-            Self::Float32(f32) => write!(f, "{f32}f32"),
-            Self::Float64(f64) => write!(f, "{f64}f64"),
+            Self::Float32(_, digits) => write!(f, "{digits}f32"),
+            Self::Float64(_, digits) => write!(f, "{digits}f64"),
             Self::True => write!(f, "true"),
             Self::False => write!(f, "false"),
         }
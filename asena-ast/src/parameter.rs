@@ -3,7 +3,6 @@ use asena_derive::*;
 use asena_leaf::ast::{GreenTree, Leaf, Lexeme, Node};
 use asena_leaf::node::TreeKind::*;
 use asena_leaf::token::kind::TokenKind;
-use if_chain::if_chain;
 use im::HashMap;
 
 use crate::*;
@@ -47,22 +46,22 @@ impl Parameter {
         !self.token(TokenKind::SelfKeyword).is_error()
     }
 
+    /// If the parameter is an instance argument, like `[Monad m]`, it's an implicit parameter
+    /// without a name, that is filled with the instance of the constraint.
+    pub fn is_instance(&self) -> bool {
+        self.matches(0, TokenKind::LeftBracket) && !self.matches(1, TokenKind::Identifier)
+    }
+
+    /// Returns the named parameters, the instance arguments don't have names, so they can't be
+    /// referenced, and aren't returned.
     pub fn compute_parameters(parameters: Vec<Parameter>) -> HashMap<FunctionId, Parameter> {
         let mut map = HashMap::new();
         for parameter in parameters.into_iter() {
-            if parameter.explicit() {
-                map.insert(parameter.name().to_fn_id(), parameter);
+            if parameter.is_instance() {
                 continue;
             }
 
-            if_chain! {
-                if let Typed::Explicit(Expr::Ann(ann)) = parameter.parameter_type();
-                if let Expr::LocalExpr(local_expr) = ann.value();
-                if let Some(name) = local_expr.is_ident();
-                then {
-                    map.insert(name.to_fn_id(), parameter);
-                }
-            }
+            map.insert(parameter.name().to_fn_id(), parameter);
         }
         map
     }
@@ -22,6 +22,77 @@ impl LiteralPat {
     }
 }
 
+/// Range pattern, matches the numbers between the two literals, including both of them.
+///
+/// The syntax is like:
+/// ```haskell
+/// 1..10
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct RangePat(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl RangePat {
+    #[ast_leaf]
+    pub fn start(&self) -> LiteralPat {
+        self.filter().first()
+    }
+
+    #[ast_leaf]
+    pub fn end(&self) -> LiteralPat {
+        self.filter().nth(1)
+    }
+}
+
+/// Or pattern, matches the value if any of its alternatives matches it, all of them should bind
+/// the same names.
+///
+/// The syntax is like:
+/// ```haskell
+/// Just 1 | Just 2
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct OrPat(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl OrPat {
+    #[ast_leaf]
+    pub fn alternatives(&self) -> Vec<Pat> {
+        self.filter()
+    }
+}
+
+/// As pattern, binds the whole value to the name, besides the names of the pattern.
+///
+/// The syntax is like:
+/// ```haskell
+/// xs@(Cons x _)
+/// ```
+#[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
+pub struct AsPat(GreenTree);
+
+#[ast_of]
+#[ast_debug]
+#[ast_walkable(AsenaVisitor)]
+#[ast_listenable(AsenaListener)]
+impl AsPat {
+    #[ast_leaf]
+    pub fn name(&self) -> BindingId {
+        self.filter().first()
+    }
+
+    #[ast_leaf]
+    pub fn pattern(&self) -> Pat {
+        self.filter().first()
+    }
+}
+
 /// Global pattern, is a global name.
 ///
 /// The syntax is like:
@@ -71,7 +142,7 @@ impl ConstructorPat {
 ///
 /// The syntax is like:
 /// ```haskell
-/// [x, ..]
+/// [x, .., y]
 /// ```
 #[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
 pub struct ListPat(GreenTree);
@@ -92,7 +163,7 @@ impl ListPat {
 ///
 /// The syntax is like:
 /// ```haskell
-/// [x, ..]
+/// [x, .., y]
 /// ```
 #[derive(Default, Node, Located, Clone, Hash, PartialEq, Eq)]
 pub struct SpreadPat(GreenTree);
@@ -135,10 +206,51 @@ ast_enum! {
         WildcardPat    <- PatWildcard,    // _
         SpreadPat      <- PatSpread,      // ..
         UnitPat        <- PatUnit,        // ()
+        #[ast_build_fn(PatGroup, Pat::group)]
         ConstructorPat <- PatConstructor, // (<global_id> <pattern...>)
         ListPat        <- PatList,        // [<pattern...>]
         GlobalPat      <- PatGlobal,      // <global>
         LiteralPat     <- PatLit,         // <literal>
+        RangePat       <- PatRange,       // <literal>..<literal>
+        OrPat          <- PatOr,          // <pattern> | <pattern...>
+        AsPat          <- PatAs,          // <local>@<pattern>
+    }
+}
+
+impl Pat {
+    /// Builds the pattern inside of the parenthesis, as the groups aren't patterns by
+    /// themselves, like `(Just x)`.
+    fn group(tree: GreenTree) -> Option<Pat> {
+        tree.filter::<Pat>().first().try_as_leaf()
+    }
+
+    /// Returns the names that the pattern binds, in the order they appear, without resolving
+    /// them. The names that don't start with a lowercase letter are constructors, like `Nil`.
+    pub fn bindings(&self) -> Vec<BindingId> {
+        match self {
+            Pat::GlobalPat(pat) => {
+                let name = pat.name();
+                let is_binding = name.to_fn_id().as_str().starts_with(char::is_lowercase);
+
+                if is_binding {
+                    vec![name]
+                } else {
+                    vec![]
+                }
+            }
+            Pat::ConstructorPat(pat) => pat.arguments().iter().flat_map(Pat::bindings).collect(),
+            Pat::ListPat(pat) => pat.items().iter().flat_map(Pat::bindings).collect(),
+            Pat::OrPat(pat) => pat
+                .alternatives()
+                .iter()
+                .next()
+                .map(Pat::bindings)
+                .unwrap_or_default(),
+            Pat::AsPat(pat) => std::iter::once(pat.name())
+                .chain(pat.pattern().bindings())
+                .collect(),
+            _ => vec![],
+        }
     }
 }
 
@@ -0,0 +1,89 @@
+//! The registry of the kinds of the syntax, the tree kinds with the categories and the nodes
+//! they're built into, and the token kinds with their categories. It's generated from the AST
+//! enums, so the external tools, like the syntax highlighters and the tree visualizers, don't
+//! need to hardcode the kinds.
+
+use asena_leaf::node::{Named, TreeKind};
+use asena_leaf::token::kind::{TokenCategory, TokenKind};
+
+use crate::*;
+
+/// The AST enum that a tree kind is built into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NodeCategory {
+    Decl,
+    Expr,
+    Stmt,
+    Pat,
+    Body,
+    Branch,
+    Variant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeKindInfo {
+    pub kind: TreeKind,
+
+    /// The name of the kind, like `EXPR_APP`, as in the debug print of the trees.
+    pub name: String,
+    pub category: Option<NodeCategory>,
+
+    /// The name of the node of the AST enum, like `App`, the kinds that aren't variants of the
+    /// AST enums don't have it.
+    pub node: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenKindInfo {
+    pub kind: TokenKind,
+
+    /// The name of the kind, like `LET_KEYWORD`.
+    pub name: String,
+    pub category: TokenCategory,
+}
+
+/// Returns all the tree kinds, in the order they're declared.
+pub fn tree_kinds() -> Vec<TreeKindInfo> {
+    let categories = [
+        (NodeCategory::Decl, Decl::KINDS),
+        (NodeCategory::Expr, Expr::KINDS),
+        (NodeCategory::Stmt, Stmt::KINDS),
+        (NodeCategory::Pat, Pat::KINDS),
+        (NodeCategory::Body, Body::KINDS),
+        (NodeCategory::Branch, Branch::KINDS),
+        (NodeCategory::Variant, Variant::KINDS),
+    ];
+
+    TreeKind::ALL
+        .iter()
+        .map(|kind| {
+            let (category, node) = categories
+                .iter()
+                .find_map(|(category, kinds)| {
+                    let (_, node) = kinds.iter().find(|(node_kind, _)| node_kind == kind)?;
+
+                    Some((Some(*category), Some(*node)))
+                })
+                .unwrap_or_default();
+
+            TreeKindInfo {
+                kind: *kind,
+                name: kind.name(),
+                category,
+                node,
+            }
+        })
+        .collect()
+}
+
+/// Returns all the token kinds, in the order they're declared.
+pub fn token_kinds() -> Vec<TokenKindInfo> {
+    TokenKind::ALL
+        .iter()
+        .map(|kind| TokenKindInfo {
+            kind: *kind,
+            name: kind.name(),
+            category: kind.category(),
+        })
+        .collect()
+}
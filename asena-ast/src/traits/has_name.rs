@@ -40,6 +40,7 @@ impl Decl {
             Decl::Error => None,
             Decl::Use(_) => None,
             Decl::Assign(_) => None,
+            Decl::Destructure(_) => None,
             Decl::Command(_) => None,
             Decl::Instance(_) => None,
             Decl::Signature(signature) => Some(signature),
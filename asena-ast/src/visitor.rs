@@ -48,6 +48,10 @@ pub trait AsenaVisitor<T: Default> {
         default()
     }
 
+    fn visit_associated_type(&mut self, value: AssociatedType) -> T {
+        default()
+    }
+
     fn visit_default_method(&mut self, value: DefaultMethod) -> T {
         default()
     }
@@ -60,6 +64,10 @@ pub trait AsenaVisitor<T: Default> {
         default()
     }
 
+    fn visit_method_impl(&mut self, value: MethodImpl) -> T {
+        default()
+    }
+
     fn visit_where(&mut self, value: Where) -> T {
         default()
     }
@@ -112,6 +120,10 @@ pub trait AsenaVisitor<T: Default> {
         self.visit_decl(value.into())
     }
 
+    fn visit_destructure(&mut self, value: Destructure) -> T {
+        self.visit_decl(value.into())
+    }
+
     fn visit_class(&mut self, value: Class) -> T {
         self.visit_decl(value.into())
     }
@@ -208,10 +220,34 @@ pub trait AsenaVisitor<T: Default> {
         self.visit_expr(value.into())
     }
 
+    fn visit_forall(&mut self, value: Forall) -> T {
+        self.visit_expr(value.into())
+    }
+
     fn visit_help(&mut self, value: Help) -> T {
         self.visit_expr(value.into())
     }
 
+    fn visit_left_section(&mut self, value: LeftSection) -> T {
+        self.visit_expr(value.into())
+    }
+
+    fn visit_right_section(&mut self, value: RightSection) -> T {
+        self.visit_expr(value.into())
+    }
+
+    fn visit_operator_section(&mut self, value: OperatorSection) -> T {
+        self.visit_expr(value.into())
+    }
+
+    fn visit_interpolation(&mut self, value: Interpolation) -> T {
+        self.visit_expr(value.into())
+    }
+
+    fn visit_unary(&mut self, value: Unary) -> T {
+        self.visit_expr(value.into())
+    }
+
     fn visit_local_expr(&mut self, value: LocalExpr) -> T {
         self.visit_expr(value.into())
     }
@@ -240,6 +276,18 @@ pub trait AsenaVisitor<T: Default> {
         self.visit_pat(value.into())
     }
 
+    fn visit_range_pat(&mut self, value: RangePat) -> T {
+        self.visit_pat(value.into())
+    }
+
+    fn visit_or_pat(&mut self, value: OrPat) -> T {
+        self.visit_pat(value.into())
+    }
+
+    fn visit_as_pat(&mut self, value: AsPat) -> T {
+        self.visit_pat(value.into())
+    }
+
     fn visit_unit_pat(&mut self, value: UnitPat) -> T {
         self.visit_pat(value.into())
     }
@@ -319,6 +367,10 @@ pub trait AsenaListener<T: Default = ()> {
         default()
     }
 
+    fn enter_associated_type(&mut self, value: AssociatedType) -> T {
+        default()
+    }
+
     fn enter_default_method(&mut self, value: DefaultMethod) -> T {
         default()
     }
@@ -331,6 +383,10 @@ pub trait AsenaListener<T: Default = ()> {
         default()
     }
 
+    fn enter_method_impl(&mut self, value: MethodImpl) -> T {
+        default()
+    }
+
     fn enter_where(&mut self, value: Where) -> T {
         default()
     }
@@ -383,6 +439,10 @@ pub trait AsenaListener<T: Default = ()> {
         self.enter_decl(value.into())
     }
 
+    fn enter_destructure(&mut self, value: Destructure) -> T {
+        self.enter_decl(value.into())
+    }
+
     fn enter_class(&mut self, value: Class) -> T {
         self.enter_decl(value.into())
     }
@@ -479,10 +539,34 @@ pub trait AsenaListener<T: Default = ()> {
         self.enter_expr(value.into())
     }
 
+    fn enter_forall(&mut self, value: Forall) -> T {
+        self.enter_expr(value.into())
+    }
+
     fn enter_help(&mut self, value: Help) -> T {
         self.enter_expr(value.into())
     }
 
+    fn enter_left_section(&mut self, value: LeftSection) -> T {
+        self.enter_expr(value.into())
+    }
+
+    fn enter_right_section(&mut self, value: RightSection) -> T {
+        self.enter_expr(value.into())
+    }
+
+    fn enter_operator_section(&mut self, value: OperatorSection) -> T {
+        self.enter_expr(value.into())
+    }
+
+    fn enter_interpolation(&mut self, value: Interpolation) -> T {
+        self.enter_expr(value.into())
+    }
+
+    fn enter_unary(&mut self, value: Unary) -> T {
+        self.enter_expr(value.into())
+    }
+
     fn enter_local_expr(&mut self, value: LocalExpr) -> T {
         self.enter_expr(value.into())
     }
@@ -511,6 +595,18 @@ pub trait AsenaListener<T: Default = ()> {
         self.enter_pat(value.into())
     }
 
+    fn enter_range_pat(&mut self, value: RangePat) -> T {
+        self.enter_pat(value.into())
+    }
+
+    fn enter_or_pat(&mut self, value: OrPat) -> T {
+        self.enter_pat(value.into())
+    }
+
+    fn enter_as_pat(&mut self, value: AsPat) -> T {
+        self.enter_pat(value.into())
+    }
+
     fn enter_unit_pat(&mut self, value: UnitPat) -> T {
         self.enter_pat(value.into())
     }
@@ -579,6 +675,10 @@ pub trait AsenaListener<T: Default = ()> {
         default()
     }
 
+    fn exit_associated_type(&mut self, value: AssociatedType) -> T {
+        default()
+    }
+
     fn exit_default_method(&mut self, value: DefaultMethod) -> T {
         default()
     }
@@ -591,6 +691,10 @@ pub trait AsenaListener<T: Default = ()> {
         default()
     }
 
+    fn exit_method_impl(&mut self, value: MethodImpl) -> T {
+        default()
+    }
+
     fn exit_where(&mut self, value: Where) -> T {
         default()
     }
@@ -643,6 +747,10 @@ pub trait AsenaListener<T: Default = ()> {
         self.exit_decl(value.into())
     }
 
+    fn exit_destructure(&mut self, value: Destructure) -> T {
+        self.exit_decl(value.into())
+    }
+
     fn exit_class(&mut self, value: Class) -> T {
         self.exit_decl(value.into())
     }
@@ -739,10 +847,34 @@ pub trait AsenaListener<T: Default = ()> {
         self.exit_expr(value.into())
     }
 
+    fn exit_forall(&mut self, value: Forall) -> T {
+        self.exit_expr(value.into())
+    }
+
     fn exit_help(&mut self, value: Help) -> T {
         self.exit_expr(value.into())
     }
 
+    fn exit_left_section(&mut self, value: LeftSection) -> T {
+        self.exit_expr(value.into())
+    }
+
+    fn exit_right_section(&mut self, value: RightSection) -> T {
+        self.exit_expr(value.into())
+    }
+
+    fn exit_operator_section(&mut self, value: OperatorSection) -> T {
+        self.exit_expr(value.into())
+    }
+
+    fn exit_interpolation(&mut self, value: Interpolation) -> T {
+        self.exit_expr(value.into())
+    }
+
+    fn exit_unary(&mut self, value: Unary) -> T {
+        self.exit_expr(value.into())
+    }
+
     fn exit_local_expr(&mut self, value: LocalExpr) -> T {
         self.exit_expr(value.into())
     }
@@ -771,6 +903,18 @@ pub trait AsenaListener<T: Default = ()> {
         self.exit_pat(value.into())
     }
 
+    fn exit_range_pat(&mut self, value: RangePat) -> T {
+        self.exit_pat(value.into())
+    }
+
+    fn exit_or_pat(&mut self, value: OrPat) -> T {
+        self.exit_pat(value.into())
+    }
+
+    fn exit_as_pat(&mut self, value: AsPat) -> T {
+        self.exit_pat(value.into())
+    }
+
     fn exit_unit_pat(&mut self, value: UnitPat) -> T {
         self.exit_pat(value.into())
     }
@@ -0,0 +1,300 @@
+//! Fuses several visitors, or several listeners, into a single traversal of the tree, so the
+//! passes that don't depend on each other can run together, like:
+//!
+//! ```rust,ignore
+//! let Walkers((first, second)) = file.walk_with(Walkers::of((first, second)));
+//! let Walkers((enter, exit)) = file.listen_with(Walkers::of((enter, exit)));
+//! ```
+//!
+//! The visitors are called in the order of the tuple, for each node, so the first visitor sees
+//! the node before the second one, and so are the `enter` and the `exit` hooks of the listeners.
+
+use asena_leaf::ast::Lexeme;
+
+use crate::*;
+
+/// A visitor made of a tuple of visitors, it implements [AsenaVisitor] by calling every visitor
+/// of the tuple, in order, and [AsenaListener] by calling every listener of the tuple.
+pub struct Walkers<T>(pub T);
+
+impl<T> Walkers<T> {
+    pub fn of(walkers: T) -> Self {
+        Self(walkers)
+    }
+
+    /// Returns the visitors back, with the state they collected in the traversal.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! fused_call {
+    ($self:ident, $method:ident, $value:ident, [$($index:tt)+]) => {
+        $($self.0.$index.$method($value.clone());)+
+    };
+}
+
+macro_rules! fused_methods {
+    ($indexes:tt; $($method:ident: $type:ty),* $(,)?) => {
+        $(fn $method(&mut self, value: $type) {
+            fused_call!(self, $method, value, $indexes)
+        })*
+    };
+}
+
+macro_rules! fused_visitor {
+    ($($walker:ident $index:tt),+) => {
+        impl<$($walker: AsenaVisitor<()>),+> AsenaVisitor<()> for Walkers<($($walker,)+)> {
+            fused_methods! {
+                [$($index)+];
+                visit_asena_file: AsenaFile,
+                visit_qualified_path: QualifiedPath,
+                visit_qualified_binding_id: BindingId,
+                visit_parameter: Parameter,
+                visit_type_variant: TypeVariant,
+                visit_constructor_variant: ConstructorVariant,
+                visit_constraint: Constraint,
+                visit_associated_type: AssociatedType,
+                visit_default_method: DefaultMethod,
+                visit_field: Field,
+                visit_method: Method,
+                visit_method_impl: MethodImpl,
+                visit_where: Where,
+                visit_decl: Decl,
+                visit_branch: Branch,
+                visit_lam_parameter: LamParameter,
+                visit_case: Case,
+                visit_expr_branch: ExprBranch,
+                visit_block_branch: BlockBranch,
+                visit_use: Use,
+                visit_trait: Trait,
+                visit_enum: Enum,
+                visit_instance: Instance,
+                visit_signature: Signature,
+                visit_assign: Assign,
+                visit_destructure: Destructure,
+                visit_class: Class,
+                visit_command: Command,
+                visit_stmt: Stmt,
+                visit_ask: Ask,
+                visit_if_stmt: IfStmt,
+                visit_let_stmt: LetStmt,
+                visit_expr_stmt: ExprStmt,
+                visit_return: Return,
+                visit_expr: Expr,
+                visit_self_expr: SelfExpr,
+                visit_unit: Unit,
+                visit_group: Group,
+                visit_infix: Infix,
+                visit_app: App,
+                visit_if: If,
+                visit_match: Match,
+                visit_dsl: Dsl,
+                visit_array: Array,
+                visit_lam: Lam,
+                visit_let: Let,
+                visit_ann: Ann,
+                visit_qual: Qual,
+                visit_pi: Pi,
+                visit_sigma: Sigma,
+                visit_forall: Forall,
+                visit_help: Help,
+                visit_left_section: LeftSection,
+                visit_right_section: RightSection,
+                visit_operator_section: OperatorSection,
+                visit_interpolation: Interpolation,
+                visit_unary: Unary,
+                visit_local_expr: LocalExpr,
+                visit_literal_expr: LiteralExpr,
+                visit_body: Body,
+                visit_do: Do,
+                visit_value: Value,
+                visit_pat: Pat,
+                visit_literal_pat: LiteralPat,
+                visit_range_pat: RangePat,
+                visit_or_pat: OrPat,
+                visit_as_pat: AsPat,
+                visit_unit_pat: UnitPat,
+                visit_global_pat: GlobalPat,
+                visit_constructor_pat: ConstructorPat,
+                visit_list_pat: ListPat,
+                visit_wildcard_pat: WildcardPat,
+                visit_spread_pat: SpreadPat,
+                visit_local: Lexeme<Local>,
+                visit_function_id: Lexeme<FunctionId>,
+                visit_literal: Lexeme<Literal>,
+            }
+        }
+    };
+}
+
+macro_rules! fused_listener {
+    ($($walker:ident $index:tt),+) => {
+        impl<$($walker: AsenaListener<()>),+> AsenaListener<()> for Walkers<($($walker,)+)> {
+            fused_methods! {
+                [$($index)+];
+                enter_typed_explicit: Expr,
+                enter_asena_file: AsenaFile,
+                enter_qualified_path: QualifiedPath,
+                enter_qualified_binding_id: BindingId,
+                enter_parameter: Parameter,
+                enter_type_variant: TypeVariant,
+                enter_constructor_variant: ConstructorVariant,
+                enter_constraint: Constraint,
+                enter_associated_type: AssociatedType,
+                enter_default_method: DefaultMethod,
+                enter_field: Field,
+                enter_method: Method,
+                enter_method_impl: MethodImpl,
+                enter_where: Where,
+                enter_decl: Decl,
+                enter_branch: Branch,
+                enter_lam_parameter: LamParameter,
+                enter_case: Case,
+                enter_expr_branch: ExprBranch,
+                enter_block_branch: BlockBranch,
+                enter_use: Use,
+                enter_trait: Trait,
+                enter_enum: Enum,
+                enter_instance: Instance,
+                enter_signature: Signature,
+                enter_assign: Assign,
+                enter_destructure: Destructure,
+                enter_class: Class,
+                enter_command: Command,
+                enter_stmt: Stmt,
+                enter_ask: Ask,
+                enter_if_stmt: IfStmt,
+                enter_let_stmt: LetStmt,
+                enter_expr_stmt: ExprStmt,
+                enter_return: Return,
+                enter_expr: Expr,
+                enter_self_expr: SelfExpr,
+                enter_unit: Unit,
+                enter_group: Group,
+                enter_infix: Infix,
+                enter_app: App,
+                enter_if: If,
+                enter_match: Match,
+                enter_dsl: Dsl,
+                enter_array: Array,
+                enter_lam: Lam,
+                enter_let: Let,
+                enter_ann: Ann,
+                enter_qual: Qual,
+                enter_pi: Pi,
+                enter_sigma: Sigma,
+                enter_forall: Forall,
+                enter_help: Help,
+                enter_left_section: LeftSection,
+                enter_right_section: RightSection,
+                enter_operator_section: OperatorSection,
+                enter_interpolation: Interpolation,
+                enter_unary: Unary,
+                enter_local_expr: LocalExpr,
+                enter_literal_expr: LiteralExpr,
+                enter_body: Body,
+                enter_do: Do,
+                enter_value: Value,
+                enter_pat: Pat,
+                enter_literal_pat: LiteralPat,
+                enter_range_pat: RangePat,
+                enter_or_pat: OrPat,
+                enter_as_pat: AsPat,
+                enter_unit_pat: UnitPat,
+                enter_global_pat: GlobalPat,
+                enter_constructor_pat: ConstructorPat,
+                enter_list_pat: ListPat,
+                enter_wildcard_pat: WildcardPat,
+                enter_spread_pat: SpreadPat,
+                visit_local: Lexeme<Local>,
+                visit_function_id: Lexeme<FunctionId>,
+                visit_literal: Lexeme<Literal>,
+                exit_typed_explicit: Expr,
+                exit_asena_file: AsenaFile,
+                exit_qualified_path: QualifiedPath,
+                exit_qualified_binding_id: BindingId,
+                exit_parameter: Parameter,
+                exit_type_variant: TypeVariant,
+                exit_constructor_variant: ConstructorVariant,
+                exit_constraint: Constraint,
+                exit_associated_type: AssociatedType,
+                exit_default_method: DefaultMethod,
+                exit_field: Field,
+                exit_method: Method,
+                exit_method_impl: MethodImpl,
+                exit_where: Where,
+                exit_decl: Decl,
+                exit_branch: Branch,
+                exit_lam_parameter: LamParameter,
+                exit_case: Case,
+                exit_expr_branch: ExprBranch,
+                exit_block_branch: BlockBranch,
+                exit_use: Use,
+                exit_trait: Trait,
+                exit_enum: Enum,
+                exit_instance: Instance,
+                exit_signature: Signature,
+                exit_assign: Assign,
+                exit_destructure: Destructure,
+                exit_class: Class,
+                exit_command: Command,
+                exit_stmt: Stmt,
+                exit_ask: Ask,
+                exit_if_stmt: IfStmt,
+                exit_let_stmt: LetStmt,
+                exit_expr_stmt: ExprStmt,
+                exit_return: Return,
+                exit_expr: Expr,
+                exit_self_expr: SelfExpr,
+                exit_unit: Unit,
+                exit_group: Group,
+                exit_infix: Infix,
+                exit_app: App,
+                exit_if: If,
+                exit_match: Match,
+                exit_dsl: Dsl,
+                exit_array: Array,
+                exit_lam: Lam,
+                exit_let: Let,
+                exit_ann: Ann,
+                exit_qual: Qual,
+                exit_pi: Pi,
+                exit_sigma: Sigma,
+                exit_forall: Forall,
+                exit_help: Help,
+                exit_left_section: LeftSection,
+                exit_right_section: RightSection,
+                exit_operator_section: OperatorSection,
+                exit_interpolation: Interpolation,
+                exit_unary: Unary,
+                exit_local_expr: LocalExpr,
+                exit_literal_expr: LiteralExpr,
+                exit_body: Body,
+                exit_do: Do,
+                exit_value: Value,
+                exit_pat: Pat,
+                exit_literal_pat: LiteralPat,
+                exit_range_pat: RangePat,
+                exit_or_pat: OrPat,
+                exit_as_pat: AsPat,
+                exit_unit_pat: UnitPat,
+                exit_global_pat: GlobalPat,
+                exit_constructor_pat: ConstructorPat,
+                exit_list_pat: ListPat,
+                exit_wildcard_pat: WildcardPat,
+                exit_spread_pat: SpreadPat,
+            }
+        }
+    };
+}
+
+fused_visitor!(A 0, B 1);
+fused_visitor!(A 0, B 1, C 2);
+fused_visitor!(A 0, B 1, C 2, D 3);
+fused_visitor!(A 0, B 1, C 2, D 3, E 4);
+
+fused_listener!(A 0, B 1);
+fused_listener!(A 0, B 1, C 2);
+fused_listener!(A 0, B 1, C 2, D 3);
+fused_listener!(A 0, B 1, C 2, D 3, E 4);
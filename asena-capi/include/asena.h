@@ -0,0 +1,51 @@
+#ifndef ASENA_H
+#define ASENA_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/* The kinds of the diagnostics, they match the `DiagnosticKind` of the `asena-report` crate. */
+typedef enum AsenaDiagnosticKind {
+  ASENA_ERROR = 1,
+  ASENA_HARD_ERROR = 2,
+  ASENA_INTERNAL_ERROR = 3,
+  ASENA_WARNING = 4,
+  ASENA_DEPRECATED = 5,
+  ASENA_INFO = 6,
+  ASENA_TIP = 7,
+  ASENA_META = 8,
+  ASENA_SYNTAX_ERROR = 9,
+  ASENA_TYPE_ERROR = 11,
+  ASENA_RESOLUTION_ERROR = 12,
+  ASENA_LINT = 13,
+  ASENA_LOWERING_ERROR = 14,
+  ASENA_CONTEXT = 15,
+  ASENA_BUILD_ERROR = 16,
+} AsenaDiagnosticKind;
+
+typedef struct AsenaDriver AsenaDriver;
+
+/* The locations are the byte offsets in the source of the file. */
+typedef struct AsenaDiagnostic {
+  AsenaDiagnosticKind kind;
+  uint16_t code;
+  size_t start;
+  size_t end;
+  const char *file;
+  const char *message;
+} AsenaDiagnostic;
+
+AsenaDriver *asena_driver_new(void);
+void asena_driver_free(AsenaDriver *driver);
+
+/* Adds, or replaces, the module with the name, returns false if the strings aren't UTF-8. */
+bool asena_driver_add_file(AsenaDriver *driver, const char *name, const char *source);
+
+/* Checks the files, and returns the amount of diagnostics. */
+size_t asena_driver_check(AsenaDriver *driver);
+
+/* Returns the diagnostic at the index, or NULL, it's valid until the next check. */
+const AsenaDiagnostic *asena_driver_diagnostic(const AsenaDriver *driver, size_t index);
+
+#endif
@@ -0,0 +1,286 @@
+//! The C interface of the front-end of the compiler, so the editors and the plugins that aren't
+//! written in Rust can embed the analyzer. The header is in `include/asena.h`.
+//!
+//! The driver owns the sources of the files, and every check analyzes them from scratch, so the
+//! files can be added, or replaced, between the checks. The diagnostics of a check, and their
+//! strings, are owned by the driver, and they're valid until the next check, or until the driver
+//! is freed.
+
+use std::{
+    collections::HashMap,
+    ffi::{c_char, CStr, CString},
+    path::PathBuf,
+    ptr,
+    sync::Arc,
+};
+
+use asena_ast_db::{
+    db::AstDatabase,
+    package::Package,
+    vfs::{FileSystem, VfsFileData},
+};
+use asena_ast_lowering::db::AstLowerrer;
+use asena_ast_resolver::db::AstResolverDatabase;
+use asena_lexer::Lexer;
+use asena_parser::Parser;
+use asena_prec::PrecDatabase;
+use asena_report::{aggregate_diagnostics, Diagnostic, DiagnosticKind, InternalError};
+
+asena_ast_lowering::frontend_database! {
+    struct CapiDatabase {}
+}
+
+impl salsa::Database for CapiDatabase {}
+
+/// A diagnostic of a check, the locations are the byte offsets in the source of the file.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsenaDiagnostic {
+    pub kind: DiagnosticKind,
+    pub code: u16,
+    pub start: usize,
+    pub end: usize,
+
+    /// The name of the file, as it was added to the driver.
+    pub file: *const c_char,
+    pub message: *const c_char,
+}
+
+/// A diagnostic with the strings its pointers point to, the buffers of the strings don't move
+/// with them, so the pointers are valid while they're alive.
+struct OwnedDiagnostic {
+    raw: AsenaDiagnostic,
+    _strings: (CString, CString),
+}
+
+impl OwnedDiagnostic {
+    fn new<E: InternalError>(file: &str, diagnostic: &Diagnostic<E>) -> Self {
+        let range = &diagnostic.message.span.range;
+        let file = to_c_string(file);
+        let message = to_c_string(&diagnostic.message.value.to_string());
+
+        Self {
+            raw: AsenaDiagnostic {
+                kind: diagnostic.kind,
+                code: diagnostic.code,
+                start: range.start(),
+                end: range.end(),
+                file: file.as_ptr(),
+                message: message.as_ptr(),
+            },
+            _strings: (file, message),
+        }
+    }
+}
+
+/// The analyzer of a package, created by [asena_driver_new], and freed by [asena_driver_free].
+#[derive(Default)]
+pub struct AsenaDriver {
+    files: Vec<(String, String)>,
+    diagnostics: Vec<OwnedDiagnostic>,
+}
+
+impl AsenaDriver {
+    /// Adds the file, replacing the source of the file with the same name, if there's one.
+    pub fn add_file(&mut self, name: &str, source: &str) {
+        match self.files.iter_mut().find(|(file, _)| file == name) {
+            Some((_, old_source)) => *old_source = source.into(),
+            None => self.files.push((name.into(), source.into())),
+        }
+    }
+
+    /// Checks the files, and returns the amount of diagnostics. The diagnostics of all the files
+    /// are deduplicated, and sorted by the file, and by the position in the file.
+    pub fn check(&mut self) -> usize {
+        self.diagnostics.clear();
+
+        let db = CapiDatabase::default();
+        let fs = FileSystem::default();
+        for (name, source) in self.files.iter() {
+            fs.memory.insert(name.clone(), source.clone());
+        }
+
+        let package = Package::new(&db, "Local", "0.0.0", Arc::new(fs));
+        let files = self
+            .files
+            .iter()
+            .map(|(name, _)| {
+                let path = format!("./{name}.ase");
+
+                VfsFileData::new(&db, name, path.as_str().into(), package)
+            })
+            .collect::<Vec<_>>();
+
+        let mut names = HashMap::new();
+        let mut diagnostics = vec![];
+        for ((name, source), file) in self.files.iter().zip(files) {
+            let path = PathBuf::from(format!("./{name}.ase"));
+            names.insert(path.clone(), name.as_str());
+
+            let tree = Parser::from(Lexer::new(path, source))
+                .run(asena_grammar::file)
+                .build_tree();
+            for diagnostic in tree.report.diagnostics.iter() {
+                diagnostics.push(diagnostic.clone().into_boxed());
+            }
+
+            let ast = db.ast(file);
+            let ast = db.infix_commands(ast.into());
+            let ast = db.ordered_prec(ast.into());
+            let ast = db.ast_resolved_file(ast.into());
+            db.hir_file(ast.into());
+
+            diagnostics.extend(db.diagnostics(file).iter().cloned());
+        }
+
+        for diagnostic in aggregate_diagnostics(diagnostics) {
+            let file = diagnostic.message.span.file.as_ref();
+            let name = file
+                .and_then(|file| names.get(file))
+                .copied()
+                .unwrap_or_default();
+
+            self.diagnostics
+                .push(OwnedDiagnostic::new(name, &diagnostic));
+        }
+
+        self.diagnostics.len()
+    }
+
+    pub fn diagnostic(&self, index: usize) -> Option<&AsenaDiagnostic> {
+        self.diagnostics
+            .get(index)
+            .map(|diagnostic| &diagnostic.raw)
+    }
+}
+
+/// The interior nul characters can't be represented in the C strings, so they're replaced.
+fn to_c_string(value: &str) -> CString {
+    CString::new(value.replace('\0', "\u{FFFD}")).unwrap_or_default()
+}
+
+/// Creates a driver without files.
+#[no_mangle]
+pub extern "C" fn asena_driver_new() -> *mut AsenaDriver {
+    Box::into_raw(Box::default())
+}
+
+/// Frees the driver, and the diagnostics of its last check.
+///
+/// # Safety
+///
+/// The driver must be created by [asena_driver_new], and it can't be used after it's freed. The
+/// null pointers are ignored.
+#[no_mangle]
+pub unsafe extern "C" fn asena_driver_free(driver: *mut AsenaDriver) {
+    if !driver.is_null() {
+        drop(Box::from_raw(driver));
+    }
+}
+
+/// Adds the file to the driver, the name is the name of the module, like `Nat`, and returns if
+/// the file was added, it isn't if any of the strings isn't valid UTF-8.
+///
+/// # Safety
+///
+/// The driver must be valid, and the strings must be nul-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn asena_driver_add_file(
+    driver: *mut AsenaDriver,
+    name: *const c_char,
+    source: *const c_char,
+) -> bool {
+    if name.is_null() || source.is_null() {
+        return false;
+    }
+
+    let Some(driver) = driver.as_mut() else {
+        return false;
+    };
+    let (Ok(name), Ok(source)) = (
+        CStr::from_ptr(name).to_str(),
+        CStr::from_ptr(source).to_str(),
+    ) else {
+        return false;
+    };
+
+    driver.add_file(name, source);
+    true
+}
+
+/// Checks the files of the driver, and returns the amount of diagnostics, they can be iterated
+/// with [asena_driver_diagnostic].
+///
+/// # Safety
+///
+/// The driver must be valid, the diagnostics of the previous check are freed.
+#[no_mangle]
+pub unsafe extern "C" fn asena_driver_check(driver: *mut AsenaDriver) -> usize {
+    match driver.as_mut() {
+        Some(driver) => driver.check(),
+        None => 0,
+    }
+}
+
+/// Returns the diagnostic of the last check at the index, or null if it's out of the bounds.
+///
+/// # Safety
+///
+/// The driver must be valid, and the diagnostic is only valid until the next check.
+#[no_mangle]
+pub unsafe extern "C" fn asena_driver_diagnostic(
+    driver: *const AsenaDriver,
+    index: usize,
+) -> *const AsenaDiagnostic {
+    driver
+        .as_ref()
+        .and_then(|driver| driver.diagnostic(index))
+        .map_or(ptr::null(), |diagnostic| diagnostic as *const _)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn driver_works() {
+        unsafe {
+            let driver = asena_driver_new();
+            let name = CString::new("Test").unwrap();
+            let source = CString::new("Main { Double 21 }").unwrap();
+
+            assert!(asena_driver_add_file(
+                driver,
+                name.as_ptr(),
+                source.as_ptr()
+            ));
+            assert_eq!(asena_driver_check(driver), 1);
+
+            let diagnostic = &*asena_driver_diagnostic(driver, 0);
+            let message = CStr::from_ptr(diagnostic.message).to_str().unwrap();
+            assert_eq!(message, "could not find the value name: `Double`");
+            assert!(asena_driver_diagnostic(driver, 1).is_null());
+
+            asena_driver_free(driver);
+        }
+    }
+
+    #[test]
+    fn sorted_diagnostics_works() {
+        let mut driver = AsenaDriver::default();
+        driver.add_file("Zeta", "Z { Triple 1 }");
+        driver.add_file("Alpha", "A { Bar 1 }\nB { Double 21 }");
+
+        assert_eq!(driver.check(), 3);
+
+        let diagnostics = (0..3)
+            .filter_map(|index| driver.diagnostic(index))
+            .map(|diagnostic| unsafe {
+                let file = CStr::from_ptr(diagnostic.file).to_str().unwrap();
+
+                (file, diagnostic.start)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics, vec![("Alpha", 4), ("Alpha", 16), ("Zeta", 4)]);
+    }
+}
@@ -1,6 +1,8 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::*;
+use quote::{format_ident, quote};
+use syn::{punctuated::Punctuated, *};
+
+use crate::util::to_camel_case;
 
 pub fn expand_derive_leaf(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
@@ -15,6 +17,36 @@ pub fn expand_derive_leaf(input: TokenStream) -> TokenStream {
         return TokenStream::new()
     };
 
+    let accessors = data.variants.iter().filter_map(|next| {
+        let Fields::Unnamed(fields) = &next.fields else {
+            return None;
+        };
+        let field = &fields.unnamed.first()?.ty;
+
+        let variant = &next.ident;
+        let snake_case = to_camel_case(variant.to_string());
+        let as_name = format_ident!("as_{}", snake_case);
+        let is_name = format_ident!("is_{}", snake_case);
+        let as_doc = format!("Returns the [`{variant}`] node, if it's a [`{name}::{variant}`].");
+        let is_doc = format!("Checks if it's a [`{name}::{variant}`].");
+
+        Some(quote! {
+            #[doc = #as_doc]
+            pub fn #as_name(&self) -> Option<#field> {
+                match self {
+                    Self::#variant(value) => Some(value.clone()),
+                    _ => None,
+                }
+            }
+
+            #[doc = #is_doc]
+            pub fn #is_name(&self) -> bool {
+                matches!(self, Self::#variant(..))
+            }
+        })
+    });
+    let accessors = accessors.collect::<Vec<_>>();
+
     let patterns = data.variants.into_iter().filter_map(|next| {
         let ast_from = next.attrs.iter().find_map(|attr| {
             let expr: Expr = if attr.path().is_ident("ast_from") {
@@ -26,13 +58,53 @@ pub fn expand_derive_leaf(input: TokenStream) -> TokenStream {
             Some(expr)
         });
 
+        // The guard is checked before building the variant, so the variants declared after it,
+        // with the same kind, are the fallbacks for the trees that doesn't match the guard.
+        let ast_guard = next.attrs.iter().find_map(|attr| {
+            let expr: Expr = if attr.path().is_ident("ast_guard") {
+                attr.parse_args().ok()?
+            } else {
+                return None;
+            };
+
+            Some(quote!(if (#expr)(&tree)))
+        });
+
+        // The custom build functions are like `#[ast_build_fn(Kind, function)]`, they are
+        // matched before the variant's kind, and the function builds the node itself.
+        let ast_build_fns = next.attrs.iter().filter_map(|attr| {
+            if !attr.path().is_ident("ast_build_fn") {
+                return None;
+            }
+
+            let args = attr
+                .parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                .ok()?;
+            let mut args = args.into_iter();
+            let (Some(kind), Some(build_fn)) = (args.next(), args.next()) else {
+                attr.path()
+                    .get_ident()?
+                    .span()
+                    .unwrap()
+                    .error("`ast_build_fn` should be like `#[ast_build_fn(Kind, function)]`");
+
+                return None;
+            };
+
+            Some(quote! { #kind => return (#build_fn)(tree), })
+        });
+        let ast_build_fns = ast_build_fns.collect::<Vec<_>>();
+
         if let Some(ast_from) = ast_from {
             let name = next.ident;
             let body = quote! {{
                 Self::#name(<#name as asena_leaf::ast::Node>::new(tree))
             }};
 
-            Some(quote! { #ast_from => #body, })
+            Some(quote! {
+                #(#ast_build_fns)*
+                #ast_from #ast_guard => #body,
+            })
         } else {
             next.ident
                 .span()
@@ -45,6 +117,10 @@ pub fn expand_derive_leaf(input: TokenStream) -> TokenStream {
     let patterns = patterns.reduce(|acc, next| quote!(#acc #next));
 
     TokenStream::from(quote! {
+        impl #name {
+            #(#accessors)*
+        }
+
         impl asena_leaf::ast::Leaf for #name {
             fn make(tree: asena_leaf::ast::GreenTree) -> Option<Self> {
                 Some(match tree.kind() {
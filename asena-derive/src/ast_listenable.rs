@@ -2,11 +2,11 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{spanned::Spanned, *};
 
-use crate::util::{iter_leaf, to_camel_case};
+use crate::util::{to_camel_case, walkable_leaves, WalkableArgs};
 
 #[allow(clippy::redundant_clone)]
 pub fn expand_ast_listenable(args: TokenStream, input: TokenStream) -> TokenStream {
-    let args: proc_macro2::TokenStream = args.into();
+    let args = parse_macro_input!(args as WalkableArgs);
     let input = parse_macro_input!(input as ItemImpl);
 
     let name = input.self_ty.clone();
@@ -21,11 +21,12 @@ pub fn expand_ast_listenable(args: TokenStream, input: TokenStream) -> TokenStre
 
     let type_name = type_path.path.get_ident().unwrap();
 
-    let leaf_properties = iter_leaf(&input).into_iter().fold(quote!(), |acc, next| {
-        let walk_name = next.name;
-
-        quote!(#acc self.#walk_name().listen(listener);)
-    });
+    let leaves = walkable_leaves(&args, &input);
+    let leaf_properties = leaves.into_iter().fold(
+        quote!(),
+        |acc, next| quote!(#acc self.#next().listen(listener);),
+    );
+    let args = args.walker;
 
     let enter_fn = to_camel_case(format!("enter{type_name}")); // to_camel_case
     let exit_fn = to_camel_case(format!("exit{type_name}")); // to_camel_case
@@ -10,7 +10,7 @@ use syn::{
     *,
 };
 
-use crate::util::{iter_leaf, to_camel_case};
+use crate::util::{to_camel_case, walkable_leaves, WalkableArgs};
 
 pub struct Args {
     pub vars: HashSet<Ident>,
@@ -36,7 +36,7 @@ impl Parse for Args {
 
 #[allow(clippy::redundant_clone)]
 pub fn expand_ast_walkable(args: TokenStream, input: TokenStream) -> TokenStream {
-    let args: proc_macro2::TokenStream = args.into();
+    let args = parse_macro_input!(args as WalkableArgs);
     let input = parse_macro_input!(input as ItemImpl);
 
     let name = input.self_ty.clone();
@@ -51,11 +51,12 @@ pub fn expand_ast_walkable(args: TokenStream, input: TokenStream) -> TokenStream
 
     let type_name = type_path.path.get_ident().unwrap();
 
-    let leaf_properties = iter_leaf(&input).into_iter().fold(quote!(), |acc, next| {
-        let walk_name = next.name;
-
-        quote!(#acc self.#walk_name().walk(walker);)
-    });
+    let leaves = walkable_leaves(&args, &input);
+    let leaf_properties = leaves.into_iter().fold(
+        quote!(),
+        |acc, next| quote!(#acc self.#next().walk(walker);),
+    );
+    let args = args.walker;
 
     let fn_name = to_camel_case(format!("visit{type_name}"));
 
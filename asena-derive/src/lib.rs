@@ -41,7 +41,15 @@ pub(crate) mod util;
 ///     }
 /// }
 /// ```
-#[proc_macro_derive(Leaf, attributes(ast_terminal, ast_from, ast_build_fn))]
+///
+/// The variants can be annotated with:
+/// - `#[ast_guard(function)]`, the variant is only built if `function(&tree)` returns `true`,
+///   otherwise the next variants with the same kind are tried, so a kind can be classified
+///   into several variants, like by the tokens of the tree;
+/// - `#[ast_build_fn(Kind, function)]`, the trees of the `Kind` are built by the `function`,
+///   that takes the tree and returns `Option<Self>`, like the groups that are built as the
+///   node inside of them.
+#[proc_macro_derive(Leaf, attributes(ast_terminal, ast_from, ast_build_fn, ast_guard))]
 pub fn derive_leaf(input: TokenStream) -> TokenStream {
     ast_derive_leaf::expand_derive_leaf(input)
 }
@@ -174,7 +182,7 @@ pub fn ast_of(args: TokenStream, input: TokenStream) -> TokenStream {
 /// #[derive(Default, Node, Located, Clone)]
 /// pub struct Help(GreenTree);
 ///
-/// #[ast_walkable(PatWalker, StmtWalker, ExprWalker)]
+/// #[ast_walkable(AsenaVisitor)]
 /// impl Help {
 ///     #[ast_leaf]
 ///     pub fn value(&self) -> Expr {
@@ -182,6 +190,12 @@ pub fn ast_of(args: TokenStream, input: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// The leaves provided by traits, like the ones of `Binary`, can be listed in the arguments:
+/// ```rust,norun
+/// #[ast_walkable(AsenaVisitor, leaves(lhs, fn_id, rhs))]
+/// impl Infix {}
+/// ```
 #[proc_macro_attribute]
 pub fn ast_walkable(args: TokenStream, input: TokenStream) -> TokenStream {
     ast_walkable::expand_ast_walkable(args, input)
@@ -222,6 +236,8 @@ pub fn ast_listener(args: TokenStream, input: TokenStream) -> TokenStream {
 }
 
 /// `ast_listenable` attribute macro, it does generates:
+/// - The [`Listenable`] implementation, calling the `enter_` and `exit_` methods of the listener
+///   around the leaves, that can be listed in the arguments like in [`macro@ast_walkable`].
 #[proc_macro_attribute]
 pub fn ast_listenable(args: TokenStream, input: TokenStream) -> TokenStream {
     ast_listenable::expand_ast_listenable(args, input)
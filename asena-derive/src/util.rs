@@ -1,6 +1,10 @@
 use proc_macro2::Span;
 use quote::quote;
-use syn::*;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    *,
+};
 
 #[allow(dead_code)]
 #[derive(Clone)]
@@ -59,3 +63,43 @@ pub fn to_camel_case(s: String) -> Ident {
         .to_lowercase();
     Ident::new(&name, Span::call_site())
 }
+
+/// The arguments of the `ast_walkable` and `ast_listenable` attributes, the trait of the walker,
+/// and the leaves that are provided by traits, like `leaves(lhs, fn_id, rhs)` for the `Binary`
+/// nodes, they're walked before the `ast_leaf` functions of the impl.
+pub struct WalkableArgs {
+    pub walker: Path,
+    pub leaves: Vec<Ident>,
+}
+
+impl Parse for WalkableArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let walker = input.parse::<Path>()?;
+        let mut leaves = vec![];
+
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            let name = input.parse::<Ident>()?;
+            if name != "leaves" {
+                return Err(Error::new(name.span(), "expected `leaves(...)`"));
+            }
+
+            let content;
+            parenthesized!(content in input);
+            leaves = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
+        }
+
+        Ok(WalkableArgs { walker, leaves })
+    }
+}
+
+/// Returns the names of the leaves to be walked, the ones of the arguments, and then the
+/// `ast_leaf` functions of the impl.
+pub fn walkable_leaves(args: &WalkableArgs, input: &ItemImpl) -> Vec<Ident> {
+    args.leaves
+        .iter()
+        .cloned()
+        .chain(iter_leaf(input).into_iter().map(|leaf| leaf.name))
+        .collect()
+}
@@ -0,0 +1,305 @@
+//! The structural diff of the syntax trees of two versions of a file, the declarations are
+//! matched by their names, and the other nodes by their structures, ignoring their locations,
+//! so moving a declaration, or changing the indentation, isn't a change.
+//!
+//! The changes point to the smallest nodes that changed, so the incremental tools, like the
+//! re-resolution of the language server, can invalidate only the declarations that changed.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use asena_ast::{AsenaFile, Decl};
+use asena_leaf::ast::Node;
+use asena_leaf::node::{Child, Tree, TreeKind};
+use asena_span::{Loc, Spanned};
+use fxhash::FxHashMap;
+
+/// A change between the old, and the new version of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AstChange {
+    /// The declaration is only in the new file.
+    Added(Decl),
+
+    /// The declaration is only in the old file.
+    Removed(Decl),
+
+    /// The declaration is in both files, but its syntax changed, the edits are in the order of
+    /// the new file.
+    Modified {
+        old: Decl,
+        new: Decl,
+        edits: Vec<AstEdit>,
+    },
+}
+
+/// An edit of a node, or a token, inside of a modified declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AstEdit {
+    /// The node at the location of the new file was inserted.
+    Inserted(Loc),
+
+    /// The node at the location of the old file was deleted.
+    Deleted(Loc),
+
+    /// The node at the location of the old file was replaced by the one of the new file.
+    Replaced { old: Loc, new: Loc },
+}
+
+/// The key that the declarations are matched by, the occurrence is the index of the
+/// declaration among the ones with the same kind and name, as the functions can have many
+/// clauses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DeclKey {
+    kind: TreeKind,
+    name: DeclName,
+    occurrence: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DeclName {
+    Named(String),
+
+    /// The declarations without names, like the `use`s, are matched by their structures, so
+    /// they're only added, or removed.
+    Unnamed(u64),
+}
+
+/// Computes the changes from the old file to the new one, the added and the modified
+/// declarations are in the order of the new file, and the removed ones are after them, in the
+/// order of the old file.
+pub fn diff(old: &AsenaFile, new: &AsenaFile) -> Vec<AstChange> {
+    let old_decls = keyed_declarations(old);
+    let old_order = old_decls
+        .iter()
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    let mut old_decls = old_decls.into_iter().collect::<FxHashMap<_, _>>();
+
+    let mut changes = vec![];
+    for (key, new_decl) in keyed_declarations(new) {
+        let Some(old_decl) = old_decls.remove(&key) else {
+            changes.push(AstChange::Added(new_decl));
+            continue;
+        };
+
+        let edits = diff_trees(&tree_of(&old_decl).value, &tree_of(&new_decl).value);
+        if !edits.is_empty() {
+            changes.push(AstChange::Modified {
+                old: old_decl,
+                new: new_decl,
+                edits,
+            });
+        }
+    }
+
+    for key in old_order {
+        if let Some(old_decl) = old_decls.remove(&key) {
+            changes.push(AstChange::Removed(old_decl));
+        }
+    }
+
+    changes
+}
+
+fn keyed_declarations(file: &AsenaFile) -> Vec<(DeclKey, Decl)> {
+    let mut occurrences = FxHashMap::<(TreeKind, DeclName), usize>::default();
+
+    file.declarations()
+        .iter()
+        .map(|decl| {
+            let tree = tree_of(decl);
+            let name = match decl {
+                Decl::Assign(assign) => DeclName::Named(assign.name().to_fn_id().to_string()),
+                _ => match decl.name() {
+                    Some(name) => DeclName::Named(name.to_fn_id().to_string()),
+                    None => DeclName::Unnamed(fingerprint(&tree.value)),
+                },
+            };
+
+            let occurrence = occurrences
+                .entry((tree.value.kind, name.clone()))
+                .or_default();
+            let key = DeclKey {
+                kind: tree.value.kind,
+                name,
+                occurrence: *occurrence,
+            };
+            *occurrence += 1;
+
+            (key, decl.clone())
+        })
+        .collect()
+}
+
+fn tree_of(decl: &Decl) -> std::sync::Arc<Spanned<Tree>> {
+    decl.clone().unwrap().into_data().or_empty()
+}
+
+/// Diffs the children of the trees, they're aligned by the longest common subsequence of their
+/// fingerprints, and the unaligned children between the aligned ones are paired by their
+/// positions, the pairs of trees with the same kind are diffed recursively.
+fn diff_trees(old: &Tree, new: &Tree) -> Vec<AstEdit> {
+    let old_children = &old.children;
+    let new_children = &new.children;
+    let old_prints = old_children
+        .iter()
+        .map(child_fingerprint)
+        .collect::<Vec<_>>();
+    let new_prints = new_children
+        .iter()
+        .map(child_fingerprint)
+        .collect::<Vec<_>>();
+
+    let mut edits = vec![];
+    let mut old_start = 0;
+    let mut new_start = 0;
+    let aligned = longest_common_subsequence(&old_prints, &new_prints);
+    let end = (old_children.len(), new_children.len());
+    for (old_end, new_end) in aligned.into_iter().chain(std::iter::once(end)) {
+        let old_gap = &old_children[old_start..old_end];
+        let new_gap = &new_children[new_start..new_end];
+        diff_gap(old_gap, new_gap, &mut edits);
+
+        old_start = old_end + 1;
+        new_start = new_end + 1;
+    }
+
+    edits
+}
+
+fn diff_gap(old: &[Spanned<Child>], new: &[Spanned<Child>], edits: &mut Vec<AstEdit>) {
+    for (old_child, new_child) in old.iter().zip(new) {
+        match (&old_child.value, &new_child.value) {
+            (Child::Tree(old_tree), Child::Tree(new_tree)) if old_tree.kind == new_tree.kind => {
+                edits.extend(diff_trees(old_tree, new_tree));
+            }
+            _ => edits.push(AstEdit::Replaced {
+                old: old_child.span.clone(),
+                new: new_child.span.clone(),
+            }),
+        }
+    }
+
+    let paired = old.len().min(new.len());
+    edits.extend(
+        old[paired..]
+            .iter()
+            .map(|child| AstEdit::Deleted(child.span.clone())),
+    );
+    edits.extend(
+        new[paired..]
+            .iter()
+            .map(|child| AstEdit::Inserted(child.span.clone())),
+    );
+}
+
+/// Returns the pairs of the indices of the equal items, in the order they appear.
+fn longest_common_subsequence(old: &[u64], new: &[u64]) -> Vec<(usize, usize)> {
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    pairs
+}
+
+/// The hash of the structure of the tree, the kinds of the nodes, and the kinds and the texts of
+/// the tokens, without the locations.
+fn fingerprint(tree: &Tree) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_tree(tree, &mut hasher);
+    hasher.finish()
+}
+
+fn child_fingerprint(child: &Spanned<Child>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_child(&child.value, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_tree(tree: &Tree, state: &mut DefaultHasher) {
+    tree.kind.hash(state);
+    tree.children.len().hash(state);
+    for child in tree.children.iter() {
+        hash_child(&child.value, state);
+    }
+}
+
+fn hash_child(child: &Child, state: &mut DefaultHasher) {
+    match child {
+        Child::Tree(tree) => hash_tree(tree, state),
+        Child::Token(token) => {
+            token.kind.hash(state);
+            token.text.hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use asena_lexer::Lexer;
+    use asena_parser::Parser;
+
+    use super::*;
+
+    fn parse(code: &str) -> AsenaFile {
+        let parser = Parser::from(Lexer::new(None, code)).run(asena_grammar::file);
+
+        AsenaFile::new(parser.build_tree().unwrap())
+    }
+
+    #[test]
+    fn diff_works() {
+        let old = parse(concat!(
+            "Double (x: Int32) : Int32 { x + x }\n",
+            "Triple (x: Int32) : Int32 { x + x + x }\n",
+            "Main { Double 10 }\n",
+        ));
+        let new = parse(concat!(
+            "Main { Double 20 }\n",
+            "Double (x: Int32) : Int32 { x + x }\n",
+            "Half (x: Int32) : Int32 { x / 2 }\n",
+        ));
+
+        let changes = diff(&old, &new);
+        assert_eq!(changes.len(), 3);
+
+        let AstChange::Modified { edits, .. } = &changes[0] else {
+            panic!("expected `Main` to be modified");
+        };
+        assert_eq!(edits.len(), 1);
+        assert!(matches!(
+            edits[0],
+            AstEdit::Replaced { ref old, ref new }
+                if old.clone().into_ranged() == Some(90..92)
+                    && new.clone().into_ranged() == Some(14..16)
+        ));
+
+        assert!(matches!(&changes[1], AstChange::Added(Decl::Signature(_))));
+        assert!(matches!(
+            &changes[2],
+            AstChange::Removed(Decl::Signature(_))
+        ));
+        assert!(diff(&old, &old).is_empty());
+    }
+}
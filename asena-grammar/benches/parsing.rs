@@ -0,0 +1,57 @@
+//! Benchmarks for parsing, and for the node metadata storage, that is used by the resolution
+//! passes, run with `cargo bench -p asena-grammar`, the resolution itself is benchmarked in
+//! `asena-ast-resolver`.
+
+#![feature(test)]
+
+extern crate test;
+
+use std::sync::Arc;
+
+use asena_ast::{AsenaFile, Binary, Infix};
+use asena_grammar::parse_asena_file;
+use asena_leaf::ast::{Node, NodeArena, StorageMode};
+use test::Bencher;
+
+#[bench]
+fn bench_parse_file(b: &mut Bencher) {
+    let arena = Arc::new(NodeArena::default());
+    let _arena = arena.enter();
+    b.iter(|| parse_asena_file!("../tests/simple.ase"));
+}
+
+#[bench]
+fn bench_parse_and_memoize(b: &mut Bencher) {
+    let arena = Arc::new(NodeArena::default());
+    let _arena = arena.enter();
+    b.iter(parse_and_memoize);
+}
+
+/// Same as [bench_parse_and_memoize], but storing the metadata inline, to compare both
+/// [StorageMode]s.
+#[bench]
+fn bench_parse_and_memoize_inline(b: &mut Bencher) {
+    let arena = Arc::new(NodeArena::with_mode(StorageMode::Inline));
+    let _arena = arena.enter();
+    b.iter(parse_and_memoize);
+}
+
+#[bench]
+fn bench_parse_file_inline(b: &mut Bencher) {
+    let arena = Arc::new(NodeArena::with_mode(StorageMode::Inline));
+    let _arena = arena.enter();
+    b.iter(|| parse_asena_file!("../tests/simple.ase"));
+}
+
+fn parse_and_memoize() -> AsenaFile {
+    let file = AsenaFile::new(parse_asena_file!("../tests/simple.ase"));
+
+    // Simulates the resolution access pattern, that memoizes the named children of every node in
+    // the tree.
+    for infix in file.descendants_of::<Infix>() {
+        test::black_box(infix.lhs());
+        test::black_box(infix.rhs());
+    }
+
+    file
+}
@@ -0,0 +1,88 @@
+//! The entry points that parse the standalone snippets of code into their typed nodes, like the
+//! expressions of the REPL, or the code synthesized by the code actions. The snippets don't need
+//! to be in a file, so the nodes don't have the paths in their locations.
+
+use std::path::PathBuf;
+
+use asena_ast::{Decl, Expr, Stmt, Typed};
+use asena_leaf::ast::{GreenTree, Leaf};
+use asena_lexer::Lexer;
+use asena_parser::{error::ParseError, Parser};
+use asena_report::Diagnostic;
+
+use crate::Linebreak;
+
+/// The node of a parsed snippet, with the diagnostics of the parsing. The node is built even if
+/// there are errors, with the error nodes in the places that couldn't be parsed, and the tokens
+/// after the node are reported.
+#[derive(Debug, Clone)]
+pub struct Parsed<T> {
+    pub node: T,
+    pub errors: Vec<Diagnostic<ParseError>>,
+}
+
+impl<T> Parsed<T> {
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Returns the node, if there aren't errors.
+    pub fn ok(self) -> Option<T> {
+        if self.has_errors() {
+            return None;
+        }
+
+        Some(self.node)
+    }
+}
+
+/// Parses the expression, like `1 + 1`.
+pub fn parse_expr(code: &str) -> Parsed<Expr> {
+    parse(code, |p| {
+        crate::expr(p, Linebreak::Cont);
+    })
+}
+
+/// Parses the expression as the source of the file, like the inputs of the REPL, so the nodes
+/// have the path of the file in their locations, and their diagnostics belong to the file.
+pub fn parse_expr_in(path: PathBuf, code: &str) -> Parsed<Expr> {
+    parse_in(Some(path), code, |p| {
+        crate::expr(p, Linebreak::Cont);
+    })
+}
+
+/// Parses the type, like `Maybe a -> a`, the types are expressions too, but they're wrapped in a
+/// [Typed] node, like in the parameters.
+pub fn parse_type(code: &str) -> Parsed<Typed> {
+    parse(code, |p| crate::type_expr(p, Linebreak::Cont))
+}
+
+/// Parses the declaration, like `Double (x: Int32) : Int32 { x + x }`.
+pub fn parse_decl(code: &str) -> Parsed<Decl> {
+    parse(code, crate::decl)
+}
+
+/// Parses the statement, like `let x = 10`.
+pub fn parse_stmt(code: &str) -> Parsed<Stmt> {
+    parse(code, |p| {
+        crate::stmt(p);
+    })
+}
+
+fn parse<T: Leaf + Default>(code: &str, grammar: fn(&mut Parser)) -> Parsed<T> {
+    parse_in(None, code, grammar)
+}
+
+fn parse_in<T>(path: Option<PathBuf>, code: &str, grammar: fn(&mut Parser)) -> Parsed<T>
+where
+    T: Leaf + Default,
+{
+    let tree = Parser::from(Lexer::new(path, code))
+        .run(grammar)
+        .build_tree();
+
+    Parsed {
+        node: T::make(GreenTree::new(tree.data)).unwrap_or_default(),
+        errors: tree.report.diagnostics,
+    }
+}
@@ -7,8 +7,10 @@ use asena_parser::error::ParseError::*;
 use asena_parser::event::MarkClosed;
 use asena_parser::Parser;
 
+pub mod entry;
 pub mod macros;
 
+pub use entry::*;
 pub use macros::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,6 +45,7 @@ const STMT_RECOVERY: &[TokenKind] = &[
     TraitKeyword,
     InstanceKeyword,
     UseKeyword,
+    PubKeyword,
 ];
 
 const EXPR_RECOVERY: &[TokenKind] = &[
@@ -55,6 +58,7 @@ const EXPR_RECOVERY: &[TokenKind] = &[
     TraitKeyword,
     InstanceKeyword,
     UseKeyword,
+    PubKeyword,
     Semi,
 ];
 
@@ -67,8 +71,10 @@ const PAT_FIRST: &[TokenKind] = &[
     LeftBracket,
     LeftParen,
     Str,
+    Char,
     TrueKeyword,
     FalseKeyword,
+    Nat,
     Int8,
     Int16,
     Int32,
@@ -86,13 +92,20 @@ const PAT_FIRST: &[TokenKind] = &[
 const EXPR_FIRST: &[TokenKind] = &[
     LetKeyword,
     Identifier,
+    DoKeyword,
     LeftBracket,
     LeftParen,
     Str,
+    StrStart,
+    Char,
     TrueKeyword,
     FalseKeyword,
+    Nat,
     MatchKeyword,
     IfKeyword,
+    ForallKeyword,
+    PiUnicode,
+    SigmaUnicode,
     Int8,
     Int16,
     Int32,
@@ -120,15 +133,23 @@ pub fn file(p: &mut Parser) {
     p.close(m, File);
 }
 
-/// Decl = DeclUse | DeclCommand | DeclSignature | DeclAssign
+/// Decl = DeclUse | DeclCommand | DeclSignature | DeclAssign | DeclDestructure
 pub fn decl(p: &mut Parser) {
-    match p.lookahead(0) {
+    // The visibility is parsed by the declarations, so it's skipped to choose the declaration.
+    let nth = usize::from(p.at(PubKeyword));
+    if nth == 1 && matches!(p.lookahead(1), HashSymbol | InstanceKeyword) {
+        p.report(UnexpectedVisibilityError);
+        return;
+    }
+
+    match p.lookahead(nth) {
         UseKeyword => decl_use(p),
         HashSymbol => decl_command(p),
         EnumKeyword => decl_enum(p),
         ClassKeyword => decl_class(p),
         TraitKeyword => decl_trait(p),
         InstanceKeyword => decl_instance(p),
+        LeftParen | LeftBracket => decl_destructure(p),
         _ => {
             if let Some(decl) = p.savepoint().run(decl_assign).as_succeded() {
                 return p.return_at(decl);
@@ -154,7 +175,7 @@ pub fn decl_command(p: &mut Parser) {
     rec_expr!(p, &[], ExpectedExprError, expr_dsl, Linebreak::Semi);
     while p.at(Comma) {
         p.expect(Comma);
-        if p.at_any(EXPR_FIRST) {
+        if _at_expr(p) {
             expr_dsl(p, Linebreak::Semi);
         } else if p.at_any(EXPR_RECOVERY) {
             p.report(ExpectedExprError);
@@ -166,9 +187,10 @@ pub fn decl_command(p: &mut Parser) {
     p.close(m, DeclCommand);
 }
 
-/// DeclUse = 'use' Global
+/// DeclUse = Visibility 'use' Global
 pub fn decl_use(p: &mut Parser) {
     let m = p.open();
+    _visibility(p);
     p.expect(UseKeyword);
     p.advance();
     while p.eat(Dot) && !p.eof() {
@@ -178,9 +200,10 @@ pub fn decl_use(p: &mut Parser) {
     p.close(m, DeclUse);
 }
 
-/// DeclAssign = Global Pat* '=' Expr
+/// DeclAssign = Visibility Global Pat* '=' Expr
 pub fn decl_assign(p: &mut Parser) {
     let m = p.open();
+    _visibility(p);
     global(p);
     p.field("name");
     while !p.eof() && !p.at(EqualSymbol) {
@@ -193,9 +216,22 @@ pub fn decl_assign(p: &mut Parser) {
     p.close(m, DeclAssign);
 }
 
-/// DeclSignature = Global Param* ':' TypeExpr
+/// DeclDestructure = Visibility Pat '=' Expr
+pub fn decl_destructure(p: &mut Parser) {
+    let m = p.open();
+    _visibility(p);
+    pat(p);
+    p.expect(EqualSymbol);
+    rec_expr!(p, &[], ExpectedAssignValueError, expr_dsl, Linebreak::Semi);
+    p.field("value");
+    _semi(p, Semi::OrNewLine);
+    p.close(m, DeclDestructure);
+}
+
+/// DeclSignature = Visibility Global Param* ':' TypeExpr
 pub fn decl_signature(p: &mut Parser) {
     let m = p.open();
+    _visibility(p);
     global(p);
     params(p);
     if p.eat(Colon) {
@@ -211,6 +247,7 @@ pub fn decl_signature(p: &mut Parser) {
 
 pub fn decl_trait(p: &mut Parser) {
     let m = p.open();
+    _visibility(p);
     p.expect(TraitKeyword);
     global(p);
     params(p);
@@ -219,6 +256,7 @@ pub fn decl_trait(p: &mut Parser) {
     }
 
     p.expect(LeftBrace);
+    _associated_types(p);
     _trait_fields(p);
     _trait_methods(p);
     p.expect(RightBrace);
@@ -273,7 +311,9 @@ pub fn decl_instance(p: &mut Parser) {
 
 pub fn _instance_impls(p: &mut Parser) {
     while !p.at(RightBrace) && !p.eof() {
-        if p.at(Identifier) {
+        if p.at(TypeKeyword) {
+            associated_type(p);
+        } else if p.at(Identifier) {
             instance_impl(p);
         } else if p.at_any(METHOD_FIRST) {
             class_method(p);
@@ -286,11 +326,13 @@ pub fn _instance_impls(p: &mut Parser) {
 
 pub fn decl_class(p: &mut Parser) {
     let m = p.open();
+    _visibility(p);
     p.expect(ClassKeyword);
     global(p);
     params(p);
     where_clause(p);
     p.expect(LeftBrace);
+    _associated_types(p);
     _class_fields(p);
     _class_methods(p);
     p.expect(RightBrace);
@@ -328,9 +370,11 @@ pub fn _class_fields(p: &mut Parser) {
     }
 }
 
-/// DeclEnum = 'enum' Global Params? GadtType? WhereClause? '{' EnumVariant* ClassMethod* '}'
+/// DeclEnum = Visibility 'enum' Global Params? GadtType? WhereClause?
+///            '{' EnumVariant* ClassMethod* '}'
 pub fn decl_enum(p: &mut Parser) {
     let m = p.open();
+    _visibility(p);
     p.expect(EnumKeyword);
     global(p);
     params(p);
@@ -457,6 +501,27 @@ pub fn trait_default(p: &mut Parser) {
     p.close(m, TraitDefault);
 }
 
+pub fn _associated_types(p: &mut Parser) {
+    while p.at(TypeKeyword) {
+        associated_type(p);
+    }
+}
+
+/// AssocType = 'type' Global ('=' TypeExpr)?
+///
+/// The traits and the classes declare the associated types, and the instances define them.
+pub fn associated_type(p: &mut Parser) {
+    let m = p.open();
+    p.expect(TypeKeyword);
+    global(p);
+    if p.eat(EqualSymbol) {
+        type_expr(p, Linebreak::Semi);
+    }
+
+    _semi(p, Semi::OrNewLine);
+    p.close(m, AssocType);
+}
+
 pub fn instance_impl(p: &mut Parser) {
     if p.at_any(METHOD_FIRST) {
         class_method(p);
@@ -529,6 +594,8 @@ pub fn params(p: &mut Parser) {
 }
 
 /// Param = ImplicitParam | ExplicitParam
+/// ImplicitParam = '[' (Identifier ':')? TypeExpr ']'
+/// ExplicitParam = '(' Identifier ':' TypeExpr ')'
 pub fn param(p: &mut Parser) -> bool {
     let m = p.open();
     let token = p.peek();
@@ -549,6 +616,12 @@ pub fn param(p: &mut Parser) -> bool {
         }
         LeftBracket => {
             p.expect(LeftBracket);
+            // The implicit parameters without names are the instance arguments, like
+            // `[Monad m]`, that are filled with the instances of the constraints.
+            if p.at(Identifier) && p.lookahead(1) == Colon {
+                p.expect(Identifier);
+                p.expect(Colon);
+            }
             type_expr(p, Linebreak::Cont);
             p.expect(RightBracket);
         }
@@ -578,7 +651,7 @@ pub fn stmt(p: &mut Parser) -> bool {
                     return false;
                 }
             }
-            if p.at_any(EXPR_FIRST) {
+            if _at_expr(p) {
                 stmt_expr(p);
                 return false;
             }
@@ -591,7 +664,7 @@ pub fn stmt(p: &mut Parser) -> bool {
 pub fn stmt_return(p: &mut Parser) {
     let m = p.open();
     p.expect(ReturnKeyword);
-    if p.at_any(EXPR_FIRST) {
+    if _at_expr(p) {
         rec_expr!(p, &[], ExpectedReturnValueError, expr_dsl, Linebreak::Semi);
     } else if p.at_any(STMT_RECOVERY) {
         p.report(ExpectedReturnStmtError);
@@ -611,16 +684,28 @@ pub fn stmt_ask(p: &mut Parser) {
 
 pub fn stmt_if(p: &mut Parser) {
     let m = p.open();
-    p.expect(IfKeyword);
-    rec_expr!(p, &[], ExpectedIfCondError);
-    if_then(p);
-    if p.at(ElseKeyword) {
-        if_else(p, Linebreak::Semi);
-    }
+    _stmt_if(p);
     _semi(p, Semi::OrNewLine);
     p.close(m, StmtIf);
 }
 
+/// StmtElse = 'else' StmtIf | 'else' Branch
+///
+/// The `else if` chains are parsed as a block with a single if statement, so the last `else`
+/// is still optional, like in a single if statement.
+pub fn stmt_else(p: &mut Parser) {
+    if p.lookahead(1) != IfKeyword {
+        return if_else(p, Linebreak::Semi);
+    }
+
+    let m = p.open();
+    p.expect(ElseKeyword);
+    let stmt = p.open();
+    _stmt_if(p);
+    p.close(stmt, StmtIf);
+    p.close(m, BranchBlock);
+}
+
 pub fn stmt_let(p: &mut Parser) {
     let m = p.open();
     p.expect(LetKeyword);
@@ -652,16 +737,26 @@ pub fn type_expr(p: &mut Parser, linebreak: Linebreak) {
 /// | ExprLet | ExprGlobal | ExprLocal
 /// | ExprLit | ExprAnn | ExprQual
 /// | ExprPi | ExprSigma | ExprHelp
+/// | ExprSection | ExprForall
 pub fn expr(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
+    // Every nested expression goes through here, so it's where the nesting is limited
+    if !p.enter() {
+        return Some(p.skip_too_deep());
+    }
+
     let token = p.peek();
-    match token.kind {
+    let result = match token.kind {
         Symbol if token.text == "\\" => expr_lam(p, linebreak),
         HelpSymbol => expr_help(p, linebreak),
         IfKeyword => expr_if(p, linebreak),
         LetKeyword => expr_let(p, linebreak),
         MatchKeyword => expr_match(p),
+        ForallKeyword => expr_forall(p, linebreak),
         _ => expr_ann(p, linebreak),
-    }
+    };
+
+    p.exit();
+    result
 }
 
 pub fn expr_let(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
@@ -703,7 +798,7 @@ pub fn if_else(p: &mut Parser, linebreak: Linebreak) {
             _stmt_block(p);
             p.close(m, BranchBlock);
         }
-        _ if p.at_any(EXPR_FIRST) => {
+        _ if _at_expr(p) => {
             rec_expr!(p, &[], ExpectedIfElseExprError, expr, linebreak);
             p.close(m, BranchExpr);
         }
@@ -715,6 +810,9 @@ pub fn if_else(p: &mut Parser, linebreak: Linebreak) {
 }
 
 /// ExprIf = 'if' Expr 'then' Expr 'else' Expr
+///
+/// The `else if` chains are parsed as an if expression in the else branch, so a dangling `else`
+/// belongs to the innermost if expression.
 pub fn expr_if(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
     let m = p.open();
     p.expect(IfKeyword);
@@ -726,7 +824,7 @@ pub fn expr_if(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
 
 pub fn case(p: &mut Parser) {
     let m = p.open();
-    pat_app(p);
+    pat_or(p);
     p.expect(DoubleArrow);
     case_branch(p);
     p.close(m, MatchCase);
@@ -739,7 +837,7 @@ pub fn case_branch(p: &mut Parser) {
             _stmt_block(p);
             p.close(m, BranchBlock);
         }
-        _ if p.at_any(EXPR_FIRST) => {
+        _ if _at_expr(p) => {
             // TODO: FIXME
             rec_expr!(p, &[], ExpectedCaseExprError, expr, Linebreak::Cont);
             p.close(m, BranchExpr);
@@ -847,13 +945,21 @@ pub fn expr_help(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
     p.close(m, ExprHelp).into()
 }
 
-/// ExprLam = '\' Identifier* '->' ExprDsl
+/// ExprLam = '\' LamParam* '->' ExprDsl
+/// LamParam = Identifier | Pat
 pub fn expr_lam(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
     let m = p.open();
     p.advance();
     while !p.eof() && !p.at(RightArrow) {
         let m = p.open();
-        p.expect(Identifier);
+        let token = p.peek();
+        match token.value.kind {
+            Identifier if token.text != "_" => p.expect(Identifier),
+            _ if p.at_any(PAT_FIRST) => {
+                pat(p);
+            }
+            _ => p.expect(Identifier),
+        }
         p.close(m, LamParam);
     }
     p.expect(RightArrow);
@@ -861,12 +967,35 @@ pub fn expr_lam(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
     p.close(m, ExprLam).into()
 }
 
+/// ExprForall = ('forall' | '∀') Identifier+ '.' Expr
+pub fn expr_forall(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
+    let m = p.open();
+    p.advance();
+    if !p.at(Identifier) {
+        p.report(ExpectedForallVariableError);
+    }
+    while !p.eof() && p.at(Identifier) {
+        p.expect(Identifier);
+    }
+
+    // the `.` followed by a space is lexed as the composition operator
+    let at_dot = p.at(Dot) || (p.at(Symbol) && p.peek().text == ".");
+    if at_dot {
+        p.advance();
+    } else {
+        p.report(ExpectedForallDotError);
+    }
+    rec_expr!(p, &[], ExpectedForallBodyError, expr, linebreak);
+    p.close(m, ExprForall).into()
+}
+
+/// ExprDsl = Expr DslBlock?
 pub fn expr_dsl(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
     let m = p.open();
     rec_expr!(p, &[], ExpectedExprError, expr, linebreak);
 
     if p.at(LeftBrace) {
-        _stmt_block(p);
+        _dsl_block(p);
         p.close(m, ExprDsl).into()
     } else {
         p.abandon(m);
@@ -874,16 +1003,16 @@ pub fn expr_dsl(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
     }
 }
 
-/// ExprBinary = ExprAccessor (Symbol ExprAccessor)*
+/// ExprBinary = ExprUnary (Symbol ExprUnary)*
 pub fn expr_binary(p: &mut Parser, linebreak: Linebreak) {
     let m = p.open();
 
-    rec_expr!(p, &[], ExpectedExprError, expr_app, linebreak);
+    rec_expr!(p, &[], ExpectedExprError, expr_unary, linebreak);
 
     // simplify by returning the lhs symbol directly
     if p.at(Symbol) {
         while !p.eof() && p.eat(Symbol) {
-            if rec_expr!(p, &[], ExpectedInfixRhsError, expr_app, linebreak) {
+            if rec_expr!(p, &[], ExpectedInfixRhsError, expr_unary, linebreak) {
                 break;
             }
         }
@@ -894,6 +1023,21 @@ pub fn expr_binary(p: &mut Parser, linebreak: Linebreak) {
     }
 }
 
+/// ExprUnary = '-' ExprUnary | ExprApp
+///
+/// The minus is only unary in the operand position, so `a -1` is still a subtraction, and the
+/// application arguments can't be negated without parenthesis, like `f (-1)`.
+pub fn expr_unary(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
+    if !_at_minus(p) {
+        return expr_app(p, linebreak);
+    }
+
+    let m = p.open();
+    p.expect(Symbol);
+    rec_expr!(p, &[], ExpectedUnaryOperandError, expr_unary, linebreak);
+    p.close(m, ExprUnary).into()
+}
+
 /// ExprAccessor = ExprApp ('.' Accessor)*
 // pub fn expr_accessor(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
 //     let m = p.open();
@@ -967,8 +1111,10 @@ pub fn expr_app(p: &mut Parser, linebreak: Linebreak) -> Option<MarkClosed> {
     lhs.into()
 }
 
+/// ExprPi = 'Π'? '(' (Identifier ':')? TypeExpr ')' '->' TypeExpr
 pub fn expr_pi(p: &mut Parser) -> MarkClosed {
     let m = p.open();
+    p.eat(PiUnicode);
     p.expect(LeftParen);
     if p.eat(Identifier) {
         p.field("parameter_name");
@@ -988,8 +1134,10 @@ pub fn expr_pi(p: &mut Parser) -> MarkClosed {
     p.close(m, ExprPi)
 }
 
+/// ExprSigma = 'Σ'? '[' (Identifier ':')? TypeExpr ']' '->' TypeExpr
 pub fn expr_sigma(p: &mut Parser) -> MarkClosed {
     let m = p.open();
+    p.eat(SigmaUnicode);
     p.expect(LeftBracket);
     if p.eat(Identifier) {
         p.field("parameter_name");
@@ -1021,6 +1169,33 @@ pub fn expr_group(p: &mut Parser) -> MarkClosed {
     p.close(m, ExprGroup)
 }
 
+/// ExprSection = '(' Symbol ExprApp ')' # Right section
+///             | '(' ExprApp Symbol ')' # Left section
+pub fn expr_section(p: &mut Parser) -> MarkClosed {
+    let m = p.open();
+    p.expect(LeftParen);
+    // The lambdas and the negations aren't sections, like in haskell, `(- 1)` is `-1`.
+    let is_prefix = matches!(p.peek().text.as_str(), "\\" | "-");
+    if !is_prefix && p.eat(Symbol) {
+        _section_operand(p);
+        p.expect(RightParen);
+        return p.close(m, ExprRightSection);
+    }
+    _section_operand(p);
+    p.expect(Symbol);
+    p.expect(RightParen);
+    p.close(m, ExprLeftSection)
+}
+
+/// ExprOperatorSection = '(' Symbol ')'
+pub fn expr_operator_section(p: &mut Parser) -> MarkClosed {
+    let m = p.open();
+    p.expect(LeftParen);
+    p.expect(Symbol);
+    p.expect(RightParen);
+    p.close(m, ExprOperatorSection)
+}
+
 pub fn expr_array(p: &mut Parser) -> MarkClosed {
     let m = p.open();
     p.expect(LeftBracket);
@@ -1029,7 +1204,7 @@ pub fn expr_array(p: &mut Parser) -> MarkClosed {
     }
     while !p.eof() && !p.at(RightBracket) {
         p.expect(Comma);
-        if p.at_any(EXPR_FIRST) {
+        if _at_expr(p) {
             expr_dsl(p, Linebreak::Cont);
         } else if p.at_any(EXPR_RECOVERY) {
             p.report(ExpectedExprError);
@@ -1043,13 +1218,40 @@ pub fn expr_array(p: &mut Parser) -> MarkClosed {
     p.close(m, ExprArray)
 }
 
+/// ExprInterpolation = StrStart Expr (StrMiddle Expr)* StrEnd
+pub fn expr_interpolation(p: &mut Parser) -> MarkClosed {
+    let m = p.open();
+    p.expect(StrStart);
+    rec_expr!(
+        p,
+        &[StrMiddle, StrEnd],
+        ExpectedExprError,
+        expr_dsl,
+        Linebreak::Cont
+    );
+    while !p.eof() && p.eat(StrMiddle) {
+        rec_expr!(
+            p,
+            &[StrMiddle, StrEnd],
+            ExpectedExprError,
+            expr_dsl,
+            Linebreak::Cont
+        );
+    }
+    p.expect(StrEnd);
+    p.close(m, ExprInterpolation)
+}
+
 /// Primary =
 ///   Lit
 /// | Local # Local
-/// | '(' Identifier ':' TypeExpr ')' '->' 'TypeExpr' # Pi
+/// | 'do' # Local
+/// | 'Π'? '(' Identifier ':' TypeExpr ')' '->' 'TypeExpr' # Pi
+/// | '(' Symbol ')' # OperatorSection
 /// | '(' ExprDsl ')' # Group
-/// | '[' Identifier ':' TypeExpr ']' '->' 'TypeExpr' # Sigma
+/// | 'Σ'? '[' Identifier ':' TypeExpr ']' '->' 'TypeExpr' # Sigma
 /// | '[' ExprDsl (',' ExprDsl)* ','? ']'  # Pi
+/// | StrStart ExprDsl (StrMiddle ExprDsl)* StrEnd # Interpolation
 pub fn primary(p: &mut Parser) -> Option<MarkClosed> {
     if let Some(literal) = lit(p, ExprLit) {
         return Some(literal);
@@ -1071,6 +1273,12 @@ pub fn primary(p: &mut Parser) -> Option<MarkClosed> {
             p.advance();
             p.close(m, ExprSelf)
         }
+        // The runner of the do blocks, like `do { x <- m }`, it's resolved like the identifiers
+        DoKeyword => {
+            let m = p.open();
+            p.advance();
+            p.close(m, ExprLocal)
+        }
         // Parse array or named sigma expressions
         // - Sigma
         // - Array
@@ -1097,15 +1305,55 @@ pub fn primary(p: &mut Parser) -> Option<MarkClosed> {
                 return Some(closed);
             }
 
+            // '(' Symbol ')' # Operator
+            if let Some((closed, operator)) = p.savepoint().as_closed(expr_operator_section) {
+                p.return_at(operator);
+                return Some(closed);
+            }
+
+            // '(' Symbol ExprApp ')' | '(' ExprApp Symbol ')' # Section
+            if let Some((closed, section)) = p.savepoint().as_closed(expr_section) {
+                p.return_at(section);
+                return Some(closed);
+            }
+
             expr_group(p)
         }
+        PiUnicode => expr_pi(p),
+        SigmaUnicode => expr_sigma(p),
+        StrStart => expr_interpolation(p),
         _ => return _non_primary(p, token.value.kind).and(None),
     };
 
     Some(result)
 }
 
+/// PatOr = PatApp ('|' PatApp)*
+pub fn pat_or(p: &mut Parser) {
+    let m = p.open();
+    pat_app(p);
+
+    // simplify by returning the single alternative directly
+    if _at_pipe(p) {
+        while !p.eof() && _at_pipe(p) {
+            p.advance();
+            pat_app(p);
+        }
+
+        p.close(m, PatOr);
+    } else {
+        p.abandon(m);
+    }
+}
+
 pub fn pat_app(p: &mut Parser) {
+    // Only identifiers can be constructor names, the other patterns, like the literals, are
+    // parsed by [pat], otherwise `'a' => ...` would be parsed as a constructor named `'a'`.
+    if !p.at(Identifier) || _at_as(p) {
+        pat(p);
+        return;
+    }
+
     if let Some(pat_constructor) = p.savepoint().run(pat_constructor).as_succeded() {
         p.return_at(pat_constructor);
     } else {
@@ -1128,9 +1376,23 @@ pub fn pat_constructor(p: &mut Parser) -> MarkClosed {
     p.close(m, PatConstructor)
 }
 
-/// Pat = '(' Global Pat* ')' | '_' | Lit | '..'
+/// Pat = '(' PatOr ')' | '_' | Lit | '..' | PatRange | PatAs
 pub fn pat(p: &mut Parser) -> Option<MarkClosed> {
+    if !p.enter() {
+        return Some(p.skip_too_deep());
+    }
+
+    let result = pat_primary(p);
+    p.exit();
+    result
+}
+
+fn pat_primary(p: &mut Parser) -> Option<MarkClosed> {
     if let Some(literal) = lit(p, PatLit) {
+        if p.at(Dot) && p.lookahead(1) == Dot {
+            return pat_range(p, literal);
+        }
+
         return Some(literal);
     }
 
@@ -1144,14 +1406,15 @@ pub fn pat(p: &mut Parser) -> Option<MarkClosed> {
         Dot => {
             let m = p.open();
             p.advance();
-            if p.eat(Dot) {
-                p.report(UnicodeError(Dot, "dot"));
-
-                return None;
+            // the `.` followed by a space is lexed as the composition operator, like in `[x, .. ]`
+            if p.at(Dot) || (p.at(Symbol) && p.peek().text == ".") {
+                p.advance();
             } else {
-                p.close(m, PatSpread)
+                p.report(ExpectedSpreadDotError);
             }
+            p.close(m, PatSpread)
         }
+        Identifier if _at_as(p) => pat_as(p),
         Identifier => {
             let m = p.open();
             global(p);
@@ -1174,7 +1437,7 @@ pub fn pat(p: &mut Parser) -> Option<MarkClosed> {
         LeftParen => {
             let m = p.open();
             p.expect(LeftParen);
-            pat_constructor(p);
+            pat_or(p);
             p.expect(RightParen);
             p.close(m, PatGroup)
         }
@@ -1184,15 +1447,51 @@ pub fn pat(p: &mut Parser) -> Option<MarkClosed> {
     Some(result)
 }
 
+/// PatAs = Global '@' Pat
+fn pat_as(p: &mut Parser) -> MarkClosed {
+    let m = p.open();
+    global(p);
+    p.advance(); // '@'
+    pat(p);
+    p.close(m, PatAs)
+}
+
+/// PatRange = Lit '..' Lit
+fn pat_range(p: &mut Parser, start: MarkClosed) -> Option<MarkClosed> {
+    let m = p.open_before(start);
+    p.expect(Dot);
+    p.expect(Dot);
+    if lit(p, PatLit).is_none() {
+        p.report(ExpectedRangeEndError);
+    }
+
+    Some(p.close(m, PatRange))
+}
+
 /// Lit =
 ///   Nat 'n'? | Int 'i8'? | Int 'u8'?
 /// | Int 'i16'? | Int 'u16'? | Int ('u' | 'i32')?
 /// | Int ('u' | 'u32')? | Int 'i64'? | Int 'u64'?
 /// | Int 'i128'? | Int 'u128'? | Float 'f32'?
 /// | Float 'f64'? | 'true' | 'false'
+/// | Str | Char
 pub fn lit(p: &mut Parser, kind: TreeKind) -> Option<MarkClosed> {
+    if let Some((type_name, max)) = _max_integer(p.lookahead(0)) {
+        let text = p.peek().text.to_string();
+        match text.parse::<u128>() {
+            Ok(value) if value <= max => {}
+            // The floats are lexed with the suffix of the integers too, like `1.5u8`.
+            Err(_) if text.contains(['.', 'e', 'E']) && text.parse::<f64>().is_ok() => {
+                return p.report(FloatIntegerSuffixError(text, type_name));
+            }
+            _ => return p.report(IntegerOutOfRangeError(text, type_name)),
+        }
+    }
+
     let result = match p.lookahead(0) {
         Str => p.terminal(kind),
+        Char => p.terminal(kind),
+        Nat => p.terminal(kind),
         TrueKeyword => p.terminal(kind),
         FalseKeyword => p.terminal(kind),
         Int8 => p.terminal(kind),
@@ -1247,11 +1546,6 @@ fn _non_primary(p: &mut Parser, kind: TokenKind) -> Option<MarkClosed> {
         WhereKeyword => p.report(StmtReservedKeywordError(WhereKeyword)),
         InKeyword => p.report(ReservedKeywordError(InKeyword)),
 
-        LambdaUnicode => p.report(UnicodeError(LambdaUnicode, "lambda")),
-        ForallUnicode => p.report(UnicodeError(LambdaUnicode, "forall")),
-        PiUnicode => p.report(UnicodeError(LambdaUnicode, "pi")),
-        SigmaUnicode => p.report(UnicodeError(LambdaUnicode, "sigma")),
-
         LeftBracket => p.report(UnicodeError(LeftBracket, "left_bracket")),
         RightBracket => p.report(UnicodeError(RightBracket, "right_bracket")),
         LeftBrace => p.report(UnicodeError(LeftBrace, "left_brace")),
@@ -1272,6 +1566,11 @@ fn _non_primary(p: &mut Parser, kind: TokenKind) -> Option<MarkClosed> {
     }
 }
 
+/// Visibility = 'pub'?
+fn _visibility(p: &mut Parser) -> bool {
+    p.eat(PubKeyword)
+}
+
 fn _semi(p: &mut Parser, mode: Semi) -> bool {
     match mode {
         Semi::Optional | Semi::OrNewLine => {
@@ -1295,8 +1594,94 @@ fn _semi(p: &mut Parser, mode: Semi) -> bool {
     }
 }
 
+/// Returns the name of the type of the integer token kind, and the biggest value that fits in
+/// it, the signed integers admit the magnitude of their minimum value, so `-128i8` can still be
+/// folded, and the un-negated ones are reported by the lowering.
+fn _max_integer(kind: TokenKind) -> Option<(&'static str, u128)> {
+    Some(match kind {
+        Int8 => ("Int8", i8::MAX as u128 + 1),
+        Int16 => ("Int16", i16::MAX as u128 + 1),
+        Int32 => ("Int32", i32::MAX as u128 + 1),
+        Int64 => ("Int64", i64::MAX as u128 + 1),
+        Int128 => ("Int128", i128::MAX as u128 + 1),
+        UInt8 => ("UInt8", u8::MAX as u128),
+        UInt16 => ("UInt16", u16::MAX as u128),
+        UInt32 => ("UInt32", u32::MAX as u128),
+        UInt64 => ("UInt64", u64::MAX as u128),
+        UInt128 => ("UInt128", u128::MAX),
+        Nat => ("Nat", u128::MAX),
+        _ => return None,
+    })
+}
+
+/// Returns if the parser is at the start of an expression, the unary minus is a symbol, so it
+/// can't be in the [EXPR_FIRST] set.
+fn _at_expr(p: &Parser) -> bool {
+    p.at_any(EXPR_FIRST) || _at_minus(p)
+}
+
+fn _at_minus(p: &Parser) -> bool {
+    p.at(Symbol) && p.peek().text == "-"
+}
+
+fn _at_pipe(p: &Parser) -> bool {
+    p.at(Symbol) && p.peek().text == "|"
+}
+
+/// Returns if the parser is at the name of an as-pattern, like `xs` in `xs@(Cons x _)`.
+fn _at_as(p: &Parser) -> bool {
+    p.lookahead(1) == Symbol && p.nth(1).map_or(false, |token| token.text == "@")
+}
+
+fn _section_operand(p: &mut Parser) {
+    if p.at_any(EXPR_FIRST) {
+        expr_app(p, Linebreak::Cont);
+    } else {
+        p.report(ExpectedExprError);
+    }
+}
+
+fn _stmt_if(p: &mut Parser) {
+    p.expect(IfKeyword);
+    rec_expr!(p, &[], ExpectedIfCondError);
+    if_then(p);
+    if p.at(ElseKeyword) {
+        stmt_else(p);
+    }
+}
+
+/// DslBlock = '{' (LamParam+ '->')? Stmt* '}'
+/// LamParam = Identifier
+fn _dsl_block(p: &mut Parser) {
+    p.expect(LeftBrace);
+    if _at_dsl_parameters(p) {
+        while !p.eof() && !p.at(RightArrow) {
+            let m = p.open();
+            p.expect(Identifier);
+            p.close(m, LamParam);
+        }
+        p.expect(RightArrow);
+    }
+    _block_stmts(p);
+}
+
+/// Returns if the block starts with the parameters, like `{ item -> ... }`, so the blocks can't
+/// start with a pi type of names, like `{ a -> b }`.
+fn _at_dsl_parameters(p: &Parser) -> bool {
+    let mut lookahead = 0;
+    while p.lookahead(lookahead) == Identifier {
+        lookahead += 1;
+    }
+
+    lookahead > 0 && p.lookahead(lookahead) == RightArrow
+}
+
 fn _stmt_block(p: &mut Parser) {
     p.expect(LeftBrace);
+    _block_stmts(p);
+}
+
+fn _block_stmts(p: &mut Parser) {
     while !p.eof() && !p.at(RightBrace) {
         if stmt(p) {
             p.report(ExpectedStmtError);
@@ -1320,7 +1705,7 @@ macro_rules! rec_expr {
         )
     };
     ($p:expr, $recovery:expr, $error:expr, $f:expr, $linebreak:expr) => {
-        if $p.at_any(EXPR_FIRST) {
+        if _at_expr($p) {
             $f($p, $linebreak);
             false
         } else {
@@ -1,8 +1,15 @@
-use asena_ast::{Binary, Infix};
+use std::sync::Arc;
+
+use asena_ast::{
+    AsenaFile, AsenaListener, AsenaVisitor, Binary, Branch, Expr, If, IfStmt, Infix, Lam, Literal,
+    LiteralExpr, Pat, Walkers,
+};
 use asena_grammar::Linebreak;
-use asena_leaf::ast::Node;
+use asena_leaf::ast::{GreenTree, Leaf, Located, Node, NodeArena, StorageMode, VirtualNode};
+use asena_leaf::node::TreeKind;
+use asena_leaf::token::{kind::TokenKind, text::TokenText};
 use asena_lexer::Lexer;
-use asena_parser::Parser;
+use asena_parser::{error::ParseError, Parser};
 
 #[test]
 fn it_works() {
@@ -19,3 +26,822 @@ fn it_works() {
 
     println!("{:#?}", infix);
 }
+
+#[test]
+fn storage_modes_works() {
+    let memoized_nodes = |mode: StorageMode| {
+        let arena = Arc::new(NodeArena::with_mode(mode));
+        let _arena = arena.enter();
+
+        let parser = Parser::from(Lexer::new(None, "1 + 2"))
+            .run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        let infix = Infix::new(parser.build_tree().unwrap());
+        infix.lhs();
+
+        arena.len()
+    };
+
+    // The mode is of the arena, so the arenas of the same thread can be in different modes
+    assert!(memoized_nodes(StorageMode::Arena) > 0);
+    assert_eq!(memoized_nodes(StorageMode::Inline), 0);
+}
+
+#[test]
+fn descendants_works() {
+    let code = "53 + 75 + 42";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    let infix = Infix::new(parser.build_tree().unwrap());
+
+    let literals = infix.descendants_of::<LiteralExpr>().collect::<Vec<_>>();
+    assert_eq!(literals.len(), 3);
+
+    let first = infix.descendants().next().unwrap();
+    assert!(first.ancestors().next().is_some());
+}
+
+#[test]
+fn listener_works() {
+    #[derive(Default)]
+    struct Exits(Vec<&'static str>);
+
+    impl AsenaListener for Exits {
+        fn exit_infix(&mut self, _: Infix) {
+            self.0.push("infix");
+        }
+
+        fn exit_literal_expr(&mut self, _: LiteralExpr) {
+            self.0.push("literal");
+        }
+    }
+
+    let code = "1 + 2 * 3";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    let infix = Infix::new(parser.build_tree().unwrap());
+
+    let mut exits = Exits::default();
+    exits.listens(infix);
+
+    // The nodes are exited after all of their children
+    assert_eq!(exits.0, ["literal", "literal", "literal", "infix", "infix"]);
+}
+
+#[test]
+fn walkers_works() {
+    #[derive(Default)]
+    struct Literals(usize);
+
+    impl AsenaVisitor<()> for Literals {
+        fn visit_literal_expr(&mut self, _: LiteralExpr) {
+            self.0 += 1;
+        }
+    }
+
+    impl AsenaListener for Literals {
+        fn enter_literal_expr(&mut self, _: LiteralExpr) {
+            self.0 += 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct Infixes(Vec<&'static str>);
+
+    impl AsenaVisitor<()> for Infixes {
+        fn visit_infix(&mut self, _: Infix) {
+            self.0.push("visit");
+        }
+    }
+
+    impl AsenaListener for Infixes {
+        fn enter_infix(&mut self, _: Infix) {
+            self.0.push("enter");
+        }
+
+        fn exit_infix(&mut self, _: Infix) {
+            self.0.push("exit");
+        }
+    }
+
+    let code = "Main { 1 + 2 * 3 }";
+    let parser = Parser::from(Lexer::new(None, code)).run(asena_grammar::file);
+    let file = AsenaFile::new(parser.build_tree().unwrap());
+
+    // Every visitor of the tuple sees every node, in the same traversal.
+    let Walkers((literals, infixes)) =
+        file.walk_with(Walkers::of((Literals::default(), Infixes::default())));
+    assert_eq!(literals.0, 3);
+    assert_eq!(infixes.0, ["visit", "visit"]);
+
+    let Walkers((literals, infixes)) =
+        file.listen_with(Walkers::of((Literals::default(), Infixes::default())));
+    assert_eq!(literals.0, 3);
+    assert_eq!(infixes.0, ["enter", "enter", "exit", "exit"]);
+}
+
+#[test]
+fn else_if_chain_works() {
+    let code = "if a then b else if c then d else e";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+    let expr = If::new(parser.build_tree().unwrap());
+
+    let Branch::ExprBranch(else_branch) = expr.else_branch() else {
+        panic!("expected an expression else branch");
+    };
+    assert!(matches!(else_branch.value(), Expr::If(_)));
+}
+
+#[test]
+fn dangling_else_works() {
+    let code = "if a then if b then c else d else e";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+    let expr = If::new(parser.build_tree().unwrap());
+
+    let Branch::ExprBranch(then_branch) = expr.then_branch() else {
+        panic!("expected an expression then branch");
+    };
+    let Expr::If(inner) = then_branch.value() else {
+        panic!("expected the else to belong to the inner if");
+    };
+    assert!(matches!(inner.else_branch(), Branch::ExprBranch(_)));
+    assert!(matches!(expr.else_branch(), Branch::ExprBranch(_)));
+}
+
+#[test]
+fn else_if_stmt_chain_works() {
+    let code = "if a { b } else if c { d }";
+    let parser = Parser::from(Lexer::new(None, code)).run(asena_grammar::stmt_if);
+    assert!(!parser.has_errors());
+    let stmt = IfStmt::new(parser.build_tree().unwrap());
+
+    let Some(Branch::BlockBranch(else_branch)) = stmt.else_branch() else {
+        panic!("expected the else if to be parsed as a block");
+    };
+    let stmts = else_branch.stmts();
+    assert_eq!(stmts.len(), 1);
+}
+
+#[test]
+fn lam_pattern_parameters_works() {
+    let code = "\\(Just x) y -> x";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+    let lam = Lam::new(parser.build_tree().unwrap());
+
+    let parameters = lam.parameters();
+    assert_eq!(parameters.len(), 2);
+    assert!(parameters[0].pattern().is_some());
+    assert!(parameters[1].pattern().is_none());
+}
+
+#[test]
+fn group_pattern_works() {
+    let code = "\\(Just (Pair x _)) -> x";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+    let lam = Lam::new(parser.build_tree().unwrap());
+
+    let Some(Pat::ConstructorPat(just)) = lam.parameters()[0].pattern() else {
+        panic!("expected a constructor pattern");
+    };
+
+    let arguments = just.arguments();
+    assert_eq!(arguments.len(), 1);
+    assert!(matches!(&arguments[0], Pat::ConstructorPat(pair) if pair.arguments().len() == 2));
+}
+
+#[test]
+fn enum_accessors_works() {
+    let code = "1 + 2";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    let expr = Expr::make(parser.build_tree().unwrap().into()).unwrap();
+
+    assert!(expr.is_infix());
+    assert!(expr.as_literal_expr().is_none());
+    assert_eq!(expr.as_infix(), Infix::try_from(expr.clone()).ok());
+    assert!(expr.as_infix().unwrap().lhs().is_literal_expr());
+}
+
+#[test]
+fn list_nodes_works() {
+    let code = "1 + 2";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    let infix = Infix::new(parser.build_tree().unwrap());
+
+    let items = vec![infix.lhs(), infix.rhs()];
+    let list = items.clone().unwrap();
+
+    assert_eq!(list.kind(), TreeKind::ListTree);
+    assert_eq!(list.filter::<Expr>().as_leaf().len(), 2);
+    assert!(list.at::<Expr>(1).as_leaf().is_literal_expr());
+    assert_eq!(Vec::<Expr>::new(list), items);
+}
+
+#[test]
+fn synthetic_origin_works() {
+    let code = "1 + 2";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    let infix = Infix::new(parser.build_tree().unwrap());
+
+    let mut tree = GreenTree::of(Infix::KIND);
+    tree.set_origin(infix.location().into_owned());
+
+    let derived = Infix::new(tree);
+    assert!(derived.is_synthetic());
+    assert_eq!(derived.location(), infix.location());
+    assert_eq!(derived.as_new_ast::<Infix>().origin(), derived.origin());
+}
+
+#[test]
+fn forall_works() {
+    let parse = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors(), "unexpected errors in `{code}`");
+
+        Expr::make(parser.build_tree().unwrap().into())
+    };
+
+    let Some(Expr::Forall(forall)) = parse("forall a b. a -> b") else {
+        panic!("expected a forall expression");
+    };
+    assert_eq!(forall.variables().len(), 2);
+    assert!(forall.value().is_pi());
+
+    assert!(matches!(parse("∀ a.a"), Some(Expr::Forall(_))));
+}
+
+#[test]
+fn malformed_trees_works() {
+    #[derive(Default)]
+    struct Exprs(usize);
+
+    impl AsenaListener for Exprs {
+        fn enter_expr(&mut self, _: Expr) {
+            self.0 += 1;
+        }
+    }
+
+    // The accessors of the incomplete trees return empty nodes instead of panicking
+    let sources = [
+        "Main {",
+        "Main { match x { 1 => } }",
+        "Main { \\x -> }",
+        "Main { if then else }",
+        "Main { (1, [2, }",
+        "Main { forall . }",
+        "Main { ? }",
+        "class where",
+        "instance : {",
+        "enum A { B : }",
+        "use",
+    ];
+
+    for code in sources {
+        let parser = Parser::from(Lexer::new(None, code)).run(asena_grammar::file);
+        assert!(parser.has_errors());
+
+        let file = AsenaFile::new(parser.build_tree().unwrap());
+        file.listens(Exprs::default());
+    }
+
+    let infix = Infix::new(GreenTree::default());
+    assert!(matches!(infix.lhs(), Expr::Error));
+    assert!(matches!(infix.rhs(), Expr::Error));
+
+    let literal = LiteralExpr::new(GreenTree::default());
+    assert_eq!(literal.literal().token.kind, TokenKind::Error);
+}
+
+#[test]
+fn parser_stuck_works() {
+    // The parameters of the blocks are looked ahead without advancing, so too many of them run
+    // out of the fuel of the parser, that is reported instead of looping forever.
+    let code = format!("Main {{ f {{ {}-> 1 }} }}", "x ".repeat(300));
+    let parser = Parser::from(Lexer::new(None, &code)).run(asena_grammar::file);
+    assert!(parser.is_stuck());
+
+    let errors = parser
+        .build_tree()
+        .report
+        .diagnostics
+        .iter()
+        .filter(|error| matches!(error.message.value, ParseError::ParserStuckError))
+        .count();
+
+    assert_eq!(errors, 1);
+}
+
+#[test]
+fn deeply_nested_works() {
+    fn nesting_errors(code: &str, max_depth: usize) -> usize {
+        let parser = Parser::from(Lexer::new(None, code))
+            .with_max_depth(max_depth)
+            .run(asena_grammar::file);
+
+        parser
+            .build_tree()
+            .report
+            .diagnostics
+            .iter()
+            .filter(|error| matches!(error.message.value, ParseError::ExprTooDeeplyNestedError))
+            .count()
+    }
+
+    let parens = format!("Main {{ {}1{} }}", "(".repeat(10_000), ")".repeat(10_000));
+    assert_eq!(nesting_errors(&parens, asena_parser::DEFAULT_MAX_DEPTH), 1);
+
+    let lambdas = format!("Main {{ {}x }}", "\\x -> ".repeat(10_000));
+    assert_eq!(nesting_errors(&lambdas, asena_parser::DEFAULT_MAX_DEPTH), 1);
+
+    let patterns = format!(
+        "Main {{ match x {{ {}y{} => y }} }}",
+        "(Just ".repeat(64),
+        ")".repeat(64)
+    );
+    assert_eq!(nesting_errors(&patterns, 32), 1);
+    assert_eq!(
+        nesting_errors(&patterns, asena_parser::DEFAULT_MAX_DEPTH),
+        0
+    );
+}
+
+#[test]
+fn deeply_nested_recovery_works() {
+    // The parsing recovers at the end of the offending expression, so the next declarations
+    // are still parsed
+    let code = format!(
+        "Main {{ {}1{} }}\nOther {{ 2 }}",
+        "(".repeat(10_000),
+        ")".repeat(10_000)
+    );
+    let tree = Parser::from(Lexer::new(None, &code))
+        .run(asena_grammar::file)
+        .build_tree();
+
+    let errors = tree
+        .report
+        .diagnostics
+        .iter()
+        .map(|error| error.message.value.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(errors, vec![ParseError::ExprTooDeeplyNestedError]);
+
+    let file = AsenaFile::new(tree.data);
+    assert_eq!(file.declarations().len(), 2);
+}
+
+#[test]
+fn sections_works() {
+    let parse = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors());
+        Expr::make(parser.build_tree().unwrap().into())
+    };
+
+    assert!(matches!(parse("(+ 1)"), Some(Expr::RightSection(_))));
+    assert!(matches!(parse("(2 *)"), Some(Expr::LeftSection(_))));
+    assert!(matches!(parse("(2 * 3)"), Some(Expr::Group(_))));
+    assert!(matches!(parse("(\\x -> x)"), Some(Expr::Group(_))));
+    assert!(matches!(parse("(+)"), Some(Expr::OperatorSection(_))));
+
+    let Some(Expr::Infix(infix)) = parse("a `add` b") else {
+        panic!("expected an infix expression");
+    };
+    assert_eq!(infix.fn_id().as_str(), "add");
+}
+
+#[test]
+fn interpolation_works() {
+    let code = r#""hello {name}, you are {age + 1}!""#;
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+
+    let Some(Expr::Interpolation(interpolation)) = Expr::make(parser.build_tree().unwrap().into())
+    else {
+        panic!("expected an interpolated string");
+    };
+
+    let chunks = interpolation
+        .chunks()
+        .into_iter()
+        .map(|chunk| match chunk.data() {
+            Literal::String(text) => text.clone(),
+            _ => panic!("expected a string chunk"),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(interpolation.values().len(), 2);
+    assert_eq!(chunks, vec!["hello ", ", you are ", "!"]);
+
+    let mut lexer = Lexer::new(None, r#""no holes \{here}""#);
+    assert_eq!(lexer.next().unwrap().kind, TokenKind::Str);
+}
+
+#[test]
+fn escaped_braces_works() {
+    let literal = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors());
+
+        match Expr::make(parser.build_tree().unwrap().into()) {
+            Some(Expr::LiteralExpr(literal)) => literal.literal().data().clone(),
+            _ => panic!("expected a plain string"),
+        }
+    };
+
+    // The doubled braces aren't holes, so the strings with them are plain strings.
+    assert!(matches!(literal(r#""{{""#), Literal::String(text) if text == "{"));
+    assert!(matches!(literal(r#""{{name}""#), Literal::String(text) if text == "{name}"));
+    assert!(matches!(literal(r#""a } b {{ c""#), Literal::String(text) if text == "a } b { c"));
+    assert!(matches!(literal(r#""}""#), Literal::String(text) if text == "}"));
+    assert!(matches!(literal(r#""}}""#), Literal::String(text) if text == "}"));
+    assert!(matches!(literal(r#""{{name}}""#), Literal::String(text) if text == "{name}"));
+
+    let code = r#""{{literal} and {hole}""#;
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+
+    let Some(Expr::Interpolation(interpolation)) = Expr::make(parser.build_tree().unwrap().into())
+    else {
+        panic!("expected an interpolated string");
+    };
+
+    assert_eq!(interpolation.values().len(), 1);
+    assert!(matches!(
+        interpolation.chunks()[0].data(),
+        Literal::String(text) if text == "{literal} and "
+    ));
+
+    // The closing braces are escaped in the chunks too, like `}}` after the hole.
+    let code = r#""{hole}}} and {{literal}}""#;
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+
+    let Some(Expr::Interpolation(interpolation)) = Expr::make(parser.build_tree().unwrap().into())
+    else {
+        panic!("expected an interpolated string");
+    };
+
+    assert_eq!(interpolation.values().len(), 1);
+    assert!(matches!(
+        interpolation.chunks().last().unwrap().data(),
+        Literal::String(text) if text == "} and {literal}"
+    ));
+}
+
+#[test]
+fn char_literal_works() {
+    let literal = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors());
+
+        match Expr::make(parser.build_tree().unwrap().into()) {
+            Some(Expr::LiteralExpr(literal)) => literal.literal().data().clone(),
+            _ => panic!("expected a literal"),
+        }
+    };
+
+    assert!(matches!(literal("'a'"), Literal::Char('a')));
+    assert!(matches!(literal("'\\n'"), Literal::Char('\n')));
+    assert!(matches!(literal("'\\''"), Literal::Char('\'')));
+
+    let code = "match c { 'a' => 1, _ => 2 }";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+
+    let Some(Expr::Match(expr)) = Expr::make(parser.build_tree().unwrap().into()) else {
+        panic!("expected a match expression");
+    };
+
+    assert!(matches!(expr.cases()[0].pat(), Pat::LiteralPat(_)));
+}
+
+#[test]
+fn range_pattern_works() {
+    let code = "match n { 0 => 1, 1..10 => 2, _ => 3 }";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+
+    let Some(Expr::Match(expr)) = Expr::make(parser.build_tree().unwrap().into()) else {
+        panic!("expected a match expression");
+    };
+
+    let Pat::RangePat(range) = expr.cases()[1].pat() else {
+        panic!("expected a range pattern");
+    };
+    assert_eq!(range.start().literal().contents(), "1");
+    assert_eq!(range.end().literal().contents(), "10");
+}
+
+#[test]
+fn list_rest_pattern_works() {
+    let code = "match xs { [] => 0, [x, .., y] => 1, [x, .. ] => 2 }";
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    assert!(!parser.has_errors());
+
+    let Some(Expr::Match(expr)) = Expr::make(parser.build_tree().unwrap().into()) else {
+        panic!("expected a match expression");
+    };
+
+    for (case, expected) in expr.cases().iter().zip([0, 3, 2]) {
+        let Pat::ListPat(list) = case.pat() else {
+            panic!("expected a list pattern");
+        };
+        assert_eq!(list.items().len(), expected);
+    }
+
+    let Pat::ListPat(list) = expr.cases()[1].pat() else {
+        panic!("expected a list pattern");
+    };
+    assert!(matches!(list.items()[1], Pat::SpreadPat(_)));
+}
+
+#[test]
+fn unary_minus_works() {
+    let parse = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors());
+        Expr::make(parser.build_tree().unwrap().into())
+    };
+
+    assert!(matches!(parse("-5"), Some(Expr::Unary(_))));
+    assert!(matches!(parse("(-5)"), Some(Expr::Group(_))));
+    assert!(matches!(parse("(- 5)"), Some(Expr::Group(_))));
+    assert!(matches!(parse("a - 5"), Some(Expr::Infix(_))));
+
+    let Some(Expr::Infix(infix)) = parse("a - -5") else {
+        panic!("expected an infix expression");
+    };
+
+    assert!(matches!(infix.rhs(), Expr::Unary(_)));
+}
+
+#[test]
+fn integer_literals_works() {
+    let literal = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors(), "unexpected errors in `{code}`");
+
+        match Expr::make(parser.build_tree().unwrap().into()) {
+            Some(Expr::LiteralExpr(literal)) => literal.literal().data().contents(),
+            _ => panic!("expected a literal"),
+        }
+    };
+
+    assert_eq!(literal("0xFF"), "255");
+    assert_eq!(literal("0o755"), "493");
+    assert_eq!(literal("0b1010"), "10");
+    assert_eq!(literal("1_000_000"), "1000000");
+    assert_eq!(literal("0xFF_u8"), "255");
+    assert_eq!(literal("1.5"), "1.5");
+
+    let out_of_range = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        parser.has_errors()
+    };
+
+    assert!(out_of_range("256u8"));
+    assert!(out_of_range("0x1_0000u16"));
+    assert!(!out_of_range("128i8"));
+}
+
+#[test]
+fn integer_literal_errors_works() {
+    let errors = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+
+        parser
+            .build_tree()
+            .report
+            .diagnostics
+            .iter()
+            .map(|error| error.message.value.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // The errors name the type of the suffix, and the floats aren't reported as out of range.
+    assert_eq!(
+        errors("256u8"),
+        vec![ParseError::IntegerOutOfRangeError("256".into(), "UInt8")]
+    );
+    assert_eq!(
+        errors("1.5u8"),
+        vec![ParseError::FloatIntegerSuffixError("1.5".into(), "UInt8")]
+    );
+    assert_eq!(
+        ParseError::IntegerOutOfRangeError("129".into(), "Int8").to_string(),
+        "the literal `129` doesn't fit in `Int8`"
+    );
+    assert_eq!(errors("129i8").len(), 1);
+}
+
+#[test]
+fn float_literals_works() {
+    let literal = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors(), "unexpected errors in `{code}`");
+
+        match Expr::make(parser.build_tree().unwrap().into()) {
+            Some(Expr::LiteralExpr(literal)) => literal.literal().data().clone(),
+            _ => panic!("expected a literal"),
+        }
+    };
+
+    assert_eq!(literal("1.50").contents(), "1.50");
+    assert_eq!(literal("1e3").contents(), "1e3");
+    assert_eq!(literal("2.5e-3f32").contents(), "2.5e-3");
+    assert_eq!(format!("{:?}", literal("1.50")), "1.50f64");
+    assert_eq!(literal("1.50").negate().unwrap().contents(), "-1.50");
+}
+
+#[test]
+fn edition_works() {
+    use asena_lexer::Edition;
+
+    let kind = |edition| Lexer::with_edition(None, "do", edition).tokens[0].kind;
+
+    assert_eq!(kind(Edition::Edition2023), TokenKind::Identifier);
+    assert_eq!(kind(Edition::Edition2024), TokenKind::DoKeyword);
+    assert_eq!("2024".parse(), Ok(Edition::LATEST));
+}
+
+#[test]
+fn raw_string_works() {
+    let literal = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors(), "unexpected errors in `{code}`");
+
+        match Expr::make(parser.build_tree().unwrap().into()) {
+            Some(Expr::LiteralExpr(literal)) => match literal.literal().data() {
+                Literal::String(text) => text.clone(),
+                _ => panic!("expected a string literal"),
+            },
+            _ => panic!("expected a literal"),
+        }
+    };
+
+    assert_eq!(literal(r#"r"\d+ {no_hole}""#), r"\d+ {no_hole}");
+    assert_eq!(literal("r#\"say \"hi\"\n\"#"), "say \"hi\"\n");
+
+    let mut lexer = Lexer::new(None, "r\"a\" r");
+    let raw = lexer.next().unwrap();
+    assert_eq!(raw.kind, TokenKind::Str);
+    assert_eq!(raw.span.range.end(), 4);
+    assert_eq!(lexer.next().unwrap().kind, TokenKind::Identifier);
+}
+
+#[test]
+fn token_texts_works() {
+    let tokens = Lexer::new(None, "foo + \"some text\" 1.5e3 §").tokens;
+    let texts = tokens
+        .iter()
+        .map(|token| (token.kind, matches!(token.text, TokenText::Interned(_))))
+        .collect::<Vec<_>>();
+
+    // Only the names are interned, the texts of the literals and of the errors are owned
+    assert_eq!(
+        texts[..5],
+        [
+            (TokenKind::Identifier, true),
+            (TokenKind::Symbol, true),
+            (TokenKind::Str, false),
+            (TokenKind::Float64, false),
+            (TokenKind::ErrorChar, false),
+        ]
+    );
+    assert_eq!(tokens[2].text, "\"some text\"");
+}
+
+#[test]
+fn unicode_identifiers_works() {
+    let texts = |code: &str| {
+        let tokens = Lexer::new(None, code).tokens.into_iter();
+        tokens
+            .map(|token| (token.kind, token.text.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    // The precomposed `é`, and the `e` followed by the combining accent
+    assert_eq!(texts("café"), texts("cafe\u{301}"));
+    assert_eq!(texts("αβ")[0].0, TokenKind::Identifier);
+
+    let expr = |code: &str| {
+        let parser =
+            Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+        assert!(!parser.has_errors(), "unexpected errors in `{code}`");
+        Expr::make(parser.build_tree().unwrap().into())
+    };
+
+    assert!(matches!(expr("λx → x"), Some(Expr::Lam(..))));
+    assert!(matches!(expr("∀ a. a"), Some(Expr::Forall(..))));
+    assert!(matches!(expr("Π (a: Int) -> a"), Some(Expr::Pi(..))));
+    assert!(matches!(expr("Σ [a: Int] -> a"), Some(Expr::Sigma(..))));
+}
+
+#[test]
+fn error_char_works() {
+    let code = "1 ¤+ 2";
+
+    let kinds = Lexer::new(None, code)
+        .tokens
+        .into_iter()
+        .map(|token| token.kind);
+    let kinds = kinds.collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Int32,
+            TokenKind::ErrorChar,
+            TokenKind::Symbol,
+            TokenKind::Int32
+        ]
+    );
+
+    let parser =
+        Parser::from(Lexer::new(None, code)).run(|p| asena_grammar::expr(p, Linebreak::Cont));
+    let errors = parser
+        .build_tree()
+        .report
+        .diagnostics
+        .into_iter()
+        .map(|error| error.message.value)
+        .collect::<Vec<_>>();
+    assert_eq!(errors, vec![ParseError::UnrecognizedCharError('¤')]);
+
+    assert!(TokenKind::MatchKeyword.is_keyword());
+    assert!(TokenKind::Char.is_literal());
+    assert!(TokenKind::LeftArrow.is_symbol());
+    assert!(!TokenKind::ErrorChar.is_symbol());
+}
+
+#[test]
+fn syntax_kinds_works() {
+    use asena_ast::syntax_kinds::{token_kinds, tree_kinds, NodeCategory};
+    use asena_leaf::token::kind::TokenCategory;
+
+    let kinds = tree_kinds();
+    assert_eq!(kinds.len(), TreeKind::ALL.len());
+
+    let app = kinds
+        .iter()
+        .find(|info| info.kind == TreeKind::ExprApp)
+        .unwrap();
+    assert_eq!(app.name, "EXPR_APP");
+    assert_eq!(app.category, Some(NodeCategory::Expr));
+    assert_eq!(app.node, Some("App"));
+
+    let param = kinds
+        .iter()
+        .find(|info| info.kind == TreeKind::Param)
+        .unwrap();
+    assert_eq!(param.category, None);
+
+    let tokens = token_kinds();
+    assert_eq!(tokens.first().map(|info| info.kind), Some(TokenKind::Error));
+    assert_eq!(tokens.last().map(|info| info.kind), Some(TokenKind::Eof));
+    assert_eq!(TokenKind::LetKeyword.category(), TokenCategory::Keyword);
+}
+
+#[test]
+fn entry_points_works() {
+    use asena_ast::{Decl, Stmt, Typed};
+
+    let expr = asena_grammar::parse_expr("1 + 2").ok().unwrap();
+    assert!(expr.is_infix());
+
+    let typed = asena_grammar::parse_type("Maybe a -> a").ok().unwrap();
+    assert!(matches!(typed, Typed::Explicit(_)));
+
+    let decl = asena_grammar::parse_decl("Double (x: Int32) : Int32 { x + x }").node;
+    assert!(matches!(decl, Decl::Signature(_)));
+
+    let stmt = asena_grammar::parse_stmt("let x = 10").node;
+    assert!(matches!(stmt, Stmt::LetStmt(_)));
+
+    assert!(asena_grammar::parse_expr("1 + 2 )").has_errors());
+}
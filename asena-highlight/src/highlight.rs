@@ -5,7 +5,7 @@ use crate::{HighlightColor, SyntaxHighlight};
 impl SyntaxHighlight for Token {
     fn highlight(&self) -> crate::HighlightColor {
         match self.kind {
-            Error => HighlightColor::Error,
+            Error | ErrorChar => HighlightColor::Error,
             Nat => HighlightColor::Number,
             InKeyword => HighlightColor::SoftKeyword,
             EqualSymbol => HighlightColor::Assign,
@@ -21,7 +21,7 @@ impl SyntaxHighlight for Token {
             Identifier if self.text == "panic" => HighlightColor::BuiltinFunction,
             // <<<
             Identifier => HighlightColor::LocalReference,
-            Str => HighlightColor::String,
+            Str | StrStart | StrMiddle | StrEnd | Char => HighlightColor::String,
             Eof => HighlightColor::Eof,
             LetKeyword | TrueKeyword | FalseKeyword | IfKeyword | ElseKeyword | ThenKeyword
             | TypeKeyword | RecordKeyword | ReturnKeyword | EnumKeyword | TraitKeyword
@@ -31,13 +31,14 @@ impl SyntaxHighlight for Token {
             | Float32 | Float64 => HighlightColor::Number,
             LeftBracket | RightBracket | LeftBrace | RightBrace | LeftParen | RightParen
             | Comma | Semi | Colon | Dot => HighlightColor::Delimitator,
-            LambdaUnicode | ForallUnicode | PiUnicode | SigmaUnicode => {
-                HighlightColor::GlobalFunction
-            }
+            PiUnicode | SigmaUnicode => HighlightColor::GlobalFunction,
             HelpSymbol | DoubleArrow | RightArrow | LeftArrow => HighlightColor::Operator,
             FunKeyword => HighlightColor::HardKeyword,
             SelfKeyword => HighlightColor::SoftKeyword,
             DefaultKeyword => HighlightColor::HardKeyword,
+            ForallKeyword => HighlightColor::HardKeyword,
+            PubKeyword => HighlightColor::HardKeyword,
+            DoKeyword => HighlightColor::HardKeyword,
         }
     }
 }
@@ -83,6 +83,10 @@ impl AsenaVisitor<()> for ParserSemanticHighlight<'_> {
         highlight_methods(self.buf, &value.methods())
     }
 
+    fn visit_associated_type(&mut self, value: AssociatedType) {
+        self.buf.annotate(&value.token(TypeKeyword), HardKeyword);
+    }
+
     fn visit_group(&mut self, group: Group) {
         self.buf.annotate(&group.token(LeftParen), Delimitator);
         self.buf.annotate(&group.token(RightParen), Delimitator);
@@ -124,13 +128,14 @@ impl AsenaVisitor<()> for ParserSemanticHighlight<'_> {
             match literal.data() {
                 Literal::Nat(_) => Number,
                 Literal::String(_) => String,
+                Literal::Char(_) => String,
                 Literal::Int8(_, _) => Number,
                 Literal::Int16(_, _) => Number,
                 Literal::Int32(_, _) => Number,
                 Literal::Int64(_, _) => Number,
                 Literal::Int128(_, _) => Number,
-                Literal::Float32(_) => Number,
-                Literal::Float64(_) => Number,
+                Literal::Float32(..) => Number,
+                Literal::Float64(..) => Number,
                 Literal::True => HardKeyword,
                 Literal::False => HardKeyword,
                 Literal::Error => crate::HighlightColor::Error,
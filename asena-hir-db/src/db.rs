@@ -1,4 +1,7 @@
-use asena_ast_db::{db::AstDatabase, package::Package, vfs::VfsFile};
+use std::sync::Arc;
+
+use asena_ast::Use;
+use asena_ast_db::{db::AstDatabase, definition::Definition, package::Package, vfs::VfsFile};
 use asena_ast_lowering::db::AstLowerrer;
 use asena_ast_resolver::db::AstResolverDatabase;
 use asena_hir::{
@@ -8,9 +11,14 @@ use asena_hir::{
 };
 use asena_leaf::ast::AstParam;
 use asena_prec::PrecDatabase;
+use asena_span::Loc;
 use if_chain::if_chain;
 use im::HashSet;
 
+use crate::exhaustiveness::NonExhaustiveMatch;
+use crate::references::Reference;
+use crate::well_founded::IllFormedEnum;
+
 #[salsa::query_group(HirDatabaseStorage)]
 pub trait HirDatabase:
     PrecDatabase + HirInterner + AstDatabase + AstLowerrer + AstResolverDatabase
@@ -24,6 +32,24 @@ pub trait HirDatabase:
     #[salsa::invoke(crate::rc::rc)]
     fn hir_rc(&self, declaration: AstParam<HirBindingGroup>) -> HirBindingGroup;
 
+    #[salsa::invoke(crate::references::file_references)]
+    fn file_references(&self, file: VfsFile) -> Arc<Vec<Reference>>;
+
+    #[salsa::invoke(crate::dead_code::dead_code)]
+    fn dead_code(&self, package: Package) -> Arc<Vec<Definition>>;
+
+    #[salsa::invoke(crate::exhaustiveness::non_exhaustive_matches)]
+    fn non_exhaustive_matches(&self, file: VfsFile) -> Arc<Vec<NonExhaustiveMatch>>;
+
+    #[salsa::invoke(crate::exhaustiveness::duplicate_cases)]
+    fn duplicate_cases(&self, file: VfsFile) -> Arc<Vec<Loc>>;
+
+    #[salsa::invoke(crate::well_founded::ill_formed_enums)]
+    fn ill_formed_enums(&self, file: VfsFile) -> Arc<Vec<IllFormedEnum>>;
+
+    #[salsa::invoke(crate::imports::unused_imports)]
+    fn unused_imports(&self, file: VfsFile) -> Arc<Vec<Use>>;
+
     fn hir_file_defs(&self, file: VfsFile) -> HashSet<HirTopLevel>;
 
     fn hir_defs(&self, pkg: Package) -> HashSet<HirTopLevel>;
@@ -37,8 +63,7 @@ fn hir_find_fn(db: &dyn HirDatabase, pkg: Package, name: String) -> Option<HirBi
         if_chain! {
             let data = db.lookup_intern_top_level(def);
             if let HirTopLevelKind::BindingGroup(group) = data.kind;
-            let local_name = db.lookup_intern_name(group.signature.name);
-            if local_name == name;
+            if group.signature.name == name.as_str();
             then {
                 return Some(group);
             }
@@ -0,0 +1,103 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use asena_ast::FunctionId;
+use asena_ast_db::{
+    definition::{Definition, DefinitionKind},
+    package::{HasDiagnostic, Package},
+    vfs::VfsFile,
+};
+use asena_ast_resolver::error::ResolutionError::UnusedDefinitionWarning;
+use asena_report::Diagnostic;
+use asena_span::Loc;
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::db::HirDatabase;
+
+/// The name of the entry point of an executable package.
+const MAIN: &str = "Main";
+
+/// A top-level definition, identified by its file and its name, so the definitions with the same
+/// name in different files are different nodes of the graph.
+type DefinitionKey = (VfsFile, FunctionId);
+
+fn key_of(definition: &Definition) -> DefinitionKey {
+    (definition.file, definition.name.clone())
+}
+
+/// Finds the top-level definition that contains the location, the signature and the assignments
+/// of the same name are considered the same definition.
+fn owner_of(definitions: &[Definition], location: &Loc) -> Option<DefinitionKey> {
+    definitions
+        .iter()
+        .find(|definition| definition.location.contains(location))
+        .map(key_of)
+}
+
+/// Reports the top-level definitions of the package, that aren't reachable from any root, as
+/// warnings, and returns them.
+///
+/// The roots are the `Main` entry point, the names exported by their modules, with `#export`, or
+/// with the `pub` visibility if the module has no export list, and the references outside any
+/// named definition, like the ones in the instances.
+pub fn dead_code(db: &dyn HirDatabase, package: Package) -> Arc<Vec<Definition>> {
+    let definitions = db.package_definitions(package);
+
+    let mut roots = definitions
+        .iter()
+        .filter(|definition| {
+            definition.name.as_str() == MAIN
+                || db.exports(definition.file).contains_key(&definition.name)
+        })
+        .map(key_of)
+        .collect::<Vec<_>>();
+    let mut edges = FxHashMap::<DefinitionKey, Vec<DefinitionKey>>::default();
+
+    for file in package.files(db).iter() {
+        for reference in db.file_references(*file).iter() {
+            let def = db.lookup_intern_def(reference.def);
+            let Some(target) = owner_of(&definitions, &def.defined_at) else {
+                continue;
+            };
+
+            match owner_of(&definitions, &reference.location) {
+                // Recursive references doesn't make a definition reachable.
+                Some(source) if source == target => {}
+                Some(source) => edges.entry(source).or_default().push(target),
+                None => roots.push(target),
+            }
+        }
+    }
+
+    let mut reachable = FxHashSet::default();
+    let mut queue = VecDeque::from(roots);
+    while let Some(key) = queue.pop_front() {
+        if !reachable.insert(key.clone()) {
+            continue;
+        }
+
+        queue.extend(edges.get(&key).into_iter().flatten().cloned());
+    }
+
+    let unused = definitions
+        .iter()
+        .filter(|definition| !reachable.contains(&key_of(definition)))
+        // The assignments are reported with their signatures, if there are any.
+        .filter(|definition| {
+            definition.kind != DefinitionKind::Assign
+                || !definitions.iter().any(|other| {
+                    key_of(other) == key_of(definition) && other.kind == DefinitionKind::Signature
+                })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for definition in unused.iter() {
+        Diagnostic::of(
+            definition.location.clone(),
+            UnusedDefinitionWarning(definition.name.clone()),
+        )
+        .push(db);
+    }
+
+    Arc::new(unused)
+}
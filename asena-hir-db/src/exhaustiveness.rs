@@ -0,0 +1,447 @@
+//! The exhaustiveness of the matches over the enums, the matches that don't cover all the
+//! constructors are reported, with a fix that adds the skeleton cases of the missing ones. The
+//! matches over the integers are checked against the bounds of their types, and the cases with
+//! the same pattern of a previous case are reported too, as they're unreachable. The matches over
+//! the lists are checked against the lengths of the lists, or against the length of the array if
+//! the scrutinee is an array literal.
+
+use std::sync::Arc;
+
+use asena_ast::{
+    visitor::new_walker, AsenaVisitor, Expr, FunctionId, Literal, LiteralCategory, Match, Pat,
+    Signed,
+};
+use asena_ast_db::{definition::DefinitionKind, package::HasDiagnostic, vfs::VfsFile};
+use asena_ast_resolver::error::ResolutionError::{
+    DuplicateCaseWarning, NonExhaustiveIntegersWarning, NonExhaustiveListsWarning,
+    NonExhaustiveMatchWarning,
+};
+use asena_ast_resolver::{PatResolution, PatResolutionKey};
+use asena_leaf::ast::{Located, Walkable};
+use asena_report::{CodeAction, TextEdit, WithError};
+use asena_span::Loc;
+use itertools::Itertools;
+
+use crate::db::HirDatabase;
+
+/// A match that doesn't cover all the constructors of the enum it matches.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct NonExhaustiveMatch {
+    pub location: Loc,
+
+    /// The missing constructors, with their arities, in the order they're declared.
+    pub missing: Vec<(FunctionId, usize)>,
+}
+
+struct MatchCollector<'db> {
+    db: &'db dyn HirDatabase,
+    file: VfsFile,
+    matches: Vec<NonExhaustiveMatch>,
+}
+
+impl<'db> MatchCollector<'db> {
+    /// Returns the missing constructors of the match, the matches with catch-all cases, and the
+    /// ones that aren't over the constructors of an enum, are considered exhaustive.
+    ///
+    /// A constructor is covered by any case of it, the nested patterns aren't checked.
+    fn missing_constructors(&self, value: &Match) -> Vec<(FunctionId, usize)> {
+        let mut variants = vec![];
+        for pat in value
+            .cases()
+            .iter()
+            .flat_map(|case| alternatives(case.pat()))
+        {
+            match pat {
+                Pat::GlobalPat(ref pat) => match &*pat.key(PatResolutionKey) {
+                    PatResolution::Variant(def) => variants.push(*def),
+                    _ => return vec![],
+                },
+                Pat::ConstructorPat(ref pat) => match &*pat.key(PatResolutionKey) {
+                    PatResolution::Variant(def) => variants.push(*def),
+                    _ => return vec![],
+                },
+                _ => return vec![],
+            }
+        }
+
+        let Some(first) = variants.first() else {
+            return vec![];
+        };
+
+        let defined_at = self.db.lookup_intern_def(*first).defined_at;
+        let package = self.db.lookup_intern_vfs_file(self.file).pkg;
+        let Some(enum_decl) = self
+            .db
+            .package_definitions(package)
+            .iter()
+            .filter(|definition| definition.kind == DefinitionKind::Enum)
+            .find(|definition| definition.location.contains(&defined_at))
+            .and_then(|definition| definition.decl.as_enum())
+        else {
+            return vec![];
+        };
+
+        let covered = variants
+            .iter()
+            .map(|def| self.db.lookup_intern_def(*def).name)
+            .collect::<Vec<_>>();
+
+        enum_decl
+            .variants()
+            .iter()
+            .map(|variant| (variant.name().to_fn_id(), variant.arity()))
+            .filter(|(name, _)| !covered.contains(name))
+            .collect()
+    }
+}
+
+/// Returns the alternatives of the pattern, the or-patterns are flattened, and the as-patterns
+/// are replaced by their patterns, like `Nothing` and `Just _` in `Nothing | xs@(Just _)`.
+fn alternatives(pat: Pat) -> Vec<Pat> {
+    match pat {
+        Pat::OrPat(ref pat) => pat
+            .alternatives()
+            .iter()
+            .cloned()
+            .flat_map(alternatives)
+            .collect(),
+        Pat::AsPat(ref pat) => alternatives(pat.pattern()),
+        pat => vec![pat],
+    }
+}
+
+/// Returns the integers that the match doesn't cover, as inclusive ranges, if all of its cases
+/// are integer literals or ranges. The bounds are the ones of the type of the first literal, like
+/// `0..255` for `255u8`, the matches with catch-all cases are considered exhaustive.
+fn missing_integers(value: &Match) -> Vec<(i128, i128)> {
+    let mut covered = vec![];
+    let mut bounds = None;
+    for pat in value
+        .cases()
+        .iter()
+        .flat_map(|case| alternatives(case.pat()))
+    {
+        let (start, end) = match pat {
+            Pat::LiteralPat(ref pat) => (pat.literal(), pat.literal()),
+            Pat::RangePat(ref pat) => (pat.start().literal(), pat.end().literal()),
+            _ => return vec![],
+        };
+
+        bounds.get_or_insert_with(|| integer_bounds(&start));
+
+        let (Some(start), Some(end)) = (integer_value(&start), integer_value(&end)) else {
+            return vec![];
+        };
+
+        covered.push((start, end));
+    }
+
+    let Some((min, max)) = bounds else {
+        return vec![];
+    };
+
+    covered.sort();
+
+    let mut missing = vec![];
+    let mut next = Some(min);
+    for (start, end) in covered {
+        let Some(from) = next else {
+            break;
+        };
+
+        if start > from {
+            missing.push((from, (start - 1).min(max)));
+        }
+        if end >= from {
+            next = end.checked_add(1);
+        }
+    }
+
+    if let Some(from) = next.filter(|from| *from <= max) {
+        missing.push((from, max));
+    }
+
+    missing.retain(|(start, _)| *start <= max);
+    missing
+}
+
+fn integer_value(literal: &Literal) -> Option<i128> {
+    if literal.category() != Some(LiteralCategory::Integer) {
+        return None;
+    }
+
+    literal.contents().parse().ok()
+}
+
+/// Returns the bounds of the type of the integer literal, the [Literal::Nat], and the unsigned
+/// 128 bits integers, are bounded by [i128::MAX].
+fn integer_bounds(literal: &Literal) -> (i128, i128) {
+    match *literal {
+        Literal::Int8(_, Signed::Signed) => (i8::MIN.into(), i8::MAX.into()),
+        Literal::Int8(_, Signed::Unsigned) => (0, u8::MAX.into()),
+        Literal::Int16(_, Signed::Signed) => (i16::MIN.into(), i16::MAX.into()),
+        Literal::Int16(_, Signed::Unsigned) => (0, u16::MAX.into()),
+        Literal::Int32(_, Signed::Signed) => (i32::MIN.into(), i32::MAX.into()),
+        Literal::Int32(_, Signed::Unsigned) => (0, u32::MAX.into()),
+        Literal::Int64(_, Signed::Signed) => (i64::MIN.into(), i64::MAX.into()),
+        Literal::Int64(_, Signed::Unsigned) => (0, u64::MAX.into()),
+        Literal::Int128(_, Signed::Signed) => (i128::MIN, i128::MAX),
+        Literal::Int128(_, Signed::Unsigned) | Literal::Nat(_) => (0, i128::MAX),
+        _ => (i128::MIN, i128::MAX),
+    }
+}
+
+/// Renders the missing integers, like `0` or `10..255`.
+fn render_integers(missing: &[(i128, i128)]) -> String {
+    missing
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("`{start}`")
+            } else {
+                format!("`{start}..{end}`")
+            }
+        })
+        .join(", ")
+}
+
+/// Returns the lengths of the lists that the match doesn't cover, as inclusive ranges, where the
+/// ranges without an end are unbounded, if all of its cases are list patterns. A list pattern
+/// with a rest, like `[x, .., y]`, covers the lists with at least the length of its other items.
+///
+/// If the scrutinee is an array literal, its length is statically known, so only it is checked.
+/// The matches with catch-all cases are considered exhaustive.
+fn missing_lengths(value: &Match) -> Vec<(usize, Option<usize>)> {
+    let mut covered = vec![];
+    for pat in value
+        .cases()
+        .iter()
+        .flat_map(|case| alternatives(case.pat()))
+    {
+        let Pat::ListPat(ref pat) = pat else {
+            return vec![];
+        };
+
+        let items = pat.items();
+        let spreads = items
+            .iter()
+            .filter(|item| matches!(item, Pat::SpreadPat(_)));
+        match spreads.count() {
+            0 => covered.push((items.len(), Some(items.len()))),
+            _ => covered.push((items.len() - 1, None)),
+        }
+    }
+
+    if covered.is_empty() {
+        return vec![];
+    }
+
+    if let Expr::Array(array) = value.scrutinee() {
+        let length = array.items().len();
+        let covers = |(start, end): &(usize, Option<usize>)| {
+            (*start..=end.unwrap_or(usize::MAX)).contains(&length)
+        };
+
+        if covered.iter().any(covers) {
+            return vec![];
+        }
+
+        return vec![(length, Some(length))];
+    }
+
+    covered.sort();
+
+    let mut missing = vec![];
+    let mut next = Some(0);
+    for (start, end) in covered {
+        let Some(from) = next else {
+            break;
+        };
+
+        if start > from {
+            missing.push((from, Some(start - 1)));
+        }
+        next = match end {
+            Some(end) => Some(from.max(end + 1)),
+            None => None,
+        };
+    }
+
+    if let Some(from) = next {
+        missing.push((from, None));
+    }
+
+    missing
+}
+
+/// Renders the missing lengths, like `0`, `2..4` or `6..`.
+fn render_lengths(missing: &[(usize, Option<usize>)]) -> String {
+    missing
+        .iter()
+        .map(|(start, end)| match end {
+            Some(end) if start == end => format!("`{start}`"),
+            Some(end) => format!("`{start}..{end}`"),
+            None => format!("`{start}..`"),
+        })
+        .join(", ")
+}
+
+impl<'db> AsenaVisitor<()> for MatchCollector<'db> {
+    fn visit_match(&mut self, value: Match) {
+        let missing = self.missing_constructors(&value);
+        if missing.is_empty() {
+            let integers = missing_integers(&value);
+            if !integers.is_empty() {
+                value
+                    .clone()
+                    .fail(NonExhaustiveIntegersWarning(render_integers(&integers)))
+                    .push(self.db);
+            }
+
+            let lengths = missing_lengths(&value);
+            if !lengths.is_empty() {
+                value
+                    .clone()
+                    .fail(NonExhaustiveListsWarning(render_lengths(&lengths)))
+                    .push(self.db);
+            }
+
+            return;
+        }
+
+        let fix = missing_cases_fix(&self.db.source(self.file), &value, &missing);
+        let names = missing
+            .iter()
+            .map(|(name, _)| format!("`{name}`"))
+            .join(", ");
+        value
+            .clone()
+            .fail(NonExhaustiveMatchWarning(names))
+            .with_fixes(fix)
+            .push(self.db);
+
+        self.matches.push(NonExhaustiveMatch {
+            location: value.location().into_owned(),
+            missing,
+        });
+    }
+}
+
+/// Reports the matches of the file that don't cover all the constructors of their enums, as
+/// warnings, and returns them.
+pub fn non_exhaustive_matches(db: &dyn HirDatabase, file: VfsFile) -> Arc<Vec<NonExhaustiveMatch>> {
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+
+    let mut collector = MatchCollector {
+        db,
+        file,
+        matches: vec![],
+    };
+    ast.walk(&mut new_walker(&mut collector));
+
+    Arc::new(collector.matches)
+}
+
+struct DuplicateCaseCollector<'db> {
+    db: &'db dyn HirDatabase,
+    source: Arc<String>,
+    duplicates: Vec<Loc>,
+}
+
+impl<'db> AsenaVisitor<()> for DuplicateCaseCollector<'db> {
+    /// The patterns are compared by their text, ignoring the whitespace, so only the identical
+    /// patterns are reported, and not the ones that are covered by a previous one.
+    fn visit_match(&mut self, value: Match) {
+        let mut patterns = vec![];
+        for case in value.cases() {
+            let pat = case.pat();
+            let location = pat.location().into_owned();
+            let Some(text) = self
+                .source
+                .get(location.range.start()..location.range.end())
+            else {
+                continue;
+            };
+
+            let text = text.split_whitespace().join(" ");
+            if patterns.contains(&text) {
+                case.fail(DuplicateCaseWarning(text)).push(self.db);
+                self.duplicates.push(location);
+            } else {
+                patterns.push(text);
+            }
+        }
+    }
+}
+
+/// Reports the cases of the matches of the file whose patterns are identical to the pattern of
+/// a previous case of the same match, as warnings, and returns the locations of their patterns.
+pub fn duplicate_cases(db: &dyn HirDatabase, file: VfsFile) -> Arc<Vec<Loc>> {
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+
+    let mut collector = DuplicateCaseCollector {
+        db,
+        source: db.source(file),
+        duplicates: vec![],
+    };
+    ast.walk(&mut new_walker(&mut collector));
+
+    Arc::new(collector.duplicates)
+}
+
+/// Renders the skeleton case of the constructor, binding the fields with wildcards, and with a
+/// hole as the value, like `Just _ => ?todo`.
+fn render_case(name: &FunctionId, arity: usize) -> String {
+    let pattern = std::iter::once(name.to_string())
+        .chain((0..arity).map(|_| "_".to_string()))
+        .join(" ");
+
+    format!("{pattern} => ?todo,")
+}
+
+/// Returns the fix that inserts the missing cases after the last case of the match. The cases
+/// are inserted in their own lines, with the indentation of the last case, if the cases are in
+/// their own lines, otherwise they're inserted in the same line.
+fn missing_cases_fix(
+    source: &str,
+    value: &Match,
+    missing: &[(FunctionId, usize)],
+) -> Option<CodeAction> {
+    let last = value.cases().last()?.location().into_owned();
+    let end = last.range.end();
+
+    // The last case may not have a trailing comma, it's needed to separate the new ones.
+    let rest = source.get(end..)?;
+    let (end, separator) = match rest.trim_start().strip_prefix(',') {
+        Some(_) => (end + rest.find(',')? + 1, ""),
+        None if source.get(..end)?.ends_with(',') => (end, ""),
+        None => (end, ","),
+    };
+
+    let start_of_line = source.get(..last.range.start())?.rfind('\n');
+    let prefix = match start_of_line {
+        Some(start) => source.get(start + 1..last.range.start())?,
+        None => "",
+    };
+
+    let cases = missing
+        .iter()
+        .map(|(name, arity)| render_case(name, *arity));
+    let text = if prefix.trim().is_empty() && start_of_line.is_some() {
+        cases.map(|case| format!("\n{prefix}{case}")).join("")
+    } else {
+        cases.map(|case| format!(" {case}")).join("")
+    };
+
+    let at = Loc::new(last.file.clone(), end, end);
+    Some(CodeAction::new(
+        "Add the missing cases",
+        vec![TextEdit::insert_after(&at, format!("{separator}{text}"))],
+    ))
+}
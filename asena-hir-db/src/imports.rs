@@ -0,0 +1,150 @@
+//! The facts about the `use` declarations of the files, and the organize imports action, that
+//! sorts them, merges the duplicated ones, and removes the unused ones.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use asena_ast::{FunctionId, Use, Visibility};
+use asena_ast_db::vfs::VfsFile;
+use asena_ast_db::{db::find_module, def::DefWithId, package::HasDiagnostic, scope::ScopeData};
+use asena_ast_resolver::error::ResolutionError::UnusedImportWarning;
+use asena_leaf::ast::Located;
+use asena_report::{CodeAction, TextEdit, WithError};
+use asena_span::Loc;
+use itertools::Itertools;
+
+use crate::db::HirDatabase;
+
+/// Reports the `use` declarations of the file, that none of the references of the file refers
+/// to, as warnings, and returns them.
+///
+/// A reference refers to a module, if the definition is in the module, or if it's one of the
+/// definitions exported by the module, and not only a definition with the same name. The public `use` declarations and the ones of modules that weren't found are
+/// never reported, as they're re-exports and resolution errors, respectively.
+pub fn unused_imports(db: &dyn HirDatabase, file: VfsFile) -> Arc<Vec<Use>> {
+    let references = db.file_references(file);
+
+    let unused = db
+        .ast(file)
+        .declarations()
+        .into_iter()
+        .filter(|decl| decl.visibility() == Visibility::Private)
+        .filter_map(|decl| decl.as_use())
+        .filter(|decl| {
+            let Some(module) = find_module(db, &decl.to_fn_id().to_string()) else {
+                return false;
+            };
+            let path = db.lookup_intern_vfs_file(module).id.path;
+            let exports = exported_definitions(db, module);
+
+            !references.iter().any(|reference| {
+                let def = db.lookup_intern_def(reference.def);
+
+                def.defined_at.file.as_ref() == Some(&path) || exports.contains(&reference.def)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for decl in unused.iter() {
+        decl.clone()
+            .fail(UnusedImportWarning(decl.to_fn_id()))
+            .push(db);
+    }
+
+    Arc::new(unused)
+}
+
+/// Returns the definitions of the exports of the module, they're created like when the module is
+/// imported, so they're the same definitions the references of the importers are resolved to.
+fn exported_definitions(db: &dyn HirDatabase, module: VfsFile) -> HashSet<DefWithId> {
+    let mut scope = ScopeData::default();
+    scope.import_exports(db, module, None);
+
+    let constructors = scope.constructors.values().map(|variant| variant.def);
+
+    scope
+        .types
+        .values()
+        .chain(scope.functions.values())
+        .copied()
+        .chain(constructors)
+        .collect()
+}
+
+/// Returns the action that organizes the `use` declarations of the file, if they aren't already
+/// organized.
+///
+/// The modules are sorted by name, the duplicated ones are merged, keeping the `pub` if any of
+/// them has it, and the unused ones are removed. The existing declarations are rewritten in
+/// place, so the declarations that are already in the right place don't have edits.
+pub fn organize_imports(db: &dyn HirDatabase, file: VfsFile) -> Option<CodeAction> {
+    let source = db.source(file);
+    let unused = db.unused_imports(file);
+
+    let imports = db
+        .ast(file)
+        .declarations()
+        .into_iter()
+        .filter_map(|decl| Some((decl.as_use()?, decl.visibility())))
+        .collect::<Vec<_>>();
+
+    let organized = imports
+        .iter()
+        .filter(|(decl, _)| !unused.contains(decl))
+        .map(|(decl, visibility)| (decl.to_fn_id(), *visibility))
+        .sorted_by(|(lhs, _), (rhs, _)| lhs.as_str().cmp(rhs.as_str()))
+        .coalesce(|(lhs, lhs_visibility), (rhs, rhs_visibility)| {
+            if lhs != rhs {
+                return Err(((lhs, lhs_visibility), (rhs, rhs_visibility)));
+            }
+
+            match (lhs_visibility, rhs_visibility) {
+                (Visibility::Private, Visibility::Private) => Ok((lhs, Visibility::Private)),
+                _ => Ok((lhs, Visibility::Public)),
+            }
+        })
+        .map(|(name, visibility)| render_use(&name, visibility))
+        .collect::<Vec<_>>();
+
+    let mut edits = vec![];
+    for (nth, (decl, _)) in imports.iter().enumerate() {
+        let loc = decl.location().into_owned();
+        let current = source
+            .get(loc.range.start()..loc.range.end())
+            .unwrap_or_default();
+
+        match organized.get(nth) {
+            Some(text) if text == current => {}
+            Some(text) => edits.push(TextEdit::replace(loc, text.clone())),
+            None => edits.push(TextEdit::delete(with_line_break(&source, loc))),
+        }
+    }
+
+    if edits.is_empty() {
+        return None;
+    }
+
+    Some(CodeAction::new("Organize imports", edits))
+}
+
+fn render_use(name: &FunctionId, visibility: Visibility) -> String {
+    match visibility {
+        Visibility::Public => format!("pub use {name}"),
+        Visibility::Private => format!("use {name}"),
+    }
+}
+
+/// Extends the location to the line break after it, if there's one, so the removed declarations
+/// doesn't leave empty lines.
+fn with_line_break(source: &str, loc: Loc) -> Loc {
+    let end = loc.range.end();
+    let rest = source.get(end..).unwrap_or_default();
+
+    let length = match rest {
+        _ if rest.starts_with("\r\n") => 2,
+        _ if rest.starts_with('\n') => 1,
+        _ => 0,
+    };
+
+    Loc::new(loc.file.clone(), loc.range.start(), end + length)
+}
@@ -1,6 +1,11 @@
 #![feature(trait_upcasting)]
 
 pub mod db;
+pub mod exhaustiveness;
+pub mod imports;
 pub mod loceval;
 pub mod mbind;
+pub mod memory;
 pub mod rc;
+pub mod references;
+pub mod well_founded;
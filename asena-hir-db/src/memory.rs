@@ -0,0 +1,118 @@
+//! The approximate memory used by the database, it's used to track the effect of the interners,
+//! and when the long running sessions, like the language server, should evict the old entries.
+//!
+//! The bytes are the sizes of the entries of the tables, the heap data of the entries, like the
+//! vectors of the HIR nodes, isn't followed, so they're a lower bound of the real usage.
+
+use std::iter::Sum;
+use std::mem::size_of;
+use std::ops::Add;
+
+use asena_ast_db::db::InternVfsFileQuery;
+use asena_hir::attr::HirAttrData;
+use asena_hir::expr::HirExprData;
+use asena_hir::hir_type::HirTypeData;
+use asena_hir::interner::*;
+use asena_hir::pattern::HirPatternData;
+use asena_hir::stmt::HirStmtData;
+use asena_hir::top_level::HirTopLevelData;
+use asena_hir::value::HirValueData;
+use asena_interner::Symbol;
+use asena_leaf::ast::NodeArena;
+use salsa::Query;
+
+use crate::db::HirDatabase;
+
+/// The amount of entries of a table, and the approximate bytes of them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub count: usize,
+    pub bytes: usize,
+}
+
+/// The memory used by the database, by the kind of the tables.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// The interned strings, they're global, so they're shared by all the databases.
+    pub symbols: MemoryUsage,
+
+    /// The metadata of the nodes of the syntax trees in the [NodeArena] of the database, like the
+    /// scopes of the resolution.
+    pub trees: MemoryUsage,
+
+    /// The interned HIR nodes, by the name of their kind, like `expr` or `pattern`.
+    pub hir: Vec<(&'static str, MemoryUsage)>,
+
+    /// The bindings of the global scope, and of the scopes of the files.
+    pub scopes: MemoryUsage,
+}
+
+impl MemoryUsage {
+    pub fn new((count, bytes): (usize, usize)) -> Self {
+        Self { count, bytes }
+    }
+}
+
+impl Add for MemoryUsage {
+    type Output = MemoryUsage;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        MemoryUsage {
+            count: self.count + rhs.count,
+            bytes: self.bytes + rhs.bytes,
+        }
+    }
+}
+
+impl Sum for MemoryUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(MemoryUsage::default(), Add::add)
+    }
+}
+
+impl MemoryReport {
+    /// The memory used by all the interned HIR nodes.
+    pub fn hir_total(&self) -> MemoryUsage {
+        self.hir.iter().map(|(_, usage)| *usage).sum()
+    }
+
+    pub fn total(&self) -> MemoryUsage {
+        self.symbols + self.trees + self.hir_total() + self.scopes
+    }
+}
+
+/// Reports the approximate memory used by the database, see the [module](self) documentation.
+pub fn memory_usage(db: &dyn HirDatabase) -> MemoryReport {
+    macro_rules! interned {
+        ($name:literal, $query:expr, $data:ty) => {{
+            let count = $query.in_db(db).entries::<Vec<_>>().len();
+            let bytes = count * size_of::<$data>();
+
+            ($name, MemoryUsage { count, bytes })
+        }};
+    }
+
+    let hir = vec![
+        interned!("attr", InternAttrQuery, HirAttrData),
+        interned!("expr", InternExprQuery, HirExprData),
+        interned!("pattern", InternPatternQuery, HirPatternData),
+        interned!("stmt", InternStmtQuery, HirStmtData),
+        interned!("type", InternTypeQuery, HirTypeData),
+        interned!("value", InternValueQuery, HirValueData),
+        interned!("top_level", InternTopLevelQuery, HirTopLevelData),
+    ];
+
+    let files = InternVfsFileQuery.in_db(db).entries::<Vec<_>>();
+    let scopes = files
+        .iter()
+        .map(|entry| MemoryUsage::new(entry.key.read_scope().memory_usage()))
+        .sum::<MemoryUsage>()
+        + MemoryUsage::new(db.global_scope().read().unwrap().memory_usage());
+
+    MemoryReport {
+        symbols: MemoryUsage::new(Symbol::memory_usage()),
+        trees: MemoryUsage::new(db.node_arena().memory_usage()),
+        hir,
+        scopes,
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use asena_ast::{
+    visitor::new_walker, AsenaVisitor, ConstructorPat, GlobalPat, Infix, LeftSection, LocalExpr,
+    OperatorSection, RightSection,
+};
+use asena_ast_db::{def::DefWithId, vfs::VfsFile};
+use asena_ast_resolver::{
+    ExprResolutionKey, PatResolution, PatResolutionKey, TypeResolution, TypeResolutionKey,
+};
+use asena_leaf::ast::{Located, Walkable};
+use asena_span::Loc;
+
+use crate::db::HirDatabase;
+
+/// A resolved name in the source code, that points to the definition it refers to.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Reference {
+    pub def: DefWithId,
+    pub location: Loc,
+}
+
+#[derive(Default)]
+struct ReferenceCollector {
+    references: Vec<Reference>,
+}
+
+impl ReferenceCollector {
+    fn push(&mut self, def: DefWithId, node: &impl Located) {
+        self.references.push(Reference {
+            def,
+            location: node.location().into_owned(),
+        });
+    }
+}
+
+impl AsenaVisitor<()> for ReferenceCollector {
+    fn visit_local_expr(&mut self, value: LocalExpr) {
+        if let Some(def) = value.key(ExprResolutionKey).def() {
+            self.push(def, &value);
+        }
+
+        if let TypeResolution::Resolved(def) = *value.key(TypeResolutionKey) {
+            self.push(def, &value);
+        }
+    }
+
+    /// The operators are resolved like the functions, including the functions between
+    /// backticks, like ``a `f` b``.
+    fn visit_infix(&mut self, value: Infix) {
+        if let Some(def) = value.key(ExprResolutionKey).def() {
+            self.push(def, &value);
+        }
+    }
+
+    fn visit_left_section(&mut self, value: LeftSection) {
+        if let Some(def) = value.key(ExprResolutionKey).def() {
+            self.push(def, &value);
+        }
+    }
+
+    fn visit_right_section(&mut self, value: RightSection) {
+        if let Some(def) = value.key(ExprResolutionKey).def() {
+            self.push(def, &value);
+        }
+    }
+
+    fn visit_operator_section(&mut self, value: OperatorSection) {
+        if let Some(def) = value.key(ExprResolutionKey).def() {
+            self.push(def, &value);
+        }
+    }
+
+    fn visit_global_pat(&mut self, value: GlobalPat) {
+        if let PatResolution::Variant(def) = *value.key(PatResolutionKey) {
+            self.push(def, &value);
+        }
+    }
+
+    fn visit_constructor_pat(&mut self, value: ConstructorPat) {
+        if let PatResolution::Variant(def) = *value.key(PatResolutionKey) {
+            self.push(def, &value);
+        }
+    }
+}
+
+/// Collects all the resolved references of the file, it's the index used to find the usages of
+/// the definitions.
+pub fn file_references(db: &dyn HirDatabase, file: VfsFile) -> Arc<Vec<Reference>> {
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+
+    let mut collector = ReferenceCollector::default();
+    ast.walk(&mut new_walker(&mut collector));
+
+    Arc::new(collector.references)
+}
@@ -0,0 +1,201 @@
+//! The well-foundedness of the enums, the enums that can't have finite values, because every
+//! variant has a field of the enum itself, like `Cons : a -> Stream a -> Stream a` without a
+//! `Nil`, are reported. The enums that are at the left of an arrow in their own fields, like
+//! `In : (Fix -> Int32) -> Fix`, aren't strictly positive, and they're reported too, as they
+//! make the type theory inconsistent.
+
+use std::sync::Arc;
+
+use asena_ast::FunctionId;
+use asena_ast_db::{package::HasDiagnostic, vfs::VfsFile};
+use asena_ast_resolver::error::ResolutionError::{InfiniteEnumError, NonPositiveEnumError};
+use asena_hir::hir_type::data::{HirTypeArgument, HirTypeFunction};
+use asena_hir::hir_type::{HirType, HirTypeKind};
+use asena_hir::top_level::{HirTopLevelEnum, HirTopLevelKind};
+use asena_hir::Name;
+use asena_report::Diagnostic;
+use asena_span::Loc;
+
+use crate::db::HirDatabase;
+
+/// An enum that can't be type checked, with the field that makes it ill-formed.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct IllFormedEnum {
+    pub name: FunctionId,
+    pub field: Loc,
+    pub reason: IllFormedReason,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum IllFormedReason {
+    /// Every variant has a field of the enum itself, the field is the one of the first variant.
+    Infinite,
+
+    /// The enum is at the left of an arrow in the field.
+    NonPositive,
+}
+
+/// Reports the ill-formed enums of the file, as errors, and returns them in the order of their
+/// fields.
+pub fn ill_formed_enums(db: &dyn HirDatabase, file: VfsFile) -> Arc<Vec<IllFormedEnum>> {
+    let mut enums = db
+        .hir_file_defs(file)
+        .into_iter()
+        .filter_map(
+            |top_level| match db.lookup_intern_top_level(top_level).kind {
+                HirTopLevelKind::Enum(enum_decl) => Some(enum_decl),
+                _ => None,
+            },
+        )
+        .flat_map(|enum_decl| check_enum(db, &enum_decl))
+        .collect::<Vec<_>>();
+    enums.sort_by_key(|ill_formed| ill_formed.field.range.start());
+
+    for ill_formed in enums.iter() {
+        let error = match ill_formed.reason {
+            IllFormedReason::Infinite => InfiniteEnumError(ill_formed.name.clone()),
+            IllFormedReason::NonPositive => NonPositiveEnumError(ill_formed.name.clone()),
+        };
+
+        Diagnostic::of(ill_formed.field.clone(), error).push(db);
+    }
+
+    Arc::new(enums)
+}
+
+fn check_enum(db: &dyn HirDatabase, enum_decl: &HirTopLevelEnum) -> Vec<IllFormedEnum> {
+    let name = enum_decl.signature.name;
+    let fn_id = FunctionId(name);
+    let ill_formed = |field: HirType, reason| IllFormedEnum {
+        name: fn_id.clone(),
+        field: db.lookup_intern_type(field).span.original,
+        reason,
+    };
+
+    let variants = enum_decl
+        .variants
+        .values()
+        .map(|variant| fields(db, variant.variant_type))
+        .collect::<Vec<_>>();
+
+    let mut errors = variants
+        .iter()
+        .flatten()
+        .filter(|field| is_negative(db, **field, name))
+        .map(|field| ill_formed(*field, IllFormedReason::NonPositive))
+        .collect::<Vec<_>>();
+
+    // The variant is recursive if a field is the enum itself, and not a function that returns
+    // it, or another type that has it, like `List a`, that can be empty.
+    let recursive = variants
+        .iter()
+        .map(|fields| {
+            fields
+                .iter()
+                .find(|field| head(db, **field) == Some(name))
+                .copied()
+        })
+        .collect::<Option<Vec<_>>>();
+
+    let first = recursive.and_then(|recursive| {
+        recursive
+            .into_iter()
+            .min_by_key(|field| db.lookup_intern_type(*field).span.original.range.start())
+    });
+    if let Some(field) = first {
+        errors.push(ill_formed(field, IllFormedReason::Infinite));
+    }
+
+    errors
+}
+
+/// Returns the types of the fields of the variant, that are the parameters of its type, like
+/// `a` and `Stream a` in `a -> Stream a -> Stream a`.
+fn fields(db: &dyn HirDatabase, mut variant_type: HirType) -> Vec<HirType> {
+    let mut fields = vec![];
+    loop {
+        match db.lookup_intern_type(variant_type).kind {
+            HirTypeKind::Forall(forall) => variant_type = forall.value,
+            HirTypeKind::App(app) if matches!(app.callee, HirTypeFunction::Pi) => {
+                let [parameter, value] = app.arguments.as_slice() else {
+                    break;
+                };
+
+                fields.extend(argument_type(parameter));
+                match argument_type(value) {
+                    Some(value) => variant_type = value,
+                    None => break,
+                }
+            }
+            _ => break,
+        }
+    }
+
+    fields
+}
+
+fn argument_type(argument: &HirTypeArgument) -> Option<HirType> {
+    match argument {
+        HirTypeArgument::Error => None,
+        HirTypeArgument::Type(argument) | HirTypeArgument::Named(_, argument) => Some(*argument),
+    }
+}
+
+/// Returns the name of the type constructor that is applied by the type, like `List` in
+/// `List a`.
+fn head(db: &dyn HirDatabase, hir_type: HirType) -> Option<Name> {
+    match db.lookup_intern_type(hir_type).kind {
+        HirTypeKind::Name(name) => Some(name.name),
+        HirTypeKind::App(app) => match app.callee {
+            HirTypeFunction::Type(callee) => head(db, callee),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn mentions(db: &dyn HirDatabase, hir_type: HirType, name: Name) -> bool {
+    match db.lookup_intern_type(hir_type).kind {
+        HirTypeKind::Name(type_name) => type_name.name == name,
+        HirTypeKind::Forall(forall) => mentions(db, forall.value, name),
+        HirTypeKind::App(app) => {
+            let callee = match app.callee {
+                HirTypeFunction::Type(callee) => mentions(db, callee, name),
+                _ => false,
+            };
+
+            callee
+                || app
+                    .arguments
+                    .iter()
+                    .filter_map(argument_type)
+                    .any(|argument| mentions(db, argument, name))
+        }
+        _ => false,
+    }
+}
+
+/// Returns if the name is at the left of an arrow anywhere in the type, like `Fix` in
+/// `List (Fix -> Int32)`.
+fn is_negative(db: &dyn HirDatabase, hir_type: HirType, name: Name) -> bool {
+    match db.lookup_intern_type(hir_type).kind {
+        HirTypeKind::Forall(forall) => is_negative(db, forall.value, name),
+        HirTypeKind::App(app) => {
+            let mut arguments = app.arguments.iter().filter_map(argument_type);
+            match app.callee {
+                HirTypeFunction::Pi => {
+                    let parameter = arguments.next();
+
+                    parameter.map_or(false, |parameter| mentions(db, parameter, name))
+                        || arguments.any(|value| is_negative(db, value, name))
+                }
+                HirTypeFunction::Type(callee) => {
+                    is_negative(db, callee, name)
+                        || arguments.any(|argument| is_negative(db, argument, name))
+                }
+                _ => arguments.any(|argument| is_negative(db, argument, name)),
+            }
+        }
+        _ => false,
+    }
+}
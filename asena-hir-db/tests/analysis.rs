@@ -0,0 +1,332 @@
+//! Tests for the analyses over the high-level intermediate representation, every test analyses
+//! the modules in the memory, and checks the results and the diagnostics of them.
+
+use asena_ast::FunctionId;
+use asena_ast_db::code_action::{apply_code_actions, code_actions};
+use asena_ast_db::{db::*, fixture::*, vfs::VfsFile};
+use asena_ast_lowering::db::{AstLowerrer, AstLowerrerStorage};
+use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
+use asena_hir::{expr::HirExprData, interner::HirStorage};
+use asena_hir_db::db::{HirDatabase, HirDatabaseStorage};
+use asena_hir_db::well_founded::IllFormedReason;
+use asena_hir_db::{imports::organize_imports, memory::memory_usage};
+use asena_prec::{db::PrecStorage, PrecDatabase};
+
+#[salsa::database(
+    PrecStorage,
+    AstDatabaseStorage,
+    HirDatabaseStorage,
+    AstLowerrerStorage,
+    AstResolverStorage,
+    HirStorage
+)]
+#[derive(Default)]
+struct TestDatabase {
+    storage: salsa::Storage<TestDatabase>,
+}
+
+impl salsa::Database for TestDatabase {}
+
+/// The messages of the diagnostics of the file, that contains the text.
+fn messages_containing(db: &TestDatabase, file: VfsFile, text: &str) -> Vec<String> {
+    db.diagnostics(file)
+        .iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .filter(|message| message.contains(text))
+        .collect()
+}
+
+#[test]
+fn organize_imports_works() {
+    let db = TestDatabase::default();
+    let modules = [
+        ("Math", "pub Double (x: Int32) : Int32 { x + x }"),
+        ("Text", "pub Twice (x: Int32) : Int32 { x * 2 }"),
+        ("Test", "use Text\nuse Math\nuse Math\nMain { Double 21 }"),
+    ];
+    let (_, files) = memory_package(&db, &modules);
+
+    let action = organize_imports(&db, files[2]).unwrap();
+    let source = apply_code_actions(&db, files[2], &[action]);
+    assert_eq!(source, "use Math\nMain { Double 21 }");
+}
+
+#[test]
+fn unused_imports_works() {
+    let db = TestDatabase::default();
+    let modules = [
+        ("Math", "pub Double (x: Int32) : Int32 { x + x }"),
+        ("Test", "use Math\nMain (Double: Int32) : Int32 { Double }"),
+    ];
+    let (_, files) = memory_package(&db, &modules);
+
+    // The parameter `Double` has the same name of the exported one, but it's another definition.
+    let unused = db.unused_imports(files[1]);
+    assert_eq!(unused.len(), 1);
+    assert_eq!(
+        messages_containing(&db, files[1], "never used"),
+        vec!["the module `Math` is never used"]
+    );
+}
+
+#[test]
+fn missing_cases_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "enum Maybe (a: Set) {\n",
+        "  Nothing : Maybe a,\n",
+        "  Just : a -> Maybe a,\n",
+        "}\n",
+        "\n",
+        "IsJust (value: Maybe a) : Bool {\n",
+        "  match value {\n",
+        "    Nothing => false,\n",
+        "  }\n",
+        "}",
+    );
+    let file = memory_file(&db, source);
+
+    let matches = db.non_exhaustive_matches(file);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].missing, vec![(FunctionId::new("Just"), 1)]);
+
+    let source = apply_code_actions(&db, file, &code_actions(&db, file));
+    assert!(source.contains("    Nothing => false,\n    Just _ => ?todo,\n  }"));
+}
+
+#[test]
+fn duplicate_cases_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "enum Maybe (a: Set) {\n",
+        "  Nothing : Maybe a,\n",
+        "  Just : a -> Maybe a,\n",
+        "}\n",
+        "\n",
+        "IsJust (value: Maybe a) : Bool {\n",
+        "  match value {\n",
+        "    Nothing => false,\n",
+        "    Just _ => true,\n",
+        "    Just  _ => false,\n",
+        "  }\n",
+        "}",
+    );
+    let file = memory_file(&db, source);
+
+    let duplicates = db.duplicate_cases(file);
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(
+        &source[duplicates[0].range.start()..duplicates[0].range.end()],
+        "Just  _"
+    );
+}
+
+#[test]
+fn missing_integers_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "Digit (n: UInt8) : Bool {\n",
+        "  match n {\n",
+        "    0u8 => false,\n",
+        "    1u8..9u8 => true,\n",
+        "    20u8..255u8 => false,\n",
+        "  }\n",
+        "}",
+    );
+    let file = memory_file(&db, source);
+
+    db.non_exhaustive_matches(file);
+
+    assert_eq!(
+        messages_containing(&db, file, "integers"),
+        vec!["the match doesn't cover the integers: `10..19`"]
+    );
+}
+
+#[test]
+fn missing_lengths_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "Head (xs: List Int32) : Int32 {\n",
+        "  match xs {\n",
+        "    [] => 0,\n",
+        "    [x] => x,\n",
+        "    [x, y, z] => x,\n",
+        "  }\n",
+        "}\n",
+        "\n",
+        "Last (n: Int32) : Int32 {\n",
+        "  match [1, 2, 3] {\n",
+        "    [x] => x,\n",
+        "    [x, .., y] => y,\n",
+        "  }\n",
+        "}\n",
+        "\n",
+        "Second (n: Int32) : Int32 {\n",
+        "  match [1, 2] {\n",
+        "    [x] => x,\n",
+        "    [x, y, z] => z,\n",
+        "  }\n",
+        "}",
+    );
+    let file = memory_file(&db, source);
+
+    db.non_exhaustive_matches(file);
+
+    let mut messages = messages_containing(&db, file, "lengths");
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            "the match doesn't cover the lists with the lengths: `2`",
+            "the match doesn't cover the lists with the lengths: `2`, `4..`",
+        ]
+    );
+}
+
+#[test]
+fn ill_formed_enums_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "enum Stream (a: Set) {\n",
+        "  More : a -> Stream a -> Stream a,\n",
+        "}\n",
+        "\n",
+        "enum Fix {\n",
+        "  In : (Fix -> Int32) -> Fix,\n",
+        "}\n",
+        "\n",
+        "enum List (a: Set) {\n",
+        "  Nil : List a,\n",
+        "  Push : a -> List a -> List a,\n",
+        "}",
+    );
+    let file = memory_file(&db, source);
+
+    let enums = db.ill_formed_enums(file);
+    let enums = enums
+        .iter()
+        .map(|ill_formed| {
+            let text = &source[ill_formed.field.range.start()..ill_formed.field.range.end()];
+
+            (ill_formed.name.to_string(), text, ill_formed.reason)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        enums,
+        vec![
+            ("Stream".to_string(), "Stream a", IllFormedReason::Infinite),
+            (
+                "Fix".to_string(),
+                "Fix -> Int32",
+                IllFormedReason::NonPositive
+            ),
+        ]
+    );
+}
+
+#[test]
+fn dead_code_works() {
+    let db = TestDatabase::default();
+    let source = concat!(
+        "Live (x: Int32) : Int32 { x + x }\n",
+        "Dead (x: Int32) : Int32 { x * x }\n",
+        "Main { Live 21 }",
+    );
+    let (local_pkg, _) = memory_package(&db, &[("Test", source)]);
+
+    let unused = db
+        .dead_code(local_pkg)
+        .iter()
+        .map(|definition| definition.name.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(unused, vec!["Dead".to_string()]);
+}
+
+/// Returns the names of the unused definitions of the modules, with the names of their modules.
+fn dead_code_of(modules: &[(&str, &str)]) -> Vec<(String, String)> {
+    let db = TestDatabase::default();
+    let (local_pkg, _) = memory_package(&db, modules);
+
+    let mut unused = db
+        .dead_code(local_pkg)
+        .iter()
+        .map(|definition| {
+            let module = db.lookup_intern_vfs_file(definition.file).name;
+
+            (module, definition.name.to_string())
+        })
+        .collect::<Vec<_>>();
+    unused.sort();
+    unused
+}
+
+#[test]
+fn dead_code_operators_works() {
+    let plus = "Plus (x: Int32) (y: Int32) : Int32 { x + y }\n";
+
+    // The functions used only as operators, or in the sections, are reachable.
+    for main in [
+        "Main { 1 `Plus` 2 }",
+        "Main { (1 `Plus`) 2 }",
+        "Main { (`Plus` 2) 1 }",
+        "Main { (`Plus`) 1 2 }",
+    ] {
+        assert!(dead_code_of(&[("Test", &format!("{plus}{main}"))]).is_empty());
+    }
+
+    let unused = dead_code_of(&[("Test", &format!("{plus}Main {{ 1 + 2 }}"))]);
+    assert_eq!(unused, vec![("Test".to_string(), "Plus".to_string())]);
+}
+
+#[test]
+fn dead_code_backticks_works() {
+    // The backticks are lexed as an operator, and the chain is reordered by the precedence, so
+    // `Max` is in a node created by the reordering.
+    let source = concat!(
+        "Max (x: Int32) (y: Int32) : Int32 { if x > y then x else y }\n",
+        "Main { 1 + 2 `Max` 3 * 4 }",
+    );
+
+    assert!(dead_code_of(&[("Test", source)]).is_empty());
+}
+
+#[test]
+fn dead_code_same_names_works() {
+    // The `Double` of `Math` is used, but the one of `Test` isn't, even if they've the same name.
+    let modules = [
+        ("Math", "pub Double (x: Int32) : Int32 { x + x }"),
+        (
+            "Test",
+            "Double (x: Int32) : Int32 { x * 2 }\nMain { Math.Double 21 }",
+        ),
+    ];
+
+    let unused = dead_code_of(&modules);
+    assert_eq!(unused, vec![("Test".to_string(), "Double".to_string())]);
+}
+
+#[test]
+fn memory_usage_works() {
+    let db = TestDatabase::default();
+    let file = memory_file(&db, "id(x) { x }\nMain { id(1) }");
+
+    let before = memory_usage(&db);
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+    db.hir_file(ast.into());
+
+    let after = memory_usage(&db);
+    let (_, exprs) = after.hir.iter().find(|(name, _)| *name == "expr").unwrap();
+
+    assert!(exprs.count > 0);
+    assert_eq!(
+        exprs.bytes,
+        exprs.count * std::mem::size_of::<HirExprData>()
+    );
+    assert!(after.hir_total().count > before.hir_total().count);
+    assert!(after.symbols.count >= before.symbols.count);
+}
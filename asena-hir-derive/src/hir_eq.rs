@@ -0,0 +1,97 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident};
+
+pub fn expand_derive_hir_eq(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+
+    let body = match input.data {
+        Data::Struct(data) => {
+            let (lhs, lhs_bindings) = bindings(&data.fields, "lhs");
+            let (rhs, rhs_bindings) = bindings(&data.fields, "rhs");
+            let comparison = comparison(&lhs_bindings, &rhs_bindings);
+
+            quote! {
+                let Self #lhs = self;
+                let Self #rhs = other;
+
+                #comparison
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                let (lhs, lhs_bindings) = bindings(&variant.fields, "lhs");
+                let (rhs, rhs_bindings) = bindings(&variant.fields, "rhs");
+                let comparison = comparison(&lhs_bindings, &rhs_bindings);
+
+                quote! {
+                    (Self::#variant_name #lhs, Self::#variant_name #rhs) => #comparison,
+                }
+            });
+
+            quote! {
+                #[allow(unreachable_patterns)]
+                match (self, other) {
+                    #(#arms)*
+                    _ => false,
+                }
+            }
+        }
+        Data::Union(_) => {
+            name.span()
+                .unwrap()
+                .error("`HirEq` can't be derived for unions");
+
+            return TokenStream::new();
+        }
+    };
+
+    TokenStream::from(quote! {
+        impl crate::eq::HirEq for #name {
+            #[allow(unused_variables)]
+            fn hir_eq(&self, other: &Self, db: &dyn crate::interner::HirInterner) -> bool {
+                #body
+            }
+        }
+    })
+}
+
+/// Creates the destructuring pattern of the fields, and the names of the bindings.
+fn bindings(fields: &Fields, prefix: &str) -> (proc_macro2::TokenStream, Vec<Ident>) {
+    match fields {
+        Fields::Named(fields) => {
+            let names = fields
+                .named
+                .iter()
+                .map(|field| field.ident.clone().unwrap())
+                .collect::<Vec<_>>();
+            let bindings = names
+                .iter()
+                .map(|name| format_ident!("{prefix}_{name}"))
+                .collect::<Vec<_>>();
+
+            (quote!({ #(#names: #bindings),* }), bindings)
+        }
+        Fields::Unnamed(fields) => {
+            let bindings = (0..fields.unnamed.len())
+                .map(|index| Ident::new(&format!("{prefix}_{index}"), Span::call_site()))
+                .collect::<Vec<_>>();
+
+            (quote!(( #(#bindings),* )), bindings)
+        }
+        Fields::Unit => (quote!(), vec![]),
+    }
+}
+
+fn comparison(lhs: &[Ident], rhs: &[Ident]) -> proc_macro2::TokenStream {
+    if lhs.is_empty() {
+        return quote!(true);
+    }
+
+    quote! {
+        #(crate::eq::HirEq::hir_eq(#lhs, #rhs, db))&&*
+    }
+}
@@ -5,6 +5,15 @@ use proc_macro2::Span;
 use quote::quote;
 use syn::{parse_quote, token::Pub, Field, FieldMutability, Fields, Ident, Token, Visibility};
 
+mod hir_eq;
+
+/// Derives the `HirEq` trait, that compares the nodes structurally, looking up the ids in the
+/// database, and ignoring the locations.
+#[proc_macro_derive(HirEq)]
+pub fn derive_hir_eq(input: TokenStream) -> TokenStream {
+    hir_eq::expand_derive_hir_eq(input)
+}
+
 #[proc_macro_attribute]
 #[allow(clippy::redundant_clone)]
 pub fn hir_node(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -79,13 +88,60 @@ pub fn hir_struct(_args: TokenStream, input: TokenStream) -> TokenStream {
 
     input.ident = data_name.clone();
 
+    // The ids are printed like `Expr#42`, without the `Hir` prefix
+    let pretty_name = name.to_string().replace("Hir", "");
+
+    // The interner's functions are named after the node, like `lookup_intern_top_level`
+    let snake_case = pretty_name
+        .chars()
+        .enumerate()
+        .flat_map(|(i, char)| {
+            if char.is_uppercase() && i > 0 {
+                vec!['_', char]
+            } else {
+                vec![char]
+            }
+        })
+        .collect::<String>()
+        .to_lowercase();
+    let lookup_fn = Ident::new(&format!("lookup_intern_{snake_case}"), Span::call_site());
+
     TokenStream::from(quote! {
-        #[derive(Default, Clone, Debug, Hash, PartialEq, Eq)]
+        #[derive(Default, Clone, Debug, Hash, PartialEq, Eq, asena_hir_derive::HirEq)]
         #input
 
-        #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+        #[derive(Copy, Clone, Hash, PartialEq, Eq)]
         pub struct #name(salsa::InternId);
 
+        impl #name {
+            /// The index of the node in the interner's arena.
+            pub fn index(&self) -> u32 {
+                self.0.as_u32()
+            }
+        }
+
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}#{}", #pretty_name, self.index())
+            }
+        }
+
+        impl crate::eq::HirEq for #name {
+            fn hir_eq(&self, other: &Self, db: &dyn crate::interner::HirInterner) -> bool {
+                if self == other {
+                    return true;
+                }
+
+                crate::eq::HirEq::hir_eq(&db.#lookup_fn(*self), &db.#lookup_fn(*other), db)
+            }
+        }
+
+        impl std::fmt::Debug for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(self, f)
+            }
+        }
+
         impl salsa::InternKey for #name {
             fn from_intern_id(id: salsa::InternId) -> Self {
                 Self(id)
@@ -0,0 +1,38 @@
+use thiserror::Error;
+
+use crate::value::Value;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum EvalError {
+    #[error("could not evaluate a node with errors")]
+    ErrorNode,
+
+    #[error("could not find the value name: `{0}`")]
+    UnboundName(String),
+
+    #[error("{0} can't be evaluated yet")]
+    Unsupported(&'static str),
+
+    #[error("invalid operands for `{0}`")]
+    InvalidOperands(&'static str),
+
+    #[error("division by zero")]
+    DivisionByZero,
+
+    #[error("`{0}` isn't a function")]
+    NotAFunction(Value),
+
+    #[error("the pattern doesn't match the value")]
+    RefutedPattern,
+
+    #[error("no case matches the value `{0}`")]
+    NonExhaustiveMatch(Value),
+
+    #[error("no declaration of `{0}` matches the arguments")]
+    NoMatchingDeclaration(String),
+
+    #[error("reached the hole `?{0}`, it should be filled in")]
+    ReachedHole(String),
+}
+
+pub type Result<T, E = EvalError> = std::result::Result<T, E>;
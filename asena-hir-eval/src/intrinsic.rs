@@ -0,0 +1,127 @@
+//! The implementations of the intrinsics, they are called with all the arguments of their
+//! arities, the partial application is handled by the interpreter.
+
+use std::{
+    cmp::Ordering,
+    io::{BufRead, Write},
+};
+
+use asena_hir::{
+    intrinsic::HirIntrinsic,
+    literal::{HirISign, HirISize},
+};
+
+use crate::{EvalError::*, Result, Value};
+
+pub fn eval(intrinsic: HirIntrinsic, arguments: Vec<Value>) -> Result<Value> {
+    use HirIntrinsic as I;
+
+    match (intrinsic, arguments.as_slice()) {
+        (I::Add | I::Sub | I::Mul | I::Div, [lhs, rhs]) => arithmetic(intrinsic, lhs, rhs),
+        (I::Neg, [Value::Int(value, size, sign)]) => Ok(Value::Int(-value, *size, *sign)),
+        (I::Neg, [Value::Float(value, size)]) => Ok(Value::Float(-value, *size)),
+        (I::Eq, [lhs, rhs]) => Ok(boolean(lhs == rhs)),
+        (I::Neq, [lhs, rhs]) => Ok(boolean(lhs != rhs)),
+        (I::Lt | I::Le | I::Gt | I::Ge, [lhs, rhs]) => {
+            let ordering = compare(lhs, rhs).ok_or(InvalidOperands(intrinsic.name()))?;
+
+            Ok(boolean(match intrinsic {
+                I::Lt => ordering.is_lt(),
+                I::Le => ordering.is_le(),
+                I::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        (I::And, [Value::Int(lhs, HirISize::U1, _), Value::Int(rhs, HirISize::U1, _)]) => {
+            Ok(boolean(*lhs != 0 && *rhs != 0))
+        }
+        (I::Or, [Value::Int(lhs, HirISize::U1, _), Value::Int(rhs, HirISize::U1, _)]) => {
+            Ok(boolean(*lhs != 0 || *rhs != 0))
+        }
+        (I::Concat, [Value::String(lhs), Value::String(rhs)]) => {
+            Ok(Value::String(format!("{lhs}{rhs}")))
+        }
+        (I::Length, [Value::String(value)]) => {
+            let length = value.chars().count() as i128;
+
+            Ok(Value::Int(length, HirISize::USize, HirISign::Unsigned))
+        }
+        (I::Show, [value]) => Ok(Value::String(value.to_string())),
+        (I::Print | I::Println, [value]) => {
+            // The strings are printed without the quotes.
+            let text = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+
+            let mut stdout = std::io::stdout();
+            let result = match intrinsic {
+                I::Println => writeln!(stdout, "{text}"),
+                _ => write!(stdout, "{text}").and_then(|_| stdout.flush()),
+            };
+            result.map_err(|_| Unsupported("writing to the standard output"))?;
+
+            Ok(Value::Unit)
+        }
+        (I::ReadLine, [Value::Unit]) => {
+            let mut line = String::new();
+            std::io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .map_err(|_| Unsupported("reading from the standard input"))?;
+
+            Ok(Value::String(line.trim_end_matches(['\r', '\n']).into()))
+        }
+        _ => Err(InvalidOperands(intrinsic.name())),
+    }
+}
+
+fn boolean(value: bool) -> Value {
+    if value {
+        Value::TRUE
+    } else {
+        Value::FALSE
+    }
+}
+
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Int(lhs, ..), Value::Int(rhs, ..)) => Some(lhs.cmp(rhs)),
+        (Value::Float(lhs, _), Value::Float(rhs, _)) => lhs.partial_cmp(rhs),
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Char(lhs), Value::Char(rhs)) => Some(lhs.cmp(rhs)),
+        _ => None,
+    }
+}
+
+fn arithmetic(intrinsic: HirIntrinsic, lhs: &Value, rhs: &Value) -> Result<Value> {
+    use HirIntrinsic as I;
+
+    match (lhs, rhs) {
+        (Value::Int(..), Value::Int(0, ..)) if intrinsic == I::Div => Err(DivisionByZero),
+        (Value::Int(lhs, size, sign), Value::Int(rhs, _, _)) => {
+            let value = match intrinsic {
+                I::Add => lhs.wrapping_add(*rhs),
+                I::Sub => lhs.wrapping_sub(*rhs),
+                I::Mul => lhs.wrapping_mul(*rhs),
+                _ => lhs / rhs,
+            };
+
+            Ok(Value::Int(value, *size, *sign))
+        }
+        (Value::Float(lhs, size), Value::Float(rhs, _)) => {
+            let value = match intrinsic {
+                I::Add => lhs + rhs,
+                I::Sub => lhs - rhs,
+                I::Mul => lhs * rhs,
+                _ => lhs / rhs,
+            };
+
+            Ok(Value::Float(value, *size))
+        }
+        (Value::String(lhs), Value::String(rhs)) if intrinsic == I::Add => {
+            Ok(Value::String(format!("{lhs}{rhs}")))
+        }
+        _ => Err(InvalidOperands(intrinsic.name())),
+    }
+}
@@ -0,0 +1,464 @@
+//! A tree-walking interpreter of the HIR, it evaluates the lowered programs directly, without
+//! the code generation, so the REPL can run the expressions, and the tests can assert on the
+//! results of the lowering.
+//!
+//! It handles the pure subset of the language: literals, the intrinsics, blocks, lambdas,
+//! matches, the variants of the enums, and the application of top-level functions.
+
+use asena_hir::{
+    expr::{
+        data::{HirBranch, HirCallee, HirMatchCase},
+        HirExpr, HirExprCall, HirExprKind, HirExprMatch,
+    },
+    hir_type::{
+        data::{HirTypeArgument, HirTypeFunction},
+        HirType, HirTypeKind,
+    },
+    interner::HirInterner,
+    intrinsic::HirIntrinsic,
+    literal::{HirISign, HirISize, HirLiteral},
+    pattern::{HirPattern, HirPatternKind},
+    stmt::{HirStmt, HirStmtKind},
+    top_level::{HirBindingGroup, HirTopLevel, HirTopLevelKind},
+    value::{instr::HirInstr, HirValue, HirValueKind},
+    Name,
+};
+use im::HashMap;
+
+pub use error::*;
+pub use value::*;
+
+use EvalError::*;
+
+pub mod error;
+pub mod intrinsic;
+pub mod value;
+
+/// The local values in scope, keyed by the interned names of the HIR.
+pub type Env = HashMap<Name, Value>;
+
+pub struct Interpreter<'a> {
+    db: &'a dyn HirInterner,
+    globals: HashMap<Name, HirBindingGroup>,
+    constructors: HashMap<Name, Constructor>,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new<I>(db: &'a dyn HirInterner, declarations: I) -> Self
+    where
+        I: IntoIterator<Item = HirTopLevel>,
+    {
+        let mut globals = HashMap::new();
+        let mut constructors = HashMap::new();
+        for top_level in declarations {
+            match db.lookup_intern_top_level(top_level).kind {
+                HirTopLevelKind::BindingGroup(group) => {
+                    globals.insert(group.signature.name, group);
+                }
+                HirTopLevelKind::Enum(enum_decl) => {
+                    let enum_name = enum_decl.signature.name.to_string();
+
+                    for (name, variant) in enum_decl.variants {
+                        let constructor = Constructor {
+                            enum_name: enum_name.clone(),
+                            name: name.to_string(),
+                            arity: arity(db, variant.variant_type),
+                        };
+
+                        constructors.insert(name, constructor);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            db,
+            globals,
+            constructors,
+        }
+    }
+
+    /// Evaluates the top-level binding with the given name.
+    pub fn eval_global(&self, name: &str) -> Result<Value> {
+        let name = Name::intern(name);
+
+        self.reference(&Env::new(), name)
+    }
+
+    pub fn value(&self, env: &Env, value: HirValue) -> Result<Value> {
+        match self.db.lookup_intern_value(value).kind {
+            HirValueKind::Error => Err(ErrorNode),
+            HirValueKind::Unit => Ok(Value::Unit),
+            HirValueKind::Expr(expr) => self.expr(env, expr.0),
+            HirValueKind::Block(block) => {
+                let mut env = env.clone();
+                let mut last = None;
+                for stmt in block.instructions {
+                    last = self.stmt(&mut env, stmt)?;
+                }
+
+                // The last statement is the value of the block, unless the value comes after the
+                // statements, like the rest of the desugared do blocks.
+                match last {
+                    Some((value, result)) if value == block.value => Ok(result),
+                    _ => self.value(&env, block.value),
+                }
+            }
+            HirValueKind::Instr(HirInstr::Block(block)) => {
+                let mut env = env.clone();
+                for instr in block.instructions {
+                    match self.db.lookup_intern_value(instr).kind {
+                        HirValueKind::Instr(HirInstr::Let(name, value)) => {
+                            let value = self.value(&env, value)?;
+                            env.insert(name, value);
+                        }
+                        _ => {
+                            self.value(&env, instr)?;
+                        }
+                    }
+                }
+
+                self.value(&env, block.value)
+            }
+            HirValueKind::Instr(HirInstr::Variable(name)) => self.reference(env, name),
+            HirValueKind::Instr(_) => Err(Unsupported("instructions")),
+            HirValueKind::Monad(_) => Err(Unsupported("monads")),
+        }
+    }
+
+    pub fn expr(&self, env: &Env, expr: HirExpr) -> Result<Value> {
+        match self.db.lookup_intern_expr(expr).kind {
+            HirExprKind::Error => Err(ErrorNode),
+            HirExprKind::Unit => Ok(Value::Unit),
+            HirExprKind::Group(group) => self.value(env, group.value),
+            HirExprKind::Literal(literal) => literal_value(&literal.0),
+            HirExprKind::Reference(reference) => self.reference(env, reference.name),
+            HirExprKind::Ann(ann) => self.value(env, ann.value),
+            HirExprKind::Call(call) => self.call(env, call),
+            HirExprKind::Match(match_expr) => self.match_expr(env, match_expr),
+            HirExprKind::Lam(lam) => Ok(Value::Closure(lam.parameters, lam.value, env.clone())),
+            HirExprKind::This => Err(Unsupported("self")),
+            HirExprKind::Help(help) => match help.hole {
+                Some(hole) => Err(ReachedHole(hole.to_string())),
+                None => Err(Unsupported("help expressions")),
+            },
+            HirExprKind::Array(_) => Err(Unsupported("arrays")),
+        }
+    }
+
+    /// Evaluates the statement, and returns the value of it, with its result, if it's a value.
+    fn stmt(&self, env: &mut Env, stmt: HirStmt) -> Result<Option<(HirValue, Value)>> {
+        match self.db.lookup_intern_stmt(stmt).kind {
+            HirStmtKind::Error => Err(ErrorNode),
+            HirStmtKind::Let(stmt) => {
+                let value = self.value(env, stmt.value)?;
+                if !self.bind(env, stmt.pattern, value)? {
+                    return Err(RefutedPattern);
+                }
+
+                Ok(None)
+            }
+            HirStmtKind::Value(stmt) => Ok(Some((stmt.0, self.value(env, stmt.0)?))),
+            HirStmtKind::Ask(_) => Err(Unsupported("ask statements")),
+            HirStmtKind::Return(_) => Err(Unsupported("return statements")),
+        }
+    }
+
+    fn reference(&self, env: &Env, name: Name) -> Result<Value> {
+        if let Some(value) = env.get(&name) {
+            return Ok(value.clone());
+        }
+
+        if let Some(group) = self.globals.get(&name) {
+            return self.apply(Value::Function(group.clone(), vec![]), vec![]);
+        }
+
+        match HirIntrinsic::from_name(&name) {
+            Some(intrinsic) => self.apply(Value::Intrinsic(intrinsic, vec![]), vec![]),
+            None => Err(UnboundName(name.to_string())),
+        }
+    }
+
+    fn call(&self, env: &Env, call: HirExprCall) -> Result<Value> {
+        let mut arguments = call
+            .arguments
+            .iter()
+            .map(|argument| self.value(env, *argument))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The DSL block is passed as the last argument, like a lambda.
+        if let Some(dsl) = call.as_dsl {
+            arguments.push(Value::Closure(dsl.parameters, dsl.value, env.clone()));
+        }
+
+        match call.callee {
+            HirCallee::Value(value) | HirCallee::Method(value) => {
+                let function = self.value(env, value)?;
+
+                self.apply(function, arguments)
+            }
+            HirCallee::Intrinsic(intrinsic) => {
+                self.apply(Value::Intrinsic(intrinsic, vec![]), arguments)
+            }
+            // The do block is desugared into the calls of `bind` and `pure` by the lowering, so
+            // it's run by evaluating its block.
+            HirCallee::Do => match arguments.pop() {
+                Some(Value::Closure(_, value, env)) => self.value(&env, value),
+                _ => Err(Unsupported("do notation without a block")),
+            },
+            HirCallee::Constructor(name) => match self.constructors.get(&name) {
+                Some(constructor) => {
+                    self.apply(Value::Constructor(constructor.clone(), vec![]), arguments)
+                }
+                None => Err(UnboundName(name.to_string())),
+            },
+        }
+    }
+
+    /// Evaluates the first case that matches the scrutinee, in the order they're written.
+    fn match_expr(&self, env: &Env, match_expr: HirExprMatch) -> Result<Value> {
+        let scrutinee = self.value(env, match_expr.scrutinee)?;
+
+        for HirMatchCase { pattern, value } in match_expr.cases.iter() {
+            let mut env = env.clone();
+            if !self.bind(&mut env, *pattern, scrutinee.clone())? {
+                continue;
+            }
+
+            return match value {
+                HirBranch::Error => Err(ErrorNode),
+                HirBranch::Expr(value) | HirBranch::Block(value) => self.value(&env, *value),
+            };
+        }
+
+        Err(NonExhaustiveMatch(scrutinee))
+    }
+
+    /// Applies the arguments to the function, if there are less arguments than the arity of the
+    /// function, it's partially applied.
+    fn apply(&self, function: Value, mut arguments: Vec<Value>) -> Result<Value> {
+        match function {
+            Value::Function(group, mut applied) => {
+                applied.append(&mut arguments);
+
+                let arity = group
+                    .declarations
+                    .iter()
+                    .map(|declaration| declaration.patterns.len())
+                    .max()
+                    .unwrap_or_default();
+
+                if applied.len() < arity {
+                    return Ok(Value::Function(group, applied));
+                }
+
+                let rest = applied.split_off(arity);
+                let value = self.invoke(&group, applied)?;
+
+                self.apply_rest(value, rest)
+            }
+            Value::Intrinsic(intrinsic, mut applied) => {
+                applied.append(&mut arguments);
+
+                if applied.len() < intrinsic.arity() {
+                    return Ok(Value::Intrinsic(intrinsic, applied));
+                }
+
+                let rest = applied.split_off(intrinsic.arity());
+                let value = self.apply_intrinsic(intrinsic, applied)?;
+
+                self.apply_rest(value, rest)
+            }
+            Value::Closure(mut parameters, value, mut env) => {
+                let rest = arguments.split_off(arguments.len().min(parameters.len()));
+                let remaining = parameters.split_off(arguments.len());
+                env.extend(parameters.into_iter().zip(arguments));
+
+                if !remaining.is_empty() {
+                    return Ok(Value::Closure(remaining, value, env));
+                }
+
+                let value = self.value(&env, value)?;
+
+                self.apply_rest(value, rest)
+            }
+            Value::Constructor(constructor, mut applied) if applied.len() < constructor.arity => {
+                applied.append(&mut arguments);
+
+                let rest = applied.split_off(applied.len().min(constructor.arity));
+
+                self.apply_rest(Value::Constructor(constructor, applied), rest)
+            }
+            _ if arguments.is_empty() => Ok(function),
+            _ => Err(NotAFunction(function)),
+        }
+    }
+
+    /// Evaluates the intrinsic, the ones that take functions, like `|>`, are applied here, as
+    /// they need the interpreter, the others are evaluated by [intrinsic::eval].
+    fn apply_intrinsic(&self, intrinsic: HirIntrinsic, arguments: Vec<Value>) -> Result<Value> {
+        use HirIntrinsic as I;
+
+        match intrinsic {
+            I::Pipe => {
+                let [argument, function] = <[Value; 2]>::try_from(arguments)
+                    .map_err(|_| InvalidOperands(intrinsic.name()))?;
+
+                self.apply(function, vec![argument])
+            }
+            I::Compose | I::ComposeLeft | I::ComposeRight => {
+                let [lhs, rhs, argument] = <[Value; 3]>::try_from(arguments)
+                    .map_err(|_| InvalidOperands(intrinsic.name()))?;
+
+                let (outer, inner) = match intrinsic {
+                    I::ComposeRight => (rhs, lhs),
+                    _ => (lhs, rhs),
+                };
+                let value = self.apply(inner, vec![argument])?;
+
+                self.apply(outer, vec![value])
+            }
+            _ => intrinsic::eval(intrinsic, arguments),
+        }
+    }
+
+    fn apply_rest(&self, value: Value, rest: Vec<Value>) -> Result<Value> {
+        if rest.is_empty() {
+            Ok(value)
+        } else {
+            self.apply(value, rest)
+        }
+    }
+
+    fn invoke(&self, group: &HirBindingGroup, arguments: Vec<Value>) -> Result<Value> {
+        for declaration in group.declarations.iter() {
+            if declaration.patterns.len() != arguments.len() {
+                continue;
+            }
+
+            let mut env = Env::new();
+            let mut matches = true;
+            for (pattern, argument) in declaration.patterns.iter().zip(arguments.iter()) {
+                matches = matches && self.bind(&mut env, *pattern, argument.clone())?;
+            }
+
+            if matches {
+                return self.value(&env, declaration.value);
+            }
+        }
+
+        Err(NoMatchingDeclaration(group.signature.name.to_string()))
+    }
+
+    /// Binds the value to the pattern, returns if the pattern matches the value.
+    fn bind(&self, env: &mut Env, pattern: HirPattern, value: Value) -> Result<bool> {
+        match self.db.lookup_intern_pattern(pattern).kind {
+            HirPatternKind::Error => Err(ErrorNode),
+            HirPatternKind::Wildcard => Ok(true),
+            HirPatternKind::Unit => Ok(value == Value::Unit),
+            HirPatternKind::Name(pattern) => {
+                env.insert(pattern.name, value);
+                Ok(true)
+            }
+            HirPatternKind::Literal(literal) => Ok(literal_value(&literal.0)? == value),
+            HirPatternKind::Range(range) => {
+                let start = literal_value(&range.start)?;
+                let end = literal_value(&range.end)?;
+
+                match (start, end, value) {
+                    (Value::Int(start, ..), Value::Int(end, ..), Value::Int(value, ..)) => {
+                        Ok(start <= value && value <= end)
+                    }
+                    _ => Ok(false),
+                }
+            }
+            HirPatternKind::Or(or) => {
+                for alternative in or.alternatives {
+                    let mut alternative_env = env.clone();
+                    if self.bind(&mut alternative_env, alternative, value.clone())? {
+                        *env = alternative_env;
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
+            HirPatternKind::As(pattern) => {
+                env.insert(pattern.name, value.clone());
+
+                self.bind(env, pattern.pattern, value)
+            }
+            HirPatternKind::This => Err(Unsupported("self patterns")),
+            HirPatternKind::Spread => Err(Unsupported("spread patterns")),
+            HirPatternKind::List(_) => Err(Unsupported("list patterns")),
+            HirPatternKind::Constructor(pattern) => {
+                let name = pattern.constructor_name.as_str();
+
+                match value {
+                    Value::Constructor(constructor, arguments)
+                        if constructor.name == name
+                            && arguments.len() == pattern.arguments.len() =>
+                    {
+                        for (pattern, argument) in pattern.arguments.into_iter().zip(arguments) {
+                            if !self.bind(env, pattern, argument)? {
+                                return Ok(false);
+                            }
+                        }
+
+                        Ok(true)
+                    }
+                    _ => Ok(false),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the amount of fields of the variant, that are the parameters of its type, like `a` in
+/// `a -> Maybe a`.
+fn arity(db: &dyn HirInterner, mut variant_type: HirType) -> usize {
+    let mut arity = 0;
+    loop {
+        match db.lookup_intern_type(variant_type).kind {
+            HirTypeKind::Forall(forall) => variant_type = forall.value,
+            HirTypeKind::App(app) if matches!(app.callee, HirTypeFunction::Pi) => {
+                let [_, HirTypeArgument::Type(value) | HirTypeArgument::Named(_, value)] =
+                    app.arguments.as_slice()
+                else {
+                    return arity;
+                };
+
+                arity += 1;
+                variant_type = *value;
+            }
+            _ => return arity,
+        }
+    }
+}
+
+fn literal_value(literal: &HirLiteral) -> Result<Value> {
+    match literal {
+        HirLiteral::Error => Err(ErrorNode),
+        HirLiteral::Int(value, size, HirISign::Signed) => {
+            // The signed literals are stored as the bits of the value, so it needs to be
+            // sign-extended with the size of the literal.
+            let value = match size {
+                HirISize::U8 => *value as i8 as i128,
+                HirISize::U16 => *value as i16 as i128,
+                HirISize::U32 => *value as i32 as i128,
+                HirISize::U64 | HirISize::USize => *value as i64 as i128,
+                HirISize::U1 | HirISize::U128 => *value as i128,
+            };
+
+            Ok(Value::Int(value, *size, HirISign::Signed))
+        }
+        HirLiteral::Int(value, size, sign) => Ok(Value::Int(*value as i128, *size, *sign)),
+        HirLiteral::Decimal(size, decimal) => format!("{decimal:?}")
+            .parse()
+            .map(|value| Value::Float(value, *size))
+            .map_err(|_| ErrorNode),
+        HirLiteral::String(string) => Ok(Value::String(string.value.clone())),
+        HirLiteral::Char(value) => Ok(Value::Char(*value)),
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt::Display;
+
+use asena_hir::{
+    intrinsic::HirIntrinsic,
+    literal::{HirFSize, HirISign, HirISize},
+    top_level::HirBindingGroup,
+    value::HirValue,
+    Name,
+};
+
+use crate::Env;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unit,
+    Int(i128, HirISize, HirISign),
+    Float(f64, HirFSize),
+    String(String),
+    Char(char),
+
+    /// A top-level function, partially applied to the arguments.
+    Function(HirBindingGroup, Vec<Value>),
+
+    /// An intrinsic function, partially applied to the arguments.
+    Intrinsic(HirIntrinsic, Vec<Value>),
+
+    /// A lambda with the environment it was created in, the parameters that were already applied
+    /// are bound in the environment.
+    Closure(Vec<Name>, HirValue, Env),
+
+    /// A variant of an enum, applied to the arguments, it's a value if it's applied to all of its
+    /// fields, otherwise it's a function that builds the value.
+    Constructor(Constructor, Vec<Value>),
+}
+
+/// The variant of an enum, the names are kept as strings, so the values can be displayed
+/// without the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constructor {
+    pub enum_name: String,
+    pub name: String,
+    pub arity: usize,
+}
+
+impl Value {
+    pub const TRUE: Value = Value::Int(1, HirISize::U1, HirISign::Unsigned);
+    pub const FALSE: Value = Value::Int(0, HirISize::U1, HirISign::Unsigned);
+
+    /// Returns the name of the type of the value, as there's no type checker yet, it's computed
+    /// from the value itself.
+    pub fn type_name(&self) -> String {
+        match self {
+            Value::Unit => "()".into(),
+            Value::Int(_, HirISize::U1, _) => "Bool".into(),
+            Value::Int(_, HirISize::USize, _) => "Nat".into(),
+            Value::Int(_, size, sign) => {
+                let bits = match size {
+                    HirISize::U8 => 8,
+                    HirISize::U16 => 16,
+                    HirISize::U32 => 32,
+                    HirISize::U64 => 64,
+                    _ => 128,
+                };
+
+                match sign {
+                    HirISign::Signed => format!("Int{bits}"),
+                    HirISign::Unsigned => format!("UInt{bits}"),
+                }
+            }
+            Value::Float(_, HirFSize::F32) => "Float32".into(),
+            Value::Float(_, HirFSize::F64) => "Float64".into(),
+            Value::String(_) => "String".into(),
+            Value::Char(_) => "Char".into(),
+            Value::Constructor(constructor, arguments) if arguments.len() == constructor.arity => {
+                constructor.enum_name.clone()
+            }
+            Value::Function(..)
+            | Value::Intrinsic(..)
+            | Value::Closure(..)
+            | Value::Constructor(..) => "<function>".into(),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Int(value, HirISize::U1, _) => write!(f, "{}", *value != 0),
+            Value::Int(value, _, _) => write!(f, "{value}"),
+            Value::Float(value, _) => write!(f, "{value:?}"),
+            Value::String(value) => write!(f, "{value:?}"),
+            Value::Char(value) => write!(f, "{value:?}"),
+            Value::Constructor(constructor, arguments) if arguments.len() == constructor.arity => {
+                write!(f, "{}", constructor.name)?;
+                for argument in arguments {
+                    match argument {
+                        Value::Constructor(_, arguments) if !arguments.is_empty() => {
+                            write!(f, " ({argument})")?
+                        }
+                        _ => write!(f, " {argument}")?,
+                    }
+                }
+
+                Ok(())
+            }
+            Value::Function(..)
+            | Value::Intrinsic(..)
+            | Value::Closure(..)
+            | Value::Constructor(..) => write!(f, "<function>"),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::sync::Arc;
+
+use asena_ast_db::{db::AstDatabase, package::*, vfs::*};
+use asena_ast_lowering::db::{AstLowerrer, AstLowerrerStorage};
+use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
+use asena_hir::interner::HirStorage;
+use asena_hir_eval::{EvalError, Interpreter, Result, Value};
+use asena_prec::{db::PrecStorage, PrecDatabase};
+
+#[salsa::database(
+    PrecStorage,
+    AstDatabaseStorage,
+    AstLowerrerStorage,
+    AstResolverStorage,
+    HirStorage
+)]
+#[derive(Default)]
+struct TestDatabase {
+    storage: salsa::Storage<TestDatabase>,
+}
+
+impl salsa::Database for TestDatabase {}
+
+/// Lowers the source code, and evaluates the `Main` binding of it.
+fn eval_main(source: &str) -> Result<Value> {
+    eval_main_with(source, LoweringOptions::default())
+}
+
+/// Same as [eval_main], but lowers the source code with the lowering options.
+fn eval_main_with(source: &str, options: LoweringOptions) -> Result<Value> {
+    eval_module_in(&TestDatabase::default(), "Test", source, options)
+}
+
+/// Evaluates the `Main` of the module of the database, lowered with the lowering options.
+fn eval_module_in(
+    db: &TestDatabase,
+    name: &str,
+    source: &str,
+    options: LoweringOptions,
+) -> Result<Value> {
+    let fs = FileSystem::default();
+    fs.memory.insert(name.into(), source.into());
+
+    let vfs = Arc::new(fs);
+    let local_pkg = Package::with_options(db, "Local", "0.0.0", vfs, Default::default(), options);
+    let file = VfsFileData::new(db, name, format!("./{name}.ase").into(), local_pkg);
+
+    db.global_scope().write().unwrap().import(db, file, None);
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+    let hir = db.hir_file(ast.into());
+
+    Interpreter::new(db, hir.declarations).eval_global("Main")
+}
+
+#[test]
+fn arithmetic_works() {
+    assert_eq!(eval_main("Main { 1 + 2 * 3 }").unwrap().to_string(), "7");
+    assert_eq!(eval_main("Main { 7 / 0 }"), Err(EvalError::DivisionByZero));
+}
+
+#[test]
+fn application_works() {
+    let value = eval_main(
+        "Double (x: Int32) : Int32 { x + x }\n\
+         Main { Double 21 }",
+    );
+
+    assert_eq!(value.unwrap().to_string(), "42");
+}
+
+#[test]
+fn intrinsics_works() {
+    assert_eq!(
+        eval_main("Main { length \"hello\" }").unwrap().to_string(),
+        "5"
+    );
+    assert_eq!(
+        eval_main("Main { concat \"a\" \"b\" }")
+            .unwrap()
+            .to_string(),
+        "\"ab\""
+    );
+    assert_eq!(eval_main("Main { show 42 }").unwrap().to_string(), "\"42\"");
+}
+
+#[test]
+fn comparison_works() {
+    // The comparisons bind tighter than the arithmetic, so the operands are grouped.
+    let value = eval_main("Main { (1 + 1) == 2 && 3 < 2 || (2 * 3 + 1) >= 7 }");
+    assert_eq!(value.unwrap(), Value::TRUE);
+
+    // The right operand of `&&` isn't evaluated, so the division doesn't fail
+    let value = eval_main("Main { 1 > 2 && (1 / 0) == 0 }");
+    assert_eq!(value.unwrap(), Value::FALSE);
+}
+
+#[test]
+fn pipe_works() {
+    let value = eval_main(
+        "Double (x: Int32) : Int32 { x + x }\n\
+         Main { 3 |> Double |> Double }",
+    );
+
+    assert_eq!(value.unwrap().to_string(), "12");
+}
+
+#[test]
+fn compose_works() {
+    let value = eval_main(
+        "Double (x: Int32) : Int32 { x + x }\n\
+         Inc (x: Int32) : Int32 { x + 1 }\n\
+         Main { (Double . Inc) 1 + (Double >> Inc) 1 }",
+    );
+
+    assert_eq!(value.unwrap().to_string(), "7");
+}
+
+#[test]
+fn lambda_works() {
+    let value = eval_main("Main { (\\x y -> x - y) 10 3 }");
+
+    assert_eq!(value.unwrap().to_string(), "7");
+}
+
+#[test]
+fn operator_sections_works() {
+    assert_eq!(eval_main("Main { (+) 1 2 }").unwrap().to_string(), "3");
+
+    // The functions between backticks are unknown operators, so they bind the tightest
+    let value = eval_main(
+        "Minus (x: Int32) (y: Int32) : Int32 { x - y }\n\
+         Main { 10 `Minus` 3 * 2 }",
+    );
+
+    assert_eq!(value.unwrap().to_string(), "14");
+}
+
+#[test]
+fn do_blocks_works() {
+    // The do block is desugared into `bind 20 (\x -> pure (x + 1))`, with the identity monad.
+    let value = eval_main(
+        "bind (m: Int32) (f: Int32 -> Int32) : Int32 { f m }\n\
+         pure (x: Int32) : Int32 { x }\n\
+         Main {\n\
+           do {\n\
+             x <- 20\n\
+             return x + 1\n\
+           }\n\
+         }",
+    );
+
+    assert_eq!(value.unwrap().to_string(), "21");
+}
+
+#[test]
+fn match_works() {
+    let value = eval_main("Main { match 'b' { 'a' => 1, _ => 2 } }");
+    assert_eq!(value.unwrap().to_string(), "2");
+
+    // The cases are tried in the order they're written, even if a catch-all case comes first.
+    let value = eval_main("Main { match 1 { n => n + 1, 1 => 5 } }");
+    assert_eq!(value.unwrap().to_string(), "2");
+
+    let value = eval_main("Main { if true then \"yes\" else \"no\" }");
+    assert_eq!(value.unwrap(), Value::String("yes".into()));
+}
+
+#[test]
+fn constructors_works() {
+    let maybe = "enum Maybe (a: Set) {\n\
+                   Nothing : Maybe a,\n\
+                   Just : a -> Maybe a,\n\
+                 }\n\
+                 FromMaybe (default: Int32) (value: Maybe Int32) : Int32 {\n\
+                   match value {\n\
+                     Nothing => default,\n\
+                     Just x => x,\n\
+                   }\n\
+                 }\n";
+
+    let value = eval_main(&format!("{maybe}Main {{ FromMaybe 0 (Just 42) }}"));
+    assert_eq!(value.unwrap().to_string(), "42");
+
+    let value = eval_main(&format!("{maybe}Main {{ FromMaybe 7 Nothing }}"));
+    assert_eq!(value.unwrap().to_string(), "7");
+
+    // The constructors are functions until they're applied to all of their fields.
+    let value = eval_main(&format!("{maybe}Main {{ 1 |> Just |> Just }}")).unwrap();
+    assert_eq!(value.to_string(), "Just (Just 1)");
+    assert_eq!(value.type_name(), "Maybe");
+}
+
+#[test]
+fn hole_works() {
+    let value = eval_main("Main { (\\x -> ?foo) 1 }");
+
+    assert_eq!(value, Err(EvalError::ReachedHole("foo".into())));
+}
+
+#[test]
+fn clauses_works() {
+    // The clauses are tried in the order they're declared, so the catch-all one comes last.
+    let value = eval_main(
+        "Fact 0 = 1\n\
+         Fact n = n * Fact (n - 1)\n\
+         Main { Fact 5 }",
+    );
+
+    assert_eq!(value.unwrap().to_string(), "120");
+}
+
+#[test]
+fn range_pattern_works() {
+    let value = eval_main("Main { match 42 { 0 => \"zero\", 1..9 => \"digit\", _ => \"many\" } }");
+    assert_eq!(value.unwrap(), Value::String("many".into()));
+
+    let value = eval_main("Main { match 7 { 0 => \"zero\", 1..9 => \"digit\", _ => \"many\" } }");
+    assert_eq!(value.unwrap(), Value::String("digit".into()));
+}
+
+#[test]
+fn or_and_as_patterns_works() {
+    let value = eval_main("Main { match 2 { 1 | 2 | 3 => \"small\", _ => \"big\" } }");
+    assert_eq!(value.unwrap(), Value::String("small".into()));
+
+    let value = eval_main("Main { match 5 { n@(4 | 5) => n * 10, _ => 0 } }");
+    assert_eq!(value.unwrap().to_string(), "50");
+}
+
+#[test]
+fn integer_overflow_works() {
+    assert_eq!(eval_main("Main { -128i8 }").unwrap().to_string(), "-128");
+    assert_eq!(eval_main("Main { 128i8 }"), Err(EvalError::ErrorNode));
+    assert_eq!(eval_main("Main { 2147483648 }"), Err(EvalError::ErrorNode));
+
+    let wrapping = LoweringOptions {
+        wrapping_integers: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        eval_main_with("Main { 128i8 }", wrapping)
+            .unwrap()
+            .to_string(),
+        "-128"
+    );
+    assert_eq!(
+        eval_main_with("Main { 2147483648 }", wrapping)
+            .unwrap()
+            .to_string(),
+        "-2147483648"
+    );
+}
+
+#[test]
+fn package_options_works() {
+    // The same package, lowered with other options, is another package of the database
+    let db = TestDatabase::default();
+    let wrapping = LoweringOptions {
+        wrapping_integers: true,
+        ..Default::default()
+    };
+
+    let value = eval_module_in(&db, "Wrapping", "Main { 128i8 }", wrapping);
+    assert_eq!(value.unwrap().to_string(), "-128");
+
+    let value = eval_module_in(&db, "Checked", "Main { 128i8 }", Default::default());
+    assert_eq!(value, Err(EvalError::ErrorNode));
+}
+
+#[test]
+fn block_statements_works() {
+    // The statements are evaluated in order, and the last one is the value of the block.
+    let value = eval_main("Main {\n  let x = 20\n  x + 1\n  x + 22\n}");
+    assert_eq!(value.unwrap().to_string(), "42");
+
+    // The statements before the last one aren't skipped, so their errors are reported.
+    let value = eval_main("Main {\n  ?early\n  1\n}");
+    assert_eq!(value, Err(EvalError::ReachedHole("early".into())));
+}
@@ -2,19 +2,19 @@ use asena_hir_derive::*;
 
 use crate::Name;
 
-#[derive(Default, Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Copy, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirAttr)]
 pub struct HirAttrInline {
     pub kind: data::HirInlineKind,
 }
 
-#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirAttr)]
 pub struct HirAttrExternal {
     pub ffi_name: Name,
 }
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_kind(HirAttr)]
 pub enum HirAttrKind {
     #[default]
@@ -29,7 +29,9 @@ pub struct HirAttr {
 }
 
 pub mod data {
-    #[derive(Default, Hash, Clone, Copy, Debug, PartialEq, Eq)]
+    use asena_hir_derive::HirEq;
+
+    #[derive(Default, Hash, Clone, Copy, Debug, PartialEq, Eq, HirEq)]
     pub enum HirInlineKind {
         #[default]
         Never,
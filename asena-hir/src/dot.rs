@@ -0,0 +1,311 @@
+//! Graphviz printer for the HIR, it renders the tree of the declarations, or the call graph
+//! between the binding groups, as DOT, like `asena dump --graph calls Foo.ase | dot -Tsvg`.
+//!
+//! The tree is rendered from the [HirSexpr] output, so both printers show the same structure,
+//! and the output is deterministic as well.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::expr::data::{HirBranch, HirCallee};
+use crate::expr::{HirExpr, HirExprKind};
+use crate::interner::HirInterner;
+use crate::sexpr::HirSexpr;
+use crate::stmt::{HirStmt, HirStmtKind};
+use crate::top_level::{HirBindingGroup, HirTopLevel, HirTopLevelKind};
+use crate::value::instr::HirInstr;
+use crate::value::monads::HirMonad;
+use crate::value::{HirValue, HirValueKind};
+use crate::Name;
+
+pub struct HirDot<'db> {
+    db: &'db dyn HirInterner,
+}
+
+impl<'db> HirDot<'db> {
+    pub fn new(db: &'db dyn HirInterner) -> Self {
+        Self { db }
+    }
+
+    /// Renders the tree of the declarations, every list of the s-expressions is a node, labeled
+    /// with its head, and the other items are its children.
+    pub fn tree<I: IntoIterator<Item = HirTopLevel>>(&self, declarations: I) -> String {
+        let sexpr = HirSexpr::new(self.db).file(declarations);
+
+        let mut graph = DotTree::default();
+        let root = graph.node("file");
+        for item in parse_sexpr(&sexpr) {
+            graph.add(root, &item);
+        }
+
+        format!("digraph hir {{\n  node [shape=box];\n{}}}\n", graph.output)
+    }
+
+    /// Renders the call graph of the binding groups, the groups of the enums, the structs, the
+    /// classes, the traits and the instances are included. The references to the names that
+    /// aren't binding groups, like the parameters, aren't edges.
+    pub fn call_graph<I: IntoIterator<Item = HirTopLevel>>(&self, declarations: I) -> String {
+        let groups = declarations
+            .into_iter()
+            .flat_map(|top_level| self.groups(top_level))
+            .map(|group| (group.signature.name.to_string(), group))
+            .sorted_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs))
+            .collect::<Vec<_>>();
+
+        let names = groups
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<BTreeSet<_>>();
+
+        let mut output = String::from("digraph calls {\n");
+        for name in names.iter() {
+            let _ = writeln!(output, "  {};", quote(name));
+        }
+
+        let mut edges = BTreeSet::new();
+        for (name, group) in groups.iter() {
+            let mut references = vec![];
+            for declaration in group.declarations.iter() {
+                self.value(declaration.value, &mut references);
+            }
+
+            for reference in references {
+                let reference = reference.to_string();
+                if names.contains(&reference) {
+                    edges.insert((name.clone(), reference));
+                }
+            }
+        }
+
+        for (source, target) in edges {
+            let _ = writeln!(output, "  {} -> {};", quote(&source), quote(&target));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    fn groups(&self, top_level: HirTopLevel) -> Vec<HirBindingGroup> {
+        let data = self.db.lookup_intern_top_level(top_level);
+
+        match data.kind {
+            HirTopLevelKind::Error => vec![],
+            HirTopLevelKind::BindingGroup(group) => vec![group],
+            HirTopLevelKind::Enum(enum_decl) => enum_decl.groups.into_iter().collect(),
+            HirTopLevelKind::Struct(struct_decl) => struct_decl.groups.into_iter().collect(),
+            HirTopLevelKind::Class(class_decl) => class_decl.groups.into_iter().collect(),
+            HirTopLevelKind::Trait(trait_decl) => trait_decl.groups.values().cloned().collect(),
+            HirTopLevelKind::Instance(instance) => instance.groups.into_iter().collect(),
+        }
+    }
+
+    /// Collects the names referenced by the value.
+    fn value(&self, value: HirValue, references: &mut Vec<Name>) {
+        let data = self.db.lookup_intern_value(value);
+
+        match data.kind {
+            HirValueKind::Error | HirValueKind::Unit => {}
+            HirValueKind::Expr(expr) => self.expr(expr.0, references),
+            HirValueKind::Block(block) => {
+                for stmt in block.instructions.iter() {
+                    self.stmt(*stmt, references);
+                }
+
+                self.value(block.value, references);
+            }
+            HirValueKind::Monad(monad) => match monad {
+                HirMonad::PureUnit => {}
+                HirMonad::Pure(value) => self.value(value, references),
+                HirMonad::Bind(_, value, then) => {
+                    self.value(value, references);
+                    self.value(then, references);
+                }
+            },
+            HirValueKind::Instr(instr) => match instr {
+                HirInstr::Null | HirInstr::Variable(_) => {}
+                HirInstr::Let(_, value)
+                | HirInstr::ObjectClone(value)
+                | HirInstr::ObjectDrop(value) => self.value(value, references),
+                HirInstr::Block(block) => {
+                    for value in block.instructions.iter() {
+                        self.value(*value, references);
+                    }
+
+                    self.value(block.value, references);
+                }
+            },
+        }
+    }
+
+    fn stmt(&self, stmt: HirStmt, references: &mut Vec<Name>) {
+        let data = self.db.lookup_intern_stmt(stmt);
+
+        match data.kind {
+            HirStmtKind::Error => {}
+            HirStmtKind::Ask(ask) => self.value(ask.value, references),
+            HirStmtKind::Let(stmt) => self.value(stmt.value, references),
+            HirStmtKind::Return(stmt) => self.value(stmt.value, references),
+            HirStmtKind::Value(stmt) => self.value(stmt.0, references),
+        }
+    }
+
+    fn expr(&self, expr: HirExpr, references: &mut Vec<Name>) {
+        let data = self.db.lookup_intern_expr(expr);
+
+        match data.kind {
+            HirExprKind::Error
+            | HirExprKind::Unit
+            | HirExprKind::This
+            | HirExprKind::Literal(_) => {}
+            HirExprKind::Reference(reference) => references.push(reference.name),
+            HirExprKind::Group(group) => self.value(group.value, references),
+            HirExprKind::Call(call) => {
+                if let HirCallee::Value(value) | HirCallee::Method(value) = call.callee {
+                    self.value(value, references);
+                }
+
+                for argument in call.arguments.iter() {
+                    self.value(*argument, references);
+                }
+
+                if let Some(dsl) = call.as_dsl {
+                    self.value(dsl.value, references);
+                }
+            }
+            HirExprKind::Match(match_expr) => {
+                self.value(match_expr.scrutinee, references);
+
+                for case in match_expr.cases.iter() {
+                    match case.value {
+                        HirBranch::Error => {}
+                        HirBranch::Expr(value) | HirBranch::Block(value) => {
+                            self.value(value, references)
+                        }
+                    }
+                }
+            }
+            HirExprKind::Help(help) => {
+                if let Some(value) = help.value {
+                    self.value(value, references);
+                }
+            }
+            HirExprKind::Ann(ann) => self.value(ann.value, references),
+            HirExprKind::Lam(lam) => self.value(lam.value, references),
+            HirExprKind::Array(array) => {
+                for item in array.items.iter() {
+                    self.value(*item, references);
+                }
+            }
+        }
+    }
+}
+
+/// An item of the s-expressions, the brackets are lists as well, headed by `[]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SexprItem {
+    Atom(String),
+    List(Vec<SexprItem>),
+}
+
+#[derive(Default)]
+struct DotTree {
+    output: String,
+    count: usize,
+}
+
+impl DotTree {
+    fn node(&mut self, label: &str) -> usize {
+        let id = self.count;
+        self.count += 1;
+
+        let _ = writeln!(self.output, "  n{id} [label={}];", quote(label));
+        id
+    }
+
+    fn add(&mut self, parent: usize, item: &SexprItem) {
+        let id = match item {
+            SexprItem::Atom(atom) => self.node(atom),
+            SexprItem::List(items) => match items.split_first() {
+                Some((SexprItem::Atom(head), children)) => {
+                    let id = self.node(head);
+                    for child in children {
+                        self.add(id, child);
+                    }
+                    id
+                }
+                _ => {
+                    let id = self.node("()");
+                    for child in items {
+                        self.add(id, child);
+                    }
+                    id
+                }
+            },
+        };
+
+        let _ = writeln!(self.output, "  n{parent} -> n{id};");
+    }
+}
+
+/// Parses the output of the [HirSexpr], the string literals are kept as atoms, with the quotes.
+fn parse_sexpr(source: &str) -> Vec<SexprItem> {
+    let mut stack: Vec<Vec<SexprItem>> = vec![vec![]];
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' | '[' => {
+                let mut list = vec![];
+                if c == '[' {
+                    list.push(SexprItem::Atom("[]".into()));
+                }
+                stack.push(list);
+            }
+            ')' | ']' if stack.len() > 1 => {
+                let list = stack.pop().unwrap_or_default();
+                if let Some(parent) = stack.last_mut() {
+                    parent.push(SexprItem::List(list));
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut atom = String::from(c);
+                let mut in_string = c == '"';
+                while let Some(&next) = chars.peek() {
+                    if !in_string && (next.is_whitespace() || "()[]".contains(next)) {
+                        break;
+                    }
+
+                    atom.push(next);
+                    chars.next();
+                    match next {
+                        '\\' if in_string => atom.extend(chars.next()),
+                        '"' if in_string => in_string = false,
+                        _ => {}
+                    }
+                }
+
+                if let Some(list) = stack.last_mut() {
+                    list.push(SexprItem::Atom(atom));
+                }
+            }
+        }
+    }
+
+    // The unclosed lists are closed at the end.
+    while stack.len() > 1 {
+        let list = stack.pop().unwrap_or_default();
+        if let Some(parent) = stack.last_mut() {
+            parent.push(SexprItem::List(list));
+        }
+    }
+
+    stack.pop().unwrap_or_default()
+}
+
+/// Quotes the label as a DOT string.
+fn quote(label: &str) -> String {
+    format!("\"{}\"", label.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,67 @@
+//! Structural equality of the HIR nodes, it compares the nodes looking up the ids in the
+//! database, and ignores the locations, so the lowering tests can compare the lowered HIR with
+//! the HIR built by hand, without caring about the interning order or the spans.
+
+use std::hash::Hash;
+
+use crate::interner::HirInterner;
+use crate::intrinsic::HirIntrinsic;
+use crate::literal::HirLiteral;
+use crate::{HirLoc, Name};
+
+pub trait HirEq {
+    fn hir_eq(&self, other: &Self, db: &dyn HirInterner) -> bool;
+}
+
+/// Implements [HirEq] for the types that are compared by [PartialEq], as they don't hold any
+/// id or location.
+macro_rules! hir_eq_by_partial_eq {
+    ($($name:ty),* $(,)?) => {
+        $(impl HirEq for $name {
+            fn hir_eq(&self, other: &Self, _: &dyn HirInterner) -> bool {
+                self == other
+            }
+        })*
+    };
+}
+
+hir_eq_by_partial_eq![Name, HirLiteral, HirIntrinsic, String, bool, char, usize];
+
+impl HirEq for HirLoc {
+    fn hir_eq(&self, _: &Self, _: &dyn HirInterner) -> bool {
+        true
+    }
+}
+
+impl<T: HirEq> HirEq for Vec<T> {
+    fn hir_eq(&self, other: &Self, db: &dyn HirInterner) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.hir_eq(b, db))
+    }
+}
+
+impl<T: HirEq> HirEq for Option<T> {
+    fn hir_eq(&self, other: &Self, db: &dyn HirInterner) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.hir_eq(b, db),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The sets are compared ignoring the order, every element should be equal to some element of
+/// the other set.
+impl<T: HirEq + Hash + Eq + Clone> HirEq for im::HashSet<T> {
+    fn hir_eq(&self, other: &Self, db: &dyn HirInterner) -> bool {
+        self.len() == other.len() && self.iter().all(|a| other.iter().any(|b| a.hir_eq(b, db)))
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: HirEq + Clone> HirEq for im::HashMap<K, V> {
+    fn hir_eq(&self, other: &Self, db: &dyn HirInterner) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, a)| other.get(key).map_or(false, |b| a.hir_eq(b, db)))
+    }
+}
@@ -1,66 +1,83 @@
 use asena_hir_derive::*;
 
-use crate::{hir_type::HirType, literal::HirLiteral, pattern::HirPattern, value::HirValue, *};
+use crate::{
+    hir_type::HirType, intrinsic::HirIntrinsic, literal::HirLiteral, pattern::HirPattern,
+    value::HirValue, *,
+};
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprLiteral(pub HirLiteral);
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprGroup {
     pub value: HirValue,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprCall {
     pub callee: data::HirCallee,
     pub arguments: Vec<HirValue>,
     pub as_dsl: Option<data::HirDsl>,
+
+    /// The locations of the original nested applications, from the innermost to the outermost,
+    /// as `f a b` is lowered into a single call, it's used to report errors on the partial
+    /// applications.
+    pub spine: Vec<HirLoc>,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprReference {
     pub name: Name,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprMatch {
     pub scrutinee: HirValue,
-    pub cases: im::HashSet<data::HirMatchCase>,
+
+    /// The cases in the order they're written, the first case that matches is taken, so the
+    /// order matters for the overlapping patterns.
+    pub cases: Vec<data::HirMatchCase>,
     pub kind: data::HirMatchKind,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprHelp {
-    pub value: HirValue,
+    /// The name of the hole, like `foo` in `?foo`, if it isn't bound to a value, otherwise it's
+    /// the value that is being asked about.
+    pub hole: Option<Name>,
+    pub value: Option<HirValue>,
+
+    /// The local bindings in scope at the hole, reported with the expected type of it.
+    pub bindings: Vec<Name>,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprAnn {
     pub value: HirValue,
     pub against: HirType,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprLam {
     pub parameters: Vec<Name>,
     pub value: HirValue,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirExpr)]
 pub struct HirExprArray {
     pub items: Vec<HirValue>,
 }
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_kind(HirExpr)]
 pub enum HirExprKind {
     #[default]
@@ -88,48 +105,49 @@ pub struct HirExpr {
 pub mod data {
     use super::*;
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub enum HirMatchKind {
         If,
         Match,
         Switch,
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub enum HirBranch {
         Error,
         Expr(HirValue),
         Block(HirValue),
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub struct HirMatchCase {
         pub pattern: HirPattern,
         pub value: HirBranch,
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub struct HirDsl {
         pub parameters: Vec<Name>,
         pub value: HirValue,
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub enum HirCallee {
+        /// Call to the value, like a function or a lambda.
         Value(HirValue),
 
+        /// Runs the do block that is passed as the block of the call, like `do { x <- m }`.
         Do,
 
-        // any operations
-        Add,
-        Sub,
-        Mul,
-        Div,
-
-        // int only operations
-        IAdd,
-        ISub,
-        IMul,
-        IDiv,
+        /// Call to a method of a class or trait, like `Point.show p`, the value is the reference
+        /// to the method.
+        Method(HirValue),
+
+        /// Call to a function implemented by the compiler, like the arithmetic operators.
+        Intrinsic(HirIntrinsic),
+
+        /// Application of an enum variant, like `Just 1`, the variants without arguments, like
+        /// `Nothing`, are calls without arguments.
+        Constructor(Name),
     }
 }
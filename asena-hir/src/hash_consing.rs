@@ -0,0 +1,101 @@
+//! The opt-in hash-consing of the HIR expressions, it's an option of the lowering of the package,
+//! see `LoweringOptions::hash_consing`. The expressions are interned by salsa, so they're already
+//! deduplicated by their data, but the data has the location, and the structurally identical
+//! expressions, like the repeated expressions of the generated code, only share the same id if
+//! they're interned without it, with [HirExpr::hash_consed].
+//!
+//! The expressions lose their locations, so the diagnostics of the HIR point to nowhere, and it's
+//! disabled by default. The statistics are of the database, see [HirInterner::hash_consing_counters].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::expr::{HirExpr, HirExprData, HirExprKind};
+use crate::interner::HirInterner;
+use crate::HirLoc;
+
+/// The statistics of the hash-consing, the requests are the expressions created with
+/// [HirExpr::hash_consed], and the hits are the ones that were already interned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HashConsingStatistics {
+    pub requests: usize,
+    pub hits: usize,
+}
+
+/// The counters of the hash-consing of a database. The ids of the interned expressions are
+/// allocated in order, so an id lower than the ones already seen is a hit. The expressions that
+/// are interned directly, without [HirExpr::new], aren't seen, and the hits of them are counted
+/// as misses.
+#[derive(Debug, Default)]
+pub struct HashConsingCounters {
+    requests: AtomicUsize,
+    hits: AtomicUsize,
+    interned: AtomicUsize,
+}
+
+impl HashConsingCounters {
+    /// Marks the expression as seen, and returns if it was already seen.
+    fn see(&self, expr: HirExpr) -> bool {
+        let index = expr.index() as usize;
+
+        index < self.interned.fetch_max(index + 1, Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for HashConsingCounters {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for HashConsingCounters {}
+
+/// Returns the statistics of the database, and clears them.
+pub fn take_statistics(db: &dyn HirInterner) -> HashConsingStatistics {
+    let counters = db.hash_consing_counters();
+
+    HashConsingStatistics {
+        requests: counters.requests.swap(0, Ordering::Relaxed),
+        hits: counters.hits.swap(0, Ordering::Relaxed),
+    }
+}
+
+pub(crate) fn hash_consing_counters(_: &dyn HirInterner) -> Arc<HashConsingCounters> {
+    Arc::new(HashConsingCounters::default())
+}
+
+impl HashConsingStatistics {
+    /// The rate of the requests that were hits, it's zero if there weren't requests.
+    pub fn hit_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / self.requests as f64
+    }
+}
+
+impl HirExpr {
+    /// Interns the expression with its location.
+    pub fn new(db: &dyn HirInterner, kind: HirExprKind, span: HirLoc) -> HirExpr {
+        let expr = db.intern_expr(HirExprData { kind, span });
+        db.hash_consing_counters().see(expr);
+        expr
+    }
+
+    /// Interns the expression without its location, so it shares the id of the structurally
+    /// identical expressions, see the [module](self) documentation.
+    pub fn hash_consed(db: &dyn HirInterner, kind: HirExprKind) -> HirExpr {
+        let expr = db.intern_expr(HirExprData {
+            kind,
+            span: HirLoc::default(),
+        });
+
+        let counters = db.hash_consing_counters();
+        let hit = counters.see(expr);
+        counters.requests.fetch_add(1, Ordering::Relaxed);
+        counters.hits.fetch_add(hit as usize, Ordering::Relaxed);
+
+        expr
+    }
+}
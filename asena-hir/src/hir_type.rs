@@ -4,21 +4,29 @@ use crate::{interner::HirInterner, Name};
 
 use self::data::HirTypeFunction;
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirType)]
 pub struct HirTypeName {
     pub name: Name,
     pub is_constructor: bool,
 }
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirType)]
 pub struct HirTypeApp {
     pub callee: HirTypeFunction,
     pub arguments: Vec<data::HirTypeArgument>,
 }
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+/// The explicitly quantified type, like `forall a. a -> a`.
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
+#[hir_node(HirType)]
+pub struct HirTypeForall {
+    pub variables: Vec<Name>,
+    pub value: HirType,
+}
+
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_kind(HirType)]
 pub enum HirTypeKind {
     #[default]
@@ -27,6 +35,7 @@ pub enum HirTypeKind {
     This,
     Name(HirTypeName),
     App(HirTypeApp),
+    Forall(HirTypeForall),
 }
 
 #[hir_struct]
@@ -66,13 +75,15 @@ impl HirType {
         })
     }
 
+    /// Builds the function type of the parameters, like `a -> b -> c`, the parameters are applied
+    /// before the return type, like the function types of the source.
     pub fn pi(db: &dyn HirInterner, parameters: &[HirType], value: HirType) -> HirType {
-        parameters.iter().fold(value, |acc, next| {
+        parameters.iter().rev().fold(value, |acc, next| {
             let kind = HirTypeKind::from(HirTypeApp {
                 callee: HirTypeFunction::Pi,
                 arguments: vec![
-                    data::HirTypeArgument::Type(acc),
                     data::HirTypeArgument::Type(*next),
+                    data::HirTypeArgument::Type(acc),
                 ],
             });
 
@@ -87,15 +98,27 @@ impl HirType {
 pub mod data {
     use super::*;
 
-    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub enum HirTypeFunction {
         #[default]
         Error,
+
+        /// The function type, applied to the parameter and the return types.
         Pi,
+
+        /// The dependent pair type, applied to the first and the second types.
+        Sigma,
+
+        /// The qualified type, applied to the constraint and the qualified types, like
+        /// `Monad m => m a`.
+        Qual,
+
+        /// The array type, applied to the type of the items, like `[a]`.
+        Array,
         Type(HirType),
     }
 
-    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub enum HirTypeArgument {
         #[default]
         Error,
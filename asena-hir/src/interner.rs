@@ -1,17 +1,16 @@
+use std::sync::Arc;
+
 use crate::attr::*;
 use crate::expr::*;
+use crate::hash_consing::HashConsingCounters;
 use crate::hir_type::*;
 use crate::pattern::*;
 use crate::stmt::*;
 use crate::top_level::*;
 use crate::value::*;
-use crate::Name;
 
 #[salsa::query_group(HirStorage)]
 pub trait HirInterner {
-    #[salsa::interned]
-    fn intern_name(&self, data: String) -> Name;
-
     #[salsa::interned]
     fn intern_attr(&self, data: HirAttrData) -> HirAttr;
 
@@ -32,4 +31,8 @@ pub trait HirInterner {
 
     #[salsa::interned]
     fn intern_top_level(&self, data: HirTopLevelData) -> HirTopLevel;
+
+    /// Returns the counters of the hash-consing of the database, see [crate::hash_consing].
+    #[salsa::invoke(crate::hash_consing::hash_consing_counters)]
+    fn hash_consing_counters(&self) -> Arc<HashConsingCounters>;
 }
@@ -0,0 +1,68 @@
+//! The registry of the intrinsic functions, that are implemented by the compiler instead of the
+//! source code. They are defined in the global scope like any other function, when it's created,
+//! so the resolution doesn't need to special-case them, and the evaluation and the code
+//! generation can dispatch on the [HirIntrinsic] ids. The variants and their names are declared
+//! by the table of `asena_ast_db::intrinsic_table`, that the global scope is created from.
+
+use asena_ast_db::{db::AstDatabase, def::DefWithId, intrinsic::intrinsic_def};
+
+/// Declares the [HirIntrinsic] variants, and their names, from the entries of the table.
+macro_rules! hir_intrinsics {
+    ($($variant:ident => $name:literal,)*) => {
+        #[derive(Hash, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        pub enum HirIntrinsic {
+            $($variant,)*
+        }
+
+        impl HirIntrinsic {
+            pub const ALL: &'static [HirIntrinsic] = &[$(Self::$variant,)*];
+
+            /// The name of the intrinsic in the global scope.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+asena_ast_db::intrinsic_table!(hir_intrinsics);
+
+impl HirIntrinsic {
+    /// The amount of arguments the intrinsic needs to be evaluated, `readLine` takes the unit, and
+    /// the compositions take the argument of the composed function.
+    pub fn arity(&self) -> usize {
+        match self {
+            Self::Neg
+            | Self::Length
+            | Self::Show
+            | Self::Print
+            | Self::Println
+            | Self::ReadLine => 1,
+            Self::Compose | Self::ComposeLeft | Self::ComposeRight => 3,
+            _ => 2,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|intrinsic| intrinsic.name() == name)
+    }
+
+    /// Returns the definition of the intrinsic, as the definitions are interned, it's always the
+    /// same for the same intrinsic. It has a virtual location, as it isn't in the source code.
+    pub fn def(&self, db: &dyn AstDatabase) -> DefWithId {
+        intrinsic_def(db, self.name())
+    }
+
+    /// Returns the intrinsic that the definition refers to, if it's an intrinsic.
+    pub fn from_def(db: &dyn AstDatabase, def: DefWithId) -> Option<Self> {
+        let data = db.lookup_intern_def(def);
+        let intrinsic = Self::from_name(data.name.as_str())?;
+
+        (intrinsic.def(db) == def).then_some(intrinsic)
+    }
+}
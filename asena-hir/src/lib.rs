@@ -8,34 +8,30 @@
 #![feature(associated_type_bounds)]
 
 use expr::HirExpr;
-use salsa::InternKey;
 
 pub mod attr;
+pub mod dot;
+pub mod eq;
 pub mod expr;
 pub mod file;
+pub mod hash_consing;
 pub mod hir_type;
 pub mod interner;
+pub mod intrinsic;
 pub mod literal;
 pub mod loc;
 pub mod pattern;
+pub mod sexpr;
 pub mod stmt;
 pub mod top_level;
 pub mod value;
 
 pub use loc::*;
 
-#[derive(Hash, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Name(salsa::InternId);
-
-impl InternKey for Name {
-    fn from_intern_id(v: salsa::InternId) -> Self {
-        Self(v)
-    }
-
-    fn as_intern_id(&self) -> salsa::InternId {
-        self.0
-    }
-}
+/// The names of the HIR are the same symbols of the syntax tree identifiers, so lowering a name
+/// doesn't intern it again, and reading it doesn't allocate, use [Name::intern] and
+/// [Name::as_str] instead of the database.
+pub type Name = asena_interner::Symbol;
 
 pub trait HirVisitor<T: Default> {
     fn visit_expr_literal(&mut self, _: HirExpr, _: &mut expr::HirExprLiteral) -> T {
@@ -25,15 +25,21 @@ pub enum HirISign {
     Unsigned,
 }
 
-#[derive(Hash, Clone, Copy, PartialEq, Eq)]
+/// The decimal literals keep the digits of the source code, without the sign, so they're printed
+/// exactly like they were written, like `1.50` or `1e3`.
+#[derive(Hash, Clone, PartialEq, Eq)]
 pub struct HirDecimal {
-    pub integer: usize,
-    pub decimal: usize,
+    pub negative: bool,
+    pub digits: String,
 }
 
 impl Debug for HirDecimal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{}", self.integer, self.decimal)
+        if self.negative {
+            write!(f, "-")?;
+        }
+
+        write!(f, "{}", self.digits)
     }
 }
 
@@ -50,6 +56,7 @@ pub enum HirLiteral {
     Int(usize, HirISize, HirISign),
     Decimal(HirFSize, HirDecimal),
     String(HirString),
+    Char(char),
 }
 
 impl HirLiteral {
@@ -2,30 +2,55 @@ use asena_hir_derive::*;
 
 use crate::{interner::HirInterner, literal::HirLiteral, Name};
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirPattern)]
 pub struct HirPatternConstructor {
     pub constructor_name: Name,
     pub arguments: Vec<HirPattern>,
 }
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+/// The items of the list, the rest of the list is matched by a [HirPatternKind::Spread] item,
+/// like `[x, .., y]`.
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirPattern)]
 pub struct HirPatternList {
     pub items: Vec<HirPattern>,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirPattern)]
 pub struct HirPatternName {
     pub name: Name,
 }
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirPattern)]
 pub struct HirPatternLiteral(pub HirLiteral);
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+/// The integers between the two literals, including both of them, like `1..10`.
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
+#[hir_node(HirPattern)]
+pub struct HirPatternRange {
+    pub start: HirLiteral,
+    pub end: HirLiteral,
+}
+
+/// Matches the value if any of the alternatives matches it, like `Just 1 | Just 2`.
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
+#[hir_node(HirPattern)]
+pub struct HirPatternOr {
+    pub alternatives: Vec<HirPattern>,
+}
+
+/// Binds the whole value to the name, besides the names of the pattern, like `xs@(Cons x _)`.
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
+#[hir_node(HirPattern)]
+pub struct HirPatternAs {
+    pub name: Name,
+    pub pattern: HirPattern,
+}
+
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_kind(HirPattern)]
 pub enum HirPatternKind {
     #[default]
@@ -38,6 +63,9 @@ pub enum HirPatternKind {
     List(HirPatternList),
     Name(HirPatternName),
     Literal(HirPatternLiteral),
+    Range(HirPatternRange),
+    Or(HirPatternOr),
+    As(HirPatternAs),
 }
 
 #[hir_struct]
@@ -71,6 +99,33 @@ impl HirPattern {
         })
     }
 
+    /// Returns the names that the pattern binds, in the order they appear.
+    pub fn bindings(self, db: &dyn HirInterner) -> Vec<Name> {
+        match db.lookup_intern_pattern(self).kind {
+            HirPatternKind::Name(pattern) => vec![pattern.name],
+            HirPatternKind::Constructor(constructor) => constructor
+                .arguments
+                .into_iter()
+                .flat_map(|argument| argument.bindings(db))
+                .collect(),
+            HirPatternKind::List(list) => list
+                .items
+                .into_iter()
+                .flat_map(|item| item.bindings(db))
+                .collect(),
+            // All the alternatives bind the same names, it's checked by the resolver.
+            HirPatternKind::Or(or) => or
+                .alternatives
+                .first()
+                .map(|alternative| alternative.bindings(db))
+                .unwrap_or_default(),
+            HirPatternKind::As(pattern) => std::iter::once(pattern.name)
+                .chain(pattern.pattern.bindings(db))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
     pub fn name(db: &dyn HirInterner, name: Name) -> HirPattern {
         let kind = HirPatternKind::from(HirPatternName { name });
 
@@ -90,7 +145,7 @@ impl HirPattern {
     }
 
     pub fn new_false(db: &dyn HirInterner) -> HirPattern {
-        let kind = HirPatternKind::from(HirPatternLiteral(HirLiteral::TRUE));
+        let kind = HirPatternKind::from(HirPatternLiteral(HirLiteral::FALSE));
 
         db.intern_pattern(HirPatternData {
             kind,
@@ -0,0 +1,511 @@
+//! S-expression printer for the HIR, it's useful to debug the lowering, and to write snapshot
+//! tests, since the HIR is interned, and the [Debug] implementations only show the ids.
+//!
+//! The printer is deterministic: nodes stored in hash sets and maps are sorted, so the output
+//! can be compared between runs.
+
+use std::iter::once;
+
+use itertools::Itertools;
+
+use crate::expr::data::{HirBranch, HirCallee, HirMatchCase};
+use crate::expr::{HirExpr, HirExprKind};
+use crate::hir_type::data::{HirTypeArgument, HirTypeFunction};
+use crate::hir_type::{HirType, HirTypeKind};
+use crate::interner::HirInterner;
+use crate::literal::HirLiteral;
+use crate::pattern::{HirPattern, HirPatternKind};
+use crate::stmt::{HirStmt, HirStmtKind};
+use crate::top_level::data::{
+    HirAssociatedType, HirDeclaration, HirParameterKind, HirSignature, HirVariant,
+};
+use crate::top_level::{HirBindingGroup, HirTopLevel, HirTopLevelKind};
+use crate::value::instr::HirInstr;
+use crate::value::monads::HirMonad;
+use crate::value::{HirValue, HirValueKind};
+use crate::Name;
+
+pub struct HirSexpr<'db> {
+    db: &'db dyn HirInterner,
+}
+
+impl<'db> HirSexpr<'db> {
+    pub fn new(db: &'db dyn HirInterner) -> Self {
+        Self { db }
+    }
+
+    /// Prints the given declarations, one per line, sorted by their position in the source code.
+    pub fn file<I: IntoIterator<Item = HirTopLevel>>(&self, declarations: I) -> String {
+        declarations
+            .into_iter()
+            .map(|top_level| {
+                let data = self.db.lookup_intern_top_level(top_level);
+                let start = data.span.original.range.start();
+
+                (start, self.top_level(top_level))
+            })
+            .sorted()
+            .map(|(_, sexpr)| sexpr)
+            .join("\n")
+    }
+
+    pub fn name(&self, name: Name) -> String {
+        name.to_string()
+    }
+
+    pub fn top_level(&self, top_level: HirTopLevel) -> String {
+        let data = self.db.lookup_intern_top_level(top_level);
+
+        match data.kind {
+            HirTopLevelKind::Error => "(error)".into(),
+            HirTopLevelKind::BindingGroup(ref group) => self.binding_group(group),
+            HirTopLevelKind::Enum(ref enum_decl) => {
+                let variants = enum_decl
+                    .variants
+                    .values()
+                    .map(|variant| self.variant(variant))
+                    .sorted();
+
+                format!(
+                    "(enum {} {} {})",
+                    self.signature(&enum_decl.signature),
+                    node(once("variants".into()).chain(variants)),
+                    self.groups(enum_decl.groups.iter()),
+                )
+            }
+            HirTopLevelKind::Struct(ref struct_decl) => format!(
+                "(struct {} {} {})",
+                self.signature(&struct_decl.signature),
+                self.fields(&struct_decl.fields),
+                self.groups(struct_decl.groups.iter()),
+            ),
+            HirTopLevelKind::Class(ref class_decl) => {
+                let superclasses = class_decl
+                    .superclasses
+                    .iter()
+                    .map(|superclass| self.hir_type(*superclass));
+
+                format!(
+                    "(class {} {} {} {} {})",
+                    self.signature(&class_decl.signature),
+                    node(once("superclasses".into()).chain(superclasses)),
+                    self.associated_types(&class_decl.associated_types),
+                    self.fields(&class_decl.fields),
+                    self.groups(class_decl.groups.iter()),
+                )
+            }
+            HirTopLevelKind::Trait(ref trait_decl) => format!(
+                "(trait {} {} {})",
+                self.signature(&trait_decl.signature),
+                self.associated_types(&trait_decl.associated_types),
+                self.groups(trait_decl.groups.values()),
+            ),
+            HirTopLevelKind::Instance(ref instance) => format!(
+                "(instance {} {} {} {})",
+                self.parameters(&instance.parameters),
+                self.hir_type(instance.signature),
+                self.associated_types(&instance.associated_types),
+                self.groups(instance.groups.iter()),
+            ),
+        }
+    }
+
+    pub fn binding_group(&self, group: &HirBindingGroup) -> String {
+        let declarations = group
+            .declarations
+            .iter()
+            .map(|declaration| self.declaration(declaration));
+        let head = format!("fun {}", self.signature(&group.signature));
+
+        node(once(head).chain(declarations))
+    }
+
+    pub fn hir_type(&self, hir_type: HirType) -> String {
+        let data = self.db.lookup_intern_type(hir_type);
+
+        match data.kind {
+            HirTypeKind::Error => "(error)".into(),
+            HirTypeKind::Unit => "()".into(),
+            HirTypeKind::This => "Self".into(),
+            HirTypeKind::Name(ref name) => self.name(name.name),
+            HirTypeKind::App(ref app) => {
+                let callee = match app.callee {
+                    HirTypeFunction::Error => "(error)".into(),
+                    HirTypeFunction::Pi => "->".into(),
+                    HirTypeFunction::Sigma => "**".into(),
+                    HirTypeFunction::Qual => "=>".into(),
+                    HirTypeFunction::Array => "[]".into(),
+                    HirTypeFunction::Type(callee) => self.hir_type(callee),
+                };
+                let arguments = app.arguments.iter().map(|argument| match argument {
+                    HirTypeArgument::Error => "(error)".into(),
+                    HirTypeArgument::Type(argument) => self.hir_type(*argument),
+                    HirTypeArgument::Named(name, argument) => {
+                        format!("({} : {})", self.name(*name), self.hir_type(*argument))
+                    }
+                });
+
+                node(once(callee).chain(arguments))
+            }
+            HirTypeKind::Forall(ref forall) => {
+                let variables = forall.variables.iter().map(|name| self.name(*name));
+
+                format!(
+                    "(forall {} {})",
+                    node(variables),
+                    self.hir_type(forall.value)
+                )
+            }
+        }
+    }
+
+    pub fn value(&self, value: HirValue) -> String {
+        let data = self.db.lookup_intern_value(value);
+
+        match data.kind {
+            HirValueKind::Error => "(error)".into(),
+            HirValueKind::Unit => "()".into(),
+            HirValueKind::Expr(ref expr) => self.expr(expr.0),
+            HirValueKind::Block(ref block) => {
+                let instructions = block.instructions.iter().map(|stmt| self.stmt(*stmt));
+                let value = self.value(block.value);
+
+                node(once("block".into()).chain(instructions).chain(once(value)))
+            }
+            HirValueKind::Monad(ref monad) => match monad {
+                HirMonad::PureUnit => "(pure ())".into(),
+                HirMonad::Pure(value) => format!("(pure {})", self.value(*value)),
+                HirMonad::Bind(name, value, then) => format!(
+                    "(bind {} {} {})",
+                    self.name(*name),
+                    self.value(*value),
+                    self.value(*then)
+                ),
+            },
+            HirValueKind::Instr(ref instr) => match instr {
+                HirInstr::Null => "(null)".into(),
+                HirInstr::Let(name, value) => {
+                    format!("(let {} {})", self.name(*name), self.value(*value))
+                }
+                HirInstr::Variable(name) => self.name(*name),
+                HirInstr::Block(block) => {
+                    let instructions = block.instructions.iter().map(|value| self.value(*value));
+                    let value = self.value(block.value);
+
+                    node(once("block".into()).chain(instructions).chain(once(value)))
+                }
+                HirInstr::ObjectClone(value) => format!("(clone {})", self.value(*value)),
+                HirInstr::ObjectDrop(value) => format!("(drop {})", self.value(*value)),
+            },
+        }
+    }
+
+    pub fn stmt(&self, stmt: HirStmt) -> String {
+        let data = self.db.lookup_intern_stmt(stmt);
+
+        match data.kind {
+            HirStmtKind::Error => "(error)".into(),
+            HirStmtKind::Ask(ref ask) => {
+                format!(
+                    "(ask {} {})",
+                    self.pattern(ask.pattern),
+                    self.value(ask.value)
+                )
+            }
+            HirStmtKind::Let(ref stmt) => {
+                format!(
+                    "(let {} {})",
+                    self.pattern(stmt.pattern),
+                    self.value(stmt.value)
+                )
+            }
+            HirStmtKind::Return(ref stmt) => format!("(return {})", self.value(stmt.value)),
+            HirStmtKind::Value(ref stmt) => self.value(stmt.0),
+        }
+    }
+
+    pub fn expr(&self, expr: HirExpr) -> String {
+        let data = self.db.lookup_intern_expr(expr);
+
+        match data.kind {
+            HirExprKind::Error => "(error)".into(),
+            HirExprKind::Unit => "()".into(),
+            HirExprKind::This => "self".into(),
+            HirExprKind::Group(ref group) => self.value(group.value),
+            HirExprKind::Literal(ref literal) => self.literal(&literal.0),
+            HirExprKind::Reference(ref reference) => self.name(reference.name),
+            HirExprKind::Call(ref call) => {
+                let callee = self.callee(&call.callee);
+                let arguments = call.arguments.iter().map(|value| self.value(*value));
+                let dsl = call.as_dsl.as_ref().map(|dsl| {
+                    let parameters = dsl.parameters.iter().map(|name| self.name(*name));
+
+                    format!("(dsl {} {})", node(parameters), self.value(dsl.value))
+                });
+
+                node(once(callee).chain(arguments).chain(dsl))
+            }
+            HirExprKind::Match(ref match_expr) => {
+                let cases = match_expr.cases.iter().map(|case| self.case(case));
+
+                let head = format!("match/{:?}", match_expr.kind);
+                let scrutinee = self.value(match_expr.scrutinee);
+
+                node(once(head).chain(once(scrutinee)).chain(cases))
+            }
+            HirExprKind::Help(ref help) => {
+                let bindings = help.bindings.iter().map(|name| self.name(*name));
+                let target = match (help.hole, help.value) {
+                    (Some(hole), _) => self.name(hole),
+                    (None, Some(value)) => self.value(value),
+                    (None, None) => "_".into(),
+                };
+
+                format!("(? {target} ({}))", bindings.collect::<Vec<_>>().join(" "))
+            }
+            HirExprKind::Ann(ref ann) => {
+                format!(
+                    "(: {} {})",
+                    self.value(ann.value),
+                    self.hir_type(ann.against)
+                )
+            }
+            HirExprKind::Lam(ref lam) => {
+                let parameters = lam.parameters.iter().map(|name| self.name(*name));
+
+                format!("(lambda {} {})", node(parameters), self.value(lam.value))
+            }
+            HirExprKind::Array(ref array) => {
+                let items = array.items.iter().map(|value| self.value(*value));
+
+                format!("[{}]", items.join(" "))
+            }
+        }
+    }
+
+    pub fn pattern(&self, pattern: HirPattern) -> String {
+        let data = self.db.lookup_intern_pattern(pattern);
+
+        match data.kind {
+            HirPatternKind::Error => "(error)".into(),
+            HirPatternKind::Wildcard => "_".into(),
+            HirPatternKind::Spread => "..".into(),
+            HirPatternKind::Unit => "()".into(),
+            HirPatternKind::This => "self".into(),
+            HirPatternKind::Constructor(ref constructor) => {
+                let arguments = constructor
+                    .arguments
+                    .iter()
+                    .map(|pattern| self.pattern(*pattern));
+
+                let head = self.name(constructor.constructor_name);
+
+                node(once(head).chain(arguments))
+            }
+            HirPatternKind::List(ref list) => {
+                let items = list.items.iter().map(|pattern| self.pattern(*pattern));
+
+                format!("[{}]", items.join(" "))
+            }
+            HirPatternKind::Name(ref name) => self.name(name.name),
+            HirPatternKind::Literal(ref literal) => self.literal(&literal.0),
+            HirPatternKind::Range(ref range) => {
+                format!(
+                    "{}..{}",
+                    self.literal(&range.start),
+                    self.literal(&range.end)
+                )
+            }
+            HirPatternKind::Or(ref or) => {
+                let alternatives = or.alternatives.iter().map(|pattern| self.pattern(*pattern));
+
+                node(once("or".into()).chain(alternatives))
+            }
+            HirPatternKind::As(ref pattern) => {
+                format!(
+                    "{}@{}",
+                    self.name(pattern.name),
+                    self.pattern(pattern.pattern)
+                )
+            }
+        }
+    }
+
+    pub fn literal(&self, literal: &HirLiteral) -> String {
+        match literal {
+            HirLiteral::Error => "(error)".into(),
+            HirLiteral::Int(value, size, sign) => format!("{value}{sign:?}{size:?}"),
+            HirLiteral::Decimal(size, value) => format!("{value:?}{size:?}"),
+            HirLiteral::String(string) => format!("{:?}", string.value),
+            HirLiteral::Char(value) => format!("{value:?}"),
+        }
+    }
+
+    fn callee(&self, callee: &HirCallee) -> String {
+        match callee {
+            HirCallee::Value(value) | HirCallee::Method(value) => self.value(*value),
+            HirCallee::Constructor(name) => self.name(*name),
+            callee => format!("{callee:?}"),
+        }
+    }
+
+    fn case(&self, case: &HirMatchCase) -> String {
+        let value = match case.value {
+            HirBranch::Error => "(error)".into(),
+            HirBranch::Expr(value) => self.value(value),
+            HirBranch::Block(value) => self.value(value),
+        };
+
+        format!("({} {value})", self.pattern(case.pattern))
+    }
+
+    fn signature(&self, signature: &HirSignature) -> String {
+        let return_type = match signature.return_type {
+            Some(return_type) => self.hir_type(return_type),
+            None => "_".into(),
+        };
+
+        format!(
+            "{} {} {return_type}",
+            self.name(signature.name),
+            self.parameters(&signature.parameters),
+        )
+    }
+
+    fn parameters(&self, parameters: &[HirParameterKind]) -> String {
+        let parameters = parameters.iter().map(|parameter| match parameter {
+            HirParameterKind::Error => "(error)".into(),
+            HirParameterKind::This => "self".into(),
+            HirParameterKind::Explicit(data) | HirParameterKind::Implicit(data) => {
+                let parameter_type = match data.parameter_type {
+                    Some(parameter_type) => self.hir_type(parameter_type),
+                    None => "_".into(),
+                };
+                let name = self.name(data.name);
+
+                match parameter {
+                    HirParameterKind::Implicit(..) => format!("[{name} : {parameter_type}]"),
+                    _ => format!("({name} : {parameter_type})"),
+                }
+            }
+        });
+
+        node(parameters)
+    }
+
+    fn declaration(&self, declaration: &HirDeclaration) -> String {
+        let patterns = declaration
+            .patterns
+            .iter()
+            .map(|pattern| self.pattern(*pattern));
+
+        format!("(= {} {})", node(patterns), self.value(declaration.value))
+    }
+
+    fn variant(&self, variant: &HirVariant) -> String {
+        format!(
+            "({} {})",
+            self.name(variant.name),
+            self.hir_type(variant.variant_type)
+        )
+    }
+
+    fn fields(&self, fields: &im::HashMap<Name, HirType>) -> String {
+        let fields = fields
+            .iter()
+            .map(|(name, field_type)| {
+                format!("({} {})", self.name(*name), self.hir_type(*field_type))
+            })
+            .sorted();
+
+        node(once("fields".into()).chain(fields))
+    }
+
+    fn associated_types(&self, types: &im::HashMap<Name, HirAssociatedType>) -> String {
+        let types = types
+            .values()
+            .map(|associated_type| match associated_type.value {
+                Some(value) => {
+                    format!(
+                        "({} {})",
+                        self.name(associated_type.name),
+                        self.hir_type(value)
+                    )
+                }
+                None => format!("({})", self.name(associated_type.name)),
+            })
+            .sorted();
+
+        node(once("types".into()).chain(types))
+    }
+
+    fn groups<'a, I: Iterator<Item = &'a HirBindingGroup>>(&self, groups: I) -> String {
+        let groups = groups.map(|group| self.binding_group(group)).sorted();
+
+        node(once("methods".into()).chain(groups))
+    }
+}
+
+/// The HIR ids that can be printed with [HirSexpr].
+pub trait HirSexprNode: Copy {
+    fn sexpr(self, printer: &HirSexpr) -> String;
+}
+
+impl HirSexprNode for HirTopLevel {
+    fn sexpr(self, printer: &HirSexpr) -> String {
+        printer.top_level(self)
+    }
+}
+
+impl HirSexprNode for HirType {
+    fn sexpr(self, printer: &HirSexpr) -> String {
+        printer.hir_type(self)
+    }
+}
+
+impl HirSexprNode for HirValue {
+    fn sexpr(self, printer: &HirSexpr) -> String {
+        printer.value(self)
+    }
+}
+
+impl HirSexprNode for HirStmt {
+    fn sexpr(self, printer: &HirSexpr) -> String {
+        printer.stmt(self)
+    }
+}
+
+impl HirSexprNode for HirExpr {
+    fn sexpr(self, printer: &HirSexpr) -> String {
+        printer.expr(self)
+    }
+}
+
+impl HirSexprNode for HirPattern {
+    fn sexpr(self, printer: &HirSexpr) -> String {
+        printer.pattern(self)
+    }
+}
+
+impl HirSexprNode for Name {
+    fn sexpr(self, printer: &HirSexpr) -> String {
+        printer.name(self)
+    }
+}
+
+/// Dumps the nodes of the database recursively, with the interned names substituted, like
+/// `db.dump(expr)`. It's meant for debugging, as the ids are only printed like `Expr#42`.
+pub trait HirDump {
+    fn dump<T: HirSexprNode>(&self, id: T) -> String;
+}
+
+impl<DB: HirInterner> HirDump for DB {
+    fn dump<T: HirSexprNode>(&self, id: T) -> String {
+        id.sexpr(&HirSexpr::new(self))
+    }
+}
+
+/// Joins the items with spaces, wrapping them in parenthesis.
+fn node<I: IntoIterator<Item = String>>(items: I) -> String {
+    format!("({})", items.into_iter().join(" "))
+}
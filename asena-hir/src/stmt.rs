@@ -2,31 +2,31 @@ use asena_hir_derive::*;
 
 use crate::{pattern::HirPattern, value::HirValue};
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirStmt)]
 pub struct HirStmtAsk {
     pub pattern: HirPattern,
     pub value: HirValue,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirStmt)]
 pub struct HirStmtLet {
     pub pattern: HirPattern,
     pub value: HirValue,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirStmt)]
 pub struct HirStmtReturn {
     pub value: HirValue,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirStmt)]
 pub struct HirStmtValue(pub HirValue);
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_kind(HirStmt)]
 pub enum HirStmtKind {
     #[default]
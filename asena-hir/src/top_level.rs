@@ -2,7 +2,7 @@ use asena_hir_derive::*;
 
 use crate::{attr::HirAttr, hir_type::HirType, *};
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirTopLevel)]
 pub struct HirTopLevelEnum {
     pub signature: data::HirSignature,
@@ -10,7 +10,7 @@ pub struct HirTopLevelEnum {
     pub groups: im::HashSet<HirBindingGroup>,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirTopLevel)]
 pub struct HirTopLevelStruct {
     pub signature: data::HirSignature,
@@ -18,29 +18,86 @@ pub struct HirTopLevelStruct {
     pub groups: im::HashSet<HirBindingGroup>,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
+#[hir_node(HirTopLevel)]
+pub struct HirTopLevelClass {
+    pub signature: data::HirSignature,
+    pub fields: im::HashMap<Name, HirType>,
+
+    /// The constraints of the `where` clause, like `Functor m` in
+    /// `class Monad (m) where Functor m`.
+    pub superclasses: Vec<HirType>,
+    pub associated_types: im::HashMap<Name, data::HirAssociatedType>,
+    pub groups: im::HashSet<HirBindingGroup>,
+}
+
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirTopLevel)]
 pub struct HirTopLevelInstance {
     pub parameters: Vec<data::HirParameterKind>,
+
+    /// The trait that is implemented, like `Functor` in `instance Functor Maybe`, it's [None]
+    /// if the head of the instance isn't an application of a trait.
+    pub trait_name: Option<Name>,
+
+    /// The arguments of the trait in the head, like `Maybe` in `instance Functor Maybe`.
+    pub arguments: Vec<HirType>,
     pub signature: HirType,
+
+    /// The definitions of the associated types of the trait, like `type Item = Int32`.
+    pub associated_types: im::HashMap<Name, data::HirAssociatedType>,
     pub groups: im::HashSet<HirBindingGroup>,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+impl HirTopLevelInstance {
+    /// Builds the dictionary of the instance, the record of the methods that is passed to the
+    /// functions constrained by the trait.
+    pub fn dictionary(&self) -> Option<data::HirDictionary> {
+        let methods = self
+            .groups
+            .iter()
+            .map(|group| (group.signature.name, group.clone()))
+            .collect();
+
+        Some(data::HirDictionary {
+            trait_name: self.trait_name?,
+            arguments: self.arguments.clone(),
+            methods,
+        })
+    }
+}
+
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirTopLevel)]
 pub struct HirTopLevelTrait {
     pub signature: data::HirSignature,
+
+    /// The associated types of the trait, that the instances define, like `type Item`.
+    pub associated_types: im::HashMap<Name, data::HirAssociatedType>,
     pub groups: im::HashMap<Name, HirBindingGroup>,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+impl HirTopLevelTrait {
+    /// Returns the default implementation of the method, that the instances without their own
+    /// implementation inherit, the abstract fields without a default don't have declarations.
+    pub fn default_method(&self, name: Name) -> Option<&HirBindingGroup> {
+        self.groups
+            .get(&name)
+            .filter(|group| !group.declarations.is_empty())
+    }
+}
+
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirTopLevel)]
 pub struct HirBindingGroup {
     pub signature: data::HirSignature,
-    pub declarations: im::HashSet<data::HirDeclaration>,
+
+    /// The equations of the function, in the order they're declared, the first one whose
+    /// patterns match the arguments is chosen.
+    pub declarations: Vec<data::HirDeclaration>,
 }
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_kind(HirTopLevel)]
 pub enum HirTopLevelKind {
     #[default]
@@ -48,6 +105,7 @@ pub enum HirTopLevelKind {
     Instance(HirTopLevelInstance),
     Enum(HirTopLevelEnum),
     Struct(HirTopLevelStruct),
+    Class(HirTopLevelClass),
     Trait(HirTopLevelTrait),
     BindingGroup(HirBindingGroup),
 }
@@ -66,18 +124,18 @@ pub mod data {
 
     use super::*;
 
-    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub struct HirDoc {
         pub text: String,
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub struct HirParameterData {
         pub name: Name,
         pub parameter_type: Option<HirType>,
     }
 
-    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub enum HirParameterKind {
         #[default]
         Error,
@@ -86,20 +144,38 @@ pub mod data {
         Implicit(HirParameterData),
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub struct HirSignature {
         pub name: Name,
         pub parameters: Vec<HirParameterKind>,
         pub return_type: Option<HirType>,
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub struct HirDeclaration {
         pub patterns: Vec<HirPattern>,
         pub value: HirValue,
     }
 
-    #[derive(Hash, Clone, Debug, PartialEq, Eq)]
+    /// The dictionary-passing representation of an instance: the instance is a record of its
+    /// methods, and the calls of trait methods are compiled into lookups of the methods in the
+    /// dictionary, that is received as an implicit parameter by the constrained functions.
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
+    pub struct HirDictionary {
+        pub trait_name: Name,
+        pub arguments: Vec<HirType>,
+        pub methods: im::HashMap<Name, HirBindingGroup>,
+    }
+
+    /// An associated type of a trait, or a class, the value is the definition of it in the
+    /// instances, and it's [None] in the declarations.
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
+    pub struct HirAssociatedType {
+        pub name: Name,
+        pub value: Option<HirType>,
+    }
+
+    #[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
     pub struct HirVariant {
         pub name: Name,
         pub variant_type: HirType,
@@ -2,6 +2,7 @@ use asena_hir_derive::*;
 
 use crate::expr::*;
 use crate::interner::HirInterner;
+use crate::literal::HirLiteral;
 use crate::stmt::*;
 
 use self::{instr::HirInstr, monads::HirMonad};
@@ -9,18 +10,18 @@ use self::{instr::HirInstr, monads::HirMonad};
 pub mod instr;
 pub mod monads;
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirValue)]
 pub struct HirValueBlock {
     pub instructions: Vec<HirStmt>,
     pub value: HirValue,
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirValue)]
 pub struct HirValueExpr(pub HirExpr);
 
-#[derive(Default, Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_kind(HirValue)]
 pub enum HirValueKind {
     #[default]
@@ -60,4 +61,20 @@ impl HirValue {
             span: Default::default(),
         })
     }
+
+    /// Creates the boolean value, that is represented by the `U1` literals.
+    pub fn of_bool(db: &dyn HirInterner, value: bool) -> HirValue {
+        let literal = if value {
+            HirLiteral::TRUE
+        } else {
+            HirLiteral::FALSE
+        };
+
+        let expr = db.intern_expr(HirExprData {
+            kind: HirExprKind::from(HirExprLiteral(literal)),
+            span: Default::default(),
+        });
+
+        HirValue::of_expr(db, expr)
+    }
 }
@@ -2,7 +2,7 @@ use crate::Name;
 
 use super::*;
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirValue)]
 pub enum HirInstr {
     Null,
@@ -13,7 +13,7 @@ pub enum HirInstr {
     ObjectDrop(HirValue),  // Object.drop
 }
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 pub struct Block {
     pub instructions: Vec<HirValue>,
     pub value: HirValue,
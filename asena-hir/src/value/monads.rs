@@ -2,7 +2,7 @@ use crate::Name;
 
 use super::*;
 
-#[derive(Hash, Clone, Debug, PartialEq, Eq)]
+#[derive(Hash, Clone, Debug, PartialEq, Eq, HirEq)]
 #[hir_node(HirValue)]
 pub enum HirMonad {
     PureUnit,
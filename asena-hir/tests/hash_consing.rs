@@ -0,0 +1,47 @@
+use asena_ast_db::{db::*, fixture::memory_file};
+use asena_hir::expr::{HirExpr, HirExprKind};
+use asena_hir::hash_consing::{self, HashConsingStatistics};
+use asena_hir::{interner::HirStorage, HirLoc};
+
+#[salsa::database(AstDatabaseStorage, HirStorage)]
+#[derive(Default)]
+struct TestDatabase {
+    storage: salsa::Storage<TestDatabase>,
+}
+
+impl salsa::Database for TestDatabase {}
+
+#[test]
+fn hash_consing_works() {
+    let db = TestDatabase::default();
+    let span = HirLoc {
+        file: Some(memory_file(&db, "")),
+        ..Default::default()
+    };
+
+    let expr = HirExpr::new(&db, HirExprKind::Unit, HirLoc::default());
+    assert_ne!(expr, HirExpr::new(&db, HirExprKind::Unit, span));
+
+    let expr = HirExpr::hash_consed(&db, HirExprKind::This);
+    let same_expr = HirExpr::hash_consed(&db, HirExprKind::This);
+
+    assert_eq!(expr, same_expr);
+    let statistics = hash_consing::take_statistics(&db);
+    assert_eq!(
+        statistics,
+        HashConsingStatistics {
+            requests: 2,
+            hits: 1
+        }
+    );
+    assert_eq!(statistics.hit_rate(), 0.5);
+    assert_eq!(
+        hash_consing::take_statistics(&db),
+        HashConsingStatistics::default()
+    );
+
+    // The statistics are of the database
+    let other_db = TestDatabase::default();
+    HirExpr::hash_consed(&other_db, HirExprKind::This);
+    assert_eq!(hash_consing::take_statistics(&other_db).hits, 0);
+}
@@ -0,0 +1,43 @@
+use asena_ast_db::db::*;
+use asena_hir::expr::{HirExpr, HirExprKind};
+use asena_hir::interner::HirStorage;
+use asena_hir::sexpr::HirDump;
+use asena_hir::value::HirValue;
+use asena_hir::{HirLoc, Name};
+
+#[salsa::database(AstDatabaseStorage, HirStorage)]
+#[derive(Default)]
+struct TestDatabase {
+    storage: salsa::Storage<TestDatabase>,
+}
+
+impl salsa::Database for TestDatabase {}
+
+#[test]
+fn hir_ids_works() {
+    let db = TestDatabase::default();
+
+    let unit = HirExpr::new(&db, HirExprKind::Unit, HirLoc::default());
+    let this = HirExpr::new(&db, HirExprKind::This, HirLoc::default());
+
+    // The ids are printed with the index of the interner, without the `Hir` prefix
+    assert_eq!(unit.to_string(), "Expr#0");
+    assert_eq!(format!("{this:?}"), "Expr#1");
+    assert_eq!(this.index(), 1);
+
+    let value = HirValue::of_expr(&db, this);
+    assert_eq!(format!("{value:?}"), "Value#0");
+}
+
+#[test]
+fn hir_dump_works() {
+    let db = TestDatabase::default();
+
+    let this = HirExpr::new(&db, HirExprKind::This, HirLoc::default());
+    let value = HirValue::of_expr(&db, this);
+
+    assert_eq!(db.dump(this), "self");
+    assert_eq!(db.dump(value), "self");
+    assert_eq!(db.dump(HirValue::unit(&db)), "()");
+    assert_eq!(db.dump(Name::intern("Maybe")), "Maybe");
+}
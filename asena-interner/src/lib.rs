@@ -0,0 +1,130 @@
+//! The interner of the names of the compiler, like the identifiers and the operators. The lexer,
+//! the syntax tree, the scopes and the HIR share the same [Symbol]s, so comparing names is
+//! comparing ids, and the names aren't allocated again on every reference.
+//!
+//! The interned strings are never freed, so only the names are interned, and the texts that
+//! aren't repeated, like the literals, are owned by their tokens.
+
+use std::{
+    fmt::{Debug, Display},
+    ops::Deref,
+    sync::{OnceLock, RwLock},
+};
+
+use fxhash::FxHashMap;
+
+/// Interned string, the strings are never freed, so they can be borrowed for the whole program.
+/// The default symbol is the empty string.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+#[derive(Default)]
+struct Interner {
+    ids: FxHashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+impl Interner {
+    /// Returns the global interner, the empty string is always the first symbol, so it's the
+    /// default one.
+    fn global() -> &'static RwLock<Interner> {
+        static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
+
+        INTERNER.get_or_init(|| {
+            let mut interner = Interner::default();
+            interner.ids.insert("", Symbol(0));
+            interner.strings.push("");
+            RwLock::new(interner)
+        })
+    }
+}
+
+impl Symbol {
+    /// Interns the string, if it was already interned, it returns the same symbol.
+    pub fn intern(string: &str) -> Self {
+        let interner = Interner::global();
+        if let Some(symbol) = interner.read().unwrap().ids.get(string) {
+            return *symbol;
+        }
+
+        let mut interner = interner.write().unwrap();
+        // Another thread could have interned it while the lock was released
+        if let Some(symbol) = interner.ids.get(string) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(interner.strings.len() as u32);
+        let string: &'static str = Box::leak(string.into());
+        interner.ids.insert(string, symbol);
+        interner.strings.push(string);
+        symbol
+    }
+
+    /// Gets the interned string of the symbol.
+    pub fn as_str(&self) -> &'static str {
+        Interner::global().read().unwrap().strings[self.0 as usize]
+    }
+
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the amount of the interned strings, and the bytes of their contents, and of the
+    /// tables of the interner. The strings are never freed, so it only grows.
+    pub fn memory_usage() -> (usize, usize) {
+        let interner = Interner::global().read().unwrap();
+        let contents = interner
+            .strings
+            .iter()
+            .map(|string| string.len())
+            .sum::<usize>();
+        let strings = interner.strings.capacity() * std::mem::size_of::<&'static str>();
+        let ids = interner.ids.capacity() * std::mem::size_of::<(&'static str, Symbol)>();
+
+        (interner.strings.len(), contents + strings + ids)
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Symbol::intern(value)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Symbol::intern(&value)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
@@ -1,6 +1,9 @@
 use std::borrow::Cow;
 use std::fmt::{Debug, Display};
-use std::ops::{ControlFlow, Deref, DerefMut, FromResidual, Try};
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "nightly")]
+use std::ops::{ControlFlow, FromResidual, Try};
 
 use asena_span::{Loc, Span, Spanned};
 
@@ -159,12 +162,12 @@ pub trait Node: Sized + Debug + Clone {
 
 impl<T: Located> Located for Vec<T> {
     fn location(&self) -> Cow<'_, Loc> {
-        if self.is_empty() {
+        let (Some(first), Some(last)) = (self.first(), self.last()) else {
             return Cow::Owned(Loc::default());
-        }
+        };
 
-        let start = self.first().unwrap().location().into_owned();
-        let end = self.last().unwrap().location().into_owned();
+        let start = first.location().into_owned();
+        let end = last.location().into_owned();
 
         Cow::Owned(start.on(end))
     }
@@ -174,11 +177,12 @@ impl<T: Terminal + 'static> Leaf for T {
     fn make(tree: GreenTree) -> Option<Self> {
         match tree.into_data() {
             GreenTreeKind::Leaf(leaf) => {
-                if leaf.data.children.is_empty() {
+                let Some(token) = leaf.data.single().cloned() else {
+                    tracing::trace!(kind = ?leaf.data.kind, "the leaf has no single token");
                     return None;
-                }
+                };
 
-                Leaf::terminal(leaf.data.replace(leaf.data.single().clone()))
+                Leaf::terminal(leaf.data.replace(token))
             }
             GreenTreeKind::Token(lexeme) => Leaf::terminal(lexeme.token),
             _ => None,
@@ -220,9 +224,14 @@ impl<T: Leaf> Leaf for Vec<T> {
                 }
                 Some(items)
             }
+            // The tokens are built with [Leaf::terminal], like in the leaves, as the terminal
+            // wrappers, like [Lexeme], can't be made from a token node.
             GreenTreeKind::Vec(children) => children
                 .into_iter()
-                .filter_map(|child| T::make(child))
+                .filter_map(|child| match child.into_data() {
+                    GreenTreeKind::Token(lexeme) => T::terminal(lexeme.token),
+                    data => T::make(data.into()),
+                })
                 .collect::<Vec<_>>()
                 .into(),
             GreenTreeKind::Token(_) => None,
@@ -232,6 +241,8 @@ impl<T: Leaf> Leaf for Vec<T> {
     }
 }
 
+pub mod arena;
+
 mod cursor;
 mod green;
 mod key;
@@ -241,6 +252,7 @@ mod tree;
 mod visitor;
 mod walk;
 
+pub use arena::{ArenaGuard, Metadata, NodeArena, NodeId, StorageMode};
 pub use cursor::*;
 pub use green::*;
 pub use key::*;
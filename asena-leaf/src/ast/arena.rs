@@ -0,0 +1,324 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use dashmap::DashMap;
+
+use super::LeafKey;
+
+/// Dynamic metadata stored in the [NodeArena], like the memoized named children, and the
+/// values set using [super::Key].
+pub type Metadata = Arc<dyn Any + Send + Sync>;
+
+/// Small index of a node in the [NodeArena], it's 64 bits wide, so the ids of a long running
+/// session, like the language server, don't wrap around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u64);
+
+/// The metadata of a single node, the vectors are really small, usually the node have at most
+/// four named children, so a linear search is faster than hashing.
+#[derive(Default)]
+pub(crate) struct NodeMeta {
+    keys: Vec<(LeafKey, Metadata)>,
+    names: Vec<(LeafKey, Metadata)>,
+}
+
+/// Central storage for the node's metadata, instead of every node allocating its own hash maps,
+/// they hold a [NodeId], and the metadata is only allocated when it's really used.
+///
+/// The arenas are owned by the databases, and the nodes are stored in the arena entered with
+/// [NodeArena::enter] when they're created. The entries are removed when the last clone of the
+/// node handle is dropped.
+pub struct NodeArena {
+    next_id: AtomicU64,
+    nodes: DashMap<NodeId, NodeMeta>,
+    mode: StorageMode,
+}
+
+/// Where the metadata of the nodes created in an arena is stored, it's selected when the arena is
+/// created, see [NodeArena::with_mode].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageMode {
+    /// In the entered [NodeArena], the nodes only hold a [NodeId], and the metadata is only
+    /// allocated when it's used. It's the best for the long running sessions, like the language
+    /// server, where most of the nodes are never queried. The nodes created outside of an arena
+    /// store their metadata inline.
+    #[default]
+    Arena,
+
+    /// In a plain [Arc] in the node, it's allocated for every node, but it skips the hashing and
+    /// the locking of the arena, that can cost more than it saves in the one-shot batch compiles,
+    /// that query every node.
+    Inline,
+}
+
+thread_local! {
+    /// The arenas entered by the current thread, the new nodes are stored in the last one.
+    static ENTERED: RefCell<Vec<Arc<NodeArena>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Leaves the arena entered with [NodeArena::enter] when it's dropped, it can't be sent to other
+/// threads, as the arena is entered only in the current one.
+pub struct ArenaGuard(PhantomData<*const ()>);
+
+impl Drop for ArenaGuard {
+    fn drop(&mut self) {
+        ENTERED.with(|entered| entered.borrow_mut().pop());
+    }
+}
+
+/// The node stored in a [NodeArena], its metadata is released when it's dropped, that is, when
+/// the last clone of the node handle is dropped, as it's reference counted.
+pub(crate) struct ArenaNode {
+    arena: Arc<NodeArena>,
+    id: NodeId,
+}
+
+impl Drop for ArenaNode {
+    fn drop(&mut self) {
+        self.arena.release(self.id);
+    }
+}
+
+/// Reference counted handle to the metadata of a node, the clones of a node share the same
+/// handle, so they share the same metadata.
+#[derive(Clone)]
+pub(crate) enum NodeHandle {
+    Arena(Arc<ArenaNode>),
+    Inline(Arc<RwLock<NodeMeta>>),
+}
+
+impl Default for NodeArena {
+    /// Creates an arena in [StorageMode::Arena], unless the `inline-metadata` feature is enabled.
+    fn default() -> Self {
+        if cfg!(feature = "inline-metadata") {
+            Self::with_mode(StorageMode::Inline)
+        } else {
+            Self::with_mode(StorageMode::Arena)
+        }
+    }
+}
+
+impl NodeArena {
+    /// Creates an arena, the nodes created while it's entered store their metadata as the mode
+    /// says.
+    pub fn with_mode(mode: StorageMode) -> Self {
+        Self {
+            next_id: AtomicU64::new(0),
+            nodes: DashMap::new(),
+            mode,
+        }
+    }
+
+    /// Enters the arena in the current thread, the nodes created until the guard is dropped store
+    /// their metadata in it, like the nodes of the trees built by the queries of a database.
+    pub fn enter(self: &Arc<Self>) -> ArenaGuard {
+        ENTERED.with(|entered| entered.borrow_mut().push(self.clone()));
+        ArenaGuard(PhantomData)
+    }
+
+    /// Returns the arena entered by the current thread, if there's any.
+    pub fn current() -> Option<Arc<NodeArena>> {
+        ENTERED.with(|entered| entered.borrow().last().cloned())
+    }
+
+    /// Returns where the metadata of the nodes created in the arena is stored.
+    pub fn mode(&self) -> StorageMode {
+        self.mode
+    }
+
+    /// Allocates a new [NodeId], it does not allocate any metadata.
+    pub fn alloc(&self) -> NodeId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        assert_ne!(id, u64::MAX, "the node ids of the arena were exhausted");
+
+        NodeId(id)
+    }
+
+    /// Returns the amount of nodes that have metadata stored in the arena.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the amount of nodes that have metadata stored in the arena, and the approximate
+    /// bytes of their entries. The values of the metadata are shared, so they aren't counted, and
+    /// neither are the nodes in [StorageMode::Inline].
+    pub fn memory_usage(&self) -> (usize, usize) {
+        let slot = std::mem::size_of::<(LeafKey, Metadata)>();
+        let bytes = self
+            .nodes
+            .iter()
+            .map(|meta| {
+                let slots = meta.keys.capacity() + meta.names.capacity();
+                std::mem::size_of::<(NodeId, NodeMeta)>() + slots * slot
+            })
+            .sum();
+
+        (self.nodes.len(), bytes)
+    }
+
+    pub fn key(&self, id: NodeId, name: LeafKey) -> Option<Metadata> {
+        let meta = self.nodes.get(&id)?;
+        find_slot(&meta.keys, name)
+    }
+
+    pub fn name(&self, id: NodeId, name: LeafKey) -> Option<Metadata> {
+        let meta = self.nodes.get(&id)?;
+        find_slot(&meta.names, name)
+    }
+
+    pub fn insert_key(&self, id: NodeId, name: LeafKey, value: Metadata) {
+        // The old value is dropped only after the shard lock is released, because dropping a
+        // metadata can drop nodes, and they remove themselves from the arena.
+        let old = {
+            let mut meta = self.nodes.entry(id).or_default();
+            replace_slot(&mut meta.keys, name, value)
+        };
+
+        drop(old);
+    }
+
+    pub fn insert_name(&self, id: NodeId, name: LeafKey, value: Metadata) {
+        let old = {
+            let mut meta = self.nodes.entry(id).or_default();
+            replace_slot(&mut meta.names, name, value)
+        };
+
+        drop(old);
+    }
+
+    /// Removes all the metadata of the given node.
+    pub fn release(&self, id: NodeId) {
+        let old = self.nodes.remove(&id);
+
+        drop(old);
+    }
+
+    /// Removes all the metadata stored in the arena.
+    pub fn clear(&self) {
+        let ids = self
+            .nodes
+            .iter()
+            .map(|entry| *entry.key())
+            .collect::<Vec<_>>();
+
+        for id in ids {
+            self.release(id);
+        }
+    }
+}
+
+impl std::fmt::Debug for NodeArena {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeArena")
+            .field("nodes", &self.nodes.len())
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+/// The arenas are compared by identity, as they're mutable storage shared by the nodes.
+impl PartialEq for NodeArena {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for NodeArena {}
+
+impl NodeHandle {
+    pub(crate) fn new() -> Self {
+        match NodeArena::current() {
+            Some(arena) if arena.mode == StorageMode::Arena => {
+                let id = arena.alloc();
+
+                Self::Arena(Arc::new(ArenaNode { arena, id }))
+            }
+            _ => Self::Inline(Default::default()),
+        }
+    }
+
+    pub(crate) fn key(&self, name: LeafKey) -> Option<Metadata> {
+        match self {
+            Self::Arena(node) => node.arena.key(node.id, name),
+            Self::Inline(meta) => find_slot(&meta.read().unwrap().keys, name),
+        }
+    }
+
+    pub(crate) fn name(&self, name: LeafKey) -> Option<Metadata> {
+        match self {
+            Self::Arena(node) => node.arena.name(node.id, name),
+            Self::Inline(meta) => find_slot(&meta.read().unwrap().names, name),
+        }
+    }
+
+    pub(crate) fn insert_key(&self, name: LeafKey, value: Metadata) {
+        match self {
+            Self::Arena(node) => node.arena.insert_key(node.id, name, value),
+            Self::Inline(meta) => {
+                let old = replace_slot(&mut meta.write().unwrap().keys, name, value);
+
+                drop(old);
+            }
+        }
+    }
+
+    pub(crate) fn insert_name(&self, name: LeafKey, value: Metadata) {
+        match self {
+            Self::Arena(node) => node.arena.insert_name(node.id, name, value),
+            Self::Inline(meta) => {
+                let old = replace_slot(&mut meta.write().unwrap().names, name, value);
+
+                drop(old);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for NodeHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Arena(node) => f.debug_tuple("Arena").field(&node.id).finish(),
+            Self::Inline(_) => f.debug_tuple("Inline").finish(),
+        }
+    }
+}
+
+impl PartialEq for NodeHandle {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Arena(a), Self::Arena(b)) => Arc::ptr_eq(a, b),
+            (Self::Inline(a), Self::Inline(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for NodeHandle {}
+
+fn find_slot(slots: &[(LeafKey, Metadata)], name: LeafKey) -> Option<Metadata> {
+    slots
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.clone())
+}
+
+fn replace_slot(
+    slots: &mut Vec<(LeafKey, Metadata)>,
+    name: LeafKey,
+    value: Metadata,
+) -> Option<Metadata> {
+    match slots.iter_mut().find(|(key, _)| *key == name) {
+        Some((_, slot)) => Some(std::mem::replace(slot, value)),
+        None => {
+            slots.push((name, value));
+            None
+        }
+    }
+}
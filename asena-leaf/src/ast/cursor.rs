@@ -1,6 +1,6 @@
 use std::{
     marker::PhantomData,
-    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
 use super::*;
@@ -21,8 +21,16 @@ impl<T: Leaf> Cursor<T> {
 
     /// Updates the value of the current cursor with a new [Cursor].
     pub fn set(&self, new_value: Cursor<T>) {
-        let mut value = self.write();
-        *value = new_value.read().clone();
+        self.transaction().set(&new_value).commit();
+    }
+
+    /// Starts a new [Transaction] with a snapshot of the current value, the mutations are only
+    /// visible after [Transaction::commit].
+    pub fn transaction(&self) -> Transaction<'_, T> {
+        Transaction {
+            cursor: self,
+            value: self.snapshot(),
+        }
     }
 
     /// Creates a new cursor with a reference to the `concrete syntax tree`, using
@@ -33,7 +41,7 @@ impl<T: Leaf> Cursor<T> {
 
     /// Deeply duplicates the current cursor and returns a new [Cursor] instance.
     pub fn as_new_node(&self) -> Self {
-        let new_node = self.read().as_new_node();
+        let new_node = self.snapshot().as_new_node();
         Self::new_raw(Self::new_inner(new_node.into()))
     }
 }
@@ -41,34 +49,43 @@ impl<T: Leaf> Cursor<T> {
 impl<T: Node + Leaf> Cursor<T> {
     /// Updates the value of the current cursor with a new [T].
     pub fn replace(&self, new_value: T) {
-        let mut value = self.write();
-        *value = new_value.unwrap();
-    }
-
-    pub fn location(&self) -> Spanned<T>
-    where
-        T: Located + 'static,
-    {
-        match self.read().data() {
-            GreenTreeKind::Token(lexeme) => {
-                let Some(value) = lexeme.value.downcast_ref::<T>() else {
-                    return Default::default();
-                };
-
-                lexeme.token.clone().swap(value.clone())
-            }
-            tree @ GreenTreeKind::Leaf(leaf) => leaf.data.replace(T::new(tree.clone())),
-            _ => Spanned::default(),
+        self.transaction().replace(new_value).commit();
+    }
+
+    /// Returns an immutable snapshot of the current value, if the cursor is empty, or the value
+    /// can't be built as [T], it returns the default value.
+    pub fn get(&self) -> T {
+        self.try_get().unwrap_or_default()
+    }
+
+    /// Returns an immutable snapshot of the current value, or a [CursorError] if the cursor is
+    /// empty, or the value can't be built as [T].
+    pub fn try_get(&self) -> Result<T, CursorError> {
+        let tree = self.snapshot();
+        match tree.data() {
+            GreenTreeKind::Token(lexeme) => match lexeme.value.downcast_ref::<T>() {
+                Some(value) => Ok(value.clone()),
+                None => Err(CursorError::Mismatch),
+            },
+            GreenTreeKind::Empty => Err(CursorError::Empty),
+            GreenTreeKind::None => Err(CursorError::Empty),
+            _ => Ok(T::new(tree)),
         }
     }
 
+    /// Returns the location of the current value, the lists span from their first to their last
+    /// items, and the empty cursors have the default location.
+    pub fn span(&self) -> Loc {
+        self.snapshot().location().into_owned()
+    }
+
     pub fn is_none(&self) -> bool {
-        matches!(self.read().data(), GreenTreeKind::None)
+        matches!(self.snapshot().data(), GreenTreeKind::None)
     }
 
     /// Returns the current cursor if it's not empty, otherwise returns false.
     pub fn is_empty(&self) -> bool {
-        match self.read().data() {
+        match self.snapshot().data() {
             GreenTreeKind::Leaf(leaf) => !leaf.data.children.is_empty(),
             GreenTreeKind::Vec(children) => !children.is_empty(),
             _ => false,
@@ -82,15 +99,21 @@ impl<T: Node + Leaf> Cursor<T> {
 
     /// Returns the current cursor if it's not empty, otherwise returns a default value.
     pub fn as_leaf(&self) -> T {
-        T::new(self.read().clone())
+        T::new(self.snapshot())
     }
 
     pub fn as_new_leaf<U: Node + Leaf>(&self) -> U {
-        U::new(self.read().clone())
+        U::new(self.snapshot())
     }
 
     pub fn make_as_leaf(&self) -> Option<T> {
-        T::make(self.read().clone())
+        T::make(self.snapshot())
+    }
+
+    /// Returns the current value, if the cursor is not empty. It's the stable alternative to the
+    /// `?` operator on cursors, and it's used by the [crate::cursor_try] macro.
+    pub fn try_as_leaf(&self) -> Option<T> {
+        self.try_get().ok()
     }
 }
 
@@ -192,15 +215,17 @@ impl<T: Leaf> Clone for Cursor<T> {
     }
 }
 
+#[cfg(feature = "nightly")]
 impl<T: Default + Leaf + Node + 'static> FromResidual for Cursor<T> {
     fn from_residual(residual: <Self as Try>::Residual) -> Self {
         match residual {
-            Some(_) => unreachable!(),
+            Some(never) => match never {},
             None => Cursor::empty(),
         }
     }
 }
 
+#[cfg(feature = "nightly")]
 impl<T: Default + Leaf + Node + 'static> Try for Cursor<T> {
     type Output = T;
 
@@ -211,19 +236,83 @@ impl<T: Default + Leaf + Node + 'static> Try for Cursor<T> {
     }
 
     fn branch(self) -> ControlFlow<Self::Residual, Self::Output> {
-        let tree = self.read();
-        match tree.data() {
-            GreenTreeKind::Token(lexeme) => match lexeme.value.downcast_ref::<T>() {
-                Some(value) => ControlFlow::Continue(value.clone()),
-                None => ControlFlow::Break(None),
-            },
-            GreenTreeKind::Empty => ControlFlow::Break(None),
-            GreenTreeKind::None => ControlFlow::Break(None),
-            _ => ControlFlow::Continue(T::new(tree.clone())),
+        match self.try_get() {
+            Ok(value) => ControlFlow::Continue(value),
+            Err(_) => ControlFlow::Break(None),
+        }
+    }
+}
+
+/// Errors that can happen when reading a [Cursor] with [Cursor::try_get].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorError {
+    /// The cursor has no value.
+    Empty,
+
+    /// The cursor value can't be built as the requested type.
+    Mismatch,
+}
+
+impl Display for CursorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the cursor is empty"),
+            Self::Mismatch => write!(f, "the cursor value does not match the requested type"),
         }
     }
 }
 
+impl std::error::Error for CursorError {}
+
+/// A pending mutation of a [Cursor]. The changes are staged in the transaction, and are only
+/// written when [Transaction::commit] is called, so no lock is held while the new value is
+/// computed, and walkers can read the cursor while the transaction is open.
+///
+/// If the transaction is dropped without being committed, the changes are discarded.
+///
+/// # Example
+/// ```rust,norun
+/// binary.find_lhs().transaction().replace(new_lhs).commit();
+/// ```
+#[must_use = "the changes are only applied when the transaction is committed"]
+pub struct Transaction<'a, T> {
+    cursor: &'a Cursor<T>,
+    value: GreenTree,
+}
+
+impl<'a, T: Leaf> Transaction<'a, T> {
+    /// Returns the staged value.
+    pub fn current(&self) -> &GreenTree {
+        &self.value
+    }
+
+    /// Stages the value of the given cursor, it takes a snapshot of it, so it's safe to use the
+    /// same cursor of the transaction.
+    pub fn set(mut self, new_value: &Cursor<T>) -> Self {
+        self.value = new_value.snapshot();
+        self
+    }
+
+    /// Stages a change using the given function.
+    pub fn update<F: FnOnce(&mut GreenTree)>(mut self, f: F) -> Self {
+        f(&mut self.value);
+        self
+    }
+
+    /// Writes the staged value into the cursor.
+    pub fn commit(self) {
+        *self.cursor.write() = self.value;
+    }
+}
+
+impl<'a, T: Node + Leaf> Transaction<'a, T> {
+    /// Stages the given node as the new value.
+    pub fn replace(mut self, new_value: T) -> Self {
+        self.value = new_value.unwrap();
+        self
+    }
+}
+
 /// Internal util functions
 impl<T> Cursor<T> {
     #[inline]
@@ -239,13 +328,28 @@ impl<T> Cursor<T> {
         Arc::new(RwLock::new(value))
     }
 
+    /// Returns a copy of the current value, the lock is released before returning, so it can
+    /// be used while the cursor is being mutated.
+    #[inline]
+    pub(crate) fn snapshot(&self) -> GreenTree {
+        self.read().clone()
+    }
+
+    /// The value is always replaced as a whole, so a poisoned lock can't hold a partially
+    /// updated tree, and it's safe to recover from it.
     #[inline]
     pub(crate) fn write(&self) -> RwLockWriteGuard<GreenTree> {
-        self.value.write().expect("Poisoned cursor value")
+        self.value.write().unwrap_or_else(PoisonError::into_inner)
     }
 
     #[inline]
     pub(crate) fn read(&self) -> RwLockReadGuard<GreenTree> {
-        self.value.read().expect("Poisoned cursor value")
+        self.value.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Node + Leaf> Located for Cursor<T> {
+    fn location(&self) -> Cow<'_, Loc> {
+        Cow::Owned(self.span())
     }
 }
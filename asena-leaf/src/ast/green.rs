@@ -3,7 +3,6 @@ use std::sync::Arc;
 use std::{any::Any, borrow::Cow, collections::HashMap};
 
 use asena_span::Spanned;
-use dashmap::DashMap;
 
 use crate::node::{Child, Named, Tree, TreeKind};
 use crate::token::token_set::HasTokens;
@@ -12,8 +11,10 @@ use super::*;
 
 mod ast_leaf;
 mod bridges;
+mod traversal;
 
 pub use ast_leaf::*;
+pub use traversal::*;
 
 /// A wrapper for the [Tree] to make it mutable and have mutable named children.
 ///
@@ -41,13 +42,24 @@ pub struct GreenTree {
     data: GreenTreeKind,
 }
 
+// The trees are shared between the snapshots of the databases, that are moved to other threads,
+// like the tasks of the language server.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<GreenTree>();
+};
+
 impl<T> Cursor<T> {
     pub fn parent(&self) -> Arc<Option<GreenTree>> {
         self.read().parent.clone()
     }
+}
 
+impl<T: Leaf> Cursor<T> {
     pub fn set_parent(&self, value: Option<GreenTree>) {
-        self.write().parent = Arc::new(value);
+        self.transaction()
+            .update(|tree| tree.parent = Arc::new(value))
+            .commit();
     }
 }
 
@@ -55,13 +67,7 @@ impl GreenTree {
     pub fn new<I: Into<Arc<Spanned<Tree>>>>(data: I) -> Self {
         let data = data.into();
 
-        Self::new_raw(GreenTreeKind::Leaf(AstLeaf {
-            children: compute_named_children(&data),
-            names: Arc::new(DashMap::new()),
-            keys: Arc::new(DashMap::new()),
-            synthetic: false,
-            data,
-        }))
+        Self::new_raw(GreenTreeKind::Leaf(AstLeaf::new(data, false)))
     }
 
     /// Creates a new node virtual node, that is not a part of the original tree, with the given
@@ -70,13 +76,7 @@ impl GreenTree {
         let mut data: Spanned<Tree> = Spanned::default();
         data.value.kind = kind;
 
-        Self::new_raw(GreenTreeKind::Leaf(AstLeaf {
-            children: HashMap::default(),
-            names: Arc::new(DashMap::new()),
-            keys: Arc::new(DashMap::new()),
-            data: Arc::new(data),
-            synthetic: true,
-        }))
+        Self::new_raw(GreenTreeKind::Leaf(AstLeaf::new(Arc::new(data), true)))
     }
 
     /// Creates a new node, based on the this green tree.
@@ -144,16 +144,23 @@ impl From<GreenTreeKind> for GreenTree {
 }
 
 impl GreenTreeKind {
-    /// Returns a cursor to the named child, if it's not an error node.
+    /// Returns a cursor to the named child, if it's not an error node. The lists return the
+    /// named child of their first item that has it.
     pub fn named_at<A: Leaf + Node + Send + Sync + 'static>(&self, name: LeafKey) -> Cursor<A> {
+        if let Self::Vec(children) = self {
+            return match children.iter().find(|child| child.has(name)) {
+                Some(child) => child.named_at(name),
+                None => Cursor::empty(),
+            };
+        }
+
         let Self::Leaf(leaf) = self else {
             return Cursor::empty();
         };
 
         let cursor = leaf
-            .names
-            .get(name)
-            .and_then(|value| value.clone().downcast::<Cursor<A>>().ok());
+            .name(name)
+            .and_then(|value| value.downcast::<Cursor<A>>().ok());
         let Some(child) = cursor else {
             let Some(child) = leaf.children.get(name) else {
                 return Cursor::empty();
@@ -170,19 +177,26 @@ impl GreenTreeKind {
         (*child).clone()
     }
 
-    /// Returns a cursor to the named terminal, if it's not an error node.
+    /// Returns a cursor to the named terminal, if it's not an error node. The lists return the
+    /// named terminal of their first item that has it.
     pub fn named_terminal<A: Terminal + 'static>(&self, name: LeafKey) -> Cursor<Lexeme<A>>
     where
         A: Send + Sync,
     {
+        if let Self::Vec(children) = self {
+            return match children.iter().find(|child| child.has(name)) {
+                Some(child) => child.named_terminal(name),
+                None => Cursor::empty(),
+            };
+        }
+
         let Self::Leaf(leaf) = self else {
             return Cursor::empty();
         };
 
         let cursor = leaf
-            .names
-            .get(name)
-            .and_then(|value| value.clone().downcast::<Cursor<Lexeme<A>>>().ok());
+            .name(name)
+            .and_then(|value| value.downcast::<Cursor<Lexeme<A>>>().ok());
         let Some(child) = cursor else {
             let Some(child) = leaf.children.get(name) else {
                 return Cursor::empty();
@@ -199,25 +213,45 @@ impl GreenTreeKind {
         (*child).clone()
     }
 
-    /// Creates a new node from the current node, if it's a leaf node, it will reset the names, and
-    /// keys hash maps, and it will compute the named children again, to really duplicate the node,
-    /// use [GreenTree::clone].
+    /// Creates a new node from the current node, if it's a leaf node, it will allocate a new
+    /// metadata handle, resetting the names and keys, and it will compute the named children
+    /// again, to really duplicate the node, use [GreenTree::clone].
     ///
     /// This method is useful to create a new node from a leaf node, and then insert it into the
     /// tree.
     pub fn as_new_node(&self) -> Self {
         match self {
-            Self::Leaf(leaf) => Self::Leaf(AstLeaf {
-                data: leaf.data.clone(),
-                synthetic: leaf.synthetic,
-                children: compute_named_children(&leaf.data),
-                names: Arc::new(DashMap::new()),
-                keys: Arc::new(DashMap::new()),
-            }),
+            Self::Leaf(leaf) => {
+                let mut new_leaf = AstLeaf::new(leaf.data.clone(), leaf.synthetic);
+                new_leaf.origin = leaf.origin.clone();
+                Self::Leaf(new_leaf)
+            }
             _ => self.clone(),
         }
     }
 
+    /// Returns if the node was created by the compiler, and isn't a part of the original tree.
+    pub fn is_synthetic(&self) -> bool {
+        matches!(self, Self::Leaf(leaf) if leaf.synthetic)
+    }
+
+    /// Returns the location of the syntax the node was derived from, if it was set with
+    /// [GreenTreeKind::set_origin].
+    pub fn origin(&self) -> Option<Loc> {
+        match self {
+            Self::Leaf(leaf) => leaf.origin.clone(),
+            _ => None,
+        }
+    }
+
+    /// Sets the location of the syntax the node was derived from, it's used as the location of
+    /// the node, so the errors in the desugared code point to the original code.
+    pub fn set_origin(&mut self, origin: Loc) {
+        if let Self::Leaf(leaf) = self {
+            leaf.origin = Some(origin);
+        }
+    }
+
     /// Inserts a key into the tree, and returns the value. It's not the same of [GreenTree::insert]
     /// because, [GreenTree::insert] sets in the `names` field
     pub fn dynamic<T: Key + Send + Sync>(&self, key: T, value: T::Value) -> Arc<T::Value> {
@@ -226,7 +260,7 @@ impl GreenTreeKind {
         };
 
         let rc = Arc::new(value);
-        leaf.keys.insert(key.name(), rc.clone());
+        leaf.insert_key(key.name(), rc.clone());
         rc as Arc<T::Value>
     }
 
@@ -237,12 +271,16 @@ impl GreenTreeKind {
             return Arc::new(value);
         };
 
-        if let Some(value) = leaf.keys.get(key.name()) {
-            return value.clone().downcast::<T::Value>().unwrap();
+        // The keys with the same name, but other value types, are replaced by the default value.
+        if let Some(Ok(value)) = leaf
+            .key(key.name())
+            .map(|value| value.downcast::<T::Value>())
+        {
+            return value;
         }
 
         let rc = Arc::new(value);
-        leaf.keys.insert(key.name(), rc.clone());
+        leaf.insert_key(key.name(), rc.clone());
         rc as Arc<T::Value>
     }
 
@@ -251,7 +289,7 @@ impl GreenTreeKind {
         T: Node + Leaf,
     {
         if let Self::Leaf(leaf) = self {
-            leaf.names.insert(name, Arc::new(Cursor::of(value)));
+            leaf.insert_name(name, Arc::new(Cursor::of(value)));
         }
     }
 
@@ -269,25 +307,22 @@ impl GreenTreeKind {
             return Cursor::empty();
         };
 
-        if let Some(x) = leaf.names.get(name) {
-            return x.downcast_ref::<Cursor<T>>().unwrap().clone();
+        // If the name was memoized with another type, it's computed again.
+        if let Some(x) = leaf.name(name) {
+            if let Some(cursor) = x.downcast_ref::<Cursor<T>>() {
+                return cursor.clone();
+            }
         }
 
         let cursor = f(tree);
-        leaf.names.insert(name, Arc::new(cursor.clone()));
+        leaf.insert_name(name, Arc::new(cursor.clone()));
         cursor
     }
 }
 
 impl Default for GreenTree {
     fn default() -> Self {
-        Self::new_raw(GreenTreeKind::Leaf(AstLeaf {
-            data: Default::default(),
-            children: HashMap::new(),
-            synthetic: false,
-            keys: Arc::new(DashMap::new()),
-            names: Arc::new(DashMap::new()),
-        }))
+        Self::new_raw(GreenTreeKind::Leaf(AstLeaf::new(Default::default(), false)))
     }
 }
 
@@ -328,8 +363,9 @@ impl HasTokens for GreenTree {
 impl Located for GreenTree {
     fn location(&self) -> Cow<'_, Loc> {
         match self.data {
-            GreenTreeKind::Leaf(ref leaf) => Cow::Borrowed(&leaf.data.span),
+            GreenTreeKind::Leaf(ref leaf) => Cow::Borrowed(leaf.span()),
             GreenTreeKind::Token(ref lexeme) => Cow::Borrowed(&lexeme.token.span),
+            GreenTreeKind::Vec(ref children) => children.location(),
             _ => Cow::Owned(Loc::default()),
         }
     }
@@ -338,7 +374,9 @@ impl Located for GreenTree {
 /// Computes the named children of the given tree, and returns a hash map with the named children.
 ///
 /// This function is used to compute the tree that the `name` property is not [None].
-fn compute_named_children(data: &Spanned<Tree>) -> HashMap<LeafKey, Arc<Spanned<Child>>> {
+pub(crate) fn compute_named_children(
+    data: &Spanned<Tree>,
+) -> HashMap<LeafKey, Arc<Spanned<Child>>> {
     let mut named_children = HashMap::new();
 
     for child in &data.children {
@@ -1,6 +1,6 @@
 use std::hash::Hash;
 
-use dashmap::DashMap;
+use crate::ast::arena::NodeHandle;
 
 use super::*;
 
@@ -10,20 +10,56 @@ pub struct AstLeaf {
 
     pub(crate) synthetic: bool,
 
-    /// A hash map of the named children.
-    pub(crate) children: HashMap<LeafKey, Arc<Spanned<Child>>>,
+    /// The location of the syntax the node was derived from, it's used instead of the tree span,
+    /// so the diagnostics on the generated code point to the original code.
+    pub(crate) origin: Option<Loc>,
 
     /// A hash map of the named children.
-    pub(crate) keys: Arc<DashMap<&'static str, Arc<dyn Any + Send + Sync>>>,
+    pub(crate) children: HashMap<LeafKey, Arc<Spanned<Child>>>,
 
-    /// Lazy names' hash map, they have to exist, to make the tree mutable.
+    /// Handle to the node's metadata, in the [crate::ast::NodeArena] or inline, depending on the
+    /// [crate::ast::StorageMode] of the arena where the node was created. It stores the keys, and the lazy names,
+    /// they have to exist, to make the tree mutable.
     ///
     /// E.g: I can't set the `lhs` node for `binary` tree, if the tree is immutable, so the
     /// lazy names should be used to compute that things.
     /// ```rs
     /// binary.lhs()
     /// ```
-    pub(crate) names: Arc<DashMap<LeafKey, Arc<dyn Any + Send + Sync>>>,
+    pub(crate) meta: NodeHandle,
+}
+
+impl AstLeaf {
+    pub(crate) fn new(data: Arc<Spanned<Tree>>, synthetic: bool) -> Self {
+        Self {
+            children: compute_named_children(&data),
+            meta: NodeHandle::new(),
+            origin: None,
+            synthetic,
+            data,
+        }
+    }
+
+    /// Returns the origin of the node, if it has one, otherwise the span of the tree.
+    pub(crate) fn span(&self) -> &Loc {
+        self.origin.as_ref().unwrap_or(&self.data.span)
+    }
+
+    pub(crate) fn key(&self, name: LeafKey) -> Option<Metadata> {
+        self.meta.key(name)
+    }
+
+    pub(crate) fn insert_key(&self, name: LeafKey, value: Metadata) {
+        self.meta.insert_key(name, value)
+    }
+
+    pub(crate) fn name(&self, name: LeafKey) -> Option<Metadata> {
+        self.meta.name(name)
+    }
+
+    pub(crate) fn insert_name(&self, name: LeafKey, value: Metadata) {
+        self.meta.insert_name(name, value)
+    }
 }
 
 impl Eq for AstLeaf {}
@@ -31,9 +67,9 @@ impl Eq for AstLeaf {}
 impl PartialEq for AstLeaf {
     fn eq(&self, other: &Self) -> bool {
         Arc::ptr_eq(&self.data, &other.data)
-            && Arc::ptr_eq(&self.names, &other.names)
-            && Arc::ptr_eq(&self.keys, &other.keys)
+            && self.meta == other.meta
             && self.synthetic == other.synthetic
+            && self.origin == other.origin
     }
 }
 
@@ -41,6 +77,7 @@ impl Hash for AstLeaf {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.data.hash(state);
         self.synthetic.hash(state);
+        self.origin.hash(state);
         self.children.iter().collect::<Vec<_>>().hash(state);
         self.children.iter().collect::<Vec<_>>().hash(state);
         self.children.iter().collect::<Vec<_>>().hash(state);
@@ -1,10 +1,16 @@
 use super::*;
 
+/// The list nodes, [GreenTreeKind::Vec], are handled like the leaves, using their items as the
+/// children, so the typed accessors work the same on both.
 impl GreenTreeKind {
     /// Checks if the tree matches the given kind.
     pub fn matches(&self, nth: usize, kind: TokenKind) -> bool {
         match self {
             Self::Leaf(leaf) => leaf.data.matches(nth, kind),
+            Self::Vec(children) => match children.get(nth).map(GreenTree::data) {
+                Some(Self::Token(lexeme)) => lexeme.token.kind == kind,
+                _ => false,
+            },
             _ => false,
         }
     }
@@ -23,6 +29,7 @@ impl GreenTreeKind {
     pub fn kind(&self) -> TreeKind {
         match self {
             Self::Leaf(leaf) => leaf.data.kind,
+            Self::Vec(..) => TreeKind::ListTree,
             _ => TreeKind::Error,
         }
     }
@@ -31,8 +38,9 @@ impl GreenTreeKind {
     /// value.
     pub fn spanned(&self) -> Spanned<()> {
         match self {
-            Self::Leaf(leaf) => leaf.data.replace(()),
+            Self::Leaf(leaf) => Spanned::new(leaf.span().clone(), ()),
             Self::Token(lexeme) => lexeme.token.replace(()),
+            Self::Vec(children) => Spanned::new(children.location().into_owned(), ()),
             _ => Spanned::default(),
         }
     }
@@ -41,6 +49,7 @@ impl GreenTreeKind {
     pub fn is_single(&self) -> bool {
         match self {
             Self::Leaf(leaf) => leaf.data.is_single(),
+            Self::Vec(children) => children.len() == 1,
             Self::Token(..) => true,
             _ => false,
         }
@@ -58,6 +67,12 @@ impl GreenTreeKind {
     pub fn filter<T: Leaf + Node>(&self) -> Cursor<Vec<T>> {
         match self {
             Self::Leaf(leaf) => leaf.data.filter(),
+            Self::Vec(children) => children
+                .iter()
+                .cloned()
+                .filter_map(T::make)
+                .collect::<Vec<_>>()
+                .into(),
             _ => Cursor::empty(),
         }
     }
@@ -66,15 +81,19 @@ impl GreenTreeKind {
     pub fn any_token(&self, kind: TokenKind) -> Vec<Spanned<Token>> {
         match self {
             Self::Leaf(leaf) => leaf.data.token(kind),
+            Self::Vec(children) => children
+                .iter()
+                .filter_map(|child| match child.data() {
+                    Self::Token(lexeme) if lexeme.token.kind == kind => Some(lexeme.token.clone()),
+                    _ => None,
+                })
+                .collect(),
             _ => vec![],
         }
     }
 
     pub fn token(&self, kind: TokenKind) -> Spanned<Token> {
-        match self {
-            Self::Leaf(leaf) => leaf.data.token(kind).first().cloned().unwrap_or_default(),
-            _ => Default::default(),
-        }
+        self.any_token(kind).first().cloned().unwrap_or_default()
     }
 
     /// Returns a terminal node, if it's not an error node.
@@ -84,6 +103,10 @@ impl GreenTreeKind {
     {
         match self {
             Self::Leaf(leaf) => leaf.data.terminal(nth),
+            Self::Vec(children) => match children.get(nth).map(GreenTree::data) {
+                Some(Self::Token(lexeme)) => Lexeme::<T>::terminal(lexeme.token.clone()).into(),
+                _ => Cursor::empty(),
+            },
             _ => Cursor::empty(),
         }
     }
@@ -95,6 +118,14 @@ impl GreenTreeKind {
     {
         match self {
             Self::Leaf(leaf) => leaf.data.filter_terminal(),
+            Self::Vec(children) => children
+                .iter()
+                .filter_map(|child| match child.data() {
+                    Self::Token(lexeme) => Lexeme::<T>::terminal(lexeme.token.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .into(),
             _ => Cursor::empty(),
         }
     }
@@ -103,6 +134,10 @@ impl GreenTreeKind {
     pub fn at<T: Node + Leaf>(&self, nth: usize) -> Cursor<T> {
         match self {
             Self::Leaf(leaf) => leaf.data.at(nth),
+            Self::Vec(children) => match children.get(nth) {
+                Some(child) => T::make(child.clone()).into(),
+                None => Cursor::empty(),
+            },
             _ => Cursor::empty(),
         }
     }
@@ -111,6 +146,7 @@ impl GreenTreeKind {
     pub fn has(&self, name: LeafKey) -> bool {
         match self {
             Self::Leaf(leaf) => matches!(leaf.children.get(name), Some(..)),
+            Self::Vec(children) => children.iter().any(|child| child.has(name)),
             _ => false,
         }
     }
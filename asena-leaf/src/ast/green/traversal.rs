@@ -0,0 +1,106 @@
+use super::*;
+
+/// Pre-order iterator over a [GreenTree], it yields the node itself, and then every descendant
+/// node in the order they appear in the source code.
+///
+/// The yielded nodes have their parent set, so [GreenTree::ancestors] can be used on them.
+pub struct Preorder {
+    stack: Vec<GreenTree>,
+}
+
+/// Iterator that walks up the tree following the `parent` references, starting from the parent
+/// of the node.
+pub struct Ancestors {
+    current: Arc<Option<GreenTree>>,
+}
+
+impl Iterator for Preorder {
+    type Item = GreenTree;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = self.stack.pop()?;
+        self.stack.extend(tree.green_children().into_iter().rev());
+        Some(tree)
+    }
+}
+
+impl Iterator for Ancestors {
+    type Item = GreenTree;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tree = (*self.current).clone()?;
+        self.current = tree.parent();
+        Some(tree)
+    }
+}
+
+impl GreenTree {
+    /// Returns a pre-order iterator over the tree, including the current node, it only walks
+    /// tree nodes, tokens are skipped.
+    pub fn preorder(&self) -> Preorder {
+        Preorder {
+            stack: vec![self.clone()],
+        }
+    }
+
+    /// Returns an iterator over all descendant nodes in pre-order, excluding the current node.
+    pub fn descendants(&self) -> impl Iterator<Item = GreenTree> {
+        self.preorder().skip(1)
+    }
+
+    /// Returns an iterator over all descendant nodes that can be built as `T`, using the
+    /// [Leaf::make] function.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// let literals = file.descendants_of::<LiteralExpr>().count();
+    /// ```
+    pub fn descendants_of<T: Leaf>(&self) -> impl Iterator<Item = T> {
+        self.descendants().filter_map(T::make)
+    }
+
+    /// Returns an iterator over the ancestors of the current node, from the closest parent to
+    /// the root of the tree. It relies on the parent references, so it's empty if the node was
+    /// not reached by a traversal, or had not the parent set.
+    pub fn ancestors(&self) -> Ancestors {
+        Ancestors {
+            current: self.parent(),
+        }
+    }
+
+    /// Returns the first ancestor that can be built as `T`, using the [Leaf::make] function.
+    pub fn ancestor_of<T: Leaf>(&self) -> Option<T> {
+        self.ancestors().find_map(T::make)
+    }
+
+    /// Returns the children tree nodes of the current node, with the parent set to the current
+    /// node.
+    fn green_children(&self) -> Vec<GreenTree> {
+        let parent = Arc::new(Some(self.clone()));
+
+        match self.data {
+            GreenTreeKind::Leaf(ref leaf) => leaf
+                .data
+                .children
+                .iter()
+                .filter_map(|child| match &child.value {
+                    Child::Tree(tree) => {
+                        let mut green = GreenTree::new(child.replace(tree.clone()));
+                        green.parent = parent.clone();
+                        Some(green)
+                    }
+                    Child::Token(..) => None,
+                })
+                .collect(),
+            GreenTreeKind::Vec(ref children) => children
+                .iter()
+                .cloned()
+                .map(|mut green| {
+                    green.parent = parent.clone();
+                    green
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
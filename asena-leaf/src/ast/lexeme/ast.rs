@@ -88,22 +88,11 @@ impl<T> Located for Lexeme<T> {
     }
 }
 
+/// Gets the token of a terminal leaf, if the leaf has no children, or the first child is a tree,
+/// it returns an error token located at the leaf, so it can still be reported.
 fn get_single_token(tree: &AstLeaf) -> Spanned<Token> {
-    match tree.data.children.first() {
-        Some(child) => match child.value {
-            Child::Token(ref value) => tree.data.replace(value.clone()),
-            Child::Tree(ref tree) => {
-                #[cfg(debug_assertions)]
-                println!("Lexeme::new: Leaf node has a tree child: {}", tree.kind);
-
-                Default::default()
-            }
-        },
-        None => {
-            #[cfg(debug_assertions)]
-            println!("Lexeme::new: Leaf node has no children: {}", tree.data.kind);
-
-            Default::default()
-        }
+    match tree.data.single() {
+        Some(token) => tree.data.replace(token.clone()),
+        None => tree.data.replace(Token::default()),
     }
 }
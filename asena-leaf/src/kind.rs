@@ -1,127 +1,142 @@
 use std::fmt::Display;
 
+use super::macros::enumerable;
 use super::named::Named;
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum TreeKind {
-    #[default]
-    Error,
-
-    File,
-
-    ListTree,
-
-    LitNat,
-    LitInt8,
-    LitUInt8,
-    LitInt16,
-    LitUInt16,
-    LitInt32,
-    LitUInt32,
-    LitInt64,
-    LitUInt64,
-    LitInt128,
-    LitUInt128,
-
-    LitFloat32,
-    LitFloat64,
-
-    LitTrue,
-    LitFalse,
-
-    LitString,
-
-    SymbolIdentifier,
-    QualifiedPathTree,
-
-    ExprUnit,
-    ExprGroup,
-    ExprBinary,
-    ExprAccessor,
-    ExprApp,
-    ExprDsl,
-    ExprArray,
-    ExprLam,
-    ExprLet,
-    ExprLocal,
-    ExprSelf,
-    ExprLit,
-    ExprAnn,
-    ExprQual,
-    ExprPi,
-    ExprSigma,
-    ExprHelp,
-    ExprIf,
-    ExprMatch,
-
-    AccessorArg,
-    AccessorExpr,
-
-    BranchExpr,
-    BranchBlock,
-
-    IfThen,
-    IfElse,
-
-    MatchCase,
-
-    IdSymbol,
-
-    PatWildcard,
-    PatSpread,
-    PatLit,
-    PatGlobal,
-    PatConstructor,
-    PatConstructorList,
-    PatList,
-    PatGroup,
-    PatUnit,
-
-    StmtAsk,
-    StmtLet,
-    StmtReturn,
-    StmtExpr,
-    StmtIf,
-
-    LetBinding,
-
-    BodyValue,
-    BodyDo,
-
-    DeclUse,
-    DeclSignature,
-    DeclAssign,
-    DeclCommand,
-    DeclClass,
-    DeclTrait,
-    DeclEnum,
-    DeclInstance,
-
-    EnumGadtType,
-
-    WhereClause,
-
-    ClassField,
-    ClassMethod,
-
-    InstanceImpl,
-
-    TraitDefault,
-
-    VariantConstructor,
-    VariantType,
-
-    Param,
-    SelfParam,
-    LamParam,
-
-    TypeConstraint,
-
-    PropertyField,
-    PropertyMethod,
-
-    TypeInfer,
-    TypeExplicit,
+enumerable! {
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub enum TreeKind {
+        #[default]
+        Error,
+
+        File,
+
+        ListTree,
+
+        LitNat,
+        LitInt8,
+        LitUInt8,
+        LitInt16,
+        LitUInt16,
+        LitInt32,
+        LitUInt32,
+        LitInt64,
+        LitUInt64,
+        LitInt128,
+        LitUInt128,
+
+        LitFloat32,
+        LitFloat64,
+
+        LitTrue,
+        LitFalse,
+
+        LitString,
+
+        SymbolIdentifier,
+        QualifiedPathTree,
+
+        ExprUnit,
+        ExprGroup,
+        ExprBinary,
+        ExprAccessor,
+        ExprApp,
+        ExprDsl,
+        ExprArray,
+        ExprLam,
+        ExprLet,
+        ExprLocal,
+        ExprSelf,
+        ExprLit,
+        ExprAnn,
+        ExprQual,
+        ExprPi,
+        ExprSigma,
+        ExprForall,
+        ExprHelp,
+        ExprLeftSection,
+        ExprRightSection,
+        ExprOperatorSection,
+        ExprInterpolation,
+        ExprUnary,
+        ExprIf,
+        ExprMatch,
+
+        AccessorArg,
+        AccessorExpr,
+
+        BranchExpr,
+        BranchBlock,
+
+        IfThen,
+        IfElse,
+
+        MatchCase,
+
+        IdSymbol,
+
+        PatWildcard,
+        PatSpread,
+        PatLit,
+        PatRange,
+        PatOr,
+        PatAs,
+        PatGlobal,
+        PatConstructor,
+        PatConstructorList,
+        PatList,
+        PatGroup,
+        PatUnit,
+
+        StmtAsk,
+        StmtLet,
+        StmtReturn,
+        StmtExpr,
+        StmtIf,
+
+        LetBinding,
+
+        BodyValue,
+        BodyDo,
+
+        DeclUse,
+        DeclSignature,
+        DeclAssign,
+        DeclDestructure,
+        DeclCommand,
+        DeclClass,
+        DeclTrait,
+        DeclEnum,
+        DeclInstance,
+
+        EnumGadtType,
+
+        WhereClause,
+
+        ClassField,
+        ClassMethod,
+
+        InstanceImpl,
+
+        TraitDefault,
+
+        AssocType,
+
+        VariantConstructor,
+        VariantType,
+
+        Param,
+        SelfParam,
+        LamParam,
+
+        TypeConstraint,
+
+        PropertyField,
+        PropertyMethod,
+
+        TypeInfer,
+        TypeExplicit,
+    }
 }
 
 impl Named for TreeKind {}
@@ -1,5 +1,4 @@
-#![feature(try_trait_v2)]
-#![feature(associated_type_bounds)]
+#![cfg_attr(feature = "nightly", feature(try_trait_v2))]
 
 pub mod ast;
 pub mod kind;
@@ -2,6 +2,27 @@ mod ast_virtual;
 
 pub use ast_virtual::*;
 
+/// Unwraps the value of a [crate::ast::Cursor], or returns the default value of the function's
+/// return type if the cursor is empty. It's the stable alternative to the `?` operator on
+/// cursors.
+///
+/// # Example
+/// ```rust,norun
+/// pub fn lhs_name(&self) -> Cursor<QualifiedPath> {
+///     let lhs = cursor_try!(self.find_lhs());
+///     ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! cursor_try {
+    ($cursor:expr) => {
+        match $crate::ast::Cursor::try_as_leaf(&$cursor) {
+            Some(value) => value,
+            None => return std::default::Default::default(),
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! ast_enum {
     (
@@ -30,6 +51,11 @@ macro_rules! ast_enum {
         }
 
         impl $name {
+            /// The tree kinds of the variants, with the names of their nodes, in the order
+            /// they're declared. The external tools use it to know the nodes of the kinds.
+            pub const KINDS: &'static [($crate::node::TreeKind, &'static str)] =
+                &[$(($kind, stringify!($variant))),*];
+
             pub fn parent(&self) -> std::sync::Arc<Option<$crate::ast::GreenTree>> {
                 use $crate::ast::Node;
 
@@ -107,3 +133,26 @@ macro_rules! ast_enum {
 }
 
 pub use ast_enum;
+
+/// Declares the enum with the `ALL` constant, that has all of its variants in the order they're
+/// declared, so the kinds can be enumerated without hardcoding them again.
+macro_rules! enumerable {
+    (
+        $(#[$outer:meta])*
+        pub enum $name:ident {
+            $($(#[$field_outer:meta])* $variant:ident),*
+            $(,)?
+        }
+    ) => {
+        $(#[$outer])*
+        pub enum $name {
+            $($(#[$field_outer])* $variant),*
+        }
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+        }
+    };
+}
+
+pub(crate) use enumerable;
@@ -44,6 +44,14 @@ macro_rules! ast_virtual {
             }
         }
 
+        impl $crate::ast::Located for $name {
+            fn location(&self) -> std::borrow::Cow<'_, asena_span::Loc> {
+                match self {
+                    $(Self::$variant(value) => $crate::ast::Located::location(value),)*
+                }
+            }
+        }
+
         impl From<$name> for $kind {
             fn from(value: $name) -> Self {
                 match value {
@@ -67,7 +75,9 @@ macro_rules! ast_virtual {
                     return value;
                 })*
 
-                panic!("Virtual nodes cannot be created from a GreenTree")
+                // If no variant can be created from the tree, the first variant holds it, so the
+                // accessors of it return the empty values instead of panicking.
+                $crate::ast_virtual_fallback!(value, $($variant),*)
             }
 
             fn unwrap(self) -> $crate::ast::GreenTree {
@@ -79,6 +89,13 @@ macro_rules! ast_virtual {
     };
 }
 
+#[macro_export]
+macro_rules! ast_virtual_fallback {
+    ($tree:expr, $first:ident $(, $variant:ident)*) => {
+        Self::$first($first::new($tree))
+    };
+}
+
 #[macro_export]
 macro_rules! ast_virtual_variant {
     ($tree:expr, #[node] $variant:ident) => {
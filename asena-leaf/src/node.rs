@@ -50,13 +50,12 @@ impl Tree {
         }
     }
 
-    pub fn single(&self) -> &Token {
-        match self.children.first() {
-            Some(token) => match &token.value {
-                Child::Token(token) => token,
-                Child::Tree(..) => panic!("called `Tree::single` on a non-terminal node"),
-            },
-            None => panic!("called `Tree::single` on a empty node"),
+    /// Returns the first token of the tree, if the tree is empty, or the first child is a tree,
+    /// it returns [None].
+    pub fn single(&self) -> Option<&Token> {
+        match &self.children.first()?.value {
+            Child::Token(token) => Some(token),
+            Child::Tree(..) => None,
         }
     }
 
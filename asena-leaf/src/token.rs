@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
-use self::{kind::TokenKind, text::Text};
+use self::kind::TokenKind;
+use self::text::{Text, TokenText};
 
 use super::named::Named;
 
@@ -12,7 +13,7 @@ pub mod token_set;
 pub struct Token {
     pub name: Option<&'static str>,
     pub kind: TokenKind,
-    pub text: String,
+    pub text: TokenText,
     pub full_text: Text,
 }
 
@@ -21,7 +22,7 @@ impl Token {
         Self {
             name: None,
             kind,
-            text: text.into(),
+            text: TokenText::new(kind, text),
             full_text: Default::default(),
         }
     }
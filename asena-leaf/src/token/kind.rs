@@ -1,79 +1,151 @@
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum TokenKind {
-    #[default]
-    Error,
-
-    Nat,
-
-    // keywords
-    LetKeyword,      // let
-    TrueKeyword,     // true
-    FalseKeyword,    // false
-    IfKeyword,       // if
-    ElseKeyword,     // else
-    ThenKeyword,     // then
-    TypeKeyword,     // type
-    RecordKeyword,   // record
-    ReturnKeyword,   // return
-    EnumKeyword,     // enum
-    TraitKeyword,    // trait
-    ClassKeyword,    // class
-    CaseKeyword,     // case
-    WhereKeyword,    // where
-    MatchKeyword,    // match
-    UseKeyword,      // use
-    InstanceKeyword, // instance
-    InKeyword,       // in
-    FunKeyword,      // fun
-    DefaultKeyword,  // default
-    SelfKeyword,     // self
-
-    // unicode
-    LambdaUnicode, // λ
-    ForallUnicode, // ∀
-    PiUnicode,     // Π
-    SigmaUnicode,  // Σ
-
-    // control symbols
-    LeftBracket,  // [
-    RightBracket, // ]
-    LeftBrace,    // {
-    RightBrace,   // }
-    LeftParen,    // (
-    RightParen,   // )
-    Comma,        // ,
-    Semi,         // ;
-    Colon,        // :
-    Dot,          // .
-    HelpSymbol,   // ?
-    EqualSymbol,  // =
-    HashSymbol,   // #
-
-    DoubleArrow, // =>
-    RightArrow,  // ->
-    LeftArrow,   // <-
-
-    // integers
-    Int8,
-    UInt8,
-    Int16,
-    UInt16,
-    Int32,
-    UInt32,
-    Int64,
-    UInt64,
-    Int128,
-    UInt128,
-
-    // floats
-    Float32,
-    Float64,
-
-    // literals
-    Symbol,
+use crate::macros::enumerable;
+
+enumerable! {
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub enum TokenKind {
+        #[default]
+        Error,
+        ErrorChar, // unrecognized character, the text of the token is the character
+
+        Nat,
+
+        // keywords
+        LetKeyword,      // let
+        TrueKeyword,     // true
+        FalseKeyword,    // false
+        IfKeyword,       // if
+        ElseKeyword,     // else
+        ThenKeyword,     // then
+        TypeKeyword,     // type
+        RecordKeyword,   // record
+        ReturnKeyword,   // return
+        EnumKeyword,     // enum
+        TraitKeyword,    // trait
+        ClassKeyword,    // class
+        CaseKeyword,     // case
+        WhereKeyword,    // where
+        MatchKeyword,    // match
+        UseKeyword,      // use
+        InstanceKeyword, // instance
+        InKeyword,       // in
+        FunKeyword,      // fun
+        DefaultKeyword,  // default
+        SelfKeyword,     // self
+        ForallKeyword,   // forall
+        PubKeyword,      // pub
+        DoKeyword,       // do, since the 2024 edition
+
+        // unicode, the `λ`, `∀` and the arrows are lexed as their ascii forms
+        PiUnicode,    // Π
+        SigmaUnicode, // Σ
+
+        // control symbols
+        LeftBracket,  // [
+        RightBracket, // ]
+        LeftBrace,    // {
+        RightBrace,   // }
+        LeftParen,    // (
+        RightParen,   // )
+        Comma,        // ,
+        Semi,         // ;
+        Colon,        // :
+        Dot,          // .
+        HelpSymbol,   // ?
+        EqualSymbol,  // =
+        HashSymbol,   // #
+
+        DoubleArrow, // =>
+        RightArrow,  // ->
+        LeftArrow,   // <-
+
+        // integers
+        Int8,
+        UInt8,
+        Int16,
+        UInt16,
+        Int32,
+        UInt32,
+        Int64,
+        UInt64,
+        Int128,
+        UInt128,
+
+        // floats
+        Float32,
+        Float64,
+
+        // literals
+        Symbol,
+        Identifier,
+        Str,
+        StrStart,  // "...{
+        StrMiddle, // }...{
+        StrEnd,    // }..."
+        Char,      // 'a'
+
+        // end of file
+        Eof,
+    }
+}
+
+/// The category of a token kind, by the groups of [TokenKind], so the external tools, like the
+/// syntax highlighters, can color the tokens without matching every kind.
+///
+/// The trivia, like the whitespace and the comments, aren't tokens, they're kept in the
+/// [crate::token::text::Text::before_whitespace] of the next token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TokenCategory {
+    Keyword,
+    Unicode,
+    Delimiter,
+    Operator,
+    Number,
+    Literal,
     Identifier,
-    Str,
+    Special,
+}
+
+impl TokenKind {
+    pub fn category(&self) -> TokenCategory {
+        use TokenKind::*;
+
+        match self {
+            Error | ErrorChar | Eof => TokenCategory::Special,
+            LetKeyword | TrueKeyword | FalseKeyword | IfKeyword | ElseKeyword | ThenKeyword
+            | TypeKeyword | RecordKeyword | ReturnKeyword | EnumKeyword | TraitKeyword
+            | ClassKeyword | CaseKeyword | WhereKeyword | MatchKeyword | UseKeyword
+            | InstanceKeyword | InKeyword | FunKeyword | DefaultKeyword | SelfKeyword
+            | ForallKeyword | PubKeyword | DoKeyword => TokenCategory::Keyword,
+            PiUnicode | SigmaUnicode => TokenCategory::Unicode,
+            LeftBracket | RightBracket | LeftBrace | RightBrace | LeftParen | RightParen
+            | Comma | Semi | Colon | Dot => TokenCategory::Delimiter,
+            HelpSymbol | EqualSymbol | HashSymbol | DoubleArrow | RightArrow | LeftArrow
+            | Symbol => TokenCategory::Operator,
+            Nat | Int8 | UInt8 | Int16 | UInt16 | Int32 | UInt32 | Int64 | UInt64 | Int128
+            | UInt128 | Float32 | Float64 => TokenCategory::Number,
+            Str | StrStart | StrMiddle | StrEnd | Char => TokenCategory::Literal,
+            Identifier => TokenCategory::Identifier,
+        }
+    }
+
+    pub fn is_keyword(&self) -> bool {
+        matches!(
+            self.category(),
+            TokenCategory::Keyword | TokenCategory::Unicode
+        )
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self.category(),
+            TokenCategory::Number | TokenCategory::Literal
+        )
+    }
 
-    // end of file
-    Eof,
+    pub fn is_symbol(&self) -> bool {
+        matches!(
+            self.category(),
+            TokenCategory::Delimiter | TokenCategory::Operator
+        )
+    }
 }
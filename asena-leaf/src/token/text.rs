@@ -1,4 +1,11 @@
-use std::fmt::Display;
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use asena_interner::Symbol;
+
+use super::kind::{TokenCategory, TokenKind};
 
 #[derive(Debug, Clone, Hash, Default, PartialEq, Eq)]
 pub struct Text {
@@ -13,3 +20,96 @@ impl Display for Text {
         Ok(())
     }
 }
+
+/// The text of a token as it was lexed. The identifiers, the keywords and the symbols are
+/// interned, as they're compared and repeated in the whole program, and they're the names of
+/// the syntax tree and of the HIR. The texts of the other tokens, like the literals and the
+/// unrecognized characters, are owned, so they're freed with their trees.
+#[derive(Clone)]
+pub enum TokenText {
+    Interned(Symbol),
+    Owned(Arc<str>),
+}
+
+impl TokenText {
+    /// Creates the text of a token of the kind, see [TokenText].
+    pub fn new(kind: TokenKind, text: &str) -> Self {
+        match kind.category() {
+            TokenCategory::Number | TokenCategory::Literal | TokenCategory::Special => {
+                Self::Owned(text.into())
+            }
+            _ => Self::Interned(Symbol::intern(text)),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Interned(symbol) => symbol.as_str(),
+            Self::Owned(text) => text,
+        }
+    }
+
+    /// Gets the interned symbol of the text, the owned texts are interned, so it should only be
+    /// used for the names.
+    pub fn symbol(&self) -> Symbol {
+        match self {
+            Self::Interned(symbol) => *symbol,
+            Self::Owned(text) => Symbol::intern(text),
+        }
+    }
+}
+
+impl Default for TokenText {
+    fn default() -> Self {
+        Self::Interned(Symbol::default())
+    }
+}
+
+impl Deref for TokenText {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl Hash for TokenText {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialEq for TokenText {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Interned(a), Self::Interned(b)) => a == b,
+            _ => self.as_str() == other.as_str(),
+        }
+    }
+}
+
+impl Eq for TokenText {}
+
+impl PartialEq<str> for TokenText {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for TokenText {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Display for TokenText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Debug for TokenText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
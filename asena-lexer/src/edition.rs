@@ -0,0 +1,74 @@
+//! The editions of the language, they gate the keywords added after the first edition, so the
+//! packages that use them as identifiers keep compiling until they opt into the new edition.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use asena_leaf::token::kind::TokenKind::{self, *};
+
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Edition {
+    #[default]
+    Edition2023,
+
+    /// Reserves `do` as a keyword.
+    Edition2024,
+}
+
+impl Edition {
+    pub const LATEST: Edition = Edition::Edition2024;
+
+    /// Returns the kind of the keyword, if the identifier is a keyword in the edition.
+    pub fn keyword(self, ident: &str) -> Option<TokenKind> {
+        let kind = match ident {
+            "let" => LetKeyword,
+            "true" => TrueKeyword,
+            "false" => FalseKeyword,
+            "if" => IfKeyword,
+            "else" => ElseKeyword,
+            "then" => ThenKeyword,
+            "type" => TypeKeyword,
+            "record" => RecordKeyword,
+            "return" => ReturnKeyword,
+            "enum" => EnumKeyword,
+            "trait" => TraitKeyword,
+            "class" => ClassKeyword,
+            "case" => CaseKeyword,
+            "where" => WhereKeyword,
+            "match" => MatchKeyword,
+            "use" => UseKeyword,
+            "in" => InKeyword,
+            "fun" => FunKeyword,
+            "self" => SelfKeyword,
+            "instance" => InstanceKeyword,
+            "default" => DefaultKeyword,
+            "forall" => ForallKeyword,
+            "pub" => PubKeyword,
+            "do" if self >= Edition::Edition2024 => DoKeyword,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Edition::Edition2023 => write!(f, "2023"),
+            Edition::Edition2024 => write!(f, "2024"),
+        }
+    }
+}
+
+impl FromStr for Edition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "2023" => Ok(Edition::Edition2023),
+            "2024" => Ok(Edition::Edition2024),
+            _ => Err(format!("unknown edition: `{s}`")),
+        }
+    }
+}
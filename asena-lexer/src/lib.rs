@@ -2,12 +2,18 @@ use std::fmt::Debug;
 use std::path::PathBuf;
 
 use chumsky::prelude::*;
+use unicode_ident::{is_xid_continue, is_xid_start};
+use unicode_normalization::UnicodeNormalization;
 
-use asena_leaf::token::kind::TokenKind::*;
+use asena_leaf::token::kind::TokenKind::{self, *};
 use asena_leaf::{node::Token, token::text::Text};
 
 use asena_span::{Loc, Spanned};
 
+pub use edition::Edition;
+
+pub mod edition;
+
 pub const SYMBOLS: &[&str] = &[
     "=", "!", ">", "<", "$", "#", "+", "-", "*", "/", "&", "|", ".", "@", "^", ":", "\\",
 ];
@@ -31,11 +37,24 @@ pub struct Lexer<'a> {
 
 /// It's the programming language, lexer, that transforms the string, into a set of [Token].
 pub fn lexer<'a>() -> impl Parser<'a, &'a str, TokenSet, LexError<'a>> {
-    let num = text::int(10)
-        .then(just('.').then(text::digits(10)).or_not())
-        .slice()
-        .map(|value: &str| Token::new(Float64, value))
-        .labelled("number"); // TODO: implement another float/integer variants
+    edition_lexer(Edition::default())
+}
+
+/// The lexer with the keywords of the edition, see [Edition::keyword].
+pub fn edition_lexer<'a>(edition: Edition) -> impl Parser<'a, &'a str, TokenSet, LexError<'a>> {
+    let num = just("0x")
+        .ignore_then(digits(16))
+        .map(|digits| (16, digits))
+        .or(just("0o").ignore_then(digits(8)).map(|digits| (8, digits)))
+        .or(just("0b").ignore_then(digits(2)).map(|digits| (2, digits)))
+        .or(digits(10)
+            .then(just('.').then(digits(10)).or_not())
+            .then(exponent().or_not())
+            .slice()
+            .map(|digits| (10, digits)))
+        .then(suffix_lexer().or_not())
+        .map(|((radix, digits), suffix)| number(radix, digits, suffix))
+        .labelled("number");
 
     let string = just('"')
         .ignore_then(none_of('"').repeated())
@@ -43,6 +62,26 @@ pub fn lexer<'a>() -> impl Parser<'a, &'a str, TokenSet, LexError<'a>> {
         .map_slice(|string: &str| Token::new(Str, string))
         .labelled("string literal");
 
+    // The raw strings don't have escapes, nor holes, like `r"\d+"`, and the ones delimited by
+    // `r#"` and `"#` can have quotes, like `r#"say "hi""#`.
+    let raw_string = just("r#\"")
+        .then(any().and_is(just("\"#").not()).repeated())
+        .then(just("\"#"))
+        .ignored()
+        .or(just("r\"")
+            .then(none_of('"').repeated())
+            .then(just('"'))
+            .ignored())
+        .map_slice(|string: &str| Token::new(Str, string))
+        .labelled("raw string literal");
+
+    let escape = just('\\').then(one_of("nrt0\\'\"")).ignored();
+    let character = just('\'')
+        .ignore_then(escape.or(none_of("\\'").ignored()))
+        .then_ignore(just('\''))
+        .map_slice(|character: &str| Token::new(Char, character))
+        .labelled("char literal");
+
     let symbol = one_of(SYMBOLS.join(""))
         .repeated()
         .at_least(1)
@@ -68,19 +107,51 @@ pub fn lexer<'a>() -> impl Parser<'a, &'a str, TokenSet, LexError<'a>> {
         .to(Token::new(Semi, ";"))
         .labelled("semi");
 
+    // The math symbols that have an ascii form are lexed as it, like `λx → x` is `\x -> x`, the
+    // full text of the tokens still has the symbols. The `Π` and `Σ` have no ascii form, they're
+    // optional prefixes of the pi and sigma types.
     let unicode = just("λ")
-        .to(Token::new(LambdaUnicode, "λ"))
-        .or(just("∀").to(Token::new(ForallUnicode, "∀")))
+        .to(Token::new(Symbol, "\\"))
+        .or(just("∀").to(Token::new(ForallKeyword, "forall")))
+        .or(just("→").to(Token::new(RightArrow, "->")))
+        .or(just("←").to(Token::new(LeftArrow, "<-")))
+        .or(just("⇒").to(Token::new(DoubleArrow, "=>")))
         .or(just("Π").to(Token::new(PiUnicode, "Π")))
         .or(just("Σ").to(Token::new(SigmaUnicode, "Σ")));
 
-    let token = control_lexer()
+    // The named functions can be used as infix operators between backticks, like ``a `add` b``,
+    // they're lexed as the symbols named by the functions.
+    let backtick = just('`')
+        .ignore_then(ident())
+        .then_ignore(just('`'))
+        .map(|ident| Token::new(Symbol, &ident))
+        .labelled("backtick operator");
+
+    // The composition is a dot followed by a whitespace, like `f . g`, otherwise it's the dot of
+    // the qualified paths, like `Foo.bar`.
+    let compose = just('.')
+        .then_ignore(one_of(" \t\r\n").rewind())
+        .to(Token::new(Symbol, "."))
+        .labelled("composition");
+
+    // The characters that no other token starts with, they're kept as tokens, so the parser can
+    // report what was skipped, instead of them silently vanishing.
+    let error_char = any()
+        .map_slice(|character: &str| Token::new(ErrorChar, character))
+        .labelled("unrecognized character");
+
+    let token = compose
+        .or(control_lexer())
         .or(semi)
         .or(unicode)
         .or(symbol)
+        .or(backtick)
         .or(num)
         .or(string)
-        .or(ident_lexer());
+        .or(character)
+        .or(raw_string)
+        .or(ident_lexer(edition))
+        .or(error_char);
 
     token
         .map_with_span(|tok, span| (tok, span))
@@ -92,6 +163,71 @@ pub fn lexer<'a>() -> impl Parser<'a, &'a str, TokenSet, LexError<'a>> {
         .collect()
 }
 
+/// Digits of the given radix, they can be separated by underscores, like `1_000_000`, but
+/// can't start with an underscore, otherwise it would be an identifier.
+fn digits<'a>(radix: u32) -> impl Parser<'a, &'a str, &'a str, LexError<'a>> + Clone {
+    any()
+        .filter(move |c: &char| c.is_digit(radix))
+        .then(
+            any()
+                .filter(move |c: &char| c.is_digit(radix) || *c == '_')
+                .repeated(),
+        )
+        .slice()
+}
+
+/// The exponent of the float literals, like `e3` in `1e3`, or `e-3` in `2.5e-3`.
+fn exponent<'a>() -> impl Parser<'a, &'a str, &'a str, LexError<'a>> + Clone {
+    one_of("eE")
+        .then(one_of("+-").or_not())
+        .then(digits(10))
+        .slice()
+}
+
+/// The number suffixes, they set the type of the number literal, like `255u8`. The longer
+/// suffixes comes first, so `u` doesn't shadow `u8`.
+fn suffix_lexer<'a>() -> impl Parser<'a, &'a str, TokenKind, LexError<'a>> + Clone {
+    choice((
+        just("i128").to(Int128),
+        just("u128").to(UInt128),
+        just("i16").to(Int16),
+        just("u16").to(UInt16),
+        just("i32").to(Int32),
+        just("u32").to(UInt32),
+        just("i64").to(Int64),
+        just("u64").to(UInt64),
+        just("i8").to(Int8),
+        just("u8").to(UInt8),
+        just("f32").to(Float32),
+        just("f64").to(Float64),
+        just("n").to(Nat),
+        just("u").to(UInt32),
+    ))
+}
+
+/// Builds the number token, the text of the integers is normalized into decimal digits without
+/// separators, so `0xFF_u8` has the text `255`. If the value doesn't fit even in a [u128], the
+/// digits are kept, and the parser reports that the value is out of range. The floats keep their
+/// digits, like `1.50` or `1e3`, only without the separators.
+fn number(radix: u32, digits: &str, suffix: Option<TokenKind>) -> Token {
+    let digits = digits.replace('_', "");
+    let is_float = radix == 10 && digits.contains(['.', 'e', 'E']);
+    let kind = match suffix {
+        Some(kind) => kind,
+        None if is_float => Float64,
+        None => Int32,
+    };
+
+    if is_float || (matches!(kind, Float32 | Float64) && radix == 10) {
+        return Token::new(kind, &digits);
+    }
+
+    match u128::from_str_radix(&digits, radix) {
+        Ok(value) => Token::new(kind, &value.to_string()),
+        Err(_) => Token::new(kind, &digits),
+    }
+}
+
 fn control_lexer<'a>() -> impl Parser<'a, &'a str, Token, LexError<'a>> {
     one_of("()[]{},.")
         .map(|control: char| match control {
@@ -111,32 +247,20 @@ fn control_lexer<'a>() -> impl Parser<'a, &'a str, Token, LexError<'a>> {
         .labelled("control flow symbol")
 }
 
-fn ident_lexer<'a>() -> impl Parser<'a, &'a str, Token, LexError<'a>> {
-    text::ident()
-        .map(|ident: &str| match ident {
-            "let" => Token::new(LetKeyword, ident),
-            "true" => Token::new(TrueKeyword, ident),
-            "false" => Token::new(FalseKeyword, ident),
-            "if" => Token::new(IfKeyword, ident),
-            "else" => Token::new(ElseKeyword, ident),
-            "then" => Token::new(ThenKeyword, ident),
-            "type" => Token::new(TypeKeyword, ident),
-            "record" => Token::new(RecordKeyword, ident),
-            "return" => Token::new(ReturnKeyword, ident),
-            "enum" => Token::new(EnumKeyword, ident),
-            "trait" => Token::new(TraitKeyword, ident),
-            "class" => Token::new(ClassKeyword, ident),
-            "case" => Token::new(CaseKeyword, ident),
-            "where" => Token::new(WhereKeyword, ident),
-            "match" => Token::new(MatchKeyword, ident),
-            "use" => Token::new(UseKeyword, ident),
-            "in" => Token::new(InKeyword, ident),
-            "fun" => Token::new(FunKeyword, ident),
-            "self" => Token::new(SelfKeyword, ident),
-            "instance" => Token::new(InstanceKeyword, ident),
-            "default" => Token::new(DefaultKeyword, ident),
-            _ => Token::new(Identifier, ident),
-        })
+/// The identifiers are the unicode XID identifiers, like `café` or `αβ`, normalized into the NFC
+/// form, so the names that look the same are the same, like the precomposed `é` and the `e`
+/// followed by the combining accent.
+fn ident<'a>() -> impl Parser<'a, &'a str, String, LexError<'a>> + Clone {
+    any()
+        .filter(|c: &char| *c == '_' || is_xid_start(*c))
+        .then(any().filter(|c: &char| is_xid_continue(*c)).repeated())
+        .slice()
+        .map(|ident: &str| ident.nfc().collect())
+}
+
+fn ident_lexer<'a>(edition: Edition) -> impl Parser<'a, &'a str, Token, LexError<'a>> {
+    ident()
+        .map(move |ident| Token::new(edition.keyword(&ident).unwrap_or(Identifier), &ident))
         .labelled("keyword")
 }
 
@@ -154,11 +278,116 @@ fn map_full_text(code: &str, mut token_set: TokenSet) -> Vec<(Token, SimpleSpan)
     token_set
 }
 
+/// Splits the string literals with holes, like `"hello {name}!"`, into the [StrStart],
+/// [StrMiddle] and [StrEnd] chunks, with the tokens of the holes between them. The spans of
+/// the chunks include the braces, so the full text of the tokens still builds the source code.
+fn split_interpolations(code: &str, token_set: TokenSet, edition: Edition) -> TokenSet {
+    let mut result = TokenSet::with_capacity(token_set.len());
+    for (token, span) in token_set {
+        if token.kind != Str || is_raw_string(&token.text) {
+            result.push((token, span));
+            continue;
+        }
+
+        match interpolate(code, span, edition) {
+            Some(tokens) => result.extend(tokens),
+            None => result.push((token, span)),
+        }
+    }
+    result
+}
+
+/// Returns if the string literal is a raw string, like `r"..."`, they don't have holes.
+fn is_raw_string(text: &str) -> bool {
+    text.starts_with('r')
+}
+
+/// Lexes the holes of the string literal in the given span. Returns [None] if the string has
+/// no holes, or if a hole can't be lexed, so it's kept as a plain string literal.
+///
+/// The braces can be escaped with `\{`, or by doubling them, like `{{`, and the hole braces can
+/// be nested.
+fn interpolate(code: &str, span: Span, edition: Edition) -> Option<TokenSet> {
+    let text = &code[span.start..span.end];
+    let mut tokens = TokenSet::new();
+    let mut chunk_kind = StrStart;
+    let mut chunk_start = span.start;
+    let mut chars = text.char_indices().skip(1);
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '{' if text[i + 1..].starts_with('{') => {
+                chars.next();
+            }
+            '{' => {
+                let hole_start = span.start + i + 1;
+                let mut hole_end = None;
+                let mut depth = 1;
+                for (j, c) in chars.by_ref() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' if depth == 1 => {
+                            hole_end = Some(span.start + j);
+                            break;
+                        }
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                }
+                let hole_end = hole_end?;
+
+                let chunk = &code[chunk_start..hole_start];
+                tokens.push((
+                    Token::new(chunk_kind, chunk),
+                    (chunk_start..hole_start).into(),
+                ));
+
+                let (hole, errors) = edition_lexer(edition)
+                    .parse(&code[hole_start..hole_end])
+                    .into_output_errors();
+                if !errors.is_empty() {
+                    return None;
+                }
+                tokens.extend(hole?.into_iter().map(|(token, span)| {
+                    let span: Span = (hole_start + span.start..hole_start + span.end).into();
+                    (token, span)
+                }));
+
+                chunk_kind = StrMiddle;
+                chunk_start = hole_end;
+            }
+            _ => {}
+        }
+    }
+
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let chunk = &code[chunk_start..span.end];
+    tokens.push((Token::new(StrEnd, chunk), (chunk_start..span.end).into()));
+
+    Some(tokens)
+}
+
 impl<'a> Lexer<'a> {
     /// Creates a new [Lexer] based in a source code
     pub fn new<I: Into<Option<PathBuf>>>(path: I, code: &'a str) -> Self {
-        let (tokens, errs) = lexer().parse(code).into_output_errors();
-        let tokens = map_full_text(code, tokens.unwrap_or_default());
+        Self::with_edition(path, code, Edition::default())
+    }
+
+    /// Creates a new [Lexer] based in a source code, with the keywords of the edition.
+    pub fn with_edition<I: Into<Option<PathBuf>>>(
+        path: I,
+        code: &'a str,
+        edition: Edition,
+    ) -> Self {
+        let (tokens, errs) = edition_lexer(edition).parse(code).into_output_errors();
+        let tokens = split_interpolations(code, tokens.unwrap_or_default(), edition);
+        let tokens = map_full_text(code, tokens);
         let path: Option<PathBuf> = path.into();
 
         Self {
@@ -217,6 +217,42 @@ pub enum ParseError {
 
     #[error("trailling comma is required")]
     RequiredTraillingCommaLint,
+
+    #[error("[internal] the parser is stuck, it ran out of fuel without advancing")]
+    ParserStuckError,
+
+    #[error("expected expression after unary minus")]
+    ExpectedUnaryOperandError,
+
+    #[error("the literal `{}` doesn't fit in `{}`", .0, .1)]
+    IntegerOutOfRangeError(String, &'static str),
+
+    #[error("expected the type variables of the forall")]
+    ExpectedForallVariableError,
+
+    #[error("expected `.` after the type variables of the forall")]
+    ExpectedForallDotError,
+
+    #[error("expected the type of the forall")]
+    ExpectedForallBodyError,
+
+    #[error("expression too deeply nested")]
+    ExprTooDeeplyNestedError,
+
+    #[error("the visibility isn't allowed in commands and instances")]
+    UnexpectedVisibilityError,
+
+    #[error("expected the literal at the end of the range")]
+    ExpectedRangeEndError,
+
+    #[error("expected the second dot of the spread pattern `..`")]
+    ExpectedSpreadDotError,
+
+    #[error("unrecognized character `{}`, it was skipped", .0.escape_debug())]
+    UnrecognizedCharError(char),
+
+    #[error("the float literal `{}` can't have the suffix of `{}`", .0, .1)]
+    FloatIntegerSuffixError(String, &'static str),
 }
 
 impl ParseError {
@@ -29,6 +29,11 @@ pub struct RedTree {
 impl<'a> Parser<'a> {
     pub fn build_tree(mut self) -> RedTree {
         let event_debugger = EventBuilder::new(self.events.clone());
+        let too_deep_at = self.too_deep.take();
+        let stuck_at = self.stuck.get().then(|| match self.tokens.get(self.index) {
+            Some(token) => token.span.clone(),
+            None => Loc::default(),
+        });
         let mut tokens = self.tokens.into_iter();
         let mut events = self.events;
         let mut stack = vec![];
@@ -37,10 +42,8 @@ impl<'a> Parser<'a> {
         // that the stack is non-empty inside the loop.
         if !matches!(events.pop(), Some(Event::Close)) {
             #[cfg(debug_assertions)]
-            {
-                println!("  -> Debug event trace: ()");
-                println!("{:?}", event_debugger);
-            };
+            tracing::debug!(events = ?event_debugger, "the events don't end with a close");
+
             let error = ParseError::EmptyStackError;
             self.errors
                 .push(Diagnostic::new(Spanned::new((0..0).into(), error)))
@@ -63,8 +66,7 @@ impl<'a> Parser<'a> {
                         // If we don't pop the last `Close` before this loop,
                         // this unwrap would trigger for it.
                         .unwrap_or_else(|| {
-                            println!("  -> Debug event trace: (Event::Close)");
-                            println!("{:?}", event_debugger.clone());
+                            tracing::error!(events = ?event_debugger, "closed the root tree");
                             panic!("Could not continue parsing");
                         })
                         .value
@@ -79,8 +81,7 @@ impl<'a> Parser<'a> {
                     stack
                         .last_mut()
                         .unwrap_or_else(|| {
-                            println!("  -> Debug event trace: (Event::Advance)");
-                            println!("{:?}", event_debugger.clone());
+                            tracing::error!(events = ?event_debugger, "advanced without a tree");
                             panic!("Could not continue parsing");
                         })
                         .value
@@ -89,8 +90,12 @@ impl<'a> Parser<'a> {
                 }
 
                 Event::Field(name) => {
-                    let last_item = stack.last_mut().unwrap();
-                    let last_child = last_item.children.last_mut().unwrap();
+                    let Some(last_item) = stack.last_mut() else {
+                        continue;
+                    };
+                    let Some(last_child) = last_item.children.last_mut() else {
+                        continue;
+                    };
                     match &mut last_child.value {
                         Child::Tree(tree) => tree.name = Some(name),
                         Child::Token(token) => token.name = Some(name),
@@ -108,12 +113,20 @@ impl<'a> Parser<'a> {
                 .push(Diagnostic::new(Spanned::new((0..0).into(), error)))
         }
 
-        if let Some(token) = tokens.next() {
+        for loc in too_deep_at {
+            let error = ParseError::ExprTooDeeplyNestedError;
+            self.errors.push(Diagnostic::new(Spanned::new(loc, error)));
+        }
+
+        if let Some(loc) = stuck_at {
+            let error = ParseError::ParserStuckError;
+            self.errors.push(Diagnostic::new(Spanned::new(loc, error)));
+        } else if let Some(token) = tokens.next() {
             let error = ParseError::StreamStillContainElements(token.kind);
             self.errors.push(Diagnostic::new(token.swap(error)));
         }
 
-        let tree = stack.pop().unwrap();
+        let tree = stack.pop().unwrap_or_default();
         let mut report = Report::new(self.source, tree.clone());
         for diagnostic in &self.errors {
             report.diagnostics.push(diagnostic.clone());
@@ -1,11 +1,12 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
-use crate::error::ParseError;
+use crate::error::ParseError::{self, UnrecognizedCharError};
 
-use asena_leaf::node::Token;
+use asena_leaf::node::{kind::TokenKind, Token};
 use asena_lexer::Lexer;
 use asena_report::Diagnostic;
-use asena_span::{Localized, Spanned};
+use asena_span::{Loc, Localized, Spanned};
 
 use self::event::Event;
 
@@ -18,6 +19,10 @@ pub mod error;
 pub mod event;
 pub mod support;
 
+/// The default limit of the nested grammar rules, like the parenthesis in `((1))`, it's small
+/// enough to not overflow the stack of the threads.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
 /// The language parser struct, it takes a [Token] iterator, that can be lazy or eager initialized
 /// to advance and identify tokens on the programming language.
 #[derive(Clone)]
@@ -26,6 +31,13 @@ pub struct Parser<'a> {
     source: &'a str,
     index: usize,
     fuel: Cell<u32>,
+    stuck: Cell<bool>,
+    depth: usize,
+    max_depth: usize,
+
+    /// Where the nesting limit was reached, it's shared with the savepoints, so the locations
+    /// reached again by the speculative parsing are reported only once.
+    too_deep: Rc<RefCell<Vec<Loc>>>,
     tokens: Vec<Spanned<Token>>,
     events: Vec<Event>,
 }
@@ -36,12 +48,23 @@ impl<'a> Parser<'a> {
             source,
             index: 0,
             fuel: Cell::new(256),
+            stuck: Cell::new(false),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            too_deep: Default::default(),
             tokens,
             errors: Default::default(),
             events: Default::default(),
         }
     }
 
+    /// Sets the limit of the nested grammar rules, the inputs nested deeper than it are reported
+    /// instead of overflowing the stack.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
     pub fn run<A>(mut self, grammar: fn(&mut Parser) -> A) -> Self {
         grammar(&mut self);
         self
@@ -50,6 +73,39 @@ impl<'a> Parser<'a> {
 
 impl<'a> From<Lexer<'a>> for Parser<'a> {
     fn from(value: Lexer<'a>) -> Self {
-        Self::new(value.source, value.tokens)
+        let (tokens, errors) = skip_error_chars(value.tokens);
+        let mut parser = Self::new(value.source, tokens);
+        parser.errors = errors;
+        parser
     }
 }
+
+/// Removes the unrecognized characters from the token stream, reporting each of them. Their
+/// text is moved into the whitespace of the next token, so the tree still builds the source.
+fn skip_error_chars(
+    tokens: Vec<Spanned<Token>>,
+) -> (Vec<Spanned<Token>>, Vec<Diagnostic<ParseError>>) {
+    let mut errors = vec![];
+    let mut skipped = String::new();
+    let mut result = Vec::with_capacity(tokens.len());
+
+    for mut token in tokens {
+        if token.kind == TokenKind::ErrorChar {
+            let character = token.text.chars().next().unwrap_or_default();
+            skipped.push_str(&token.full_text.to_string());
+            errors.push(Diagnostic::new(
+                token.swap(UnrecognizedCharError(character)),
+            ));
+            continue;
+        }
+
+        if !skipped.is_empty() {
+            let skipped = std::mem::take(&mut skipped);
+            token.full_text.before_whitespace.insert_str(0, &skipped);
+        }
+
+        result.push(token);
+    }
+
+    (result, errors)
+}
@@ -77,6 +77,12 @@ impl<'a> Parser<'a> {
     }
 
     pub fn advance(&mut self) {
+        // The parser stopped consuming tokens, and the error will be reported when building the
+        // tree, so it does nothing.
+        if self.is_stuck() {
+            return;
+        }
+
         #[cfg(debug_assertions)]
         assert!(!self.eof(), "Found eof at index {}", self.index);
 
@@ -85,8 +91,64 @@ impl<'a> Parser<'a> {
         self.index += 1;
     }
 
+    /// Returns if the parser reached the end of the token stream, or if it's stuck, so the
+    /// grammar loops can finish.
     pub fn eof(&mut self) -> bool {
-        self.tokens.len() == self.index
+        self.tokens.len() == self.index || self.is_stuck()
+    }
+
+    /// Returns if the parser ran out of fuel, it means that the grammar was looking ahead
+    /// without advancing, and that it's a bug in the grammar.
+    pub fn is_stuck(&self) -> bool {
+        self.stuck.get()
+    }
+
+    /// Enters a nested grammar rule, it returns `false` if the nesting limit was reached, then
+    /// the rule should be skipped with [Parser::skip_too_deep], and [Parser::exit] shouldn't be
+    /// called.
+    pub fn enter(&mut self) -> bool {
+        if self.depth >= self.max_depth {
+            return false;
+        }
+
+        self.depth += 1;
+        true
+    }
+
+    /// Skips the rule that reached the nesting limit into an error node, until the end of the
+    /// offending expression, like the delimiter that closes the enclosing rule, so the parsing
+    /// recovers after it.
+    pub fn skip_too_deep(&mut self) -> MarkClosed {
+        use TokenKind::*;
+
+        let loc = self.peek().span.clone();
+        let mut too_deep = self.too_deep.borrow_mut();
+        if !too_deep.contains(&loc) {
+            too_deep.push(loc);
+        }
+        drop(too_deep);
+
+        let mark = self.open();
+        let start = self.index;
+        let mut nesting = 0usize;
+        while !self.eof() {
+            match self.lookahead(0) {
+                LeftParen | LeftBracket | LeftBrace => nesting += 1,
+                RightParen | RightBracket | RightBrace if nesting == 0 => break,
+                RightParen | RightBracket | RightBrace => nesting -= 1,
+                Comma | Semi | DoubleArrow if nesting == 0 => break,
+                _ if nesting == 0 && self.index > start && self.at_newline(0) => break,
+                _ => {}
+            }
+
+            self.advance();
+        }
+
+        self.close(mark, TreeKind::Error)
+    }
+
+    pub fn exit(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
     }
 
     pub fn savepoint(&self) -> Self {
@@ -95,13 +157,22 @@ impl<'a> Parser<'a> {
             source: self.source,
             index: self.index,
             fuel: Cell::new(256),
+            stuck: Cell::new(self.stuck.get()),
+            depth: self.depth,
+            max_depth: self.max_depth,
+            too_deep: self.too_deep.clone(),
             events: self.events.clone(),
             tokens: self.tokens.clone(),
         }
     }
 
     pub fn return_at(&mut self, point: Self) {
+        if point.is_stuck() {
+            self.stuck.set(true);
+        }
+
         self.index = point.index;
+        self.depth = point.depth;
         self.events = point.events;
     }
 
@@ -179,10 +250,16 @@ impl<'a> Parser<'a> {
             .map_or(TokenKind::Eof, |token| token.value.kind)
     }
 
+    /// Looks ahead the token stream, every call consumes fuel, that is only restored when the
+    /// parser advances. If the fuel ends, the parser is marked as stuck, and it behaves like it
+    /// reached the end of the file, so the grammar can't loop forever.
     pub fn nth(&self, lookahead: usize) -> Option<&Spanned<Token>> {
-        #[cfg(debug_assertions)]
         if self.fuel.get() == 0 {
-            panic!("parser is stuck")
+            self.stuck.set(true);
+        }
+
+        if self.is_stuck() {
+            return None;
         }
 
         self.fuel.set(self.fuel.get() - 1);
@@ -7,7 +7,7 @@ use asena_derive::*;
 use asena_ast::command::Result;
 use asena_ast::*;
 
-use asena_report::{Diagnostic, Reports};
+use asena_report::{DiagnosticSink, Reports};
 use im::HashMap;
 
 use crate::PrecDatabase;
@@ -23,7 +23,7 @@ impl<'db> InfixHandler<'db> {
 }
 
 impl<'db> Reports for InfixHandler<'db> {
-    fn errors(&self) -> Arc<RwLock<Vec<Diagnostic<asena_report::BoxInternalError>>>> {
+    fn sink(&self) -> &dyn DiagnosticSink {
         todo!()
     }
 }
@@ -53,18 +53,20 @@ impl<'db> CommandHandler for InfixHandler<'db> {
 
 /// The default precedence table for Asena Language in the Standard Library.
 ///   - `->`, `=>`
-///   - `^`, `>>`, `<<`, `|`, `&`
+///   - `.`, `^`, `>>`, `<<`, `|`, `&`
 ///   - `>`, `>=`, `<=`, `<`
-///   - `==`, `!=`
+///   - `==`, `!=`, `/=`
 ///   - `||`, `&&`
 ///   - `$`, `%`, `=>>`, `@`
 ///   - `^^`
 ///   - `*`, `/`
 ///   - `+`, `-`
+///   - `|>`
 pub fn default_prec_table() -> HashMap<FunctionId, Entry> {
     let mut table = HashMap::new();
 
-    // `^`, `>>`, `<<`, `|`, `&`
+    // `.`, `^`, `>>`, `<<`, `|`, `&`
+    table.insert(".".into(), Entry::new(".", Assoc::Right, 9));
     table.insert("^".into(), Entry::new("==", Assoc::Right, 9));
     table.insert("|".into(), Entry::new("|", Assoc::Right, 9));
     table.insert("&".into(), Entry::new("&", Assoc::Right, 9));
@@ -77,9 +79,10 @@ pub fn default_prec_table() -> HashMap<FunctionId, Entry> {
     table.insert(">=".into(), Entry::new(">=", Assoc::Right, 8));
     table.insert("<=".into(), Entry::new("<=", Assoc::Right, 8));
 
-    // `==`, `!=`
+    // `==`, `!=`, `/=`
     table.insert("==".into(), Entry::new("==", Assoc::Right, 7));
     table.insert("!=".into(), Entry::new("!=", Assoc::Right, 7));
+    table.insert("/=".into(), Entry::new("/=", Assoc::Right, 7));
 
     // `||`, `&&`
     table.insert("||".into(), Entry::new("||", Assoc::Right, 6));
@@ -102,6 +105,9 @@ pub fn default_prec_table() -> HashMap<FunctionId, Entry> {
     table.insert("+".into(), Entry::new("+", Assoc::Right, 1));
     table.insert("-".into(), Entry::new("-", Assoc::Right, 1));
 
+    // `|>`
+    table.insert("|>".into(), Entry::new("|>", Assoc::Left, 0));
+
     table
 }
 
@@ -1,5 +1,6 @@
 use asena_ast_db::{commands::CommandHandlerEval, db::AstDatabase};
 use asena_leaf::ast::AstParam;
+use asena_report::profile::{self, ProfilePhase};
 
 use super::*;
 
@@ -16,11 +17,17 @@ fn prec_table(_db: &dyn PrecDatabase) -> PrecTable {
 }
 
 fn ordered_prec(db: &dyn PrecDatabase, file: AstParam<AsenaFile>) -> AsenaFile {
-    file.data.walks(PrecReorder { db })
+    let _arena = db.node_arena().enter();
+    profile::phase(ProfilePhase::Precedence, || {
+        file.data.walks(PrecReorder { db })
+    })
 }
 
 fn infix_commands(db: &dyn PrecDatabase, file: AstParam<AsenaFile>) -> AsenaFile {
-    let mut handler = InfixHandler::new(db);
-    let eval = CommandHandlerEval::new(db, &mut handler);
-    file.data.walks(eval)
+    let _arena = db.node_arena().enter();
+    profile::phase(ProfilePhase::Precedence, || {
+        let mut handler = InfixHandler::new(db);
+        let eval = CommandHandlerEval::new(db, &mut handler);
+        file.data.walks(eval)
+    })
 }
@@ -1,6 +1,7 @@
 #![feature(trait_upcasting)]
 
 use asena_ast::*;
+use asena_leaf::ast::Lexeme;
 
 pub mod commands;
 pub mod db;
@@ -16,6 +17,10 @@ impl<'db> AsenaVisitor<()> for PrecReorder<'db> {
     fn visit_qual(&mut self, value: Qual) {
         self.impl_reorder_prec(&value);
     }
+
+    fn visit_infix(&mut self, value: Infix) {
+        self.impl_reorder_chain(&value);
+    }
 }
 
 impl<'db> PrecReorder<'db> {
@@ -44,4 +49,81 @@ impl<'db> PrecReorder<'db> {
 
         Some(())
     }
+
+    /// Reorders a chain of infix expressions, like `a == b + c * d && e`, that the parser builds
+    /// leaning to the right, as `a == (b + (c * (d && e)))`. The operands and the operators are
+    /// collected from the right spine, and the tree is rebuilt with the precedence of the
+    /// operators, the unknown operators bind the tightest.
+    fn impl_reorder_chain(&mut self, binary: &impl Binary) -> Option<()> {
+        let mut operands = vec![binary.lhs()];
+        let mut operators = vec![binary.fn_id()];
+        let mut rhs = binary.rhs();
+        while let Expr::Infix(ref infix) = rhs {
+            operands.push(infix.lhs());
+            operators.push(infix.fn_id());
+            rhs = infix.rhs();
+        }
+        operands.push(rhs);
+
+        // There's nothing to reorder in a single operation
+        if operators.len() < 2 {
+            return None;
+        }
+
+        let prec_table = self.db.prec_table();
+        let prec_table = prec_table.read().unwrap();
+        let entry_of = |fn_id: &Lexeme<FunctionId>| {
+            prec_table
+                .get(&**fn_id)
+                .cloned()
+                .unwrap_or_else(|| Entry::new((**fn_id).clone(), Assoc::Left, u8::MAX))
+        };
+
+        let mut operands = operands.into_iter();
+        let mut output = vec![operands.next()?];
+        let mut stack: Vec<(Lexeme<FunctionId>, Entry)> = vec![];
+        for (fn_id, operand) in operators.into_iter().zip(operands) {
+            let entry = entry_of(&fn_id);
+            while let Some((_, top)) = stack.last() {
+                let binds_tighter = top.order > entry.order
+                    || (top.order == entry.order && entry.assoc == Assoc::Left);
+                if !binds_tighter {
+                    break;
+                }
+
+                let (top_fn_id, _) = stack.pop()?;
+                let rhs = output.pop()?;
+                let lhs = output.pop()?;
+                output.push(new_binary(binary, lhs, top_fn_id, rhs));
+            }
+
+            stack.push((fn_id, entry));
+            output.push(operand);
+        }
+
+        // The last operation is the root of the chain, so it's set in the node itself
+        while stack.len() > 1 {
+            let (fn_id, _) = stack.pop()?;
+            let rhs = output.pop()?;
+            let lhs = output.pop()?;
+            output.push(new_binary(binary, lhs, fn_id, rhs));
+        }
+
+        let (fn_id, _) = stack.pop()?;
+        let rhs = output.pop()?;
+        let lhs = output.pop()?;
+        binary.set_lhs(lhs);
+        binary.set_fn_id(fn_id);
+        binary.set_rhs(rhs);
+
+        Some(())
+    }
+}
+
+fn new_binary(binary: &impl Binary, lhs: Expr, fn_id: Lexeme<FunctionId>, rhs: Expr) -> Expr {
+    let new_binary = binary.as_new_ast::<VirtualBinary>();
+    new_binary.set_lhs(lhs);
+    new_binary.set_fn_id(fn_id);
+    new_binary.set_rhs(rhs);
+    new_binary.into()
 }
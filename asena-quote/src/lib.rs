@@ -0,0 +1,91 @@
+//! The quasiquoting of the Asena snippets, [quote_ast!] builds the typed AST nodes from the
+//! inline snippets, so the synthetic trees of the tests, and of the expansions of the commands,
+//! don't need to be built by hand with `GreenTree::of`.
+//!
+//! The snippets are parsed when the macro is expanded, so the syntax errors are reported at
+//! compile time, and they're parsed again, with [asena_grammar::entry], when the code runs, as
+//! the trees can't be constants. The crates that use the macro should depend on
+//! `asena-grammar`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+/// The kind of the node, and the snippet, like `expr, "1 + 1"`.
+struct QuoteInput {
+    kind: Ident,
+    code: LitStr,
+}
+
+impl Parse for QuoteInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // The `type` is a keyword, so it's parsed as any identifier.
+        let kind = input.call(Ident::parse_any)?;
+        input.parse::<Token![,]>()?;
+        let code = input.parse()?;
+
+        Ok(Self { kind, code })
+    }
+}
+
+/// Builds the typed AST node of the snippet, the kind is one of `expr`, `type`, `decl`, or
+/// `stmt`, and the node is an `Expr`, a `Typed`, a `Decl`, or a `Stmt`, respectively.
+///
+/// # Example
+/// ```rust,norun
+/// let double = quote_ast!(decl, "Double (x: Int32) : Int32 { x + x }");
+/// let lam = quote_ast!(expr, "\\x -> x");
+/// ```
+#[proc_macro]
+pub fn quote_ast(input: TokenStream) -> TokenStream {
+    let QuoteInput { kind, code } = syn::parse_macro_input!(input as QuoteInput);
+    let source = code.value();
+
+    let (entry, errors) = match kind.to_string().as_str() {
+        "expr" => (
+            quote!(parse_expr),
+            messages(asena_grammar::parse_expr(&source)),
+        ),
+        "type" => (
+            quote!(parse_type),
+            messages(asena_grammar::parse_type(&source)),
+        ),
+        "decl" => (
+            quote!(parse_decl),
+            messages(asena_grammar::parse_decl(&source)),
+        ),
+        "stmt" => (
+            quote!(parse_stmt),
+            messages(asena_grammar::parse_stmt(&source)),
+        ),
+        _ => {
+            let message = "expected one of `expr`, `type`, `decl`, or `stmt`";
+
+            return syn::Error::new(kind.span(), message)
+                .into_compile_error()
+                .into();
+        }
+    };
+
+    if !errors.is_empty() {
+        let message = format!("the snippet can't be parsed: {}", errors.join(", "));
+
+        return syn::Error::new(code.span(), message)
+            .into_compile_error()
+            .into();
+    }
+
+    TokenStream::from(quote! {
+        ::asena_grammar::#entry(#code).node
+    })
+}
+
+fn messages<T>(parsed: asena_grammar::Parsed<T>) -> Vec<String> {
+    parsed
+        .errors
+        .into_iter()
+        .map(|diagnostic| diagnostic.message.value.to_string())
+        .collect()
+}
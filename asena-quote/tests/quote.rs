@@ -0,0 +1,17 @@
+use asena_ast::{Decl, Expr, Stmt, Typed};
+use asena_quote::quote_ast;
+
+#[test]
+fn quote_ast_works() {
+    let expr = quote_ast!(expr, "\\x -> x");
+    assert!(matches!(expr, Expr::Lam(_)));
+
+    let typed = quote_ast!(type, "Maybe a");
+    assert!(matches!(typed, Typed::Explicit(_)));
+
+    let decl = quote_ast!(decl, "Double (x: Int32) : Int32 { x + x }");
+    assert!(matches!(decl, Decl::Signature(_)));
+
+    let stmt = quote_ast!(stmt, "return 10");
+    assert!(matches!(stmt, Stmt::Return(_)));
+}
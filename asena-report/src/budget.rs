@@ -0,0 +1,265 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::*;
+
+/// The phase of the compiler that reported a diagnostic, it's used to query if a phase failed,
+/// so the driver can stop the pipeline before running the next ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    Parsing,
+    Resolution,
+    Lowering,
+    Typing,
+    Other,
+}
+
+/// Configuration of the reporting side of the compiler.
+#[derive(Debug, Default, Clone)]
+pub struct ReportConfig {
+    /// The amount of errors that can be reported before stopping the analysis, if it's
+    /// [None], there's no limit.
+    pub max_errors: Option<usize>,
+
+    /// Warning codes that should be treated as errors.
+    pub deny: im::HashSet<u16>,
+}
+
+/// Keeps track of the number of errors reported in each [Phase], and escalates the denied
+/// warnings into errors, using the [ReportConfig].
+#[derive(Debug, Default)]
+pub struct ErrorBudget {
+    config: RwLock<ReportConfig>,
+    errors: AtomicUsize,
+
+    /// The diagnostics dropped after the budget was exhausted, they're summarized at the end.
+    dropped: AtomicUsize,
+    phases: RwLock<fxhash::FxHashMap<Phase, usize>>,
+}
+
+impl DiagnosticKind {
+    pub fn phase(&self) -> Phase {
+        match self {
+            DiagnosticKind::SyntaxError => Phase::Parsing,
+            DiagnosticKind::ResolutionError => Phase::Resolution,
+            DiagnosticKind::LoweringError => Phase::Lowering,
+            DiagnosticKind::TypeError => Phase::Typing,
+            _ => Phase::Other,
+        }
+    }
+}
+
+impl ReportConfig {
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    pub fn deny(mut self, code: u16) -> Self {
+        self.deny.insert(code);
+        self
+    }
+}
+
+impl ErrorBudget {
+    pub fn new(config: ReportConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            ..Default::default()
+        }
+    }
+
+    pub fn config(&self) -> ReportConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: ReportConfig) {
+        *self.config.write().unwrap() = config;
+    }
+
+    /// Applies the configuration to the diagnostic, and records it. Returns [None] if the
+    /// budget is already exhausted, so the diagnostic should be dropped.
+    pub fn admit<E>(&self, mut diagnostic: Diagnostic<E>) -> Option<Diagnostic<E>> {
+        let config = self.config.read().unwrap();
+        if !diagnostic.kind.is_error() && config.deny.contains(&diagnostic.code) {
+            diagnostic.kind = DiagnosticKind::Error;
+        }
+
+        // The errors are checked against the limit and counted in a single atomic operation, so
+        // the queries running in parallel can't admit more errors than the limit.
+        let max_errors = config.max_errors.unwrap_or(usize::MAX);
+        let admitted = if diagnostic.kind.is_error() {
+            self.errors
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |errors| {
+                    (errors < max_errors).then_some(errors + 1)
+                })
+                .is_ok()
+        } else {
+            self.error_count() < max_errors
+        };
+
+        if !admitted {
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+            return None;
+        }
+
+        if diagnostic.kind.is_error() {
+            let mut phases = self.phases.write().unwrap();
+            *phases.entry(diagnostic.kind.phase()).or_default() += 1;
+        }
+
+        Some(diagnostic)
+    }
+
+    /// Forgets the errors reported so far, the configuration is kept.
+    pub fn reset(&self) {
+        self.errors.store(0, Ordering::SeqCst);
+        self.dropped.store(0, Ordering::SeqCst);
+        self.phases.write().unwrap().clear();
+    }
+
+    /// Returns the amount of errors reported.
+    pub fn error_count(&self) -> usize {
+        self.errors.load(Ordering::SeqCst)
+    }
+
+    /// Returns the amount of diagnostics dropped after the error limit was reached.
+    pub fn dropped_count(&self) -> usize {
+        self.dropped.load(Ordering::SeqCst)
+    }
+
+    /// Returns the note that is reported after the diagnostics, if some of them were dropped
+    /// because the error limit was reached.
+    pub fn overflow_summary(&self) -> Option<String> {
+        let dropped = self.dropped_count();
+        if dropped == 0 {
+            return None;
+        }
+
+        let errors = match self.error_count() {
+            1 => "1 error".to_string(),
+            count => format!("{count} errors"),
+        };
+        let diagnostics = match dropped {
+            1 => "1 more diagnostic wasn't".to_string(),
+            count => format!("{count} more diagnostics weren't"),
+        };
+
+        Some(format!(
+            "the analysis stopped after {errors}, {diagnostics} reported"
+        ))
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    /// Returns if there are errors reported by the given phase.
+    pub fn has_errors_in(&self, phase: Phase) -> bool {
+        self.phases
+            .read()
+            .unwrap()
+            .get(&phase)
+            .map_or(false, |count| *count > 0)
+    }
+
+    /// Returns if the error limit was reached, and the analysis should stop.
+    pub fn is_exhausted(&self) -> bool {
+        match self.config.read().unwrap().max_errors {
+            Some(max_errors) => self.error_count() >= max_errors,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DiagnosticKind::{LoweringError, ResolutionError, Warning};
+
+    #[derive(thiserror::Error, Debug, Clone)]
+    #[error("the test diagnostic")]
+    struct TestError(DiagnosticKind, u16);
+
+    impl InternalError for TestError {
+        fn code(&self) -> u16 {
+            self.1
+        }
+
+        fn kind(&self) -> DiagnosticKind {
+            self.0
+        }
+    }
+
+    /// Admits a diagnostic of the kind, returns the kind it was admitted with.
+    fn admit(budget: &ErrorBudget, kind: DiagnosticKind, code: u16) -> Option<DiagnosticKind> {
+        let diagnostic = Diagnostic::of(Loc::default(), TestError(kind, code));
+
+        budget.admit(diagnostic).map(|diagnostic| diagnostic.kind)
+    }
+
+    #[test]
+    fn max_errors_works() {
+        let budget = ErrorBudget::new(ReportConfig::default().with_max_errors(2));
+
+        assert_eq!(admit(&budget, Warning, 1), Some(Warning));
+        assert_eq!(admit(&budget, ResolutionError, 0), Some(ResolutionError));
+        assert_eq!(admit(&budget, LoweringError, 0), Some(LoweringError));
+        assert!(budget.is_exhausted());
+
+        // After the limit, both the errors and the warnings are dropped.
+        assert_eq!(admit(&budget, LoweringError, 0), None);
+        assert_eq!(admit(&budget, Warning, 1), None);
+        assert_eq!(budget.error_count(), 2);
+        assert!(budget.has_errors_in(Phase::Resolution));
+        assert!(!budget.has_errors_in(Phase::Typing));
+    }
+
+    #[test]
+    fn max_errors_in_parallel_works() {
+        let budget = ErrorBudget::new(ReportConfig::default().with_max_errors(10));
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..100 {
+                        admit(&budget, LoweringError, 0);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(budget.error_count(), 10);
+        assert_eq!(budget.dropped_count(), 790);
+    }
+
+    #[test]
+    fn overflow_summary_works() {
+        let budget = ErrorBudget::new(ReportConfig::default().with_max_errors(1));
+        admit(&budget, LoweringError, 0);
+        assert_eq!(budget.overflow_summary(), None);
+
+        admit(&budget, LoweringError, 0);
+        assert_eq!(
+            budget.overflow_summary().as_deref(),
+            Some("the analysis stopped after 1 error, 1 more diagnostic wasn't reported")
+        );
+
+        admit(&budget, Warning, 1);
+        assert_eq!(
+            budget.overflow_summary().as_deref(),
+            Some("the analysis stopped after 1 error, 2 more diagnostics weren't reported")
+        );
+
+        budget.reset();
+        assert_eq!(budget.overflow_summary(), None);
+    }
+
+    #[test]
+    fn deny_works() {
+        let budget = ErrorBudget::new(ReportConfig::default().deny(1));
+
+        assert_eq!(admit(&budget, Warning, 1), Some(DiagnosticKind::Error));
+        assert_eq!(admit(&budget, Warning, 2), Some(Warning));
+        assert_eq!(budget.error_count(), 1);
+    }
+}
@@ -28,6 +28,9 @@ pub struct Diagnostic<T> {
     pub code: u16,
     pub message: Spanned<T>,
     pub children: Vec<Diagnostic<T>>,
+
+    /// The fixes suggested for the diagnostic, that can be applied by the tools.
+    pub fixes: Vec<CodeAction>,
 }
 
 pub trait WithError {
@@ -47,6 +50,7 @@ impl<E: InternalError> Diagnostic<E> {
             code: error.code(),
             message: error,
             children: vec![],
+            fixes: vec![],
         }
     }
 
@@ -64,10 +68,43 @@ impl<E: InternalError> Diagnostic<E> {
             code: message.code(),
             message,
             children: vec![],
+            fixes: vec![],
         });
 
         self
     }
+
+    pub fn with_fix(mut self, fix: CodeAction) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
+    pub fn with_fixes<I: IntoIterator<Item = CodeAction>>(mut self, fixes: I) -> Self {
+        self.fixes.extend(fixes);
+        self
+    }
+
+    /// Returns the fixes of the diagnostic, and of its children.
+    pub fn all_fixes(&self) -> Vec<CodeAction> {
+        self.fixes
+            .iter()
+            .cloned()
+            .chain(self.children.iter().flat_map(|child| child.all_fixes()))
+            .collect()
+    }
+}
+
+impl<E: Clone + Send + Sync + InternalError + 'static> Diagnostic<E> {
+    /// Erases the type of the errors of the diagnostic, and of its children.
+    pub fn into_boxed(self) -> Diagnostic<BoxInternalError> {
+        Diagnostic {
+            kind: self.kind,
+            code: self.code,
+            message: self.message.map(BoxInternalError::new),
+            children: self.children.into_iter().map(Self::into_boxed).collect(),
+            fixes: self.fixes,
+        }
+    }
 }
 
 impl<T: InternalError> Eq for Diagnostic<T> {}
@@ -81,6 +118,7 @@ impl<T: InternalError> PartialEq for Diagnostic<T> {
             && self.message.value.kind() == other.message.value.kind()
             && self.message.value.to_string() == other.message.value.to_string()
             && self.children == other.children
+            && self.fixes == other.fixes
     }
 }
 
@@ -97,5 +135,29 @@ impl<T: InternalError> Hash for Diagnostic<T> {
         code.hash(state);
         kind.hash(state);
         self.children.hash(state);
+        self.fixes.hash(state);
     }
 }
+
+/// Aggregates the diagnostics of many files, the duplicated ones are removed, keeping the first,
+/// and they're sorted by the file, and by the position in the file. The sort is stable, so the
+/// diagnostics at the same position keep the order they were reported.
+pub fn aggregate_diagnostics<E, I>(diagnostics: I) -> Vec<Diagnostic<E>>
+where
+    E: Clone + InternalError,
+    I: IntoIterator<Item = Diagnostic<E>>,
+{
+    let mut seen = std::collections::HashSet::new();
+    let mut diagnostics = diagnostics
+        .into_iter()
+        .filter(|diagnostic| seen.insert(diagnostic.clone()))
+        .collect::<Vec<_>>();
+
+    diagnostics.sort_by_key(|diagnostic| {
+        let span = &diagnostic.message.span;
+
+        (span.file.clone(), span.range.start(), span.range.end())
+    });
+
+    diagnostics
+}
@@ -8,33 +8,31 @@ use std::{error::Error, fmt::Display};
 use asena_leaf::node::Tree;
 use asena_span::{Loc, Spanned};
 
+pub use budget::*;
 pub use errors::*;
 pub use ide_diagnostic::*;
 pub use quickfix::*;
 pub use report::*;
+pub use sink::*;
 
 pub use Fragment::*;
 
+pub mod budget;
 pub mod errors;
 pub mod ide_diagnostic;
+pub mod profile;
 pub mod quickfix;
 pub mod report;
+pub mod sink;
 
 pub trait Reports {
-    fn errors(&self) -> Arc<RwLock<Vec<Diagnostic<BoxInternalError>>>>;
+    fn sink(&self) -> &dyn DiagnosticSink;
 
     #[track_caller]
     fn diagnostic<E>(&self, diagnostic: Diagnostic<E>)
     where
         E: Clone + Send + Sync + InternalError + 'static,
     {
-        let errors = self.errors();
-        let mut errors = errors.try_write().unwrap();
-        errors.push(Diagnostic {
-            message: diagnostic.message.map(BoxInternalError::new),
-            kind: diagnostic.kind,
-            code: diagnostic.code,
-            children: vec![],
-        });
+        self.sink().emit(diagnostic.into_boxed());
     }
 }
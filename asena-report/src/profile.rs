@@ -0,0 +1,172 @@
+//! The opt-in profiler of the compiler, it records the wall-time of the phases, and the salsa
+//! query statistics, after [enable] is called. It's disabled by default, so the phases only pay
+//! an atomic load when it isn't used.
+//!
+//! The times are inclusive: a phase that demands another file, like the resolution demanding
+//! the parsing of an imported module, includes the time of the nested phases.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProfilePhase {
+    Lexing,
+    Parsing,
+    Precedence,
+    Resolution,
+    Lowering,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseTiming {
+    pub runs: usize,
+    pub total: Duration,
+}
+
+/// The statistics of a salsa query, the executions are the misses, and the validations are the
+/// memoized values of older revisions that were reused. The hits in the same revision aren't
+/// reported by salsa, so they aren't counted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QueryStatistics {
+    pub executions: usize,
+    pub validations: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryEvent {
+    Executed,
+    Validated,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProfileReport {
+    pub phases: BTreeMap<ProfilePhase, PhaseTiming>,
+    pub queries: BTreeMap<String, QueryStatistics>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static REPORT: Mutex<ProfileReport> = Mutex::new(ProfileReport {
+    phases: BTreeMap::new(),
+    queries: BTreeMap::new(),
+});
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs the function, recording its wall-time in the phase, if the profiler is enabled.
+pub fn phase<T>(phase: ProfilePhase, f: impl FnOnce() -> T) -> T {
+    if !is_enabled() {
+        return f();
+    }
+
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+
+    let mut report = REPORT.lock().unwrap();
+    let timing = report.phases.entry(phase).or_default();
+    timing.runs += 1;
+    timing.total += elapsed;
+
+    value
+}
+
+/// Records the event of the query, if the profiler is enabled. The name is the name of the
+/// query, without the keys, like `ast`.
+pub fn query(name: &str, event: QueryEvent) {
+    if !is_enabled() {
+        return;
+    }
+
+    let mut report = REPORT.lock().unwrap();
+    let statistics = report.queries.entry(name.to_string()).or_default();
+    match event {
+        QueryEvent::Executed => statistics.executions += 1,
+        QueryEvent::Validated => statistics.validations += 1,
+    }
+}
+
+/// Returns the report of the recorded data, and clears it.
+pub fn take_report() -> ProfileReport {
+    std::mem::take(&mut *REPORT.lock().unwrap())
+}
+
+impl ProfileReport {
+    /// Renders the report as aligned tables, the phases and the queries are sorted by the time,
+    /// and by the executions, respectively.
+    pub fn to_table(&self) -> String {
+        let mut output = String::new();
+
+        let _ = writeln!(output, "{:<16} {:>8} {:>12}", "phase", "runs", "time (ms)");
+        let phases = self.phases.iter().map(|(phase, timing)| (*phase, *timing));
+        for (phase, timing) in sorted_by_key(phases, |(_, timing)| timing.total) {
+            let millis = timing.total.as_secs_f64() * 1000.0;
+            let phase = format!("{phase:?}");
+
+            let _ = writeln!(output, "{phase:<16} {:>8} {millis:>12.3}", timing.runs);
+        }
+
+        let _ = writeln!(output);
+        let _ = writeln!(
+            output,
+            "{:<32} {:>10} {:>10}",
+            "query", "executed", "validated"
+        );
+        let queries = self.queries.iter().map(|(name, stats)| (name, *stats));
+        for (name, stats) in sorted_by_key(queries, |(_, stats)| stats.executions) {
+            let _ = writeln!(
+                output,
+                "{name:<32} {:>10} {:>10}",
+                stats.executions, stats.validations
+            );
+        }
+
+        output
+    }
+
+    /// Renders the report as a JSON object, the times are in milliseconds.
+    pub fn to_json(&self) -> String {
+        let phases = self
+            .phases
+            .iter()
+            .map(|(phase, timing)| {
+                let millis = timing.total.as_secs_f64() * 1000.0;
+
+                format!(
+                    "\"{phase:?}\":{{\"runs\":{},\"time_ms\":{millis}}}",
+                    timing.runs
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let queries = self
+            .queries
+            .iter()
+            .map(|(name, stats)| {
+                format!(
+                    "{:?}:{{\"executed\":{},\"validated\":{}}}",
+                    name, stats.executions, stats.validations
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{{\"phases\":{{{phases}}},\"queries\":{{{queries}}}}}")
+    }
+}
+
+/// Sorts the items by the key, from the biggest to the smallest.
+fn sorted_by_key<T, K: Ord>(items: impl Iterator<Item = T>, key: impl Fn(&T) -> K) -> Vec<T> {
+    let mut items = items.collect::<Vec<_>>();
+    items.sort_by(|lhs, rhs| key(rhs).cmp(&key(lhs)));
+    items
+}
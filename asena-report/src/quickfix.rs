@@ -19,6 +19,110 @@ pub struct Quickfix {
     pub message: Vec<Fragment>,
 }
 
+/// A replacement of the text at the location, the insertions have empty locations, and the
+/// deletions have empty texts.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct TextEdit {
+    pub loc: Loc,
+    pub text: String,
+}
+
+/// A fix suggested by a diagnostic, like "add `use Nat`", that can be applied to the file the
+/// edits are located in.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CodeAction {
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+}
+
+impl TextEdit {
+    pub fn replace<S: Into<String>>(loc: Loc, text: S) -> Self {
+        Self {
+            loc,
+            text: text.into(),
+        }
+    }
+
+    pub fn delete(loc: Loc) -> Self {
+        Self::replace(loc, "")
+    }
+
+    /// Inserts the text before the start of the location.
+    pub fn insert_before<S: Into<String>>(loc: &Loc, text: S) -> Self {
+        let start = loc.range.start();
+
+        Self::replace(Loc::new(loc.file.clone(), start, start), text)
+    }
+
+    /// Inserts the text after the end of the location.
+    pub fn insert_after<S: Into<String>>(loc: &Loc, text: S) -> Self {
+        let end = loc.range.end();
+
+        Self::replace(Loc::new(loc.file.clone(), end, end), text)
+    }
+}
+
+impl CodeAction {
+    pub fn new<S: Into<String>>(title: S, edits: Vec<TextEdit>) -> Self {
+        Self {
+            title: title.into(),
+            edits,
+        }
+    }
+
+    /// Applies the edits to the source of the file, see [apply_edits].
+    pub fn apply(&self, source: &str) -> String {
+        apply_edits(source, &self.edits)
+    }
+}
+
+/// Applies the edits to the source, the locations are the ones of the original source. The edits
+/// that overlap the previous ones, and the virtual ones, are skipped.
+pub fn apply_edits(source: &str, edits: &[TextEdit]) -> String {
+    let mut edits = edits
+        .iter()
+        .filter_map(|edit| Some((edit.loc.clone().into_ranged()?, edit.text.as_str())))
+        .filter(|(range, _)| range.start <= range.end && range.end <= source.len())
+        .collect::<Vec<_>>();
+    edits.sort_by_key(|(range, _)| (range.start, range.end));
+
+    let mut output = String::with_capacity(source.len());
+    let mut last = 0;
+    for (range, text) in edits {
+        if range.start < last {
+            continue;
+        }
+
+        output.push_str(&source[last..range.start]);
+        output.push_str(text);
+        last = range.end;
+    }
+
+    output.push_str(&source[last..]);
+    output
+}
+
+impl From<Quickfix> for CodeAction {
+    fn from(quickfix: Quickfix) -> Self {
+        let title = quickfix
+            .message
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let edits = quickfix
+            .message
+            .iter()
+            .map(|fragment| match (fragment, quickfix.position) {
+                (Insert(code), Position::Before) => TextEdit::insert_before(&quickfix.loc, code),
+                (Insert(code), Position::After) => TextEdit::insert_after(&quickfix.loc, code),
+                (Remove(_), _) => TextEdit::delete(quickfix.loc.clone()),
+            })
+            .collect();
+
+        Self::new(title.join(", "), edits)
+    }
+}
+
 impl Display for Fragment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -24,6 +24,7 @@ impl<E: InternalError> Report<E> {
             code: message.code(),
             message,
             children: vec![],
+            fixes: vec![],
         });
 
         self.diagnostics.last_mut().unwrap()
@@ -0,0 +1,177 @@
+use super::*;
+
+/// The receiving side of the diagnostics, every reported diagnostic is emitted into a sink,
+/// that can collect them, forward them to a language client, or fail a test on the first
+/// error.
+pub trait DiagnosticSink: Send + Sync {
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>);
+}
+
+/// Sink that forwards the diagnostics to a function, it's useful to forward them to another
+/// channel, like the language server client.
+pub struct FnSink<F>(pub F);
+
+/// Sink that panics on the first error, warnings and other non error diagnostics are ignored.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PanicSink;
+
+/// Sink that emits the diagnostics into both sinks.
+#[derive(Debug, Default, Clone)]
+pub struct Tee<A, B>(pub A, pub B);
+
+/// A dynamic set of sinks, the diagnostics emitted are forwarded to all of them.
+#[derive(Default, Clone)]
+pub struct Sinks(Arc<RwLock<Vec<Arc<dyn DiagnosticSink>>>>);
+
+impl DiagnosticKind {
+    /// Returns if the diagnostic kind should fail the compilation.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            DiagnosticKind::Error
+                | DiagnosticKind::HardError
+                | DiagnosticKind::InternalError
+                | DiagnosticKind::SyntaxError
+                | DiagnosticKind::TypeError
+                | DiagnosticKind::ResolutionError
+                | DiagnosticKind::LoweringError
+                | DiagnosticKind::BuildError
+        )
+    }
+}
+
+impl Sinks {
+    pub fn add<S: DiagnosticSink + 'static>(&self, sink: S) {
+        self.0.write().unwrap().push(Arc::new(sink));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl DiagnosticSink for Sinks {
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>) {
+        // Clones the sinks, so a sink can add new sinks without deadlocking.
+        let sinks = self.0.read().unwrap().clone();
+
+        for sink in sinks {
+            sink.emit(diagnostic.clone());
+        }
+    }
+}
+
+impl Debug for Sinks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Sinks").field(&self.len()).finish()
+    }
+}
+
+impl DiagnosticSink for RwLock<Vec<Diagnostic<BoxInternalError>>> {
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>) {
+        self.write().unwrap().push(diagnostic);
+    }
+}
+
+impl<S: DiagnosticSink + ?Sized> DiagnosticSink for Arc<S> {
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>) {
+        S::emit(self, diagnostic)
+    }
+}
+
+impl<F> DiagnosticSink for FnSink<F>
+where
+    F: Fn(Diagnostic<BoxInternalError>) + Send + Sync,
+{
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>) {
+        (self.0)(diagnostic)
+    }
+}
+
+impl DiagnosticSink for PanicSink {
+    #[track_caller]
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>) {
+        if diagnostic.kind.is_error() {
+            panic!(
+                "unexpected diagnostic at {:?}: {}",
+                diagnostic.message.span, diagnostic.message.value
+            );
+        }
+    }
+}
+
+impl<A: DiagnosticSink, B: DiagnosticSink> DiagnosticSink for Tee<A, B> {
+    fn emit(&self, diagnostic: Diagnostic<BoxInternalError>) {
+        self.0.emit(diagnostic.clone());
+        self.1.emit(diagnostic);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(thiserror::Error, Debug, Clone)]
+    #[error("the test diagnostic")]
+    struct TestError(DiagnosticKind);
+
+    impl InternalError for TestError {
+        fn kind(&self) -> DiagnosticKind {
+            self.0
+        }
+    }
+
+    fn diagnostic(kind: DiagnosticKind) -> Diagnostic<BoxInternalError> {
+        Diagnostic::of(Loc::default(), TestError(kind)).into_boxed()
+    }
+
+    #[test]
+    fn sinks_works() {
+        let first = Arc::new(RwLock::new(vec![]));
+        let second = Arc::new(RwLock::new(vec![]));
+
+        let sinks = Sinks::default();
+        sinks.add(first.clone());
+        sinks.add(Tee(second.clone(), PanicSink));
+        assert_eq!(sinks.len(), 2);
+
+        // Every sink receives every diagnostic, in the order they're emitted.
+        sinks.emit(diagnostic(DiagnosticKind::Warning));
+        sinks.emit(diagnostic(DiagnosticKind::Info));
+
+        let kinds = |sink: &RwLock<Vec<Diagnostic<BoxInternalError>>>| {
+            sink.read()
+                .unwrap()
+                .iter()
+                .map(|diagnostic| diagnostic.kind)
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![DiagnosticKind::Warning, DiagnosticKind::Info];
+        assert_eq!(kinds(&first), expected);
+        assert_eq!(kinds(&second), expected);
+    }
+
+    #[test]
+    fn sinks_added_while_emitting_works() {
+        let sinks = Sinks::default();
+        let inner = sinks.clone();
+        sinks.add(FnSink(move |_: Diagnostic<BoxInternalError>| {
+            inner.add(PanicSink)
+        }));
+
+        // The sink added while emitting doesn't receive the diagnostic being emitted.
+        sinks.emit(diagnostic(DiagnosticKind::Error));
+        assert_eq!(sinks.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected diagnostic")]
+    fn panic_sink_works() {
+        PanicSink.emit(diagnostic(DiagnosticKind::Warning));
+        PanicSink.emit(diagnostic(DiagnosticKind::Error));
+    }
+}
@@ -3,22 +3,21 @@ use std::{
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
 };
 
 use asena_ast_db::{
+    cancellation::{CancellationToken, Cancelled},
     db::AstDatabase,
     package::Package,
     vfs::{FileSystem, VfsFile, VfsFileData, VfsPath},
 };
-use asena_ast_lowering::db::AstLowerrer;
-use asena_ast_resolver::db::AstResolverDatabase;
-use asena_prec::PrecDatabase;
 use asena_report::BoxInternalError;
+use asena_span::LineIndex;
 use im::HashSet;
 use itertools::Itertools;
-use ropey::Rope;
+use salsa::{ParallelDatabase, Snapshot};
 use tower_lsp::{
     jsonrpc::Result,
     lsp_types::{
@@ -31,16 +30,17 @@ use tower_lsp::{
     Client, LanguageServer,
 };
 
+use crate::ide_db::IdeDatabase;
+
 #[derive(Debug, Clone)]
 pub struct Backend {
     pub client: Client,
-    pub db: Arc<crate::ide_db::IdeDatabase>,
+    pub db: Arc<Mutex<IdeDatabase>>,
     pub workspace_ready: Arc<AtomicBool>,
-}
-
-unsafe impl Send for Backend {}
 
-unsafe impl Sync for Backend {}
+    /// The token of the analysis of the last edit, it's cancelled when a new edit arrives.
+    pub cancellation: Arc<Mutex<CancellationToken>>,
+}
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
@@ -133,33 +133,43 @@ impl Backend {
 
         backend.info("file opened").await;
 
+        // The analysis of the previous edit is stale, so it's cancelled.
+        let token = CancellationToken::new();
+        std::mem::replace(&mut *self.cancellation.lock().unwrap(), token.clone()).cancel();
+
         tokio::spawn(async move {
             while let false = backend.workspace_ready.load(Ordering::SeqCst) {}
 
             backend.info("  file opened within workspace").await;
 
-            let module_ref = backend.db.path_module(PathBuf::from(params.uri.path()));
-            let vfs_file = backend.db.vfs_file(module_ref);
-            let file = backend.db.ast(vfs_file);
-            let file = backend.db.infix_commands(file.into());
-            let file = backend.db.ordered_prec(file.into());
-            let file = backend.db.ast_resolved_file(file.into());
-            let _hir = backend.db.hir_file(file.into());
-
-            let source = backend.db.source(vfs_file);
-            let source = ropey::Rope::from(source.to_string());
-
-            let diagnostics = backend
-                .db
-                .diagnostics(vfs_file)
-                .into_iter()
-                .enumerate()
-                .map(|(order, diagnostic)| OrdDiagnostic { order, diagnostic })
-                .collect::<HashSet<OrdDiagnostic>>()
-                .into_iter()
-                .sorted_by(|d, n| d.order.cmp(&n.order))
-                .filter_map(|d| backend.build_diagnostic(d.diagnostic, &source))
-                .collect_vec();
+            // The text is written under the lock, after checking the token, so a stale edit
+            // can't overwrite the text of a newer one.
+            let vfs_file = {
+                let mut db = backend.db.lock().unwrap();
+                if token.is_cancelled() {
+                    return;
+                }
+
+                let module_ref = db.path_module(PathBuf::from(params.uri.path()));
+                let Some(vfs_file) = db.vfs_file(module_ref) else {
+                    return;
+                };
+
+                db.write_file(vfs_file, params.text);
+                vfs_file
+            };
+
+            // The cancellations by salsa are retried with a snapshot of the new revision, and the
+            // ones by the token are dropped, as a newer edit publishes its own diagnostics.
+            let diagnostics = loop {
+                let db = backend.snapshot();
+
+                match db.run_pipeline_cancellable(vfs_file, &token) {
+                    Ok(()) => break backend.diagnostics(&db, vfs_file),
+                    Err(Cancelled::Revision) => continue,
+                    Err(Cancelled::Token) => return,
+                }
+            };
 
             backend
                 .client
@@ -168,6 +178,29 @@ impl Backend {
         });
     }
 
+    /// Returns a read-only snapshot of the database, that can be moved to other threads, like
+    /// the tasks of the diagnostics. The snapshots are cancelled when the database is changed,
+    /// and the changes wait until all the snapshots are dropped.
+    pub fn snapshot(&self) -> Snapshot<IdeDatabase> {
+        self.db.lock().unwrap().snapshot()
+    }
+
+    /// Returns the diagnostics of the file in the order they were reported, without the
+    /// duplicates, like the ones reported by the queries that were executed again.
+    fn diagnostics(&self, db: &IdeDatabase, vfs_file: VfsFile) -> Vec<Diagnostic> {
+        let line_index = db.line_index(vfs_file);
+
+        db.diagnostics(vfs_file)
+            .into_iter()
+            .enumerate()
+            .map(|(order, diagnostic)| OrdDiagnostic { order, diagnostic })
+            .collect::<HashSet<OrdDiagnostic>>()
+            .into_iter()
+            .sorted_by(|d, n| d.order.cmp(&n.order))
+            .filter_map(|d| self.build_diagnostic(d.diagnostic, &line_index))
+            .collect_vec()
+    }
+
     pub async fn info(&self, message: impl Into<String>) {
         self.client
             .log_message(MessageType::INFO, message.into())
@@ -179,8 +212,12 @@ impl Backend {
         let mut files = tokio::fs::read_dir(path).await?;
         let vfs = Arc::new(FileSystem {
             base_dir: Some(path.into()),
+            ..Default::default()
         });
-        let pkg = Package::new(&*self.db, "Local", "0.0.0", vfs);
+        let pkg = {
+            let db = self.db.lock().unwrap();
+            Package::new(&*db, "Local", "0.0.0", vfs)
+        };
         self.info(format!("loading workspace: {path:?}")).await;
 
         while let Some(entry) = files.next_entry().await? {
@@ -191,7 +228,7 @@ impl Backend {
             let vfs_path = VfsPath { path };
             let metadata = entry.metadata().await?;
             if metadata.is_file() && vfs_path.path.extension().unwrap_or_default() == "ase" {
-                VfsFileData::new(&*self.db, &name, vfs_path, pkg);
+                VfsFileData::new(&*self.db.lock().unwrap(), &name, vfs_path, pkg);
             }
         }
 
@@ -200,21 +237,26 @@ impl Backend {
         Ok(())
     }
 
-    fn build_diagnostic(&self, diagnostic: AsenaDiagnostic, rope: &Rope) -> Option<Diagnostic> {
+    fn build_diagnostic(
+        &self,
+        diagnostic: AsenaDiagnostic,
+        index: &LineIndex,
+    ) -> Option<Diagnostic> {
         let message = diagnostic.message.to_string();
         let range = diagnostic.message.span.range;
 
-        let start = Self::offset_to_position(range.start(), rope)?;
-        let end = Self::offset_to_position(range.end(), rope)?;
+        let start = Self::offset_to_position(range.start(), index);
+        let end = Self::offset_to_position(range.end(), index);
 
         Some(Diagnostic::new_simple(Range::new(start, end), message))
     }
 
-    fn offset_to_position(offset: usize, rope: &Rope) -> Option<Position> {
-        let line = rope.try_char_to_line(offset).ok()?;
-        let first_char_of_line = rope.try_line_to_char(line).ok()?;
-        let column = offset - first_char_of_line;
-        Some(Position::new(line as u32, column as u32))
+    /// Converts the byte offset into the position of the protocol, the columns of it are in
+    /// UTF-16 code units.
+    fn offset_to_position(offset: usize, index: &LineIndex) -> Position {
+        let line_col = index.to_utf16(index.line_col(offset));
+
+        Position::new(line_col.line, line_col.col)
     }
 }
 
@@ -1,33 +1,96 @@
 use asena_ast_db::{
-    db::{AstDatabase, AstDatabaseStorage},
+    cancellation::{CancellationToken, Cancelled},
+    db::{AstDatabase, AstQuery, CstQuery},
     vfs::VfsFile,
 };
-use asena_ast_lowering::db::{AstLowerrer, AstLowerrerStorage};
-use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
-use asena_hir::interner::HirStorage;
-use asena_hir_db::db::HirDatabaseStorage;
-use asena_prec::{db::PrecStorage, PrecDatabase};
+use asena_ast_lowering::db::{
+    AstLowerrer, HirBlockQuery, HirBranchQuery, HirClassQuery, HirEnumQuery, HirFileQuery,
+    HirInstanceQuery, HirPatternQuery, HirStmtQuery, HirTraitQuery, HirTypeQuery, HirValueQuery,
+};
+use asena_ast_resolver::db::{AstResolvedFileQuery, AstResolverDatabase};
+use asena_hir_db::db::{HirDatabaseStorage, HirFileDefsQuery, HirMbindQuery, HirRcQuery};
+use asena_prec::{
+    db::{InfixCommandsQuery, OrderedPrecQuery},
+    PrecDatabase,
+};
+use salsa::{Database, Durability};
 use std::{
     fmt::Debug,
     panic::{resume_unwind, AssertUnwindSafe},
     sync::Mutex,
 };
 
-#[salsa::database(
-    PrecStorage,
-    AstDatabaseStorage,
-    HirDatabaseStorage,
-    AstLowerrerStorage,
-    AstResolverStorage,
-    HirStorage
-)]
-#[derive(Default)]
-pub struct IdeDatabase {
-    pub storage: salsa::Storage<IdeDatabase>,
-    pub logs: Mutex<Vec<salsa::Event>>,
+/// The amount of the entries of the queries of the trees that are kept in the memory, see
+/// [IdeDatabase::set_lru_capacity].
+pub const DEFAULT_LRU_CAPACITY: usize = 128;
+
+/// The maximum amount of the salsa events that are kept to be dumped, the older half is dropped
+/// when it's reached.
+const MAX_LOGS: usize = 4096;
+
+asena_ast_lowering::frontend_database! {
+    pub struct IdeDatabase [HirDatabaseStorage] {
+        pub logs: Mutex<Vec<salsa::Event>>,
+    }
 }
 
 impl IdeDatabase {
+    pub fn new(lru_capacity: usize) -> Self {
+        let mut db = Self::default();
+        db.set_lru_capacity(lru_capacity);
+        db
+    }
+
+    /// Sets the capacity of the LRU of the queries of the trees, and of the lowering into the
+    /// HIR, zero disables the LRU. The keys of the passes are the trees of the previous passes,
+    /// so every edit of a file creates new entries, and without the LRU the old ones are never
+    /// evicted. The queries keyed by the files, like the references, have one entry per file, so
+    /// they aren't in the LRU.
+    ///
+    /// The interned HIR nodes aren't collected, salsa doesn't collect the interned values, but
+    /// an evicted file that didn't change is lowered again into the same ids. Salsa doesn't
+    /// evict the values with untracked inputs either, so the queries of the files in the memory
+    /// of the file system, like the ones edited in the editor, are only evicted after they're
+    /// saved and read from the disk.
+    pub fn set_lru_capacity(&mut self, capacity: usize) {
+        use salsa::Query;
+
+        CstQuery.in_db_mut(self).set_lru_capacity(capacity);
+        AstQuery.in_db_mut(self).set_lru_capacity(capacity);
+        InfixCommandsQuery
+            .in_db_mut(self)
+            .set_lru_capacity(capacity);
+        OrderedPrecQuery.in_db_mut(self).set_lru_capacity(capacity);
+        AstResolvedFileQuery
+            .in_db_mut(self)
+            .set_lru_capacity(capacity);
+        HirFileQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirTraitQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirClassQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirInstanceQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirEnumQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirTypeQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirStmtQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirBlockQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirPatternQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirValueQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirBranchQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirFileDefsQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirMbindQuery.in_db_mut(self).set_lru_capacity(capacity);
+        HirRcQuery.in_db_mut(self).set_lru_capacity(capacity);
+    }
+
+    /// Replaces the source of the file with the text of the editor, and starts a new revision of
+    /// the database, so the queries that read the file run again. It waits until the snapshots
+    /// of the previous revision are dropped, their queries are cancelled as soon as they run.
+    pub fn write_file(&mut self, vfs_file: VfsFile, text: String) {
+        let data = self.lookup_intern_vfs_file(vfs_file);
+        data.vfs().memory.insert(data.name.clone(), text);
+
+        self.salsa_runtime_mut().synthetic_write(Durability::LOW);
+        data.pkg.clear_file_diagnostics(self, vfs_file);
+    }
+
     pub fn run_pipeline_catching(&self, vfs_file: VfsFile) {
         let db = AssertUnwindSafe(self);
         let result = std::panic::catch_unwind(|| {
@@ -41,13 +104,37 @@ impl IdeDatabase {
         match result {
             Ok(value) => value,
             Err(err) => {
-                eprintln!("pipeline of the compiler during lowering:");
+                tracing::error!("the compiler panicked, the queries of the pipeline were:");
                 db.dump_and_write_logs();
                 resume_unwind(err);
             }
         }
     }
 
+    /// Runs the pipeline until the lowering, checking the token between the phases. The salsa
+    /// cancellations, when the inputs change while the queries are running, are returned as
+    /// [Cancelled::Revision], so the pipeline can be retried.
+    pub fn run_pipeline_cancellable(
+        &self,
+        vfs_file: VfsFile,
+        token: &CancellationToken,
+    ) -> Result<(), Cancelled> {
+        let db = AssertUnwindSafe(self);
+        let token = AssertUnwindSafe(token);
+        Cancelled::catch(|| {
+            token.check()?;
+            let file = db.ast(vfs_file);
+            token.check()?;
+            let file = db.infix_commands(file.into());
+            let file = db.ordered_prec(file.into());
+            token.check()?;
+            let file = db.ast_resolved_file(file.into());
+            token.check()?;
+            let _hir = db.hir_file(file.into());
+            token.check()
+        })
+    }
+
     pub fn dump_and_write_logs(&self) {
         use salsa::EventKind::*;
 
@@ -62,7 +149,7 @@ impl IdeDatabase {
                     let key = database_key.debug(self);
                     let runtime_id = other_runtime_id;
 
-                    log::debug!("will block on {:?} (runtime id: {:?})", key, runtime_id);
+                    tracing::debug!(?key, ?runtime_id, "will block on the query");
                 }
                 WillExecute { database_key } => {
                     let debug = database_key.debug(self);
@@ -86,16 +173,18 @@ impl IdeDatabase {
                     }
 
                     if let 0 = count {
-                        println!("  -> {debug:?}");
+                        tracing::error!(query = ?debug, "executed the query");
                     } else {
-                        println!("  -> {debug:?} x{count}");
+                        tracing::error!(query = ?debug, count, "executed the query repeatedly");
                     }
                 }
                 WillCheckCancellation => {
-                    log::debug!("will check cancellation");
+                    tracing::debug!("will check the cancellation");
                 }
                 DidValidateMemoizedValue { database_key } => {
-                    log::debug!("did validate memoized value {:?}", database_key.debug(self));
+                    let key = database_key.debug(self);
+
+                    tracing::debug!(?key, "did validate the memoized value");
                 }
             }
 
@@ -106,7 +195,12 @@ impl IdeDatabase {
 
 impl salsa::Database for IdeDatabase {
     fn salsa_event(&self, event_fn: salsa::Event) {
-        self.logs.lock().unwrap().push(event_fn);
+        let mut logs = self.logs.lock().unwrap();
+        if logs.len() >= MAX_LOGS {
+            logs.drain(..MAX_LOGS / 2);
+        }
+
+        logs.push(event_fn);
     }
 }
 
@@ -126,3 +220,89 @@ impl Debug for IdeDatabase {
         f.debug_struct("IdeDatabase").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use asena_ast_db::{package::Package, vfs::*};
+
+    use super::*;
+
+    #[test]
+    fn edits_works() {
+        let mut db = IdeDatabase::new(DEFAULT_LRU_CAPACITY);
+        let vfs = Arc::new(FileSystem::default());
+        vfs.memory.insert("Test".into(), String::new());
+
+        let pkg = Package::new(&db, "Local", "0.0.0", vfs);
+        let file = VfsFileData::new(&db, "Test", "./Test.ase".into(), pkg);
+
+        let mut analyze = |text: &str| {
+            db.write_file(file, text.into());
+            db.run_pipeline_cancellable(file, &CancellationToken::new())
+                .unwrap();
+
+            db.diagnostics(file)
+                .iter()
+                .map(|diagnostic| diagnostic.message.value.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // The second edit is analyzed against its own text, instead of the memoized tree of the
+        // first one, and the diagnostics of the first one are cleared.
+        assert_eq!(
+            analyze("Main { foo }"),
+            vec!["could not find the value name: `foo`"]
+        );
+        assert_eq!(
+            analyze("Main { bar }"),
+            vec!["could not find the value name: `bar`"]
+        );
+        assert!(analyze("Main { 1 }").is_empty());
+    }
+
+    #[test]
+    fn lru_works() {
+        // The files in the memory have untracked inputs, and they're never evicted, so the
+        // files are read from the disk
+        let base_dir = std::env::temp_dir().join(format!("asena-lru-{}", std::process::id()));
+        std::fs::create_dir_all(&base_dir).unwrap();
+        std::fs::write(base_dir.join("A.ase"), "Main { 1 }").unwrap();
+        std::fs::write(base_dir.join("B.ase"), "Main { 2 }").unwrap();
+
+        let cst_executions = |capacity: usize| {
+            let db = IdeDatabase::new(capacity);
+            let vfs = Arc::new(FileSystem {
+                base_dir: Some(base_dir.clone()),
+                ..Default::default()
+            });
+
+            let pkg = Package::new(&db, "Local", "0.0.0", vfs);
+            let path = |name: &str| VfsPath {
+                path: base_dir.join(name).with_extension("ase"),
+            };
+            let a = VfsFileData::new(&db, "A", path("A"), pkg);
+            let b = VfsFileData::new(&db, "B", path("B"), pkg);
+            for file in [a, b, a] {
+                db.run_pipeline_catching(file);
+            }
+
+            let logs = db.logs.lock().unwrap();
+            logs.iter()
+                .filter(|event| match event.kind {
+                    salsa::EventKind::WillExecute { database_key } => {
+                        format!("{:?}", database_key.debug(&db)).starts_with("cst")
+                    }
+                    _ => false,
+                })
+                .count()
+        };
+
+        // The tree of `A` is evicted by the tree of `B`, and it's computed again
+        assert_eq!(cst_executions(1), 3);
+        assert_eq!(cst_executions(DEFAULT_LRU_CAPACITY), 2);
+
+        std::fs::remove_dir_all(&base_dir).unwrap();
+    }
+}
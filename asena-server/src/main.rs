@@ -1,6 +1,6 @@
 #![feature(try_blocks)]
 
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::{atomic::AtomicBool, Arc, Mutex};
 
 use tower_lsp::{LspService, Server};
 
@@ -9,19 +9,31 @@ use crate::backend::Backend;
 pub mod backend;
 pub mod ide_db;
 
+/// Installs the subscriber of the logs on the standard error, as the standard output is used by
+/// the protocol, the targets are filtered by the `RUST_LOG` environment variable.
+fn install_tracing() {
+    use tracing_subscriber::EnvFilter;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+}
+
 #[tokio::main]
 async fn main() {
-    env_logger::init();
+    install_tracing();
 
-    let db = ide_db::IdeDatabase::default();
+    let db = ide_db::IdeDatabase::new(ide_db::DEFAULT_LRU_CAPACITY);
 
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
     let (service, socket) = LspService::build(|client| Backend {
         client,
-        db: Arc::new(db),
+        db: Arc::new(Mutex::new(db)),
         workspace_ready: Arc::new(AtomicBool::new(false)),
+        cancellation: Default::default(),
     })
     .finish();
 
@@ -5,8 +5,10 @@ use std::{
 
 pub type Localized<T> = Spanned<Box<T>>;
 
+mod line_index;
 mod loc;
 
+pub use line_index::*;
 pub use loc::*;
 
 /// Localized reference in the heap, using [Box], and [Loc], to localize stuff in the source code
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+/// A position in the source code, the line and the column start at zero. The column is in bytes,
+/// or in UTF-16 code units, if it's converted with [LineIndex::to_utf16], like the positions of
+/// the language server protocol.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+/// A character that takes more than one byte, by its byte columns in the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct WideChar {
+    start: u32,
+    end: u32,
+}
+
+impl WideChar {
+    fn utf8_len(&self) -> u32 {
+        self.end - self.start
+    }
+
+    fn utf16_len(&self) -> u32 {
+        if self.utf8_len() == 4 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// Converts the byte offsets of a source code into lines and columns, and back, by the binary
+/// search of the starts of the lines.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// The offsets of the starts of the lines, the first line starts at zero.
+    line_starts: Vec<usize>,
+
+    /// The characters that aren't ASCII of each line, they're only needed for the UTF-16
+    /// columns, so the lines in ASCII don't store anything.
+    wide_chars: HashMap<u32, Vec<WideChar>>,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars = HashMap::<u32, Vec<WideChar>>::new();
+        let mut line = 0;
+        let mut line_start = 0;
+
+        for (offset, c) in source.char_indices() {
+            if c == '\n' {
+                line += 1;
+                line_start = offset + 1;
+                line_starts.push(line_start);
+                continue;
+            }
+
+            if !c.is_ascii() {
+                let start = (offset - line_start) as u32;
+                let end = start + c.len_utf8() as u32;
+                wide_chars
+                    .entry(line)
+                    .or_default()
+                    .push(WideChar { start, end });
+            }
+        }
+
+        Self {
+            line_starts,
+            wide_chars,
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the line and the byte column of the offset, the offsets past the end are in the
+    /// last line.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let col = offset - self.line_starts[line];
+
+        LineCol {
+            line: line as u32,
+            col: col as u32,
+        }
+    }
+
+    /// Returns the offset of the line and the byte column, or [None] if the line doesn't exist.
+    pub fn offset(&self, line_col: LineCol) -> Option<usize> {
+        let start = self.line_starts.get(line_col.line as usize)?;
+
+        Some(start + line_col.col as usize)
+    }
+
+    /// Converts the byte column into the UTF-16 column.
+    pub fn to_utf16(&self, line_col: LineCol) -> LineCol {
+        let mut col = line_col.col;
+        for wide_char in self.wide_chars_of(line_col.line) {
+            if wide_char.end > line_col.col {
+                break;
+            }
+
+            col -= wide_char.utf8_len() - wide_char.utf16_len();
+        }
+
+        LineCol { col, ..line_col }
+    }
+
+    /// Converts the UTF-16 column into the byte column.
+    pub fn to_utf8(&self, line_col: LineCol) -> LineCol {
+        let mut col = line_col.col;
+        for wide_char in self.wide_chars_of(line_col.line) {
+            if wide_char.start >= col {
+                break;
+            }
+
+            col += wide_char.utf8_len() - wide_char.utf16_len();
+        }
+
+        LineCol { col, ..line_col }
+    }
+
+    fn wide_chars_of(&self, line: u32) -> &[WideChar] {
+        self.wide_chars.get(&line).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_index_works() {
+        let index = LineIndex::new("Main {\n  \"λé𝕊\" x\n}");
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_col(0), LineCol { line: 0, col: 0 });
+        assert_eq!(index.line_col(7), LineCol { line: 1, col: 0 });
+        assert_eq!(index.offset(LineCol { line: 2, col: 0 }), Some(22));
+
+        // The `x` is after `λ` and `é`, that are two bytes, and `𝕊`, that is four bytes.
+        let x = LineCol { line: 1, col: 13 };
+        let utf16 = index.to_utf16(x);
+        assert_eq!(utf16, LineCol { line: 1, col: 9 });
+        assert_eq!(index.to_utf8(utf16), x);
+    }
+}
@@ -33,6 +33,17 @@ impl Loc {
     pub fn into_ranged(self) -> Option<Range<usize>> {
         self.range.into_ranged()
     }
+
+    /// Returns if the other location is inside this location, in the same file. The virtual
+    /// locations doesn't contain anything.
+    pub fn contains(&self, other: &Loc) -> bool {
+        match (&self.range, &other.range) {
+            (TextRange::Actual(a), TextRange::Actual(b)) => {
+                self.file == other.file && a.start <= b.start && b.end <= a.end
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
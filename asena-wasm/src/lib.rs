@@ -0,0 +1,117 @@
+//! The front-end of the compiler for the browsers, built for `wasm32-unknown-unknown`, it's used
+//! by the online playground. The sources are only kept in the memory, as there's no filesystem,
+//! and the results are returned as JSON strings, so the bindings don't need extra glue code.
+//!
+//! Build it with `wasm-pack build asena-wasm --target web`.
+
+use std::sync::Arc;
+
+use asena_ast_db::{
+    db::AstDatabase,
+    package::Package,
+    vfs::{FileSystem, VfsFileData},
+};
+use asena_ast_lowering::db::AstLowerrer;
+use asena_ast_resolver::db::AstResolverDatabase;
+use asena_lexer::Lexer;
+use asena_parser::Parser;
+use asena_prec::PrecDatabase;
+use asena_report::{Diagnostic, InternalError};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// The name of the module of the playground.
+const MODULE: &str = "Main";
+
+asena_ast_lowering::frontend_database! {
+    pub struct PlaygroundDatabase {}
+}
+
+impl salsa::Database for PlaygroundDatabase {}
+
+/// A diagnostic of the playground, the locations are the byte offsets in the source code.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaygroundDiagnostic {
+    pub kind: String,
+    pub code: u16,
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl<E: InternalError> From<&Diagnostic<E>> for PlaygroundDiagnostic {
+    fn from(diagnostic: &Diagnostic<E>) -> Self {
+        let range = &diagnostic.message.span.range;
+
+        Self {
+            kind: format!("{:?}", diagnostic.kind),
+            code: diagnostic.code,
+            message: diagnostic.message.value.to_string(),
+            start: range.start(),
+            end: range.end(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ParseResult {
+    tree: String,
+    diagnostics: Vec<PlaygroundDiagnostic>,
+}
+
+fn syntax_diagnostics(source: &str) -> (String, Vec<PlaygroundDiagnostic>) {
+    let tree = Parser::from(Lexer::new(None, source))
+        .run(asena_grammar::file)
+        .build_tree();
+    let diagnostics = tree.report.diagnostics.iter().map(Into::into).collect();
+
+    (format!("{:#?}", tree.data.value), diagnostics)
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "null".into())
+}
+
+/// Parses the source code, and returns the syntax tree, and the syntax errors, as a JSON object
+/// like `{ "tree": "...", "diagnostics": [...] }`.
+#[wasm_bindgen]
+pub fn parse(source: &str) -> String {
+    let (tree, diagnostics) = syntax_diagnostics(source);
+
+    to_json(&ParseResult { tree, diagnostics })
+}
+
+/// Checks the source code, as the `Main` module of a package without dependencies, and returns
+/// the diagnostics of all the phases until the lowering, as a JSON array.
+#[wasm_bindgen]
+pub fn check(source: &str) -> String {
+    let (_, mut diagnostics) = syntax_diagnostics(source);
+
+    let db = PlaygroundDatabase::default();
+    let fs = FileSystem::default();
+    fs.memory.insert(MODULE.into(), source.into());
+
+    let package = Package::new(&db, "Playground", "0.0.0", Arc::new(fs));
+    let file = VfsFileData::new(
+        &db,
+        MODULE,
+        format!("./{MODULE}.ase").as_str().into(),
+        package,
+    );
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+    db.hir_file(ast.into());
+
+    diagnostics.extend(db.diagnostics(file).iter().map(Into::into));
+
+    to_json(&diagnostics)
+}
+
+/// Formats the source code, see [asena_ast_formatter::format].
+#[wasm_bindgen]
+pub fn format(source: &str) -> String {
+    asena_ast_formatter::format(source)
+}
@@ -0,0 +1,41 @@
+//! Tests for the bindings of the playground, they check the JSON returned to the browsers.
+
+use asena_wasm::{check, format, parse};
+use serde_json::Value;
+
+fn diagnostics(json: &str) -> Vec<Value> {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn parse_works() {
+    let result: Value = serde_json::from_str(&parse("Main { 1 + 1 }")).unwrap();
+
+    assert!(result["tree"].as_str().unwrap().contains("DeclSignature"));
+    assert_eq!(result["diagnostics"], serde_json::json!([]));
+}
+
+#[test]
+fn check_works() {
+    let source = "Main { Missing }";
+    let diagnostics = diagnostics(&check(source))
+        .into_iter()
+        .filter(|diagnostic| diagnostic["message"].as_str().unwrap().contains("Missing"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["message"],
+        "could not find the value name: `Missing`"
+    );
+
+    // The offsets are of the source code, so the playground can highlight them.
+    let start = diagnostics[0]["start"].as_u64().unwrap() as usize;
+    let end = diagnostics[0]["end"].as_u64().unwrap() as usize;
+    assert_eq!(&source[start..end], "Missing");
+}
+
+#[test]
+fn format_works() {
+    assert_eq!(format("Main {\n\t1   \n}"), "Main {\n  1\n}\n");
+}
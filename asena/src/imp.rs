@@ -1,35 +1,23 @@
-use asena_ast_db::{
-    db::{AstDatabase, AstDatabaseStorage},
-    vfs::VfsFile,
-};
-use asena_ast_lowering::db::AstLowerrerStorage;
-use asena_ast_resolver::db::{AstResolverDatabase, AstResolverStorage};
-use asena_hir::interner::HirStorage;
-use asena_hir_db::db::HirDatabaseStorage;
+use asena_ast_db::{db::AstDatabase, vfs::VfsFile};
+use asena_ast_resolver::db::AstResolverDatabase;
+use asena_hir_db::db::{HirDatabase, HirDatabaseStorage};
 use asena_hir_lowering::{
     db::{LlirDatabase, LlirStorage},
     LlirConfig,
 };
 use asena_leaf::ast::Located;
-use asena_prec::{db::PrecStorage, PrecDatabase};
+use asena_prec::PrecDatabase;
+use asena_report::profile::{self, QueryEvent};
+use asena_report::Phase;
 use std::{
     panic::{resume_unwind, AssertUnwindSafe},
     sync::Mutex,
 };
 
-#[salsa::database(
-    PrecStorage,
-    AstDatabaseStorage,
-    HirDatabaseStorage,
-    AstLowerrerStorage,
-    AstResolverStorage,
-    HirStorage,
-    LlirStorage
-)]
-#[derive(Default)]
-pub struct DatabaseImpl {
-    pub storage: salsa::Storage<DatabaseImpl>,
-    pub logs: Mutex<Vec<salsa::Event>>,
+asena_ast_lowering::frontend_database! {
+    pub struct DatabaseImpl [HirDatabaseStorage, LlirStorage] {
+        pub logs: Mutex<Vec<salsa::Event>>,
+    }
 }
 
 impl DatabaseImpl {
@@ -42,13 +30,22 @@ impl DatabaseImpl {
             let file = db.ast_resolved_file(file.into());
             let pkg = db.package_of(file.location().into_owned());
 
+            // The lowering relies on the resolved names, so it would only cascade the errors.
+            let budget = pkg.budget(*db);
+            if budget.has_errors_in(Phase::Resolution) || budget.is_exhausted() {
+                tracing::error!("skipping lowering, the resolution of the package failed");
+                return;
+            }
+
+            // The unreachable definitions are only reported as warnings, so they're lowered too.
+            db.dead_code(pkg);
             db.llir_package(pkg, config).unwrap(); // TODO: handle
         });
 
         match result {
             Ok(value) => value,
             Err(err) => {
-                eprintln!("pipeline of the compiler during lowering:");
+                tracing::error!("the compiler panicked, the queries of the pipeline were:");
                 db.dump_and_write_logs();
                 resume_unwind(err);
             }
@@ -69,7 +66,7 @@ impl DatabaseImpl {
                     let key = database_key.debug(self);
                     let runtime_id = other_runtime_id;
 
-                    log::debug!("will block on {:?} (runtime id: {:?})", key, runtime_id);
+                    tracing::debug!(?key, ?runtime_id, "will block on the query");
                 }
                 WillExecute { database_key } => {
                     let debug = database_key.debug(self);
@@ -93,16 +90,18 @@ impl DatabaseImpl {
                     }
 
                     if let 0 = count {
-                        println!("  -> {debug:?}");
+                        tracing::error!(query = ?debug, "executed the query");
                     } else {
-                        println!("  -> {debug:?} x{count}");
+                        tracing::error!(query = ?debug, count, "executed the query repeatedly");
                     }
                 }
                 WillCheckCancellation => {
-                    log::debug!("will check cancellation");
+                    tracing::debug!("will check the cancellation");
                 }
                 DidValidateMemoizedValue { database_key } => {
-                    log::debug!("did validate memoized value {:?}", database_key.debug(self));
+                    let key = database_key.debug(self);
+
+                    tracing::debug!(?key, "did validate the memoized value");
                 }
             }
 
@@ -113,6 +112,24 @@ impl DatabaseImpl {
 
 impl salsa::Database for DatabaseImpl {
     fn salsa_event(&self, event_fn: salsa::Event) {
+        if profile::is_enabled() {
+            let event = match event_fn.kind {
+                salsa::EventKind::WillExecute { database_key } => {
+                    Some((database_key, QueryEvent::Executed))
+                }
+                salsa::EventKind::DidValidateMemoizedValue { database_key } => {
+                    Some((database_key, QueryEvent::Validated))
+                }
+                _ => None,
+            };
+
+            if let Some((database_key, event)) = event {
+                // The keys are rendered like `ast(VfsFile(..))`, so the name is before the keys.
+                let key = format!("{:?}", database_key.debug(self));
+                profile::query(key.split('(').next().unwrap_or_default(), event);
+            }
+        }
+
         self.logs.lock().unwrap().push(event_fn);
     }
 }
@@ -5,15 +5,16 @@
 #![feature(lazy_cell)]
 #![feature(downcast_unchecked)]
 
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use asena_grammar::Linebreak;
 use asena_highlight::{Annotator, VirtualFile};
 use asena_lexer::Lexer;
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 pub mod imp;
 pub mod panik;
+pub mod repl;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -21,6 +22,17 @@ pub mod panik;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Command,
+
+    /// Records the time of the phases, and the query statistics, and prints them on the
+    /// standard error at the end.
+    #[clap(long, global = true, value_enum)]
+    pub profile: Option<ProfileFormat>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    Table,
+    Json,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -55,19 +67,63 @@ pub struct EvalArgs {
     pub file: String,
 }
 
+#[derive(Args, Debug, Clone)]
+#[clap(about = "Starts an interactive session, that loads declarations and evaluates expressions.")]
+pub struct ReplArgs {}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Graph {
+    /// The tree of the lowered declarations.
+    Hir,
+
+    /// The calls between the binding groups.
+    Calls,
+}
+
+#[derive(Args, Debug, Clone)]
+#[clap(about = "Lowers a `.ase` file, and prints a graph of it in the DOT format.")]
+pub struct DumpArgs {
+    /// The graph to print.
+    #[clap(short = 'g', long, value_enum, default_value = "hir")]
+    pub graph: Graph,
+
+    /// Stops at the first error, instead of printing the graph of the file with the errors.
+    #[clap(long, default_value = "false")]
+    pub fail_fast: bool,
+
+    /// A "file.ase" to dump
+    pub file: String,
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum Command {
     Rename(RenameArgs),
     Search(SearchArgs),
     Highlight(HighlightArgs),
     Eval(EvalArgs),
+    Repl(ReplArgs),
+    Dump(DumpArgs),
 }
 
 pub fn run_cli() {
     let cli = Cli::parse();
+    if cli.profile.is_some() {
+        asena_report::profile::enable();
+    }
+
+    run_command(cli.command);
 
+    let report = asena_report::profile::take_report();
+    match cli.profile {
+        Some(ProfileFormat::Table) => eprint!("{}", report.to_table()),
+        Some(ProfileFormat::Json) => eprintln!("{}", report.to_json()),
+        None => {}
+    }
+}
+
+pub fn run_command(command: Command) {
     use asena_parser::Parser;
-    match cli.command {
+    match command {
         Command::Rename(..) => todo!(),
         Command::Search(..) => todo!(),
         Command::Highlight(args) if !args.semantic => {
@@ -100,11 +156,77 @@ pub fn run_cli() {
             let tree = parser.build_tree();
             println!("{:#?}", tree.data());
         }
+        Command::Repl(..) => {
+            repl::Repl::default().run().unwrap();
+        }
+        Command::Dump(args) => {
+            print!("{}", dump(&args));
+        }
+    }
+}
+
+/// Lowers the file, as a module of a package in its directory, and renders the graph of it. The
+/// diagnostics are printed on the standard error as soon as they're reported.
+pub fn dump(args: &DumpArgs) -> String {
+    use asena_ast_db::{db::AstDatabase, package::Package, vfs::*};
+    use asena_ast_lowering::db::AstLowerrer;
+    use asena_ast_resolver::db::AstResolverDatabase;
+    use asena_hir::dot::HirDot;
+    use asena_prec::PrecDatabase;
+
+    let path = PathBuf::from(&args.file);
+    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+    let fs = FileSystem {
+        base_dir: path.parent().map(PathBuf::from),
+        ..Default::default()
+    };
+
+    let db = imp::DatabaseImpl::default();
+    let pkg = Package::new(&db, "Local", "0.0.0", Arc::new(fs));
+    let file = VfsFileData::new(&db, &name, args.file.as_str().into(), pkg);
+
+    let fail_fast = args.fail_fast;
+    pkg.on_diagnostic(&db, move |diagnostic| {
+        let span = &diagnostic.message.span;
+        eprintln!(
+            "{:?} at {span}: {}",
+            diagnostic.kind, diagnostic.message.value
+        );
+
+        if fail_fast && diagnostic.kind.is_error() {
+            std::process::exit(1);
+        }
+    });
+
+    let ast = db.ast(file);
+    let ast = db.infix_commands(ast.into());
+    let ast = db.ordered_prec(ast.into());
+    let ast = db.ast_resolved_file(ast.into());
+    let hir = db.hir_file(ast.into());
+
+    let dot = HirDot::new(&db);
+    match args.graph {
+        Graph::Hir => dot.tree(hir.declarations),
+        Graph::Calls => dot.call_graph(hir.declarations),
     }
 }
 
+/// Installs the subscriber of the logs on the standard error, the targets are filtered by the
+/// `ASENA_LOG` environment variable, like `ASENA_LOG=asena_ast_resolver=debug`, and only the
+/// errors are shown by default.
+fn install_tracing() {
+    use tracing_subscriber::EnvFilter;
+
+    let filter = EnvFilter::try_from_env("ASENA_LOG").unwrap_or_else(|_| EnvFilter::new("error"));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
 fn main() {
-    env_logger::init();
+    install_tracing();
     panik::install_asena_panic_hook();
 
     run_cli();
@@ -118,9 +240,9 @@ mod tests {
 
     #[test]
     fn pipeline_works() {
-        env_logger::builder()
-            .is_test(true)
-            .filter_level(log::LevelFilter::Error)
+        tracing_subscriber::fmt()
+            .with_env_filter("error")
+            .with_test_writer()
             .try_init()
             .unwrap();
 
@@ -137,4 +259,28 @@ mod tests {
         db.run_pipeline_catching(file, LlirConfig::default());
         db.lookup_intern_package(local_pkg).print_diagnostics(&db);
     }
+
+    #[test]
+    fn repl_works() {
+        let mut repl = crate::repl::Repl::default();
+        repl.eval_line("Two { 1 + 1 }");
+
+        let value = repl.eval_expr("Two * 3").unwrap().unwrap();
+        assert_eq!(value.to_string(), "6");
+        assert_eq!(value.type_name(), "Int32");
+    }
+
+    #[test]
+    fn profile_works() {
+        use asena_report::profile::{self, ProfilePhase};
+
+        profile::enable();
+        crate::repl::Repl::default().eval_expr("1 + 1");
+
+        let report = profile::take_report();
+        assert!(report.phases[&ProfilePhase::Parsing].runs > 0);
+        assert!(report.phases[&ProfilePhase::Lowering].runs > 0);
+        assert!(report.queries["hir_file"].executions > 0);
+        assert!(report.to_json().starts_with("{\"phases\":{"));
+    }
 }
@@ -0,0 +1,200 @@
+//! The interactive mode of the compiler. Every input is either a declaration, that is added to
+//! the module of the REPL, or an expression, that is evaluated against the declarations loaded
+//! so far.
+//!
+//! The database lives as long as the REPL, the module and the input are files in the memory,
+//! that are replaced on every input, so only the queries that depend on them run again.
+
+use std::{
+    io::{BufRead, Write},
+    panic::AssertUnwindSafe,
+    path::PathBuf,
+    sync::Arc,
+};
+
+use asena_ast::{AsenaListener, Decl, Typed};
+use asena_ast_db::{db::AstDatabase, package::*, vfs::*};
+use asena_ast_lowering::db::AstLowerrer;
+use asena_ast_resolver::db::AstResolverDatabase;
+use asena_ast_resolver::{decl::AstResolver, scopes::*};
+use asena_grammar::{parse_decl, parse_expr_in};
+use asena_hir::file::InternalAsenaFile;
+use asena_hir_eval::{Env, Interpreter, Value};
+use asena_prec::{PrecDatabase, PrecReorder};
+use salsa::{Database, Durability};
+
+use crate::imp::DatabaseImpl;
+
+/// The name of the module that holds the declarations typed in the REPL.
+const MODULE: &str = "Repl";
+
+/// The name of the file that holds the expression being evaluated, so its diagnostics are
+/// rendered against it.
+const INPUT: &str = "Input";
+
+pub struct Repl {
+    db: DatabaseImpl,
+    vfs: Arc<FileSystem>,
+    pkg: Package,
+    module: VfsFile,
+    declarations: Vec<String>,
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        let db = DatabaseImpl::default();
+
+        // The files are read as soon as they're created, so they start empty.
+        let vfs = Arc::new(FileSystem::default());
+        vfs.memory.insert(MODULE.into(), String::new());
+        vfs.memory.insert(INPUT.into(), String::new());
+
+        let pkg = Package::new(&db, MODULE, "0.0.0", vfs.clone());
+        let module = VfsFileData::new(&db, MODULE, file_path(MODULE).as_str().into(), pkg);
+        VfsFileData::new(&db, INPUT, file_path(INPUT).as_str().into(), pkg);
+
+        Self {
+            db,
+            vfs,
+            pkg,
+            module,
+            declarations: vec![],
+        }
+    }
+}
+
+impl Repl {
+    /// Runs the REPL on the standard input, until the end of the input or `:quit`.
+    pub fn run(&mut self) -> std::io::Result<()> {
+        let stdin = std::io::stdin();
+        let mut lines = stdin.lock().lines();
+
+        loop {
+            print!("> ");
+            std::io::stdout().flush()?;
+
+            let Some(line) = lines.next().transpose()? else {
+                return Ok(());
+            };
+
+            match line.trim() {
+                "" => {}
+                ":q" | ":quit" => return Ok(()),
+                input => self.eval_line(input),
+            }
+        }
+    }
+
+    pub fn eval_line(&mut self, input: &str) {
+        if is_decl(input) {
+            return self.load_decl(input);
+        }
+
+        match self.eval_expr(input) {
+            Some(Ok(value)) => println!("{value} : {}", value.type_name()),
+            Some(Err(error)) => eprintln!("error: {error}"),
+            None => {}
+        }
+    }
+
+    /// Evaluates the expression against the declarations. Returns [None] if the expression
+    /// couldn't be loaded, the diagnostics are already printed.
+    pub fn eval_expr(&mut self, input: &str) -> Option<asena_hir_eval::Result<Value>> {
+        self.write_file(INPUT, input.to_string());
+
+        let db = &self.db;
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let module = self.lower_module();
+
+            let _arena = db.node_arena().enter();
+            let parsed = parse_expr_in(PathBuf::from(file_path(INPUT)), input);
+            for error in parsed.errors {
+                error.push(db);
+            }
+
+            // The names of the expression are resolved in the scope of the module, like the
+            // ones of its declarations.
+            let expr = parsed.node.walks(PrecReorder { db });
+            let mut resolver = AstResolver::new(db, self.module);
+            ScopeResolver::empty(Level::Value, &mut resolver).listens(expr.clone());
+
+            (module, asena_ast_lowering::lower_value(db, expr.into()))
+        }));
+
+        self.print_diagnostics();
+
+        match result {
+            Ok((module, value)) if !self.pkg.budget(db).has_errors() => {
+                let interpreter = Interpreter::new(db, module.declarations);
+
+                Some(interpreter.value(&Env::new(), value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Adds the declaration to the module, if it can be loaded without errors, otherwise the
+    /// module goes back to the declarations loaded so far.
+    fn load_decl(&mut self, input: &str) {
+        self.declarations.push(input.to_string());
+        self.write_file(MODULE, self.declarations.join("\n"));
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| self.lower_module()));
+        self.print_diagnostics();
+
+        if result.is_err() || self.pkg.budget(&self.db).has_errors() {
+            self.declarations.pop();
+            self.write_file(MODULE, self.declarations.join("\n"));
+        }
+    }
+
+    /// Replaces the source of the file in the memory, and starts a new revision of the
+    /// database, so the queries that read it run again. The diagnostics of the previous input
+    /// are cleared, so only the ones of this input are printed.
+    fn write_file(&mut self, name: &str, source: String) {
+        self.vfs.memory.insert(name.into(), source);
+        self.db.salsa_runtime_mut().synthetic_write(Durability::LOW);
+        self.pkg.clear_diagnostics(&self.db);
+    }
+
+    /// Parses, resolves and lowers the module, the queries are memoized, so it only runs again
+    /// if the declarations changed.
+    fn lower_module(&self) -> InternalAsenaFile {
+        let ast = self.db.ast(self.module);
+        let ast = self.db.infix_commands(ast.into());
+        let ast = self.db.ordered_prec(ast.into());
+        let ast = self.db.ast_resolved_file(ast.into());
+
+        self.db.hir_file(ast.into())
+    }
+
+    fn print_diagnostics(&self) {
+        self.db
+            .lookup_intern_package(self.pkg)
+            .print_diagnostics(&self.db);
+    }
+}
+
+/// The path of the file in the memory, relative to the package.
+fn file_path(name: &str) -> String {
+    format!("./{name}.ase")
+}
+
+/// Checks if the input is a declaration, otherwise it's an expression. The inputs that are both,
+/// like `Two { 1 + 1 }`, are declarations, except the bare names, like `Two`, that would be
+/// signatures without parameters, types or bodies.
+fn is_decl(input: &str) -> bool {
+    let Some(decl) = parse_decl(input).ok() else {
+        return false;
+    };
+
+    match decl {
+        Decl::Error => false,
+        Decl::Signature(signature) => {
+            !signature.parameters().is_empty()
+                || signature.body().is_some()
+                || !matches!(signature.return_type(), Typed::Infer)
+        }
+        _ => true,
+    }
+}
@@ -0,0 +1,33 @@
+//! Feeds arbitrary source code into the lexer and the parser, run with:
+//! `cargo fuzz run parse_source`.
+
+#![no_main]
+
+use asena_leaf::token::token_set::HasTokens;
+use asena_lexer::Lexer;
+use asena_parser::Parser;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|code: &str| {
+    let lexer = Lexer::new(None, code);
+    let expected = lexer
+        .tokens
+        .iter()
+        .map(|token| token.full_text.to_string())
+        .collect::<String>();
+
+    let parser = Parser::from(lexer).run(asena_grammar::file);
+    assert!(!parser.is_stuck(), "parser is stuck");
+
+    // The tree must contain every token of the stream, in the same order, so printing it
+    // back should give the same text.
+    let tree = parser.build_tree();
+    let printed = tree
+        .data
+        .tokens()
+        .iter()
+        .map(|token| token.full_text.to_string())
+        .collect::<String>();
+
+    assert_eq!(expected, printed, "tree does not round-trip");
+});
@@ -0,0 +1,83 @@
+//! Feeds mutated token streams into the parser, the tokens are taken from a valid program, and
+//! the fuzzer input is used to drop, duplicate and swap them, the printed tree is formatted too.
+//! Run with: `cargo fuzz run parse_tokens`.
+
+#![no_main]
+
+use asena_leaf::token::token_set::HasTokens;
+use asena_lexer::Lexer;
+use asena_parser::Parser;
+use libfuzzer_sys::fuzz_target;
+
+const SEED: &str = include_str!("../../asena-grammar/tests/simple.ase");
+
+fuzz_target!(|mutations: &[u8]| {
+    let lexer = Lexer::new(None, SEED);
+    let mut tokens = lexer.tokens.clone();
+
+    for chunk in mutations.chunks_exact(2) {
+        if tokens.is_empty() {
+            break;
+        }
+
+        let index = chunk[1] as usize % tokens.len();
+        match chunk[0] % 3 {
+            0 => {
+                tokens.remove(index);
+            }
+            1 => {
+                let token = tokens[index].clone();
+                tokens.insert(index, token);
+            }
+            _ => {
+                let other = (index + 1) % tokens.len();
+                tokens.swap(index, other);
+            }
+        }
+    }
+
+    let expected = tokens
+        .iter()
+        .map(|token| token.value.kind)
+        .collect::<Vec<_>>();
+    let parser = Parser::new(SEED, tokens).run(asena_grammar::file);
+    assert!(!parser.is_stuck(), "parser is stuck");
+
+    let tree = parser.build_tree();
+    let printed = tree
+        .data
+        .tokens()
+        .iter()
+        .map(|token| token.value.kind)
+        .collect::<Vec<_>>();
+
+    assert_eq!(expected, printed, "tree does not round-trip");
+
+    // The formatter only changes the layout, so the printed tree must keep its tokens after
+    // being formatted, and formatting it again must not change it.
+    let source = tree
+        .data
+        .tokens()
+        .iter()
+        .map(|token| token.full_text.to_string())
+        .collect::<String>();
+    let formatted = asena_ast_formatter::format(&source);
+    let kinds = |code: &str| {
+        Lexer::new(None, code)
+            .tokens
+            .into_iter()
+            .map(|token| token.value.kind)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        kinds(&source),
+        kinds(&formatted),
+        "formatter changes the tokens"
+    );
+    assert_eq!(
+        formatted,
+        asena_ast_formatter::format(&formatted),
+        "formatter is not idempotent"
+    );
+});